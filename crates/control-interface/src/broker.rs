@@ -70,6 +70,17 @@ pub mod v1 {
         )
     }
 
+    pub fn rollback_config(
+        topic_prefix: &Option<String>,
+        lattice: &str,
+        config_name: &str,
+    ) -> String {
+        format!(
+            "{}.config.rollback.{config_name}",
+            prefix(topic_prefix, lattice, CTL_API_VERSION_1)
+        )
+    }
+
     pub fn put_label(topic_prefix: &Option<String>, lattice: &str, host_id: &str) -> String {
         format!(
             "{}.label.put.{host_id}",
@@ -139,6 +150,20 @@ pub mod v1 {
                 prefix(topic_prefix, lattice, CTL_API_VERSION_1)
             )
         }
+
+        pub fn cordon_host(topic_prefix: &Option<String>, lattice: &str, host_id: &str) -> String {
+            format!(
+                "{}.host.cordon.{host_id}",
+                prefix(topic_prefix, lattice, CTL_API_VERSION_1)
+            )
+        }
+
+        pub fn drain_host(topic_prefix: &Option<String>, lattice: &str, host_id: &str) -> String {
+            format!(
+                "{}.host.drain.{host_id}",
+                prefix(topic_prefix, lattice, CTL_API_VERSION_1)
+            )
+        }
     }
 
     pub mod queries {
@@ -184,5 +209,39 @@ pub mod v1 {
                 prefix(topic_prefix, lattice, CTL_API_VERSION_1),
             )
         }
+
+        pub fn config_revision(
+            topic_prefix: &Option<String>,
+            lattice: &str,
+            config_name: &str,
+            revision: u64,
+        ) -> String {
+            format!(
+                "{}.config.get.{config_name}.{revision}",
+                prefix(topic_prefix, lattice, CTL_API_VERSION_1),
+            )
+        }
+
+        pub fn config_history(
+            topic_prefix: &Option<String>,
+            lattice: &str,
+            config_name: &str,
+        ) -> String {
+            format!(
+                "{}.config.history.{config_name}",
+                prefix(topic_prefix, lattice, CTL_API_VERSION_1),
+            )
+        }
+
+        pub fn provider_logs(
+            topic_prefix: &Option<String>,
+            lattice: &str,
+            host_id: &str,
+        ) -> String {
+            format!(
+                "{}.provider.logs.{host_id}",
+                prefix(topic_prefix, lattice, CTL_API_VERSION_1),
+            )
+        }
     }
 }
@@ -6,18 +6,22 @@ use core::time::Duration;
 use std::collections::{BTreeMap, HashMap};
 
 use async_nats::Subscriber;
-use cloudevents::event::Event;
+use cloudevents::event::{AttributesReader as _, Data, Event};
 use futures::{StreamExt, TryFutureExt};
 use serde::de::DeserializeOwned;
 use tokio::sync::mpsc::Receiver;
 use tracing::{debug, error, instrument, trace};
 
+use crate::types::config::ConfigRevision;
 use crate::types::ctl::{
-    CtlResponse, ScaleComponentCommand, StartProviderCommand, StopHostCommand, StopProviderCommand,
-    UpdateComponentCommand,
+    ComponentResourceLimits, CordonHostCommand, CtlResponse, DrainHostCommand,
+    GetProviderLogsCommand, RollbackConfigCommand, ScaleComponentCommand, StartProviderCommand,
+    StopHostCommand, StopProviderCommand, UpdateComponentCommand,
 };
 use crate::types::host::{Host, HostInventory, HostLabel};
+use crate::types::inventory::{InventoryDelta, InventoryUpdate};
 use crate::types::link::Link;
+use crate::types::provider::ProviderLogs;
 use crate::types::registry::RegistryCredential;
 use crate::types::rpc::{
     ComponentAuctionAck, ComponentAuctionRequest, DeleteInterfaceLinkDefinitionRequest,
@@ -211,8 +215,9 @@ impl Client {
 
     /// Performs an component auction within the lattice, publishing a set of constraints and the
     /// metadata for the component in question. This will always wait for the full period specified by
-    /// _duration_, and then return the set of gathered results. It is then up to the client to
-    /// choose from among the "auction winners" to issue the appropriate command to start an component.
+    /// _duration_, and then return the set of gathered results, ordered by descending score. It is
+    /// then up to the client to choose from among the "auction winners" to issue the appropriate
+    /// command to start an component.
     /// Clients cannot assume that auctions will always return at least one result.
     #[instrument(level = "debug", skip_all)]
     pub async fn perform_component_auction(
@@ -221,24 +226,41 @@ impl Client {
         component_id: &str,
         constraints: impl Into<BTreeMap<String, String>>,
     ) -> Result<Vec<CtlResponse<ComponentAuctionAck>>> {
-        let subject = broker::v1::component_auction_subject(&self.topic_prefix, &self.lattice);
-        let bytes = json_serialize(
+        self.perform_component_auction_request(
             ComponentAuctionRequest::builder()
-                .component_ref(IdentifierKind::is_component_ref(component_ref)?)
-                .component_id(IdentifierKind::is_component_id(component_id)?)
+                .component_ref(component_ref.into())
+                .component_id(component_id.into())
                 .constraints(constraints.into())
                 .build()?,
-        )?;
+        )
+        .await
+    }
+
+    /// Performs a component auction within the lattice using a complete auction request, which can
+    /// also require a minimum amount of available memory or anti-affinity with running instances of
+    /// the same component. See [`Client::perform_component_auction`]
+    #[instrument(level = "debug", skip_all)]
+    pub async fn perform_component_auction_request(
+        &self,
+        mut request: ComponentAuctionRequest,
+    ) -> Result<Vec<CtlResponse<ComponentAuctionAck>>> {
+        request.component_ref = IdentifierKind::is_component_ref(&request.component_ref)?;
+        request.component_id = IdentifierKind::is_component_id(&request.component_id)?;
+        let subject = broker::v1::component_auction_subject(&self.topic_prefix, &self.lattice);
+        let bytes = json_serialize(request)?;
         debug!("component_auction:publish {}", &subject);
-        self.publish_and_wait(subject, bytes).await
+        let mut acks: Vec<CtlResponse<ComponentAuctionAck>> =
+            self.publish_and_wait(subject, bytes).await?;
+        acks.sort_by_key(|ack| core::cmp::Reverse(ack.data().map(ComponentAuctionAck::score)));
+        Ok(acks)
     }
 
     /// Performs a provider auction within the lattice, publishing a set of constraints and the
     /// metadata for the provider in question.
     ///
-    /// This will always wait for the full period specified by _duration_, and then return the set of gathered results.
-    /// It is then up to the client to choose from among the "auction winners" and issue the appropriate command to start a
-    /// provider.
+    /// This will always wait for the full period specified by _duration_, and then return the set of gathered results,
+    /// ordered by descending score. It is then up to the client to choose from among the "auction winners" and issue the
+    /// appropriate command to start a provider.
     ///
     /// Clients should not assume that auctions will always return at least one result.
     ///
@@ -255,16 +277,33 @@ impl Client {
         provider_id: &str,
         constraints: impl Into<BTreeMap<String, String>>,
     ) -> Result<Vec<CtlResponse<ProviderAuctionAck>>> {
-        let subject = broker::v1::provider_auction_subject(&self.topic_prefix, &self.lattice);
-        let bytes = json_serialize(
+        self.perform_provider_auction_request(
             ProviderAuctionRequest::builder()
-                .provider_ref(IdentifierKind::is_provider_ref(provider_ref)?)
-                .provider_id(IdentifierKind::is_provider_id(provider_id)?)
+                .provider_ref(provider_ref.into())
+                .provider_id(provider_id.into())
                 .constraints(constraints.into())
                 .build()?,
-        )?;
+        )
+        .await
+    }
+
+    /// Performs a provider auction within the lattice using a complete auction request, which can
+    /// also require a minimum amount of available memory or anti-affinity with running instances of
+    /// the same provider. See [`Client::perform_provider_auction`]
+    #[instrument(level = "debug", skip_all)]
+    pub async fn perform_provider_auction_request(
+        &self,
+        mut request: ProviderAuctionRequest,
+    ) -> Result<Vec<CtlResponse<ProviderAuctionAck>>> {
+        request.provider_ref = IdentifierKind::is_provider_ref(&request.provider_ref)?;
+        request.provider_id = IdentifierKind::is_provider_id(&request.provider_id)?;
+        let subject = broker::v1::provider_auction_subject(&self.topic_prefix, &self.lattice);
+        let bytes = json_serialize(request)?;
         debug!("provider_auction:publish {}", &subject);
-        self.publish_and_wait(subject, bytes).await
+        let mut acks: Vec<CtlResponse<ProviderAuctionAck>> =
+            self.publish_and_wait(subject, bytes).await?;
+        acks.sort_by_key(|ack| core::cmp::Reverse(ack.data().map(ProviderAuctionAck::score)));
+        Ok(acks)
     }
 
     /// Sends a request to the given host to scale a given component.
@@ -298,22 +337,60 @@ impl Client {
         annotations: Option<BTreeMap<String, String>>,
         config: Vec<String>,
     ) -> Result<CtlResponse<()>> {
-        let host_id = IdentifierKind::is_host_id(host_id)?;
-        let subject = broker::v1::commands::scale_component(
-            &self.topic_prefix,
-            &self.lattice,
-            host_id.as_str(),
-        );
-        debug!("scale_component:request {}", &subject);
-        let bytes = json_serialize(ScaleComponentCommand {
+        self.request_scale_component(ScaleComponentCommand {
             max_instances,
             component_ref: IdentifierKind::is_component_ref(component_ref)?,
             component_id: IdentifierKind::is_component_id(component_id)?,
-            host_id,
+            host_id: IdentifierKind::is_host_id(host_id)?,
             annotations,
             config,
             ..Default::default()
-        })?;
+        })
+        .await
+    }
+
+    /// Sends a request to the given host to scale a given component, applying the given resource
+    /// limits to every instance of it.
+    ///
+    /// The host validates the limits against its own maximum settings and rejects the request if
+    /// any of them is exceeded. See [`Client::scale_component`] for details on the remaining arguments
+    /// and the semantics of the acknowledgement.
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(level = "debug", skip_all)]
+    pub async fn scale_component_with_limits(
+        &self,
+        host_id: &str,
+        component_ref: &str,
+        component_id: &str,
+        max_instances: u32,
+        annotations: Option<BTreeMap<String, String>>,
+        config: Vec<String>,
+        limits: ComponentResourceLimits,
+    ) -> Result<CtlResponse<()>> {
+        self.request_scale_component(ScaleComponentCommand {
+            max_instances,
+            component_ref: IdentifierKind::is_component_ref(component_ref)?,
+            component_id: IdentifierKind::is_component_id(component_id)?,
+            host_id: IdentifierKind::is_host_id(host_id)?,
+            annotations,
+            config,
+            limits: Some(limits),
+            ..Default::default()
+        })
+        .await
+    }
+
+    async fn request_scale_component(
+        &self,
+        command: ScaleComponentCommand,
+    ) -> Result<CtlResponse<()>> {
+        let subject = broker::v1::commands::scale_component(
+            &self.topic_prefix,
+            &self.lattice,
+            &command.host_id,
+        );
+        debug!("scale_component:request {}", &subject);
+        let bytes = json_serialize(command)?;
         match self.request_timeout(subject, bytes, self.timeout).await {
             Ok(msg) => Ok(json_deserialize(&msg.payload)?),
             Err(e) => Err(format!("Did not receive scale component acknowledgement: {e}").into()),
@@ -526,6 +603,91 @@ impl Client {
         }
     }
 
+    /// Get the named config item as it was at the given revision. Revisions are listed by
+    /// [`Client::get_config_history`]
+    ///
+    /// # Arguments
+    ///
+    /// * `config_name` - The name of the config to fetch
+    /// * `revision` - The revision of the config to fetch
+    #[instrument(level = "debug", skip_all)]
+    pub async fn get_config_revision(
+        &self,
+        config_name: &str,
+        revision: u64,
+    ) -> Result<CtlResponse<HashMap<String, String>>> {
+        let subject = broker::v1::queries::config_revision(
+            &self.topic_prefix,
+            &self.lattice,
+            config_name,
+            revision,
+        );
+        debug!(%subject, %config_name, revision, "Getting config revision");
+        match self
+            .request_timeout(subject, Vec::default(), self.timeout)
+            .await
+        {
+            Ok(msg) => json_deserialize(&msg.payload),
+            Err(e) => Err(format!(
+                "Did not receive a response to get config revision request: {e}"
+            )
+            .into()),
+        }
+    }
+
+    /// Get the revision history of the named config item, ordered from oldest to newest.
+    /// Deleting a config item discards its history
+    ///
+    /// # Arguments
+    ///
+    /// * `config_name` - The name of the config to fetch the history of
+    #[instrument(level = "debug", skip_all)]
+    pub async fn get_config_history(
+        &self,
+        config_name: &str,
+    ) -> Result<CtlResponse<Vec<ConfigRevision>>> {
+        let subject =
+            broker::v1::queries::config_history(&self.topic_prefix, &self.lattice, config_name);
+        debug!(%subject, %config_name, "Getting config history");
+        match self
+            .request_timeout(subject, Vec::default(), self.timeout)
+            .await
+        {
+            Ok(msg) => json_deserialize(&msg.payload),
+            Err(e) => {
+                Err(format!("Did not receive a response to get config history request: {e}").into())
+            }
+        }
+    }
+
+    /// Restore the named config item to the value it had at the given revision. The restored
+    /// value is stored as a new revision, which is returned
+    ///
+    /// # Arguments
+    ///
+    /// * `config_name` - The name of the config to roll back
+    /// * `revision` - The revision of the config to restore
+    #[instrument(level = "debug", skip_all)]
+    pub async fn rollback_config(
+        &self,
+        config_name: &str,
+        revision: u64,
+    ) -> Result<CtlResponse<ConfigRevision>> {
+        let subject = broker::v1::rollback_config(&self.topic_prefix, &self.lattice, config_name);
+        debug!(%subject, %config_name, revision, "Rolling back config");
+        let bytes = json_serialize(
+            RollbackConfigCommand::builder()
+                .revision(revision)
+                .build()?,
+        )?;
+        match self.request_timeout(subject, bytes, self.timeout).await {
+            Ok(msg) => json_deserialize(&msg.payload),
+            Err(e) => {
+                Err(format!("Did not receive a response to rollback config request: {e}").into())
+            }
+        }
+    }
+
     /// Put a new (or update an existing) label on the given host.
     ///
     /// # Arguments
@@ -708,6 +870,42 @@ impl Client {
         }
     }
 
+    /// Retrieves the most recent output of a provider running on a specific host.
+    ///
+    /// # Arguments
+    ///
+    /// * `host_id` - ID of the host the provider is running on
+    /// * `provider_id` - ID of the provider
+    /// * `since` - (optional) only return lines with a greater sequence number, used to follow
+    ///   the output of a provider by passing the sequence number of the last line received
+    /// * `max_lines` - (optional) maximum number of lines to return
+    ///
+    #[instrument(level = "debug", skip_all)]
+    pub async fn get_provider_logs(
+        &self,
+        host_id: &str,
+        provider_id: &str,
+        since: Option<u64>,
+        max_lines: Option<usize>,
+    ) -> Result<CtlResponse<ProviderLogs>> {
+        let host_id = IdentifierKind::is_host_id(host_id)?;
+
+        let subject =
+            broker::v1::queries::provider_logs(&self.topic_prefix, &self.lattice, host_id.as_str());
+        debug!("get_provider_logs:request {}", &subject);
+        let bytes = json_serialize(GetProviderLogsCommand {
+            host_id,
+            provider_id: IdentifierKind::is_component_id(provider_id)?,
+            since,
+            max_lines,
+        })?;
+
+        match self.request_timeout(subject, bytes, self.timeout).await {
+            Ok(msg) => Ok(json_deserialize(&msg.payload)?),
+            Err(e) => Err(format!("Did not receive provider logs from target host: {e}").into()),
+        }
+    }
+
     /// Issues a command to a specific host to perform a graceful termination.
     ///
     /// The target host will acknowledge receipt of the command before it attempts a shutdown.
@@ -741,6 +939,79 @@ impl Client {
         }
     }
 
+    /// Issues a command to a specific host to cordon itself. A cordoned host no longer responds to
+    /// component or provider auctions, so schedulers place new workloads elsewhere, but keeps
+    /// running the workloads it already has
+    ///
+    /// # Arguments
+    ///
+    /// * `host_id` - ID of the host to cordon
+    ///
+    #[instrument(level = "debug", skip_all)]
+    pub async fn cordon_host(&self, host_id: &str) -> Result<CtlResponse<()>> {
+        self.set_host_cordoned(host_id, true).await
+    }
+
+    /// Issues a command to a specific host to uncordon itself, so that it responds to auctions
+    /// again. Hosts, which have been drained, remain cordoned until uncordoned
+    ///
+    /// # Arguments
+    ///
+    /// * `host_id` - ID of the host to uncordon
+    ///
+    #[instrument(level = "debug", skip_all)]
+    pub async fn uncordon_host(&self, host_id: &str) -> Result<CtlResponse<()>> {
+        self.set_host_cordoned(host_id, false).await
+    }
+
+    async fn set_host_cordoned(&self, host_id: &str, cordoned: bool) -> Result<CtlResponse<()>> {
+        let host_id = IdentifierKind::is_host_id(host_id)?;
+        let subject =
+            broker::v1::commands::cordon_host(&self.topic_prefix, &self.lattice, host_id.as_str());
+        debug!("cordon_host:request {}", &subject);
+        let bytes = json_serialize(CordonHostCommand { host_id, cordoned })?;
+
+        match self.request_timeout(subject, bytes, self.timeout).await {
+            Ok(msg) => Ok(json_deserialize(&msg.payload)?),
+            Err(e) => Err(format!("Did not receive cordon host acknowledgement: {e}").into()),
+        }
+    }
+
+    /// Issues a command to a specific host to drain its workloads.
+    ///
+    /// The target host cordons itself, publishes a `host_draining` event and acknowledges receipt
+    /// of the command. It then waits for in-flight invocations to finish before stopping all of its
+    /// components and providers, and publishes a `host_drained` event once done.
+    ///
+    /// # Arguments
+    ///
+    /// * `host_id` - ID of the host to drain
+    /// * `timeout_ms` - (optional) amount of time to wait for in-flight invocations to finish
+    /// * `stop_host` - whether the host should stop once drained
+    ///
+    #[instrument(level = "debug", skip_all)]
+    pub async fn drain_host(
+        &self,
+        host_id: &str,
+        timeout_ms: Option<u64>,
+        stop_host: bool,
+    ) -> Result<CtlResponse<()>> {
+        let host_id = IdentifierKind::is_host_id(host_id)?;
+        let subject =
+            broker::v1::commands::drain_host(&self.topic_prefix, &self.lattice, host_id.as_str());
+        debug!("drain_host:request {}", &subject);
+        let bytes = json_serialize(DrainHostCommand {
+            host_id,
+            timeout: timeout_ms,
+            stop_host,
+        })?;
+
+        match self.request_timeout(subject, bytes, self.timeout).await {
+            Ok(msg) => Ok(json_deserialize(&msg.payload)?),
+            Err(e) => Err(format!("Did not receive drain host acknowledgement: {e}").into()),
+        }
+    }
+
     /// Publish a message and wait for a response
     async fn publish_and_wait<D: DeserializeOwned>(
         &self,
@@ -766,6 +1037,106 @@ impl Client {
         Ok(collect_sub_timeout::<D>(sub, self.auction_timeout, subject.as_str()).await)
     }
 
+    /// Watches the inventory of a host.
+    ///
+    /// Returns the receiver end of a channel, which first receives a snapshot of the inventory,
+    /// followed by the changes to it derived from the events published by the host. Events are
+    /// numbered by the host, and if an event is missed, a new snapshot is retrieved and sent before
+    /// any further changes. Changes can be applied to a snapshot using [`HostInventory::apply`].
+    ///
+    /// The channel is closed if a new snapshot cannot be retrieved.
+    ///
+    /// # Arguments
+    ///
+    /// * `host_id` - ID of the host to watch
+    ///
+    #[instrument(level = "debug", skip_all)]
+    pub async fn watch_host_inventory(&self, host_id: &str) -> Result<Receiver<InventoryUpdate>> {
+        let host_id = IdentifierKind::is_host_id(host_id)?;
+        // Subscribe before retrieving the snapshot, so that no events are missed in between
+        let mut sub = self
+            .nc
+            .subscribe(format!("wasmbus.evt.{}.>", self.lattice))
+            .await?;
+        let inventory = self.host_inventory_snapshot(&host_id).await?;
+        let (sender, receiver) = tokio::sync::mpsc::channel(5000);
+        let client = self.clone();
+        tokio::spawn(async move {
+            let mut sequence = inventory.sequence();
+            let Ok(()) = sender.send(InventoryUpdate::Snapshot(inventory)).await else {
+                return;
+            };
+            while let Some(msg) = sub.next().await {
+                let Ok(evt) = json_deserialize::<Event>(&msg.payload) else {
+                    error!("Object received on event stream was not a CloudEvent");
+                    continue;
+                };
+                if evt.source().as_str() != host_id {
+                    continue;
+                }
+                let Some(event_type) = evt.ty().strip_prefix("com.wasmcloud.lattice.") else {
+                    continue;
+                };
+                let data = match evt.data() {
+                    Some(Data::Json(data)) => data.clone(),
+                    Some(Data::String(data)) => serde_json::from_str(data).unwrap_or_default(),
+                    Some(Data::Binary(data)) => serde_json::from_slice(data).unwrap_or_default(),
+                    None => serde_json::Value::Null,
+                };
+                let event_sequence = data.get("sequence").and_then(serde_json::Value::as_u64);
+                if let Some(event_sequence) = event_sequence {
+                    if event_sequence > sequence + 1 {
+                        debug!(
+                            expected = sequence + 1,
+                            received = event_sequence,
+                            "missed host events, retrieving inventory snapshot"
+                        );
+                        let inventory = match client.host_inventory_snapshot(&host_id).await {
+                            Ok(inventory) => inventory,
+                            Err(error) => {
+                                error!(%error, "failed to retrieve host inventory snapshot");
+                                return;
+                            }
+                        };
+                        sequence = inventory.sequence();
+                        let Ok(()) = sender.send(InventoryUpdate::Snapshot(inventory)).await else {
+                            return;
+                        };
+                    }
+                    // Events up to the sequence of the snapshot are reflected in it
+                    if event_sequence <= sequence {
+                        continue;
+                    }
+                    sequence = event_sequence;
+                }
+                let Some(delta) = InventoryDelta::from_event(event_type, data) else {
+                    continue;
+                };
+                trace!(?delta, "received host inventory change");
+                let Ok(()) = sender
+                    .send(InventoryUpdate::Delta {
+                        sequence: event_sequence.unwrap_or_default(),
+                        delta,
+                    })
+                    .await
+                else {
+                    return;
+                };
+            }
+        });
+        Ok(receiver)
+    }
+
+    /// Retrieves the inventory of a host, failing if the host did not return one
+    async fn host_inventory_snapshot(&self, host_id: &str) -> Result<HostInventory> {
+        let res = self.get_host_inventory(host_id).await?;
+        if !res.succeeded() {
+            return Err(format!("Failed to retrieve host inventory: {}", res.message()).into());
+        }
+        res.into_data()
+            .ok_or_else(|| "Host did not return an inventory".into())
+    }
+
     /// Returns the receiver end of a channel that subscribes to the lattice event stream.
     ///
     /// Any [`Event`]s that are published after this channel is created
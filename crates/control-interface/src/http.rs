@@ -0,0 +1,808 @@
+//! Control interface client using the HTTP/JSON transport
+//!
+//! Hosts serve the control interface over HTTP/JSON on their HTTP administration endpoint if
+//! configured with a bearer token. Unlike the NATS [`Client`](crate::Client), an [`HttpClient`]
+//! communicates with a single host, so auctions and host queries only return its response.
+
+use core::fmt::{self, Debug};
+use core::time::Duration;
+
+use std::collections::{BTreeMap, HashMap};
+
+use cloudevents::event::Event;
+use reqwest::{StatusCode, Url};
+use serde::de::DeserializeOwned;
+use tokio::sync::mpsc::Receiver;
+use tracing::{debug, error, instrument, trace};
+
+use crate::types::config::ConfigRevision;
+use crate::types::ctl::{
+    ComponentResourceLimits, CordonHostCommand, CtlResponse, DrainHostCommand,
+    GetProviderLogsCommand, RollbackConfigCommand, ScaleComponentCommand, StartProviderCommand,
+    StopHostCommand, StopProviderCommand, UpdateComponentCommand,
+};
+use crate::types::host::{Host, HostInventory, HostLabel};
+use crate::types::link::Link;
+use crate::types::provider::ProviderLogs;
+use crate::types::registry::RegistryCredential;
+use crate::types::rpc::{
+    ComponentAuctionAck, ComponentAuctionRequest, DeleteInterfaceLinkDefinitionRequest,
+    ProviderAuctionAck, ProviderAuctionRequest,
+};
+use crate::{json_deserialize, json_serialize, HostLabelIdentifier, IdentifierKind, Result};
+
+/// A client builder that can be used to fluently provide configuration settings used to construct
+/// the HTTP control interface client
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct HttpClientBuilder {
+    url: Url,
+    token: String,
+    timeout: Duration,
+}
+
+impl Debug for HttpClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpClientBuilder")
+            .field("url", &self.url)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl HttpClientBuilder {
+    /// Creates a new client builder for the HTTP administration endpoint of a host at `url`,
+    /// authenticating requests with the bearer `token`, with all other configuration values set to
+    /// their defaults
+    #[must_use]
+    pub fn new(url: Url, token: impl Into<String>) -> HttpClientBuilder {
+        HttpClientBuilder {
+            url,
+            token: token.into(),
+            timeout: Duration::from_secs(2),
+        }
+    }
+
+    /// Sets the timeout for control interface requests issued by the client. If not set, the
+    /// default will be 2 seconds
+    #[must_use]
+    pub fn timeout(self, timeout: Duration) -> HttpClientBuilder {
+        HttpClientBuilder { timeout, ..self }
+    }
+
+    /// Constructs the client with the given configuration from the builder
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL cannot be used as a base URL or the HTTP client cannot be
+    /// initialized
+    pub fn build(self) -> Result<HttpClient> {
+        if self.url.cannot_be_a_base() {
+            return Err(format!("invalid control interface URL `{}`", self.url).into());
+        }
+        let http = reqwest::Client::builder()
+            .build()
+            .map_err(|e| format!("failed to build HTTP client: {e}"))?;
+        Ok(HttpClient {
+            http,
+            url: self.url,
+            token: self.token,
+            timeout: self.timeout,
+        })
+    }
+}
+
+/// Control interface client for a single host, using the HTTP/JSON transport
+#[derive(Clone)]
+#[non_exhaustive]
+pub struct HttpClient {
+    /// Internal `reqwest` client
+    http: reqwest::Client,
+    /// URL of the HTTP administration endpoint of the host
+    url: Url,
+    /// Bearer token requests are authenticated with
+    token: String,
+    /// Timeout
+    timeout: Duration,
+}
+
+impl Debug for HttpClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpClient")
+            .field("url", &self.url)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl HttpClient {
+    /// Retrieve the URL of the HTTP administration endpoint in use by the [`HttpClient`]
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// Returns the URL of `path` on the HTTP administration endpoint
+    fn endpoint<'a>(&self, path: impl IntoIterator<Item = &'a str>) -> Url {
+        let mut url = self.url.clone();
+        if let Ok(mut segments) = url.path_segments_mut() {
+            segments.pop_if_empty().extend(path);
+        }
+        url
+    }
+
+    /// Perform a request for the control interface operation identified by `operation`, sending
+    /// `payload` if set. Returns `None` if the host did not respond, e.g. to an auction
+    #[instrument(level = "debug", skip_all)]
+    async fn send<T: DeserializeOwned>(
+        &self,
+        operation: &[&str],
+        payload: Option<Vec<u8>>,
+    ) -> Result<Option<T>> {
+        let url = self.endpoint(
+            ["api", "v1", "ctl"]
+                .into_iter()
+                .chain(operation.iter().copied()),
+        );
+        debug!(%url, "control interface request");
+        let req = match payload {
+            Some(payload) => self.http.post(url).body(payload),
+            None => self.http.get(url),
+        };
+        let res = req
+            .bearer_auth(&self.token)
+            .timeout(self.timeout)
+            .send()
+            .await?;
+        match res.status() {
+            StatusCode::NO_CONTENT => Ok(None),
+            StatusCode::UNAUTHORIZED => Err("control interface request was not authorized".into()),
+            status if status.is_success() => json_deserialize(&res.bytes().await?).map(Some),
+            status => {
+                let body = res.bytes().await?;
+                let message = json_deserialize::<CtlResponse<()>>(&body)
+                    .map(|res| res.message().to_string())
+                    .unwrap_or_else(|_| String::from_utf8_lossy(&body).into_owned());
+                Err(
+                    format!("control interface request failed with status {status}: {message}")
+                        .into(),
+                )
+            }
+        }
+    }
+
+    /// Perform a request for the control interface operation identified by `operation`, which the
+    /// host must respond to
+    async fn request<T: DeserializeOwned>(
+        &self,
+        operation: &[&str],
+        payload: Option<Vec<u8>>,
+    ) -> Result<T> {
+        self.send(operation, payload)
+            .await?
+            .ok_or_else(|| "host did not respond to control interface request".into())
+    }
+
+    /// Queries the host for its description. The returned list contains the response of the host
+    #[instrument(level = "debug", skip_all)]
+    pub async fn get_hosts(&self) -> Result<Vec<CtlResponse<Host>>> {
+        match self.request(&["host", "ping"], None).await {
+            Ok(host) => Ok(vec![host]),
+            Err(e) => Err(format!("Did not receive host description: {e}").into()),
+        }
+    }
+
+    /// Retrieves the contents of the host
+    #[instrument(level = "debug", skip_all)]
+    pub async fn get_host_inventory(&self, host_id: &str) -> Result<CtlResponse<HostInventory>> {
+        let host_id = IdentifierKind::is_host_id(host_id)?;
+        match self.request(&["host", "get", host_id.as_str()], None).await {
+            Ok(inventory) => Ok(inventory),
+            Err(e) => Err(format!("Did not receive host inventory from target host: {e}").into()),
+        }
+    }
+
+    /// Retrieves the full set of all cached claims of the host
+    #[instrument(level = "debug", skip_all)]
+    pub async fn get_claims(&self) -> Result<CtlResponse<Vec<HashMap<String, String>>>> {
+        match self.request(&["claims", "get"], None).await {
+            Ok(claims) => Ok(claims),
+            Err(e) => Err(format!("Did not receive claims from host: {e}").into()),
+        }
+    }
+
+    /// Performs a component auction on the host. The returned list is empty if the host does not
+    /// satisfy the constraints of the auction. See [`Client::perform_component_auction`](crate::Client::perform_component_auction)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn perform_component_auction(
+        &self,
+        component_ref: &str,
+        component_id: &str,
+        constraints: impl Into<BTreeMap<String, String>>,
+    ) -> Result<Vec<CtlResponse<ComponentAuctionAck>>> {
+        self.perform_component_auction_request(
+            ComponentAuctionRequest::builder()
+                .component_ref(component_ref.into())
+                .component_id(component_id.into())
+                .constraints(constraints.into())
+                .build()?,
+        )
+        .await
+    }
+
+    /// Performs a component auction on the host using a complete auction request. See [`Client::perform_component_auction_request`](crate::Client::perform_component_auction_request)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn perform_component_auction_request(
+        &self,
+        mut request: ComponentAuctionRequest,
+    ) -> Result<Vec<CtlResponse<ComponentAuctionAck>>> {
+        request.component_ref = IdentifierKind::is_component_ref(&request.component_ref)?;
+        request.component_id = IdentifierKind::is_component_id(&request.component_id)?;
+        let bytes = json_serialize(request)?;
+        let ack = self.send(&["component", "auction"], Some(bytes)).await?;
+        Ok(ack.into_iter().collect())
+    }
+
+    /// Performs a provider auction on the host. The returned list is empty if the host does not
+    /// satisfy the constraints of the auction. See [`Client::perform_provider_auction`](crate::Client::perform_provider_auction)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn perform_provider_auction(
+        &self,
+        provider_ref: &str,
+        provider_id: &str,
+        constraints: impl Into<BTreeMap<String, String>>,
+    ) -> Result<Vec<CtlResponse<ProviderAuctionAck>>> {
+        self.perform_provider_auction_request(
+            ProviderAuctionRequest::builder()
+                .provider_ref(provider_ref.into())
+                .provider_id(provider_id.into())
+                .constraints(constraints.into())
+                .build()?,
+        )
+        .await
+    }
+
+    /// Performs a provider auction on the host using a complete auction request. See [`Client::perform_provider_auction_request`](crate::Client::perform_provider_auction_request)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn perform_provider_auction_request(
+        &self,
+        mut request: ProviderAuctionRequest,
+    ) -> Result<Vec<CtlResponse<ProviderAuctionAck>>> {
+        request.provider_ref = IdentifierKind::is_provider_ref(&request.provider_ref)?;
+        request.provider_id = IdentifierKind::is_provider_id(&request.provider_id)?;
+        let bytes = json_serialize(request)?;
+        let ack = self.send(&["provider", "auction"], Some(bytes)).await?;
+        Ok(ack.into_iter().collect())
+    }
+
+    /// Sends a request to the host to scale a given component. See [`Client::scale_component`](crate::Client::scale_component)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn scale_component(
+        &self,
+        host_id: &str,
+        component_ref: &str,
+        component_id: &str,
+        max_instances: u32,
+        annotations: Option<BTreeMap<String, String>>,
+        config: Vec<String>,
+    ) -> Result<CtlResponse<()>> {
+        self.request_scale_component(ScaleComponentCommand {
+            max_instances,
+            component_ref: IdentifierKind::is_component_ref(component_ref)?,
+            component_id: IdentifierKind::is_component_id(component_id)?,
+            host_id: IdentifierKind::is_host_id(host_id)?,
+            annotations,
+            config,
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Sends a request to the host to scale a given component, applying the given resource limits
+    /// to every instance of it. See [`Client::scale_component_with_limits`](crate::Client::scale_component_with_limits)
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(level = "debug", skip_all)]
+    pub async fn scale_component_with_limits(
+        &self,
+        host_id: &str,
+        component_ref: &str,
+        component_id: &str,
+        max_instances: u32,
+        annotations: Option<BTreeMap<String, String>>,
+        config: Vec<String>,
+        limits: ComponentResourceLimits,
+    ) -> Result<CtlResponse<()>> {
+        self.request_scale_component(ScaleComponentCommand {
+            max_instances,
+            component_ref: IdentifierKind::is_component_ref(component_ref)?,
+            component_id: IdentifierKind::is_component_id(component_id)?,
+            host_id: IdentifierKind::is_host_id(host_id)?,
+            annotations,
+            config,
+            limits: Some(limits),
+            ..Default::default()
+        })
+        .await
+    }
+
+    async fn request_scale_component(
+        &self,
+        command: ScaleComponentCommand,
+    ) -> Result<CtlResponse<()>> {
+        let host_id = command.host_id.clone();
+        let bytes = json_serialize(command)?;
+        match self
+            .request(&["component", "scale", host_id.as_str()], Some(bytes))
+            .await
+        {
+            Ok(ack) => Ok(ack),
+            Err(e) => Err(format!("Did not receive scale component acknowledgement: {e}").into()),
+        }
+    }
+
+    /// Replaces the registry credential map of the host
+    #[instrument(level = "debug", skip_all)]
+    pub async fn put_registries(
+        &self,
+        registries: HashMap<String, RegistryCredential>,
+    ) -> Result<CtlResponse<()>> {
+        let bytes = json_serialize(&registries)?;
+        match self.request(&["registry", "put"], Some(bytes)).await {
+            Ok(ack) => Ok(ack),
+            Err(e) => Err(format!("Failed to push registry credential map: {e}").into()),
+        }
+    }
+
+    /// Puts a link into the lattice
+    #[instrument(level = "debug", skip_all)]
+    pub async fn put_link(&self, link: Link) -> Result<CtlResponse<()>> {
+        IdentifierKind::is_component_id(&link.source_id)?;
+        IdentifierKind::is_component_id(&link.target)?;
+        IdentifierKind::is_link_name(&link.name)?;
+
+        let bytes = json_serialize(link)?;
+        match self.request(&["link", "put"], Some(bytes)).await {
+            Ok(ack) => Ok(ack),
+            Err(e) => Err(format!("Did not receive put link acknowledgement: {e}").into()),
+        }
+    }
+
+    /// Deletes a link from the lattice
+    #[instrument(level = "debug", skip_all)]
+    pub async fn delete_link(
+        &self,
+        source_id: &str,
+        link_name: &str,
+        wit_namespace: &str,
+        wit_package: &str,
+    ) -> Result<CtlResponse<()>> {
+        let ld = DeleteInterfaceLinkDefinitionRequest::from_source_and_link_metadata(
+            &IdentifierKind::is_component_id(source_id)?,
+            &IdentifierKind::is_link_name(link_name)?,
+            wit_namespace,
+            wit_package,
+        );
+        let bytes = json_serialize(&ld)?;
+        match self.request(&["link", "del"], Some(bytes)).await {
+            Ok(ack) => Ok(ack),
+            Err(e) => Err(format!("Did not receive delete link acknowledgement: {e}").into()),
+        }
+    }
+
+    /// Retrieves the list of link definitions known to the host
+    #[instrument(level = "debug", skip_all)]
+    pub async fn get_links(&self) -> Result<CtlResponse<Vec<Link>>> {
+        match self.request(&["link", "get"], None).await {
+            Ok(links) => Ok(links),
+            Err(e) => Err(format!("Did not receive a response to get links: {e}").into()),
+        }
+    }
+
+    /// Puts a named config, replacing any data that is already present
+    #[instrument(level = "debug", skip_all)]
+    pub async fn put_config(
+        &self,
+        config_name: &str,
+        config: impl Into<HashMap<String, String>>,
+    ) -> Result<CtlResponse<()>> {
+        let data = json_serialize(config.into())?;
+        match self
+            .request(&["config", "put", config_name], Some(data))
+            .await
+        {
+            Ok(ack) => Ok(ack),
+            Err(e) => Err(format!("Did not receive a response to put config request: {e}").into()),
+        }
+    }
+
+    /// Delete the named config item
+    #[instrument(level = "debug", skip_all)]
+    pub async fn delete_config(&self, config_name: &str) -> Result<CtlResponse<()>> {
+        match self
+            .request(&["config", "del", config_name], Some(Vec::default()))
+            .await
+        {
+            Ok(ack) => Ok(ack),
+            Err(e) => {
+                Err(format!("Did not receive a response to delete config request: {e}").into())
+            }
+        }
+    }
+
+    /// Get the named config item. See [`Client::get_config`](crate::Client::get_config)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn get_config(
+        &self,
+        config_name: &str,
+    ) -> Result<CtlResponse<HashMap<String, String>>> {
+        match self.request(&["config", "get", config_name], None).await {
+            Ok(config) => Ok(config),
+            Err(e) => Err(format!("Did not receive a response to get config request: {e}").into()),
+        }
+    }
+
+    /// Get the named config item as it was at the given revision. See [`Client::get_config_revision`](crate::Client::get_config_revision)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn get_config_revision(
+        &self,
+        config_name: &str,
+        revision: u64,
+    ) -> Result<CtlResponse<HashMap<String, String>>> {
+        let revision = revision.to_string();
+        match self
+            .request(&["config", "get", config_name, revision.as_str()], None)
+            .await
+        {
+            Ok(config) => Ok(config),
+            Err(e) => Err(format!(
+                "Did not receive a response to get config revision request: {e}"
+            )
+            .into()),
+        }
+    }
+
+    /// Get the revision history of the named config item. See [`Client::get_config_history`](crate::Client::get_config_history)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn get_config_history(
+        &self,
+        config_name: &str,
+    ) -> Result<CtlResponse<Vec<ConfigRevision>>> {
+        match self
+            .request(&["config", "history", config_name], None)
+            .await
+        {
+            Ok(history) => Ok(history),
+            Err(e) => {
+                Err(format!("Did not receive a response to get config history request: {e}").into())
+            }
+        }
+    }
+
+    /// Restore the named config item to the value it had at the given revision. See [`Client::rollback_config`](crate::Client::rollback_config)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn rollback_config(
+        &self,
+        config_name: &str,
+        revision: u64,
+    ) -> Result<CtlResponse<ConfigRevision>> {
+        let bytes = json_serialize(
+            RollbackConfigCommand::builder()
+                .revision(revision)
+                .build()?,
+        )?;
+        match self
+            .request(&["config", "rollback", config_name], Some(bytes))
+            .await
+        {
+            Ok(revision) => Ok(revision),
+            Err(e) => {
+                Err(format!("Did not receive a response to rollback config request: {e}").into())
+            }
+        }
+    }
+
+    /// Put a new (or update an existing) label on the host
+    #[instrument(level = "debug", skip_all)]
+    pub async fn put_label(
+        &self,
+        host_id: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<CtlResponse<()>> {
+        let host_id = IdentifierKind::is_host_id(host_id)?;
+        let bytes = json_serialize(HostLabel {
+            key: key.to_string(),
+            value: value.to_string(),
+        })?;
+        match self
+            .request(&["label", "put", host_id.as_str()], Some(bytes))
+            .await
+        {
+            Ok(ack) => Ok(ack),
+            Err(e) => Err(format!("Did not receive put label acknowledgement: {e}").into()),
+        }
+    }
+
+    /// Removes a label from the host
+    #[instrument(level = "debug", skip_all)]
+    pub async fn delete_label(&self, host_id: &str, key: &str) -> Result<CtlResponse<()>> {
+        let host_id = IdentifierKind::is_host_id(host_id)?;
+        let bytes = json_serialize(HostLabelIdentifier {
+            key: key.to_string(),
+        })?;
+        match self
+            .request(&["label", "del", host_id.as_str()], Some(bytes))
+            .await
+        {
+            Ok(ack) => Ok(ack),
+            Err(e) => Err(format!("Did not receive remove label acknowledgement: {e}").into()),
+        }
+    }
+
+    /// Command the host to replace an existing component with a new component indicated by an OCI
+    /// image reference. See [`Client::update_component`](crate::Client::update_component)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn update_component(
+        &self,
+        host_id: &str,
+        existing_component_id: &str,
+        new_component_ref: &str,
+        annotations: Option<BTreeMap<String, String>>,
+    ) -> Result<CtlResponse<()>> {
+        let host_id = IdentifierKind::is_host_id(host_id)?;
+        let bytes = json_serialize(UpdateComponentCommand {
+            host_id: host_id.clone(),
+            component_id: IdentifierKind::is_component_id(existing_component_id)?,
+            new_component_ref: IdentifierKind::is_component_ref(new_component_ref)?,
+            annotations,
+        })?;
+        match self
+            .request(&["component", "update", host_id.as_str()], Some(bytes))
+            .await
+        {
+            Ok(ack) => Ok(ack),
+            Err(e) => Err(format!("Did not receive update component acknowledgement: {e}").into()),
+        }
+    }
+
+    /// Command the host to start a provider with a given OCI reference. See [`Client::start_provider`](crate::Client::start_provider)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn start_provider(
+        &self,
+        host_id: &str,
+        provider_ref: &str,
+        provider_id: &str,
+        annotations: Option<BTreeMap<String, String>>,
+        provider_configuration: Vec<String>,
+    ) -> Result<CtlResponse<()>> {
+        let host_id = IdentifierKind::is_host_id(host_id)?;
+        let mut cmd = StartProviderCommand::builder()
+            .host_id(&host_id)
+            .provider_ref(&IdentifierKind::is_provider_ref(provider_ref)?)
+            .provider_id(&IdentifierKind::is_component_id(provider_id)?);
+        if let Some(annotations) = annotations {
+            cmd = cmd.annotations(annotations);
+        }
+        let cmd = cmd.config(provider_configuration).build()?;
+        let bytes = json_serialize(cmd)?;
+        match self
+            .request(&["provider", "start", host_id.as_str()], Some(bytes))
+            .await
+        {
+            Ok(ack) => Ok(ack),
+            Err(e) => Err(format!("Did not receive start provider acknowledgement: {e}").into()),
+        }
+    }
+
+    /// Issues a command to the host to stop a provider. See [`Client::stop_provider`](crate::Client::stop_provider)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn stop_provider(&self, host_id: &str, provider_id: &str) -> Result<CtlResponse<()>> {
+        let host_id = IdentifierKind::is_host_id(host_id)?;
+        let bytes = json_serialize(StopProviderCommand {
+            host_id: host_id.clone(),
+            provider_id: IdentifierKind::is_component_id(provider_id)?,
+        })?;
+        match self
+            .request(&["provider", "stop", host_id.as_str()], Some(bytes))
+            .await
+        {
+            Ok(ack) => Ok(ack),
+            Err(e) => Err(format!("Did not receive stop provider acknowledgement: {e}").into()),
+        }
+    }
+
+    /// Retrieves the most recent output of a provider running on the host. See [`Client::get_provider_logs`](crate::Client::get_provider_logs)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn get_provider_logs(
+        &self,
+        host_id: &str,
+        provider_id: &str,
+        since: Option<u64>,
+        max_lines: Option<usize>,
+    ) -> Result<CtlResponse<ProviderLogs>> {
+        let host_id = IdentifierKind::is_host_id(host_id)?;
+        let bytes = json_serialize(GetProviderLogsCommand {
+            host_id: host_id.clone(),
+            provider_id: IdentifierKind::is_component_id(provider_id)?,
+            since,
+            max_lines,
+        })?;
+        match self
+            .request(&["provider", "logs", host_id.as_str()], Some(bytes))
+            .await
+        {
+            Ok(logs) => Ok(logs),
+            Err(e) => Err(format!("Did not receive provider logs from target host: {e}").into()),
+        }
+    }
+
+    /// Issues a command to the host to perform a graceful termination. See [`Client::stop_host`](crate::Client::stop_host)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn stop_host(
+        &self,
+        host_id: &str,
+        timeout_ms: Option<u64>,
+    ) -> Result<CtlResponse<()>> {
+        let host_id = IdentifierKind::is_host_id(host_id)?;
+        let bytes = json_serialize(StopHostCommand {
+            host_id: host_id.clone(),
+            timeout: timeout_ms,
+        })?;
+        match self
+            .request(&["host", "stop", host_id.as_str()], Some(bytes))
+            .await
+        {
+            Ok(ack) => Ok(ack),
+            Err(e) => Err(format!("Did not receive stop host acknowledgement: {e}").into()),
+        }
+    }
+
+    /// Issues a command to the host to cordon itself. See [`Client::cordon_host`](crate::Client::cordon_host)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn cordon_host(&self, host_id: &str) -> Result<CtlResponse<()>> {
+        self.set_host_cordoned(host_id, true).await
+    }
+
+    /// Issues a command to the host to uncordon itself. See [`Client::uncordon_host`](crate::Client::uncordon_host)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn uncordon_host(&self, host_id: &str) -> Result<CtlResponse<()>> {
+        self.set_host_cordoned(host_id, false).await
+    }
+
+    async fn set_host_cordoned(&self, host_id: &str, cordoned: bool) -> Result<CtlResponse<()>> {
+        let host_id = IdentifierKind::is_host_id(host_id)?;
+        let bytes = json_serialize(CordonHostCommand {
+            host_id: host_id.clone(),
+            cordoned,
+        })?;
+        match self
+            .request(&["host", "cordon", host_id.as_str()], Some(bytes))
+            .await
+        {
+            Ok(ack) => Ok(ack),
+            Err(e) => Err(format!("Did not receive cordon host acknowledgement: {e}").into()),
+        }
+    }
+
+    /// Issues a command to the host to drain its workloads. See [`Client::drain_host`](crate::Client::drain_host)
+    #[instrument(level = "debug", skip_all)]
+    pub async fn drain_host(
+        &self,
+        host_id: &str,
+        timeout_ms: Option<u64>,
+        stop_host: bool,
+    ) -> Result<CtlResponse<()>> {
+        let host_id = IdentifierKind::is_host_id(host_id)?;
+        let bytes = json_serialize(DrainHostCommand {
+            host_id: host_id.clone(),
+            timeout: timeout_ms,
+            stop_host,
+        })?;
+        match self
+            .request(&["host", "drain", host_id.as_str()], Some(bytes))
+            .await
+        {
+            Ok(ack) => Ok(ack),
+            Err(e) => Err(format!("Did not receive drain host acknowledgement: {e}").into()),
+        }
+    }
+
+    /// Returns the receiver end of a channel that receives the events published by the host, which
+    /// are streamed as Server-Sent Events.
+    ///
+    /// # Arguments
+    ///
+    /// * `event_types` - List of types of events to listen for, all events are received if empty
+    ///
+    #[allow(clippy::missing_errors_doc)] // TODO: Document errors
+    pub async fn events_receiver(&self, event_types: Vec<String>) -> Result<Receiver<Event>> {
+        let mut url = self.endpoint(["api", "v1", "events"]);
+        for event_type in &event_types {
+            url.query_pairs_mut().append_pair("type", event_type);
+        }
+        let mut res = self.http.get(url).bearer_auth(&self.token).send().await?;
+        if !res.status().is_success() {
+            return Err(
+                format!("failed to subscribe to events with status {}", res.status()).into(),
+            );
+        }
+        let (sender, receiver) = tokio::sync::mpsc::channel(5000);
+        tokio::spawn(async move {
+            let mut buf = Vec::default();
+            loop {
+                let chunk = match res.chunk().await {
+                    Ok(Some(chunk)) => chunk,
+                    Ok(None) => break,
+                    Err(error) => {
+                        error!(%error, "failed to read event stream");
+                        break;
+                    }
+                };
+                buf.extend_from_slice(&chunk);
+                while let Some(end) = buf.windows(2).position(|w| w == b"\n\n") {
+                    let frame: Vec<u8> = buf.drain(..end + 2).collect();
+                    let Some(evt) = parse_event(&String::from_utf8_lossy(&frame)) else {
+                        continue;
+                    };
+                    trace!("received event: {:?}", evt);
+                    let Ok(()) = sender.send(evt).await else {
+                        return;
+                    };
+                }
+            }
+        });
+        Ok(receiver)
+    }
+}
+
+/// Parse a Server-Sent Event frame carrying a CloudEvent. Returns `None` for frames without data,
+/// such as keep-alive comments, and for invalid events
+fn parse_event(frame: &str) -> Option<Event> {
+    let data: Vec<&str> = frame
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect();
+    if data.is_empty() {
+        return None;
+    }
+    match json_deserialize::<Event>(data.join("\n").as_bytes()) {
+        Ok(evt) => Some(evt),
+        Err(_) => {
+            error!("Object received on event stream was not a CloudEvent");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint() {
+        for base in ["http://127.0.0.1:8080", "http://127.0.0.1:8080/"] {
+            let client = HttpClientBuilder::new(base.parse().unwrap(), "token")
+                .build()
+                .unwrap();
+            assert_eq!(
+                client
+                    .endpoint(["api", "v1", "ctl", "config", "get", "a/b"])
+                    .as_str(),
+                "http://127.0.0.1:8080/api/v1/ctl/config/get/a%2Fb"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_event() {
+        assert!(parse_event(": keep-alive\n\n").is_none());
+        let evt = parse_event(
+            "event: host_heartbeat\ndata: {\"specversion\":\"1.0\",\"id\":\"1\",\"type\":\"com.wasmcloud.lattice.host_heartbeat\",\"source\":\"host\"}\n\n",
+        )
+        .expect("failed to parse event");
+        assert_eq!(
+            cloudevents::AttributesReader::ty(&evt),
+            "com.wasmcloud.lattice.host_heartbeat"
+        );
+    }
+}
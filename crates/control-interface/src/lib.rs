@@ -14,6 +14,9 @@
 //! result should be handled for protocol (timeouts, no hosts available) and deserialization errors (invalid response payload).
 //! The inner result is the actual response from the host(s) and should be handled for application-level errors.
 //!
+//! With the `http` feature enabled, [`HttpClient`] provides the same functions for a single host,
+//! using the HTTP/JSON transport served on the host HTTP administration endpoint instead of NATS.
+//!
 //! [docs-control-interface]: <https://wasmcloud.com/docs/hosts/lattice-protocols/control-interface>
 //! [wash]: <https://wasmcloud.com/docs/ecosystem/wash>
 
@@ -25,10 +28,17 @@ mod otel;
 pub mod client;
 pub use client::{Client, ClientBuilder};
 
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "http")]
+pub use http::{HttpClient, HttpClientBuilder};
+
 mod types;
 pub use types::component::*;
+pub use types::config::*;
 pub use types::ctl::*;
 pub use types::host::*;
+pub use types::inventory::*;
 pub use types::link::*;
 pub use types::provider::*;
 pub use types::registry::*;
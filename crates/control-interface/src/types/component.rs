@@ -34,6 +34,14 @@ pub struct ComponentDescription {
     /// The maximum number of concurrent requests this instance can handle
     #[serde(default)]
     pub(crate) max_instances: u32,
+
+    /// SHA256 digest of the component bytes running on the host, of the form `sha256:<hex>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) digest: Option<String>,
+
+    /// The claims embedded in the component, if present, e.g. `issuer`, `subject` and `expires`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) claims: Option<BTreeMap<String, String>>,
 }
 
 #[derive(Default, Clone, PartialEq, Eq)]
@@ -45,6 +53,8 @@ pub struct ComponentDescriptionBuilder {
     annotations: Option<BTreeMap<String, String>>,
     revision: Option<i32>,
     max_instances: Option<u32>,
+    digest: Option<String>,
+    claims: Option<BTreeMap<String, String>>,
 }
 
 impl ComponentDescriptionBuilder {
@@ -84,6 +94,18 @@ impl ComponentDescriptionBuilder {
         self
     }
 
+    #[must_use]
+    pub fn digest(mut self, v: String) -> Self {
+        self.digest = Some(v);
+        self
+    }
+
+    #[must_use]
+    pub fn claims(mut self, v: BTreeMap<String, String>) -> Self {
+        self.claims = Some(v);
+        self
+    }
+
     pub fn build(self) -> Result<ComponentDescription> {
         Ok(ComponentDescription {
             image_ref: self
@@ -94,6 +116,8 @@ impl ComponentDescriptionBuilder {
             revision: self.revision.unwrap_or_default(),
             max_instances: self.max_instances.unwrap_or_default(),
             annotations: self.annotations,
+            digest: self.digest,
+            claims: self.claims,
         })
     }
 }
@@ -129,6 +153,16 @@ impl ComponentDescription {
         self.max_instances
     }
 
+    /// Get the SHA256 digest of the component bytes, if known
+    pub fn digest(&self) -> Option<&str> {
+        self.digest.as_deref()
+    }
+
+    /// Get the claims embedded in the component, if present
+    pub fn claims(&self) -> Option<&BTreeMap<String, String>> {
+        self.claims.as_ref()
+    }
+
     #[must_use]
     pub fn builder() -> ComponentDescriptionBuilder {
         ComponentDescriptionBuilder::default()
@@ -264,6 +298,8 @@ mod tests {
                 annotations: Some(BTreeMap::from([("a".into(), "b".into())])),
                 revision: 0,
                 max_instances: 1,
+                digest: Some("sha256:abcd".into()),
+                claims: Some(BTreeMap::from([("issuer".into(), "AAAA".into())])),
             },
             ComponentDescription::builder()
                 .id("id".into())
@@ -273,6 +309,8 @@ mod tests {
                 .annotations(BTreeMap::from([("a".into(), "b".into())]))
                 .revision(0)
                 .max_instances(1)
+                .digest("sha256:abcd".into())
+                .claims(BTreeMap::from([("issuer".into(), "AAAA".into())]))
                 .build()
                 .unwrap()
         )
@@ -0,0 +1,130 @@
+//! Data types used when managing named configuration in a lattice
+
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+
+/// A revision of a named configuration, as recorded by the configuration store of the lattice
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct ConfigRevision {
+    /// Name of the configuration
+    #[serde(default)]
+    pub(crate) name: String,
+    /// Revision number assigned by the configuration store, increasing with every change
+    #[serde(default)]
+    pub(crate) revision: u64,
+    /// When the revision was created, as an RFC 3339 timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) created_at: Option<String>,
+    /// Whether the revision records the deletion of the configuration
+    #[serde(default)]
+    pub(crate) deleted: bool,
+}
+
+impl ConfigRevision {
+    /// Get the name of the configuration
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the revision number
+    #[must_use]
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Get when the revision was created, as an RFC 3339 timestamp
+    #[must_use]
+    pub fn created_at(&self) -> Option<&str> {
+        self.created_at.as_deref()
+    }
+
+    /// Get whether the revision records the deletion of the configuration
+    #[must_use]
+    pub fn deleted(&self) -> bool {
+        self.deleted
+    }
+
+    #[must_use]
+    pub fn builder() -> ConfigRevisionBuilder {
+        ConfigRevisionBuilder::default()
+    }
+}
+
+/// Builds [`ConfigRevision`]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ConfigRevisionBuilder {
+    name: Option<String>,
+    revision: Option<u64>,
+    created_at: Option<String>,
+    deleted: Option<bool>,
+}
+
+impl ConfigRevisionBuilder {
+    /// Name of the configuration
+    #[must_use]
+    pub fn name(mut self, v: &str) -> Self {
+        self.name = Some(v.into());
+        self
+    }
+
+    /// Revision number assigned by the configuration store
+    #[must_use]
+    pub fn revision(mut self, v: u64) -> Self {
+        self.revision = Some(v);
+        self
+    }
+
+    /// When the revision was created, as an RFC 3339 timestamp
+    #[must_use]
+    pub fn created_at(mut self, v: &str) -> Self {
+        self.created_at = Some(v.into());
+        self
+    }
+
+    /// Whether the revision records the deletion of the configuration
+    #[must_use]
+    pub fn deleted(mut self, v: bool) -> Self {
+        self.deleted = Some(v);
+        self
+    }
+
+    /// Build [`ConfigRevision`]
+    pub fn build(self) -> Result<ConfigRevision> {
+        Ok(ConfigRevision {
+            name: self.name.ok_or_else(|| "name is required".to_string())?,
+            revision: self
+                .revision
+                .ok_or_else(|| "revision is required".to_string())?,
+            created_at: self.created_at,
+            deleted: self.deleted.unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ConfigRevision;
+
+    #[test]
+    fn config_revision_builder() {
+        assert_eq!(
+            ConfigRevision {
+                name: "name".into(),
+                revision: 3,
+                created_at: Some("2024-01-01T00:00:00Z".into()),
+                deleted: false,
+            },
+            ConfigRevision::builder()
+                .name("name")
+                .revision(3)
+                .created_at("2024-01-01T00:00:00Z")
+                .build()
+                .unwrap()
+        );
+        assert!(ConfigRevision::builder().name("name").build().is_err());
+    }
+}
@@ -116,6 +116,10 @@ pub struct ScaleComponentCommand {
     /// Normally this is implemented by the receiver (ex. wasmcloud host) as a *separate* update component call
     /// being made shortly after this command (scale) is processed.
     pub(crate) allow_update: bool,
+    /// Optional resource limits applied to every instance of this component. Limits that are not
+    /// set default to the host-wide settings, and limits exceeding them are rejected by the host.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) limits: Option<ComponentResourceLimits>,
 }
 
 impl ScaleComponentCommand {
@@ -154,6 +158,11 @@ impl ScaleComponentCommand {
         &self.host_id
     }
 
+    #[must_use]
+    pub fn limits(&self) -> Option<&ComponentResourceLimits> {
+        self.limits.as_ref()
+    }
+
     #[must_use]
     pub fn builder() -> ScaleComponentCommandBuilder {
         ScaleComponentCommandBuilder::default()
@@ -171,6 +180,7 @@ pub struct ScaleComponentCommandBuilder {
     host_id: Option<String>,
     config: Option<Vec<String>>,
     allow_update: Option<bool>,
+    limits: Option<ComponentResourceLimits>,
 }
 
 impl ScaleComponentCommandBuilder {
@@ -221,6 +231,12 @@ impl ScaleComponentCommandBuilder {
         self
     }
 
+    #[must_use]
+    pub fn limits(mut self, v: ComponentResourceLimits) -> Self {
+        self.limits = Some(v);
+        self
+    }
+
     pub fn build(self) -> Result<ScaleComponentCommand> {
         Ok(ScaleComponentCommand {
             component_ref: self
@@ -236,6 +252,110 @@ impl ScaleComponentCommandBuilder {
                 .ok_or_else(|| "host id is required for scaling hosts host".to_string())?,
             config: self.config.unwrap_or_default(),
             allow_update: self.allow_update.unwrap_or_default(),
+            limits: self.limits,
+        })
+    }
+}
+
+/// Resource limits applied to every instance of a component, overriding the host-wide settings
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct ComponentResourceLimits {
+    /// Maximum amount of linear memory, in bytes, that a single linear memory of a component
+    /// instance can grow to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) max_linear_memory: Option<u64>,
+    /// Maximum execution time of a single invocation of the component, in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) max_execution_time_ms: Option<u64>,
+    /// Maximum number of elements in a single table of a component instance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) max_table_elements: Option<u32>,
+    /// Maximum number of invocations of the component handled concurrently on the host. This is
+    /// capped by the maximum number of instances of the component
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) max_concurrency: Option<u32>,
+}
+
+impl ComponentResourceLimits {
+    #[must_use]
+    pub fn max_linear_memory(&self) -> Option<u64> {
+        self.max_linear_memory
+    }
+
+    #[must_use]
+    pub fn max_execution_time_ms(&self) -> Option<u64> {
+        self.max_execution_time_ms
+    }
+
+    #[must_use]
+    pub fn max_table_elements(&self) -> Option<u32> {
+        self.max_table_elements
+    }
+
+    #[must_use]
+    pub fn max_concurrency(&self) -> Option<u32> {
+        self.max_concurrency
+    }
+
+    #[must_use]
+    pub fn builder() -> ComponentResourceLimitsBuilder {
+        ComponentResourceLimitsBuilder::default()
+    }
+}
+
+/// Builder for [`ComponentResourceLimits`]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ComponentResourceLimitsBuilder {
+    max_linear_memory: Option<u64>,
+    max_execution_time_ms: Option<u64>,
+    max_table_elements: Option<u32>,
+    max_concurrency: Option<u32>,
+}
+
+impl ComponentResourceLimitsBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn max_linear_memory(mut self, v: u64) -> Self {
+        self.max_linear_memory = Some(v);
+        self
+    }
+
+    #[must_use]
+    pub fn max_execution_time_ms(mut self, v: u64) -> Self {
+        self.max_execution_time_ms = Some(v);
+        self
+    }
+
+    #[must_use]
+    pub fn max_table_elements(mut self, v: u32) -> Self {
+        self.max_table_elements = Some(v);
+        self
+    }
+
+    #[must_use]
+    pub fn max_concurrency(mut self, v: u32) -> Self {
+        self.max_concurrency = Some(v);
+        self
+    }
+
+    pub fn build(self) -> Result<ComponentResourceLimits> {
+        if self.max_concurrency == Some(0) {
+            return Err("max concurrency must be greater than zero".into());
+        }
+        if self.max_execution_time_ms == Some(0) {
+            return Err("max execution time must be greater than zero".into());
+        }
+        Ok(ComponentResourceLimits {
+            max_linear_memory: self.max_linear_memory,
+            max_execution_time_ms: self.max_execution_time_ms,
+            max_table_elements: self.max_table_elements,
+            max_concurrency: self.max_concurrency,
         })
     }
 }
@@ -426,6 +546,156 @@ impl StopHostCommandBuilder {
     }
 }
 
+/// A command sent to request that the given host be cordoned or uncordoned. A cordoned host does
+/// not respond to component or provider auctions, but keeps running its existing workloads
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct CordonHostCommand {
+    /// The ID of the target host
+    #[serde(default)]
+    pub(crate) host_id: String,
+    /// Whether the host should be cordoned (`true`) or uncordoned (`false`)
+    #[serde(default = "default_cordoned")]
+    pub(crate) cordoned: bool,
+}
+
+fn default_cordoned() -> bool {
+    true
+}
+
+impl CordonHostCommand {
+    #[must_use]
+    pub fn host_id(&self) -> &str {
+        &self.host_id
+    }
+
+    #[must_use]
+    pub fn cordoned(&self) -> bool {
+        self.cordoned
+    }
+
+    #[must_use]
+    pub fn builder() -> CordonHostCommandBuilder {
+        CordonHostCommandBuilder::default()
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CordonHostCommandBuilder {
+    host_id: Option<String>,
+    cordoned: Option<bool>,
+}
+
+impl CordonHostCommandBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn host_id(mut self, v: &str) -> Self {
+        self.host_id = Some(v.into());
+        self
+    }
+
+    #[must_use]
+    pub fn cordoned(mut self, v: bool) -> Self {
+        self.cordoned = Some(v);
+        self
+    }
+
+    pub fn build(self) -> Result<CordonHostCommand> {
+        Ok(CordonHostCommand {
+            host_id: self
+                .host_id
+                .ok_or_else(|| "host id is required for cordoning host".to_string())?,
+            cordoned: self.cordoned.unwrap_or_else(default_cordoned),
+        })
+    }
+}
+
+/// A command sent to request that the given host drain its workloads. The host is cordoned,
+/// waits for in-flight invocations to finish and then stops all components and providers
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct DrainHostCommand {
+    /// The ID of the target host
+    #[serde(default)]
+    pub(crate) host_id: String,
+    /// An optional timeout, in milliseconds, to wait for in-flight invocations to finish
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) timeout: Option<u64>,
+    /// Whether the host should stop once drained
+    #[serde(default)]
+    pub(crate) stop_host: bool,
+}
+
+impl DrainHostCommand {
+    #[must_use]
+    pub fn host_id(&self) -> &str {
+        &self.host_id
+    }
+
+    #[must_use]
+    pub fn timeout(&self) -> Option<u64> {
+        self.timeout
+    }
+
+    #[must_use]
+    pub fn stop_host(&self) -> bool {
+        self.stop_host
+    }
+
+    #[must_use]
+    pub fn builder() -> DrainHostCommandBuilder {
+        DrainHostCommandBuilder::default()
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct DrainHostCommandBuilder {
+    host_id: Option<String>,
+    timeout: Option<u64>,
+    stop_host: Option<bool>,
+}
+
+impl DrainHostCommandBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn host_id(mut self, v: &str) -> Self {
+        self.host_id = Some(v.into());
+        self
+    }
+
+    #[must_use]
+    pub fn timeout(mut self, v: u64) -> Self {
+        self.timeout = Some(v);
+        self
+    }
+
+    #[must_use]
+    pub fn stop_host(mut self, v: bool) -> Self {
+        self.stop_host = Some(v);
+        self
+    }
+
+    pub fn build(self) -> Result<DrainHostCommand> {
+        Ok(DrainHostCommand {
+            host_id: self
+                .host_id
+                .ok_or_else(|| "host id is required for draining host".to_string())?,
+            timeout: self.timeout,
+            stop_host: self.stop_host.unwrap_or_default(),
+        })
+    }
+}
+
 /// A request to stop the given provider on the indicated host
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 #[non_exhaustive]
@@ -598,15 +868,200 @@ impl UpdateComponentCommandBuilder {
     }
 }
 
+/// A request for the most recent output of the given provider on the indicated host
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct GetProviderLogsCommand {
+    /// Host ID on which the provider is running
+    #[serde(default)]
+    pub(crate) host_id: String,
+    /// Unique identifier of the provider
+    #[serde(default)]
+    pub(crate) provider_id: String,
+    /// Only return lines with a sequence number greater than this one, used to follow the output
+    /// of a provider
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) since: Option<u64>,
+    /// Maximum number of lines to return, the most recent lines are returned if more are buffered
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) max_lines: Option<usize>,
+}
+
+impl GetProviderLogsCommand {
+    #[must_use]
+    pub fn host_id(&self) -> &str {
+        &self.host_id
+    }
+
+    #[must_use]
+    pub fn provider_id(&self) -> &str {
+        &self.provider_id
+    }
+
+    #[must_use]
+    pub fn since(&self) -> Option<u64> {
+        self.since
+    }
+
+    #[must_use]
+    pub fn max_lines(&self) -> Option<usize> {
+        self.max_lines
+    }
+
+    #[must_use]
+    pub fn builder() -> GetProviderLogsCommandBuilder {
+        GetProviderLogsCommandBuilder::default()
+    }
+}
+
+/// Builder for [`GetProviderLogsCommand`]s
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct GetProviderLogsCommandBuilder {
+    host_id: Option<String>,
+    provider_id: Option<String>,
+    since: Option<u64>,
+    max_lines: Option<usize>,
+}
+
+impl GetProviderLogsCommandBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn host_id(mut self, v: &str) -> Self {
+        self.host_id = Some(v.into());
+        self
+    }
+
+    #[must_use]
+    pub fn provider_id(mut self, v: &str) -> Self {
+        self.provider_id = Some(v.into());
+        self
+    }
+
+    #[must_use]
+    pub fn since(mut self, v: u64) -> Self {
+        self.since = Some(v);
+        self
+    }
+
+    #[must_use]
+    pub fn max_lines(mut self, v: usize) -> Self {
+        self.max_lines = Some(v);
+        self
+    }
+
+    pub fn build(self) -> Result<GetProviderLogsCommand> {
+        Ok(GetProviderLogsCommand {
+            host_id: self
+                .host_id
+                .ok_or_else(|| "host id is required for getting provider logs".to_string())?,
+            provider_id: self
+                .provider_id
+                .ok_or_else(|| "provider id is required for getting provider logs".to_string())?,
+            since: self.since,
+            max_lines: self.max_lines,
+        })
+    }
+}
+
+/// A command sent to request that a named configuration be restored to the value it had at the
+/// given revision, which is stored as a new revision
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct RollbackConfigCommand {
+    /// The revision of the configuration to restore
+    #[serde(default)]
+    pub(crate) revision: u64,
+}
+
+impl RollbackConfigCommand {
+    #[must_use]
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    #[must_use]
+    pub fn builder() -> RollbackConfigCommandBuilder {
+        RollbackConfigCommandBuilder::default()
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct RollbackConfigCommandBuilder {
+    revision: Option<u64>,
+}
+
+impl RollbackConfigCommandBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn revision(mut self, v: u64) -> Self {
+        self.revision = Some(v);
+        self
+    }
+
+    pub fn build(self) -> Result<RollbackConfigCommand> {
+        Ok(RollbackConfigCommand {
+            revision: self
+                .revision
+                .ok_or_else(|| "revision is required for rolling back config".to_string())?,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeMap;
 
     use super::{
-        ScaleComponentCommand, StartProviderCommand, StopHostCommand, StopProviderCommand,
-        UpdateComponentCommand,
+        ComponentResourceLimits, CordonHostCommand, DrainHostCommand, GetProviderLogsCommand,
+        RollbackConfigCommand, ScaleComponentCommand, StartProviderCommand, StopHostCommand,
+        StopProviderCommand, UpdateComponentCommand,
     };
 
+    #[test]
+    fn rollback_config_command_builder() {
+        assert_eq!(
+            RollbackConfigCommand { revision: 7 },
+            RollbackConfigCommand::builder()
+                .revision(7)
+                .build()
+                .unwrap()
+        );
+        assert!(RollbackConfigCommand::builder().build().is_err());
+    }
+
+    #[test]
+    fn get_provider_logs_command_builder() {
+        assert_eq!(
+            GetProviderLogsCommand {
+                host_id: "host_id".into(),
+                provider_id: "provider_id".into(),
+                since: Some(41),
+                max_lines: Some(100),
+            },
+            GetProviderLogsCommand::builder()
+                .host_id("host_id")
+                .provider_id("provider_id")
+                .since(41)
+                .max_lines(100)
+                .build()
+                .unwrap()
+        );
+        assert!(GetProviderLogsCommand::builder()
+            .host_id("host_id")
+            .build()
+            .is_err());
+    }
+
     #[test]
     fn scale_component_command_builder() {
         assert_eq!(
@@ -618,6 +1073,10 @@ mod tests {
                 allow_update: true,
                 annotations: Some(BTreeMap::from([("a".into(), "b".into())])),
                 max_instances: 1,
+                limits: Some(ComponentResourceLimits {
+                    max_concurrency: Some(1),
+                    ..Default::default()
+                }),
             },
             ScaleComponentCommand::builder()
                 .component_ref("component_ref")
@@ -627,11 +1086,40 @@ mod tests {
                 .allow_update(true)
                 .annotations(BTreeMap::from([("a".into(), "b".into())]))
                 .max_instances(1)
+                .limits(
+                    ComponentResourceLimits::builder()
+                        .max_concurrency(1)
+                        .build()
+                        .unwrap()
+                )
                 .build()
                 .unwrap()
         )
     }
 
+    #[test]
+    fn component_resource_limits_builder() {
+        assert_eq!(
+            ComponentResourceLimits {
+                max_linear_memory: Some(1024),
+                max_execution_time_ms: Some(100),
+                max_table_elements: Some(10),
+                max_concurrency: Some(2),
+            },
+            ComponentResourceLimits::builder()
+                .max_linear_memory(1024)
+                .max_execution_time_ms(100)
+                .max_table_elements(10)
+                .max_concurrency(2)
+                .build()
+                .unwrap()
+        );
+        assert!(ComponentResourceLimits::builder()
+            .max_concurrency(0)
+            .build()
+            .is_err());
+    }
+
     #[test]
     fn start_provider_command_builder() {
         assert_eq!(
@@ -668,6 +1156,39 @@ mod tests {
         )
     }
 
+    #[test]
+    fn cordon_host_command_builder() {
+        assert_eq!(
+            CordonHostCommand {
+                host_id: "host_id".into(),
+                cordoned: true,
+            },
+            CordonHostCommand::builder()
+                .host_id("host_id")
+                .build()
+                .unwrap()
+        );
+        let cmd: CordonHostCommand = serde_json::from_str(r#"{"host_id":"host_id"}"#).unwrap();
+        assert!(cmd.cordoned());
+    }
+
+    #[test]
+    fn drain_host_command_builder() {
+        assert_eq!(
+            DrainHostCommand {
+                host_id: "host_id".into(),
+                timeout: Some(1),
+                stop_host: true,
+            },
+            DrainHostCommand::builder()
+                .host_id("host_id")
+                .timeout(1)
+                .stop_host(true)
+                .build()
+                .unwrap()
+        )
+    }
+
     #[test]
     fn stop_provider_command_builder() {
         assert_eq!(
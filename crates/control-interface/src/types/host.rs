@@ -243,6 +243,15 @@ pub struct HostInventory {
     /// The host uptime in seconds
     #[serde(default)]
     pub(crate) uptime_seconds: u64,
+
+    /// The sequence number of the last event published by the host, which is reflected in this
+    /// inventory
+    #[serde(default)]
+    pub(crate) sequence: u64,
+
+    /// Whether the host is cordoned, i.e. does not respond to auctions
+    #[serde(default)]
+    pub(crate) cordoned: bool,
 }
 
 impl HostInventory {
@@ -286,6 +295,18 @@ impl HostInventory {
         self.uptime_seconds
     }
 
+    /// Get the sequence number of the last event published by the host, which is reflected in
+    /// this inventory. Changes derived from events with greater sequence numbers can be applied to
+    /// the inventory using [`HostInventory::apply`]
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Get whether the host is cordoned, i.e. does not respond to component or provider auctions
+    pub fn cordoned(&self) -> bool {
+        self.cordoned
+    }
+
     #[must_use]
     pub fn builder() -> HostInventoryBuilder {
         HostInventoryBuilder::default()
@@ -303,6 +324,8 @@ pub struct HostInventoryBuilder {
     version: Option<String>,
     uptime_human: Option<String>,
     uptime_seconds: Option<u64>,
+    sequence: Option<u64>,
+    cordoned: Option<bool>,
 }
 
 impl HostInventoryBuilder {
@@ -354,6 +377,18 @@ impl HostInventoryBuilder {
         self
     }
 
+    #[must_use]
+    pub fn sequence(mut self, v: u64) -> Self {
+        self.sequence = Some(v);
+        self
+    }
+
+    #[must_use]
+    pub fn cordoned(mut self, v: bool) -> Self {
+        self.cordoned = Some(v);
+        self
+    }
+
     pub fn build(self) -> Result<HostInventory> {
         Ok(HostInventory {
             components: self.components.unwrap_or_default(),
@@ -374,6 +409,8 @@ impl HostInventoryBuilder {
             uptime_seconds: self
                 .uptime_seconds
                 .ok_or_else(|| "uptime_seconds is required".to_string())?,
+            sequence: self.sequence.unwrap_or_default(),
+            cordoned: self.cordoned.unwrap_or_default(),
         })
     }
 }
@@ -479,7 +516,9 @@ mod tests {
                 labels: BTreeMap::from([("a".into(), "b".into())]),
                 version: "1.0.0".into(),
                 uptime_human: "t".into(),
-                uptime_seconds: 1
+                uptime_seconds: 1,
+                sequence: 2,
+                cordoned: true,
             },
             HostInventory::builder()
                 .components(Vec::from([ComponentDescription::default()]))
@@ -490,6 +529,8 @@ mod tests {
                 .version("1.0.0".into())
                 .uptime_human("t".into())
                 .uptime_seconds(1)
+                .sequence(2)
+                .cordoned(true)
                 .build()
                 .unwrap()
         )
@@ -0,0 +1,323 @@
+//! Data types used when watching the inventory of a host on a wasmCloud lattice
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::types::component::ComponentDescription;
+use crate::types::host::HostInventory;
+use crate::types::link::Link;
+use crate::types::provider::ProviderDescription;
+
+/// A change to the inventory of a host, derived from an event published by the host
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum InventoryDelta {
+    /// A component was scaled. A `max_instances` of `0` indicates that the component was stopped
+    ComponentScaled {
+        component_id: String,
+        image_ref: String,
+        max_instances: u32,
+        annotations: BTreeMap<String, String>,
+    },
+    /// A provider was started
+    ProviderStarted {
+        provider_id: String,
+        image_ref: String,
+        annotations: BTreeMap<String, String>,
+    },
+    /// A provider was stopped
+    ProviderStopped { provider_id: String },
+    /// The labels of the host were changed
+    LabelsChanged { labels: BTreeMap<String, String> },
+    /// The host was cordoned or uncordoned
+    CordonChanged { cordoned: bool },
+    /// A link was put into the lattice
+    LinkPut(Link),
+    /// A link was deleted from the lattice
+    LinkDeleted {
+        source_id: String,
+        name: String,
+        wit_namespace: String,
+        wit_package: String,
+    },
+}
+
+#[derive(Deserialize)]
+struct ComponentScaledEvent {
+    component_id: String,
+    #[serde(default)]
+    image_ref: String,
+    max_instances: u64,
+    #[serde(default)]
+    annotations: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct ProviderStartedEvent {
+    provider_id: String,
+    #[serde(default)]
+    image_ref: String,
+    #[serde(default)]
+    annotations: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct ProviderStoppedEvent {
+    provider_id: String,
+}
+
+#[derive(Deserialize)]
+struct LabelsChangedEvent {
+    labels: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct HostCordonedEvent {
+    cordoned: bool,
+}
+
+#[derive(Deserialize)]
+struct LinkDeletedEvent {
+    source_id: String,
+    name: String,
+    wit_namespace: String,
+    wit_package: String,
+}
+
+impl InventoryDelta {
+    /// Derive the change to the inventory of a host from an event of type `event_type`, e.g.
+    /// `component_scaled`, published by the host. Returns `None` for events, which do not change
+    /// the inventory
+    pub(crate) fn from_event(event_type: &str, data: serde_json::Value) -> Option<Self> {
+        match event_type {
+            "component_scaled" => {
+                let ComponentScaledEvent {
+                    component_id,
+                    image_ref,
+                    max_instances,
+                    annotations,
+                } = serde_json::from_value(data).ok()?;
+                Some(Self::ComponentScaled {
+                    component_id,
+                    image_ref,
+                    max_instances: max_instances.try_into().unwrap_or(u32::MAX),
+                    annotations,
+                })
+            }
+            "provider_started" => {
+                let ProviderStartedEvent {
+                    provider_id,
+                    image_ref,
+                    annotations,
+                } = serde_json::from_value(data).ok()?;
+                Some(Self::ProviderStarted {
+                    provider_id,
+                    image_ref,
+                    annotations,
+                })
+            }
+            "provider_stopped" => {
+                let ProviderStoppedEvent { provider_id } = serde_json::from_value(data).ok()?;
+                Some(Self::ProviderStopped { provider_id })
+            }
+            "labels_changed" => {
+                let LabelsChangedEvent { labels } = serde_json::from_value(data).ok()?;
+                Some(Self::LabelsChanged { labels })
+            }
+            "host_cordoned" => {
+                let HostCordonedEvent { cordoned } = serde_json::from_value(data).ok()?;
+                Some(Self::CordonChanged { cordoned })
+            }
+            "host_draining" => Some(Self::CordonChanged { cordoned: true }),
+            "linkdef_set" => serde_json::from_value(data).ok().map(Self::LinkPut),
+            "linkdef_deleted" => {
+                let LinkDeletedEvent {
+                    source_id,
+                    name,
+                    wit_namespace,
+                    wit_package,
+                } = serde_json::from_value(data).ok()?;
+                Some(Self::LinkDeleted {
+                    source_id,
+                    name,
+                    wit_namespace,
+                    wit_package,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// An update of the inventory of a watched host
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum InventoryUpdate {
+    /// A full snapshot of the inventory, sent when the watch starts and whenever events published
+    /// by the host were missed
+    Snapshot(HostInventory),
+    /// A change to the inventory, derived from the event with the given sequence number. The
+    /// sequence number is `0` for hosts, which do not number their events
+    Delta {
+        sequence: u64,
+        delta: InventoryDelta,
+    },
+}
+
+impl HostInventory {
+    /// Apply a change to the inventory. Applying the same change more than once has no further
+    /// effect. Changes to links do not affect the inventory
+    pub fn apply(&mut self, delta: &InventoryDelta) {
+        match delta {
+            InventoryDelta::ComponentScaled {
+                component_id,
+                max_instances: 0,
+                ..
+            } => {
+                self.components
+                    .retain(|component| component.id != *component_id);
+            }
+            InventoryDelta::ComponentScaled {
+                component_id,
+                image_ref,
+                max_instances,
+                annotations,
+            } => {
+                if let Some(component) = self
+                    .components
+                    .iter_mut()
+                    .find(|component| component.id == *component_id)
+                {
+                    component.image_ref.clone_from(image_ref);
+                    component.max_instances = *max_instances;
+                    component.annotations = Some(annotations.clone());
+                } else {
+                    self.components.push(ComponentDescription {
+                        id: component_id.clone(),
+                        image_ref: image_ref.clone(),
+                        max_instances: *max_instances,
+                        annotations: Some(annotations.clone()),
+                        ..Default::default()
+                    });
+                }
+            }
+            InventoryDelta::ProviderStarted {
+                provider_id,
+                image_ref,
+                annotations,
+            } => {
+                if let Some(provider) = self
+                    .providers
+                    .iter_mut()
+                    .find(|provider| provider.id == *provider_id)
+                {
+                    provider.image_ref = Some(image_ref.clone());
+                    provider.annotations = Some(annotations.clone());
+                    provider.failed = false;
+                } else {
+                    self.providers.push(ProviderDescription {
+                        id: provider_id.clone(),
+                        image_ref: Some(image_ref.clone()),
+                        annotations: Some(annotations.clone()),
+                        ..Default::default()
+                    });
+                }
+            }
+            InventoryDelta::ProviderStopped { provider_id } => {
+                self.providers
+                    .retain(|provider| provider.id != *provider_id);
+            }
+            InventoryDelta::LabelsChanged { labels } => {
+                self.labels.clone_from(labels);
+            }
+            InventoryDelta::CordonChanged { cordoned } => {
+                self.cordoned = *cordoned;
+            }
+            InventoryDelta::LinkPut(..) | InventoryDelta::LinkDeleted { .. } => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_json::json;
+
+    use super::{HostInventory, InventoryDelta};
+
+    #[test]
+    fn inventory_delta_from_event() {
+        assert_eq!(
+            InventoryDelta::from_event(
+                "component_scaled",
+                json!({
+                    "component_id": "echo",
+                    "image_ref": "ghcr.io/wasmcloud/components/echo:0.1.0",
+                    "max_instances": 2,
+                    "annotations": {},
+                    "host_id": "host",
+                    "sequence": 3,
+                }),
+            ),
+            Some(InventoryDelta::ComponentScaled {
+                component_id: "echo".into(),
+                image_ref: "ghcr.io/wasmcloud/components/echo:0.1.0".into(),
+                max_instances: 2,
+                annotations: BTreeMap::default(),
+            })
+        );
+        assert_eq!(
+            InventoryDelta::from_event(
+                "labels_changed",
+                json!({ "host_id": "host", "labels": { "a": "b" } }),
+            ),
+            Some(InventoryDelta::LabelsChanged {
+                labels: BTreeMap::from([("a".into(), "b".into())]),
+            })
+        );
+        assert_eq!(
+            InventoryDelta::from_event("host_draining", json!({ "host_id": "host" })),
+            Some(InventoryDelta::CordonChanged { cordoned: true })
+        );
+        assert_eq!(
+            InventoryDelta::from_event("host_heartbeat", json!({ "sequence": 4 })),
+            None
+        );
+    }
+
+    #[test]
+    fn host_inventory_apply() {
+        let mut inventory = HostInventory::default();
+        let scaled = InventoryDelta::ComponentScaled {
+            component_id: "echo".into(),
+            image_ref: "echo:0.1.0".into(),
+            max_instances: 2,
+            annotations: BTreeMap::default(),
+        };
+        inventory.apply(&scaled);
+        inventory.apply(&scaled);
+        assert_eq!(inventory.components().len(), 1);
+        assert_eq!(inventory.components()[0].max_instances(), 2);
+
+        inventory.apply(&InventoryDelta::ProviderStarted {
+            provider_id: "http".into(),
+            image_ref: "http:0.1.0".into(),
+            annotations: BTreeMap::default(),
+        });
+        assert_eq!(inventory.providers().len(), 1);
+
+        inventory.apply(&InventoryDelta::ComponentScaled {
+            component_id: "echo".into(),
+            image_ref: "echo:0.1.0".into(),
+            max_instances: 0,
+            annotations: BTreeMap::default(),
+        });
+        inventory.apply(&InventoryDelta::ProviderStopped {
+            provider_id: "http".into(),
+        });
+        assert!(inventory.components().is_empty());
+        assert!(inventory.providers().is_empty());
+    }
+}
@@ -1,8 +1,10 @@
 //! Collection of types that are commonly used/necessary in control interface operations
 
 pub mod component;
+pub mod config;
 pub mod ctl;
 pub mod host;
+pub mod inventory;
 pub mod link;
 pub mod provider;
 pub mod registry;
@@ -26,6 +26,10 @@ pub struct ProviderDescription {
     /// this provider instance
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub(crate) annotations: Option<BTreeMap<String, String>>,
+    /// Whether the provider failed and is no longer restarted by the host, e.g. because it
+    /// was crash-looping
+    #[serde(default)]
+    pub(crate) failed: bool,
 }
 
 impl ProviderDescription {
@@ -54,6 +58,11 @@ impl ProviderDescription {
         self.annotations.as_ref()
     }
 
+    /// Get whether the provider failed and is no longer restarted by the host
+    pub fn failed(&self) -> bool {
+        self.failed
+    }
+
     #[must_use]
     pub fn builder() -> ProviderDescriptionBuilder {
         ProviderDescriptionBuilder::default()
@@ -69,6 +78,7 @@ pub struct ProviderDescriptionBuilder {
     name: Option<String>,
     revision: Option<i32>,
     annotations: Option<BTreeMap<String, String>>,
+    failed: Option<bool>,
 }
 
 impl ProviderDescriptionBuilder {
@@ -108,6 +118,13 @@ impl ProviderDescriptionBuilder {
         self
     }
 
+    /// Whether the provider failed and is no longer restarted by the host
+    #[must_use]
+    pub fn failed(mut self, v: bool) -> Self {
+        self.failed = Some(v);
+        self
+    }
+
     /// Build a [`ProviderDescription`]
     pub fn build(self) -> Result<ProviderDescription> {
         Ok(ProviderDescription {
@@ -116,6 +133,214 @@ impl ProviderDescriptionBuilder {
             name: self.name,
             revision: self.revision.unwrap_or_default(),
             annotations: self.annotations,
+            failed: self.failed.unwrap_or_default(),
+        })
+    }
+}
+
+/// The stream of a provider process a log line was written to
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum ProviderLogStream {
+    /// Standard output of the provider process
+    #[default]
+    Stdout,
+    /// Standard error of the provider process
+    Stderr,
+}
+
+impl ProviderLogStream {
+    /// Get the name of the stream
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Stdout => "stdout",
+            Self::Stderr => "stderr",
+        }
+    }
+}
+
+/// A line of output written by a capability provider process
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct ProviderLogLine {
+    /// Sequence number of the line, increasing monotonically for every line a provider writes
+    #[serde(default)]
+    pub(crate) sequence: u64,
+    /// Time at which the line was captured by the host, in RFC 3339 format
+    #[serde(default)]
+    pub(crate) timestamp: String,
+    /// The stream the line was written to
+    #[serde(default)]
+    pub(crate) stream: ProviderLogStream,
+    /// The contents of the line
+    #[serde(default)]
+    pub(crate) line: String,
+}
+
+impl ProviderLogLine {
+    /// Get the sequence number of the line
+    #[must_use]
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Get the time at which the line was captured by the host
+    #[must_use]
+    pub fn timestamp(&self) -> &str {
+        &self.timestamp
+    }
+
+    /// Get the stream the line was written to
+    #[must_use]
+    pub fn stream(&self) -> ProviderLogStream {
+        self.stream
+    }
+
+    /// Get the contents of the line
+    #[must_use]
+    pub fn line(&self) -> &str {
+        &self.line
+    }
+
+    #[must_use]
+    pub fn builder() -> ProviderLogLineBuilder {
+        ProviderLogLineBuilder::default()
+    }
+}
+
+/// Builds [`ProviderLogLine`]s
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ProviderLogLineBuilder {
+    sequence: Option<u64>,
+    timestamp: Option<String>,
+    stream: Option<ProviderLogStream>,
+    line: Option<String>,
+}
+
+impl ProviderLogLineBuilder {
+    /// Sequence number of the line
+    #[must_use]
+    pub fn sequence(mut self, v: u64) -> Self {
+        self.sequence = Some(v);
+        self
+    }
+
+    /// Time at which the line was captured by the host, in RFC 3339 format
+    #[must_use]
+    pub fn timestamp(mut self, v: &str) -> Self {
+        self.timestamp = Some(v.into());
+        self
+    }
+
+    /// The stream the line was written to
+    #[must_use]
+    pub fn stream(mut self, v: ProviderLogStream) -> Self {
+        self.stream = Some(v);
+        self
+    }
+
+    /// The contents of the line
+    #[must_use]
+    pub fn line(mut self, v: &str) -> Self {
+        self.line = Some(v.into());
+        self
+    }
+
+    /// Build a [`ProviderLogLine`]
+    pub fn build(self) -> Result<ProviderLogLine> {
+        Ok(ProviderLogLine {
+            sequence: self
+                .sequence
+                .ok_or_else(|| "sequence is required".to_string())?,
+            timestamp: self.timestamp.unwrap_or_default(),
+            stream: self.stream.unwrap_or_default(),
+            line: self.line.unwrap_or_default(),
+        })
+    }
+}
+
+/// The most recent output of a capability provider process, as buffered by the host
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct ProviderLogs {
+    /// Provider's unique identifier
+    #[serde(default)]
+    pub(crate) provider_id: String,
+    /// Buffered lines, ordered by sequence number
+    #[serde(default)]
+    pub(crate) lines: Vec<ProviderLogLine>,
+    /// Whether lines requested were already evicted from the buffer of the host
+    #[serde(default)]
+    pub(crate) truncated: bool,
+}
+
+impl ProviderLogs {
+    /// Get the ID of the provider
+    #[must_use]
+    pub fn provider_id(&self) -> &str {
+        &self.provider_id
+    }
+
+    /// Get the buffered lines, ordered by sequence number
+    #[must_use]
+    pub fn lines(&self) -> &[ProviderLogLine] {
+        &self.lines
+    }
+
+    /// Get whether lines requested were already evicted from the buffer of the host
+    #[must_use]
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+
+    #[must_use]
+    pub fn builder() -> ProviderLogsBuilder {
+        ProviderLogsBuilder::default()
+    }
+}
+
+/// Builds [`ProviderLogs`]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct ProviderLogsBuilder {
+    provider_id: Option<String>,
+    lines: Option<Vec<ProviderLogLine>>,
+    truncated: Option<bool>,
+}
+
+impl ProviderLogsBuilder {
+    /// Provider's unique identifier
+    #[must_use]
+    pub fn provider_id(mut self, v: &str) -> Self {
+        self.provider_id = Some(v.into());
+        self
+    }
+
+    /// Buffered lines, ordered by sequence number
+    #[must_use]
+    pub fn lines(mut self, v: Vec<ProviderLogLine>) -> Self {
+        self.lines = Some(v);
+        self
+    }
+
+    /// Whether lines requested were already evicted from the buffer of the host
+    #[must_use]
+    pub fn truncated(mut self, v: bool) -> Self {
+        self.truncated = Some(v);
+        self
+    }
+
+    /// Build [`ProviderLogs`]
+    pub fn build(self) -> Result<ProviderLogs> {
+        Ok(ProviderLogs {
+            provider_id: self
+                .provider_id
+                .ok_or_else(|| "provider_id is required".to_string())?,
+            lines: self.lines.unwrap_or_default(),
+            truncated: self.truncated.unwrap_or_default(),
         })
     }
 }
@@ -124,7 +349,7 @@ impl ProviderDescriptionBuilder {
 mod tests {
     use std::collections::BTreeMap;
 
-    use super::ProviderDescription;
+    use super::{ProviderDescription, ProviderLogLine, ProviderLogStream, ProviderLogs};
 
     #[test]
     fn provider_description_builder() {
@@ -135,6 +360,7 @@ mod tests {
                 name: Some("name".into()),
                 annotations: Some(BTreeMap::from([("a".into(), "b".into())])),
                 revision: 0,
+                failed: true,
             },
             ProviderDescription::builder()
                 .id("id")
@@ -142,8 +368,43 @@ mod tests {
                 .name("name")
                 .annotations(BTreeMap::from([("a".into(), "b".into())]))
                 .revision(0)
+                .failed(true)
                 .build()
                 .unwrap()
         )
     }
+
+    #[test]
+    fn provider_logs_builder() {
+        let line = ProviderLogLine {
+            sequence: 3,
+            timestamp: "2024-01-01T00:00:00Z".into(),
+            stream: ProviderLogStream::Stderr,
+            line: "hello".into(),
+        };
+        assert_eq!(
+            line,
+            ProviderLogLine::builder()
+                .sequence(3)
+                .timestamp("2024-01-01T00:00:00Z")
+                .stream(ProviderLogStream::Stderr)
+                .line("hello")
+                .build()
+                .unwrap()
+        );
+        assert_eq!(
+            ProviderLogs {
+                provider_id: "id".into(),
+                lines: vec![line.clone()],
+                truncated: true,
+            },
+            ProviderLogs::builder()
+                .provider_id("id")
+                .lines(vec![line])
+                .truncated(true)
+                .build()
+                .unwrap()
+        );
+        assert!(ProviderLogs::builder().build().is_err());
+    }
 }
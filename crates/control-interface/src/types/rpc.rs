@@ -24,6 +24,9 @@ pub struct ComponentAuctionAck {
     /// Constraints that were used in the auction
     #[serde(default)]
     pub(crate) constraints: BTreeMap<String, String>,
+    /// How well suited the bidder is to run the component, from `0` to `100`. Higher is better
+    #[serde(default)]
+    pub(crate) score: u32,
 }
 
 impl ComponentAuctionAck {
@@ -39,6 +42,7 @@ impl ComponentAuctionAck {
             component_id: component_id.into(),
             host_id: host_id.into(),
             constraints: constraints.into(),
+            score: 0,
         }
     }
 
@@ -66,6 +70,12 @@ impl ComponentAuctionAck {
         &self.constraints
     }
 
+    /// Get how well suited the bidder is to run the component, from `0` to `100`. Higher is better
+    #[must_use]
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
     pub fn builder() -> ComponentAuctionAckBuilder {
         ComponentAuctionAckBuilder::default()
     }
@@ -77,6 +87,7 @@ pub struct ComponentAuctionAckBuilder {
     component_id: Option<String>,
     host_id: Option<String>,
     constraints: Option<BTreeMap<String, String>>,
+    score: Option<u32>,
 }
 
 impl ComponentAuctionAckBuilder {
@@ -104,6 +115,12 @@ impl ComponentAuctionAckBuilder {
         self
     }
 
+    #[must_use]
+    pub fn score(mut self, v: u32) -> Self {
+        self.score = Some(v);
+        self
+    }
+
     pub fn build(self) -> Result<ComponentAuctionAck> {
         Ok(ComponentAuctionAck {
             component_ref: self
@@ -116,6 +133,7 @@ impl ComponentAuctionAckBuilder {
                 .host_id
                 .ok_or_else(|| "host_id is required".to_string())?,
             constraints: self.constraints.unwrap_or_default(),
+            score: self.score.unwrap_or_default(),
         })
     }
 }
@@ -133,6 +151,13 @@ pub struct ComponentAuctionRequest {
     pub(crate) component_id: String,
     /// The set of constraints that must match the labels of a suitable target host
     pub(crate) constraints: BTreeMap<String, String>,
+    /// The minimum amount of memory, in bytes, that must be available on a suitable target host
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) min_available_memory: Option<u64>,
+    /// Whether hosts already running a component with the same image reference must not bid.
+    /// If `false`, such hosts bid with a lower score
+    #[serde(default)]
+    pub(crate) anti_affinity: bool,
 }
 
 impl ComponentAuctionRequest {
@@ -154,6 +179,18 @@ impl ComponentAuctionRequest {
         &self.constraints
     }
 
+    /// Get the minimum amount of memory, in bytes, that must be available on a suitable host
+    #[must_use]
+    pub fn min_available_memory(&self) -> Option<u64> {
+        self.min_available_memory
+    }
+
+    /// Get whether hosts already running a component with the same image reference must not bid
+    #[must_use]
+    pub fn anti_affinity(&self) -> bool {
+        self.anti_affinity
+    }
+
     pub fn builder() -> ComponentAuctionRequestBuilder {
         ComponentAuctionRequestBuilder::default()
     }
@@ -164,6 +201,8 @@ pub struct ComponentAuctionRequestBuilder {
     component_ref: Option<String>,
     component_id: Option<String>,
     constraints: Option<BTreeMap<String, String>>,
+    min_available_memory: Option<u64>,
+    anti_affinity: Option<bool>,
 }
 
 impl ComponentAuctionRequestBuilder {
@@ -185,6 +224,18 @@ impl ComponentAuctionRequestBuilder {
         self
     }
 
+    #[must_use]
+    pub fn min_available_memory(mut self, v: u64) -> Self {
+        self.min_available_memory = Some(v);
+        self
+    }
+
+    #[must_use]
+    pub fn anti_affinity(mut self, v: bool) -> Self {
+        self.anti_affinity = Some(v);
+        self
+    }
+
     pub fn build(self) -> Result<ComponentAuctionRequest> {
         Ok(ComponentAuctionRequest {
             component_ref: self
@@ -194,6 +245,8 @@ impl ComponentAuctionRequestBuilder {
                 .component_id
                 .ok_or_else(|| "component_id is required".to_string())?,
             constraints: self.constraints.unwrap_or_default(),
+            min_available_memory: self.min_available_memory,
+            anti_affinity: self.anti_affinity.unwrap_or_default(),
         })
     }
 }
@@ -218,6 +271,9 @@ pub struct ProviderAuctionAck {
     /// The constraints provided for the auction
     #[serde(default)]
     pub(crate) constraints: BTreeMap<String, String>,
+    /// How well suited the bidder is to run the provider, from `0` to `100`. Higher is better
+    #[serde(default)]
+    pub(crate) score: u32,
 }
 
 impl ProviderAuctionAck {
@@ -245,6 +301,12 @@ impl ProviderAuctionAck {
         &self.constraints
     }
 
+    /// Get how well suited the bidder is to run the provider, from `0` to `100`. Higher is better
+    #[must_use]
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
     #[must_use]
     pub fn builder() -> ProviderAuctionAckBuilder {
         ProviderAuctionAckBuilder::default()
@@ -257,6 +319,7 @@ pub struct ProviderAuctionAckBuilder {
     provider_ref: Option<String>,
     provider_id: Option<String>,
     constraints: Option<BTreeMap<String, String>>,
+    score: Option<u32>,
 }
 
 impl ProviderAuctionAckBuilder {
@@ -284,6 +347,12 @@ impl ProviderAuctionAckBuilder {
         self
     }
 
+    #[must_use]
+    pub fn score(mut self, v: u32) -> Self {
+        self.score = Some(v);
+        self
+    }
+
     pub fn build(self) -> Result<ProviderAuctionAck> {
         Ok(ProviderAuctionAck {
             provider_ref: self
@@ -296,6 +365,7 @@ impl ProviderAuctionAckBuilder {
                 .host_id
                 .ok_or_else(|| "host_id is required".to_string())?,
             constraints: self.constraints.unwrap_or_default(),
+            score: self.score.unwrap_or_default(),
         })
     }
 }
@@ -316,6 +386,15 @@ pub struct ProviderAuctionRequest {
 
     /// The set of constraints that must match the labels of a suitable target host
     pub(crate) constraints: BTreeMap<String, String>,
+
+    /// The minimum amount of memory, in bytes, that must be available on a suitable target host
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) min_available_memory: Option<u64>,
+
+    /// Whether hosts already running a provider with the same image reference must not bid.
+    /// If `false`, such hosts bid with a lower score
+    #[serde(default)]
+    pub(crate) anti_affinity: bool,
 }
 
 impl ProviderAuctionRequest {
@@ -337,6 +416,18 @@ impl ProviderAuctionRequest {
         &self.constraints
     }
 
+    /// Get the minimum amount of memory, in bytes, that must be available on a suitable host
+    #[must_use]
+    pub fn min_available_memory(&self) -> Option<u64> {
+        self.min_available_memory
+    }
+
+    /// Get whether hosts already running a provider with the same image reference must not bid
+    #[must_use]
+    pub fn anti_affinity(&self) -> bool {
+        self.anti_affinity
+    }
+
     /// Build a new [`ProviderAuctionRequest`]
     #[must_use]
     pub fn builder() -> ProviderAuctionRequestBuilder {
@@ -349,6 +440,8 @@ pub struct ProviderAuctionRequestBuilder {
     provider_ref: Option<String>,
     provider_id: Option<String>,
     constraints: Option<BTreeMap<String, String>>,
+    min_available_memory: Option<u64>,
+    anti_affinity: Option<bool>,
 }
 
 impl ProviderAuctionRequestBuilder {
@@ -370,6 +463,18 @@ impl ProviderAuctionRequestBuilder {
         self
     }
 
+    #[must_use]
+    pub fn min_available_memory(mut self, v: u64) -> Self {
+        self.min_available_memory = Some(v);
+        self
+    }
+
+    #[must_use]
+    pub fn anti_affinity(mut self, v: bool) -> Self {
+        self.anti_affinity = Some(v);
+        self
+    }
+
     pub fn build(self) -> Result<ProviderAuctionRequest> {
         Ok(ProviderAuctionRequest {
             provider_ref: self
@@ -379,6 +484,8 @@ impl ProviderAuctionRequestBuilder {
                 .provider_id
                 .ok_or_else(|| "provider_id is required".to_string())?,
             constraints: self.constraints.unwrap_or_default(),
+            min_available_memory: self.min_available_memory,
+            anti_affinity: self.anti_affinity.unwrap_or_default(),
         })
     }
 }
@@ -512,13 +619,15 @@ mod tests {
                 component_ref: "component_ref".into(),
                 component_id: "component_id".into(),
                 host_id: "host_id".into(),
-                constraints: BTreeMap::from([("a".into(), "b".into())])
+                constraints: BTreeMap::from([("a".into(), "b".into())]),
+                score: 42,
             },
             ComponentAuctionAck::builder()
                 .component_ref("component_ref".into())
                 .component_id("component_id".into())
                 .host_id("host_id".into())
                 .constraints(BTreeMap::from([("a".into(), "b".into())]))
+                .score(42)
                 .build()
                 .unwrap()
         )
@@ -530,12 +639,16 @@ mod tests {
             ComponentAuctionRequest {
                 component_ref: "component_ref".into(),
                 component_id: "component_id".into(),
-                constraints: BTreeMap::from([("a".into(), "b".into())])
+                constraints: BTreeMap::from([("a".into(), "b".into())]),
+                min_available_memory: Some(1024),
+                anti_affinity: true,
             },
             ComponentAuctionRequest::builder()
                 .component_ref("component_ref".into())
                 .component_id("component_id".into())
                 .constraints(BTreeMap::from([("a".into(), "b".into())]))
+                .min_available_memory(1024)
+                .anti_affinity(true)
                 .build()
                 .unwrap()
         )
@@ -548,13 +661,15 @@ mod tests {
                 provider_ref: "provider_ref".into(),
                 provider_id: "provider_id".into(),
                 host_id: "host_id".into(),
-                constraints: BTreeMap::from([("a".into(), "b".into())])
+                constraints: BTreeMap::from([("a".into(), "b".into())]),
+                score: 42,
             },
             ProviderAuctionAck::builder()
                 .provider_ref("provider_ref".into())
                 .provider_id("provider_id".into())
                 .host_id("host_id".into())
                 .constraints(BTreeMap::from([("a".into(), "b".into())]))
+                .score(42)
                 .build()
                 .unwrap()
         )
@@ -566,12 +681,16 @@ mod tests {
             ProviderAuctionRequest {
                 provider_ref: "provider_ref".into(),
                 provider_id: "provider_id".into(),
-                constraints: BTreeMap::from([("a".into(), "b".into())])
+                constraints: BTreeMap::from([("a".into(), "b".into())]),
+                min_available_memory: Some(1024),
+                anti_affinity: true,
             },
             ProviderAuctionRequest::builder()
                 .provider_ref("provider_ref".into())
                 .provider_id("provider_id".into())
                 .constraints(BTreeMap::from([("a".into(), "b".into())]))
+                .min_available_memory(1024)
+                .anti_affinity(true)
                 .build()
                 .unwrap()
         )
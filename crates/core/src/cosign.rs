@@ -0,0 +1,363 @@
+//! Verification of [cosign] signatures attached to OCI artifacts.
+//!
+//! Only keyed signatures are supported. Keyless (Fulcio certificate) signatures are rejected,
+//! since verifying them requires a trust root that is not configured here. Transparency log
+//! bundles attached by `cosign sign` are verified offline against a configured set of Rekor keys.
+//!
+//! [cosign]: https://github.com/sigstore/cosign
+
+use std::path::Path;
+
+use anyhow::{bail, ensure, Context as _};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use oci_client::client::ImageLayer;
+use oci_client::Reference;
+use ring::signature::{self, UnparsedPublicKey, VerificationAlgorithm};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::SignaturePolicy;
+
+/// Media type of the layers of a cosign signature artifact
+pub const SIMPLE_SIGNING_MEDIA_TYPE: &str = "application/vnd.dev.cosign.simplesigning.v1+json";
+
+const SIGNATURE_ANNOTATION: &str = "dev.cosignproject.cosign/signature";
+const CERTIFICATE_ANNOTATION: &str = "dev.sigstore.cosign/certificate";
+const BUNDLE_ANNOTATION: &str = "dev.sigstore.cosign/bundle";
+
+// DER-encoded `SubjectPublicKeyInfo` prefixes of the key types supported by cosign, which are
+// followed directly by the raw public key
+const SPKI_P256_PREFIX: &[u8] = &[
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+    0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00,
+];
+const SPKI_P384_PREFIX: &[u8] = &[
+    0x30, 0x76, 0x30, 0x10, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x05, 0x2b,
+    0x81, 0x04, 0x00, 0x22, 0x03, 0x62, 0x00,
+];
+const SPKI_ED25519_PREFIX: &[u8] = &[
+    0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+
+/// Returns the reference under which cosign stores signatures for the artifact with `digest`
+/// in the repository of `img`
+pub fn signature_reference(img: &Reference, digest: &str) -> anyhow::Result<Reference> {
+    let (algorithm, hex) = digest
+        .split_once(':')
+        .with_context(|| format!("invalid artifact digest `{digest}`"))?;
+    Ok(Reference::with_tag(
+        img.registry().to_string(),
+        img.repository().to_string(),
+        format!("{algorithm}-{hex}.sig"),
+    ))
+}
+
+struct PublicKey {
+    algorithm: &'static dyn VerificationAlgorithm,
+    key: Vec<u8>,
+}
+
+impl PublicKey {
+    fn from_spki(spki: &[u8]) -> anyhow::Result<Self> {
+        let (algorithm, key): (&'static dyn VerificationAlgorithm, _) =
+            if let Some(key) = spki.strip_prefix(SPKI_P256_PREFIX) {
+                (&signature::ECDSA_P256_SHA256_ASN1, key)
+            } else if let Some(key) = spki.strip_prefix(SPKI_P384_PREFIX) {
+                (&signature::ECDSA_P384_SHA384_ASN1, key)
+            } else if let Some(key) = spki.strip_prefix(SPKI_ED25519_PREFIX) {
+                (&signature::ED25519, key)
+            } else {
+                bail!("unsupported public key type, expected ECDSA P-256, P-384 or Ed25519")
+            };
+        Ok(Self {
+            algorithm,
+            key: key.to_vec(),
+        })
+    }
+
+    fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        UnparsedPublicKey::new(self.algorithm, &self.key)
+            .verify(message, signature)
+            .is_ok()
+    }
+}
+
+async fn load_public_keys(paths: &[impl AsRef<Path>]) -> anyhow::Result<Vec<PublicKey>> {
+    let mut keys = Vec::with_capacity(paths.len());
+    for path in paths {
+        let path = path.as_ref();
+        let pem = fs::read(path)
+            .await
+            .with_context(|| format!("failed to read public key `{}`", path.display()))?;
+        let mut found = false;
+        for spki in rustls_pemfile::public_keys(&mut pem.as_slice()) {
+            let spki =
+                spki.with_context(|| format!("failed to parse public key `{}`", path.display()))?;
+            keys.push(
+                PublicKey::from_spki(spki.as_ref())
+                    .with_context(|| format!("invalid public key `{}`", path.display()))?,
+            );
+            found = true;
+        }
+        ensure!(found, "no public keys found in `{}`", path.display());
+    }
+    Ok(keys)
+}
+
+/// Signed payload of a cosign signature
+#[derive(Deserialize)]
+struct SimpleSigning {
+    critical: Critical,
+}
+
+#[derive(Deserialize)]
+struct Critical {
+    image: CriticalImage,
+}
+
+#[derive(Deserialize)]
+struct CriticalImage {
+    #[serde(rename = "docker-manifest-digest")]
+    docker_manifest_digest: String,
+}
+
+/// Transparency log bundle attached to a cosign signature
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct RekorBundle {
+    signed_entry_timestamp: String,
+    payload: RekorPayload,
+}
+
+/// Transparency log entry covered by the signed entry timestamp. Fields are declared in
+/// lexicographic order, so that serializing this struct produces canonical JSON.
+#[derive(Deserialize, Serialize)]
+struct RekorPayload {
+    body: String,
+    #[serde(rename = "integratedTime")]
+    integrated_time: i64,
+    #[serde(rename = "logID")]
+    log_id: String,
+    #[serde(rename = "logIndex")]
+    log_index: i64,
+}
+
+/// Verifier of cosign signatures, constructed from a [`SignaturePolicy`]
+pub struct SignatureVerifier {
+    keys: Vec<PublicKey>,
+    rekor_keys: Vec<PublicKey>,
+}
+
+impl SignatureVerifier {
+    /// Load the keys referenced by `policy`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the keys cannot be read or are not supported
+    pub async fn load(policy: &SignaturePolicy) -> anyhow::Result<Self> {
+        let keys = load_public_keys(&policy.public_keys)
+            .await
+            .context("failed to load signature public keys")?;
+        let rekor_keys = load_public_keys(&policy.rekor_public_keys)
+            .await
+            .context("failed to load transparency log public keys")?;
+        Ok(Self { keys, rekor_keys })
+    }
+
+    /// Verify that at least one of the signature `layers` is a valid signature over the artifact
+    /// with manifest `digest`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing why each signature was rejected if none is valid
+    pub fn verify(&self, digest: &str, layers: &[ImageLayer]) -> anyhow::Result<()> {
+        let mut rejected = Vec::new();
+        for layer in layers
+            .iter()
+            .filter(|layer| layer.media_type == SIMPLE_SIGNING_MEDIA_TYPE)
+        {
+            match self.verify_layer(digest, layer) {
+                Ok(()) => return Ok(()),
+                Err(err) => rejected.push(format!("{err:#}")),
+            }
+        }
+        if rejected.is_empty() {
+            bail!("no signatures found for artifact `{digest}`")
+        }
+        bail!(
+            "no valid signatures found for artifact `{digest}`: {}",
+            rejected.join("; ")
+        )
+    }
+
+    fn verify_layer(&self, digest: &str, layer: &ImageLayer) -> anyhow::Result<()> {
+        let annotations = layer.annotations.as_ref();
+        let annotation = |key: &str| annotations.and_then(|annotations| annotations.get(key));
+        ensure!(
+            annotation(CERTIFICATE_ANNOTATION).is_none(),
+            "keyless signatures are not supported"
+        );
+        let encoded_signature = annotation(SIGNATURE_ANNOTATION)
+            .context("signature layer is missing the signature annotation")?;
+        let signature = BASE64
+            .decode(encoded_signature)
+            .context("failed to decode signature")?;
+        let payload = &layer.data[..];
+        ensure!(
+            self.keys.iter().any(|key| key.verify(payload, &signature)),
+            "signature was not produced by a trusted key"
+        );
+
+        let signed: SimpleSigning =
+            serde_json::from_slice(payload).context("failed to parse signed payload")?;
+        let docker_manifest_digest = signed.critical.image.docker_manifest_digest;
+        ensure!(
+            docker_manifest_digest == digest,
+            "signature is for artifact `{docker_manifest_digest}`"
+        );
+
+        if !self.rekor_keys.is_empty() {
+            let bundle = annotation(BUNDLE_ANNOTATION)
+                .context("signature is missing a transparency log bundle")?;
+            self.verify_bundle(bundle, encoded_signature, payload)
+                .context("failed to verify transparency log bundle")?;
+        }
+        Ok(())
+    }
+
+    fn verify_bundle(
+        &self,
+        bundle: &str,
+        encoded_signature: &str,
+        payload: &[u8],
+    ) -> anyhow::Result<()> {
+        let RekorBundle {
+            signed_entry_timestamp,
+            payload: entry,
+        } = serde_json::from_str(bundle).context("failed to parse bundle")?;
+        let timestamp = BASE64
+            .decode(signed_entry_timestamp)
+            .context("failed to decode signed entry timestamp")?;
+        let canonical = serde_json::to_vec(&entry).context("failed to encode bundle payload")?;
+        ensure!(
+            self.rekor_keys
+                .iter()
+                .any(|key| key.verify(&canonical, &timestamp)),
+            "signed entry timestamp was not produced by a trusted transparency log"
+        );
+
+        // The entry itself must be a record of this signature over this payload
+        let body: serde_json::Value = BASE64
+            .decode(&entry.body)
+            .context("failed to decode entry body")
+            .and_then(|body| serde_json::from_slice(&body).context("failed to parse entry body"))?;
+        ensure!(
+            body.pointer("/spec/signature/content")
+                .and_then(serde_json::Value::as_str)
+                == Some(encoded_signature),
+            "transparency log entry records a different signature"
+        );
+        let payload_hash = ring::digest::digest(&ring::digest::SHA256, payload)
+            .as_ref()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>();
+        ensure!(
+            body.pointer("/spec/data/hash/value")
+                .and_then(serde_json::Value::as_str)
+                == Some(payload_hash.as_str()),
+            "transparency log entry records a different payload"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use anyhow::Result;
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair as _};
+
+    use super::*;
+
+    const DIGEST: &str = "sha256:8f3b7a40e5d5b6f5e2a1c0d9b8a7f6e5d4c3b2a1908f7e6d5c4b3a2918f7e6d5";
+
+    fn key_pair() -> Result<(Ed25519KeyPair, PublicKey)> {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new())
+            .map_err(|_| anyhow::anyhow!("failed to generate key"))?;
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref())
+            .map_err(|_| anyhow::anyhow!("failed to parse key"))?;
+        let spki = [SPKI_ED25519_PREFIX, pair.public_key().as_ref()].concat();
+        let key = PublicKey::from_spki(&spki)?;
+        Ok((pair, key))
+    }
+
+    fn signature_layer(pair: &Ed25519KeyPair, digest: &str) -> ImageLayer {
+        let payload = format!(
+            r#"{{"critical":{{"identity":{{"docker-reference":"localhost:5000/foo"}},"image":{{"docker-manifest-digest":"{digest}"}},"type":"cosign container image signature"}},"optional":null}}"#
+        );
+        let signature = BASE64.encode(pair.sign(payload.as_bytes()));
+        ImageLayer::new(
+            payload.into_bytes().into(),
+            SIMPLE_SIGNING_MEDIA_TYPE.to_string(),
+            Some(BTreeMap::from([(
+                SIGNATURE_ANNOTATION.to_string(),
+                signature,
+            )])),
+        )
+    }
+
+    #[test]
+    fn test_signature_reference() -> Result<()> {
+        let img: Reference = "localhost:5000/foo:0.1.0".parse()?;
+        let sig = signature_reference(&img, DIGEST)?;
+        assert_eq!(sig.registry(), "localhost:5000");
+        assert_eq!(sig.repository(), "foo");
+        assert_eq!(
+            sig.tag(),
+            Some("sha256-8f3b7a40e5d5b6f5e2a1c0d9b8a7f6e5d4c3b2a1908f7e6d5c4b3a2918f7e6d5.sig")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify() -> Result<()> {
+        let (pair, key) = key_pair()?;
+        let (other_pair, other_key) = key_pair()?;
+        let verifier = SignatureVerifier {
+            keys: vec![key],
+            rekor_keys: Vec::new(),
+        };
+        verifier.verify(DIGEST, &[signature_layer(&pair, DIGEST)])?;
+        // Any valid signature is sufficient
+        verifier.verify(
+            DIGEST,
+            &[
+                signature_layer(&other_pair, DIGEST),
+                signature_layer(&pair, DIGEST),
+            ],
+        )?;
+        // Signature by an untrusted key
+        assert!(verifier
+            .verify(DIGEST, &[signature_layer(&other_pair, DIGEST)])
+            .is_err());
+        // Signature for a different artifact
+        assert!(verifier
+            .verify(DIGEST, &[signature_layer(&pair, "sha256:0000")])
+            .is_err());
+        // No signatures at all
+        assert!(verifier.verify(DIGEST, &[]).is_err());
+        // Transparency log bundle required, but missing
+        let verifier = SignatureVerifier {
+            keys: vec![other_key],
+            rekor_keys: vec![key_pair()?.1],
+        };
+        assert!(verifier
+            .verify(DIGEST, &[signature_layer(&other_pair, DIGEST)])
+            .is_err());
+        Ok(())
+    }
+}
@@ -13,11 +13,22 @@ pub use link::*;
 pub mod otel;
 pub use otel::*;
 
+#[cfg(feature = "oci")]
+pub mod cosign;
+
 #[cfg(feature = "oci")]
 pub mod oci;
 #[cfg(feature = "oci")]
 pub use oci::*;
 
+#[cfg(feature = "oci")]
+pub mod oci_cache;
+#[cfg(feature = "oci")]
+pub use oci_cache::*;
+
+#[cfg(feature = "oci")]
+pub mod oci_layout;
+
 pub mod par;
 pub use par::*;
 
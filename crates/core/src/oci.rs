@@ -1,27 +1,31 @@
-use std::env::temp_dir;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use anyhow::{bail, Context as _};
+use anyhow::{bail, ensure, Context as _};
 use oci_client::client::ClientProtocol;
-use oci_client::client::ImageData;
 use oci_client::Reference;
 use oci_wasm::WASM_LAYER_MEDIA_TYPE;
 use oci_wasm::WASM_MANIFEST_MEDIA_TYPE;
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
 use wascap::jwt;
 
-use crate::RegistryConfig;
+use crate::cosign::{self, SignatureVerifier};
+use crate::oci_cache::{ArtifactCache, ArtifactPin};
 use crate::{tls, UseParFileCache};
+use crate::{RegistryConfig, SignaturePolicy};
 
-const PROVIDER_ARCHIVE_MEDIA_TYPE: &str = "application/vnd.wasmcloud.provider.archive.layer.v1+par";
-const WASM_MEDIA_TYPE: &str = "application/vnd.module.wasm.content.layer.v1+wasm";
-const OCI_MEDIA_TYPE: &str = "application/vnd.oci.image.layer.v1.tar";
+pub(crate) const PROVIDER_ARCHIVE_MEDIA_TYPE: &str =
+    "application/vnd.wasmcloud.provider.archive.layer.v1+par";
+pub(crate) const WASM_MEDIA_TYPE: &str = "application/vnd.module.wasm.content.layer.v1+wasm";
+pub(crate) const OCI_MEDIA_TYPE: &str = "application/vnd.oci.image.layer.v1.tar";
 
 /// Whether to update an OCI artifact cache
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 #[non_exhaustive]
+#[deprecated(
+    since = "0.21.0",
+    note = "fetched OCI artifacts are always stored in the `ArtifactCache` of the `OciFetcher`, `OciFetcher::fetch_path` ignores its `_cache` argument"
+)]
 pub enum OciArtifactCacheUpdate {
     /// Do not update the OCI artifact cache
     #[default]
@@ -37,6 +41,8 @@ pub struct OciFetcher {
     allow_latest: bool,
     allow_insecure: bool,
     auth: oci_client::secrets::RegistryAuth,
+    signature_policy: SignaturePolicy,
+    cache: ArtifactCache,
 }
 
 impl Default for OciFetcher {
@@ -46,6 +52,8 @@ impl Default for OciFetcher {
             allow_latest: false,
             allow_insecure: false,
             auth: oci_client::secrets::RegistryAuth::Anonymous,
+            signature_policy: SignaturePolicy::default(),
+            cache: ArtifactCache::default(),
         }
     }
 }
@@ -57,6 +65,7 @@ impl From<&RegistryConfig> for OciFetcher {
             allow_latest,
             allow_insecure,
             additional_ca_paths,
+            signature_policy,
             ..
         }: &RegistryConfig,
     ) -> Self {
@@ -65,6 +74,8 @@ impl From<&RegistryConfig> for OciFetcher {
             allow_latest: *allow_latest,
             allow_insecure: *allow_insecure,
             additional_ca_paths: additional_ca_paths.clone(),
+            signature_policy: signature_policy.clone(),
+            cache: ArtifactCache::default(),
         }
     }
 }
@@ -76,6 +87,7 @@ impl From<RegistryConfig> for OciFetcher {
             allow_latest,
             allow_insecure,
             additional_ca_paths,
+            signature_policy,
             ..
         }: RegistryConfig,
     ) -> Self {
@@ -84,41 +96,21 @@ impl From<RegistryConfig> for OciFetcher {
             allow_latest,
             allow_insecure,
             additional_ca_paths,
+            signature_policy,
+            cache: ArtifactCache::default(),
         }
     }
 }
 
 /// Default directory in which OCI artifacts are cached
 pub async fn oci_cache_dir() -> anyhow::Result<PathBuf> {
-    let path = temp_dir().join("wasmcloud_ocicache");
+    let path = ArtifactCache::default_dir();
     if !fs::try_exists(&path).await? {
         fs::create_dir_all(&path).await?;
     }
     Ok(path)
 }
 
-#[allow(unused)]
-async fn cache_oci_image(
-    image: ImageData,
-    cache_filepath: impl AsRef<Path>,
-    digest_filepath: impl AsRef<Path>,
-) -> std::io::Result<()> {
-    let mut cache_file = fs::File::create(cache_filepath).await?;
-    let content = image
-        .layers
-        .into_iter()
-        .flat_map(|l| l.data)
-        .collect::<Vec<_>>();
-    cache_file.write_all(&content).await?;
-    cache_file.flush().await?;
-    if let Some(digest) = image.digest {
-        let mut digest_file = fs::File::create(digest_filepath).await?;
-        digest_file.write_all(digest.as_bytes()).await?;
-        digest_file.flush().await?;
-    }
-    Ok(())
-}
-
 fn prune_filepath(img: &str) -> String {
     let mut img = img.replace(':', "_");
     img = img.replace('/', "_");
@@ -134,25 +126,57 @@ pub enum CacheResult {
 }
 
 impl OciFetcher {
-    /// Fetch an OCI artifact to a path and return that path. Returns the path and whether or not
-    /// there was a cache hit/miss
+    /// Fetch an OCI artifact to a path in `output_dir` and return that path. Returns the path and
+    /// whether or not there was a cache hit/miss
+    ///
+    /// The artifact is fetched through the artifact cache and copied to `output_dir`, regardless
+    /// of `cache`.
+    #[deprecated(
+        since = "0.21.0",
+        note = "use `OciFetcher::fetch_cached_path`, which returns the path of the artifact in the artifact cache; the `_cache` argument is ignored"
+    )]
+    #[allow(deprecated)]
     pub async fn fetch_path(
         &self,
         output_dir: impl AsRef<Path>,
         img: impl AsRef<str>,
         accepted_media_types: Vec<&str>,
-        cache: OciArtifactCacheUpdate,
+        _cache: OciArtifactCacheUpdate,
+    ) -> anyhow::Result<(PathBuf, CacheResult)> {
+        let _pin = self.pin(&img);
+        let (cached, result) = self.fetch_cached_path(&img, accepted_media_types).await?;
+        let path = output_dir
+            .as_ref()
+            .join(prune_filepath(&img.as_ref().to_lowercase()));
+        fs::copy(&cached, &path)
+            .await
+            .with_context(|| format!("failed to copy OCI artifact to `{}`", path.display()))?;
+        Ok((path, result))
+    }
+
+    /// Pin the artifact fetched under `img` in the artifact cache, see [`ArtifactCache::pin`]
+    fn pin(&self, img: impl AsRef<str>) -> ArtifactPin {
+        // Artifacts are cached under the lowercase reference, see `fetch_cached_path`
+        self.cache.pin(img.as_ref().to_lowercase())
+    }
+
+    /// Fetch an OCI artifact into the artifact cache and return the path of the cached content.
+    /// Returns the path and whether or not there was a cache hit/miss
+    ///
+    /// The content may be evicted by a concurrent garbage collection of the cache, unless `img`
+    /// is [pinned](ArtifactCache::pin) until the content has been read.
+    pub async fn fetch_cached_path(
+        &self,
+        img: impl AsRef<str>,
+        accepted_media_types: Vec<&str>,
     ) -> anyhow::Result<(PathBuf, CacheResult)> {
-        let output_dir = output_dir.as_ref();
         let img = img.as_ref().to_lowercase(); // the OCI spec does not allow for capital letters in references
         if !self.allow_latest && img.ends_with(":latest") {
             bail!("fetching images tagged 'latest' is currently prohibited in this host. This option can be overridden with WASMCLOUD_OCI_ALLOW_LATEST")
         }
-        let pruned_filepath = prune_filepath(&img);
-        let cache_file = output_dir.join(&pruned_filepath);
-        let mut digest_file = output_dir.join(&pruned_filepath).clone();
-        digest_file.set_extension("digest");
 
+        // Artifacts are cached under the reference they were requested by
+        let cache_ref = img.clone();
         let img = Reference::from_str(&img)?;
 
         let protocol = if self.allow_insecure {
@@ -178,16 +202,16 @@ impl OciFetcher {
             ..Default::default()
         });
 
-        // In case of a cache miss where the file does not exist, pull a fresh OCI Image
-        if fs::metadata(&cache_file).await.is_ok() {
-            let (_, oci_digest) = c
-                .pull_manifest(&img, &self.auth)
-                .await
-                .context("failed to fetch OCI manifest")?;
-            // If the digest file doesn't exist that is ok, we just unwrap to an empty string
-            let file_digest = fs::read_to_string(&digest_file).await.unwrap_or_default();
-            if !oci_digest.is_empty() && !file_digest.is_empty() && file_digest == oci_digest {
-                return Ok((cache_file, CacheResult::Hit));
+        // Resolve the reference to a manifest digest, which the cache is keyed by. This ensures
+        // that a mutable tag never resolves to stale cached content.
+        let (_, oci_digest) = c
+            .pull_manifest(&img, &self.auth)
+            .await
+            .context("failed to fetch OCI manifest")?;
+        if !oci_digest.is_empty() {
+            if let Some(path) = self.cache.get(&oci_digest, &cache_ref).await? {
+                self.verify_signature(&c, &img, &oci_digest).await?;
+                return Ok((path, CacheResult::Hit));
             }
         }
 
@@ -208,14 +232,77 @@ impl OciFetcher {
                 imgdata.layers.len()
             )
         }
-        // Update the OCI artifact cache if specified
-        if let OciArtifactCacheUpdate::Update = cache {
-            cache_oci_image(imgdata, &cache_file, digest_file)
-                .await
-                .context("failed to cache OCI bytes")?;
-        }
+        let digest = imgdata
+            .digest
+            .clone()
+            .filter(|digest| !digest.is_empty())
+            .unwrap_or(oci_digest);
+        ensure!(
+            !digest.is_empty(),
+            "registry did not return a digest for the OCI artifact"
+        );
+        self.verify_signature(&c, &img, &digest).await?;
+        let content = imgdata
+            .layers
+            .into_iter()
+            .flat_map(|l| l.data)
+            .collect::<Vec<_>>();
+        let path = self
+            .cache
+            .put(&digest, &cache_ref, &content)
+            .await
+            .context("failed to cache OCI bytes")?;
 
-        Ok((cache_file, CacheResult::Miss))
+        Ok((path, CacheResult::Miss))
+    }
+
+    /// Fetch an OCI artifact of any supported type into the artifact cache, so that subsequent
+    /// fetches of it are served from the cache
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if fetching fails
+    pub async fn prefetch(&self, oci_ref: impl AsRef<str>) -> anyhow::Result<CacheResult> {
+        let _pin = self.pin(&oci_ref);
+        let (_, cache) = self
+            .fetch_cached_path(
+                oci_ref,
+                vec![
+                    WASM_MEDIA_TYPE,
+                    OCI_MEDIA_TYPE,
+                    WASM_LAYER_MEDIA_TYPE,
+                    PROVIDER_ARCHIVE_MEDIA_TYPE,
+                ],
+            )
+            .await
+            .context("failed to fetch OCI path")?;
+        Ok(cache)
+    }
+
+    /// Verify the signatures attached to the artifact `img` with manifest `digest` against the
+    /// configured [`SignaturePolicy`]. This is a no-op if signature verification is disabled.
+    async fn verify_signature(
+        &self,
+        c: &oci_client::Client,
+        img: &Reference,
+        digest: &str,
+    ) -> anyhow::Result<()> {
+        if !self.signature_policy.is_enabled() {
+            return Ok(());
+        }
+        let verifier = SignatureVerifier::load(&self.signature_policy).await?;
+        let sig_ref = cosign::signature_reference(img, digest)?;
+        let signatures = c
+            .pull(
+                &sig_ref,
+                &self.auth,
+                vec![cosign::SIMPLE_SIGNING_MEDIA_TYPE],
+            )
+            .await
+            .with_context(|| format!("failed to fetch signatures for `{img}` from `{sig_ref}`"))?;
+        verifier
+            .verify(digest, &signatures.layers)
+            .with_context(|| format!("signature verification failed for `{img}`"))
     }
 
     /// Fetch component from OCI
@@ -224,12 +311,11 @@ impl OciFetcher {
     ///
     /// Returns an error if either fetching fails or reading the fetched OCI path fails
     pub async fn fetch_component(&self, oci_ref: impl AsRef<str>) -> anyhow::Result<Vec<u8>> {
+        let _pin = self.pin(&oci_ref);
         let (path, _) = self
-            .fetch_path(
-                oci_cache_dir().await?,
+            .fetch_cached_path(
                 oci_ref,
                 vec![WASM_MEDIA_TYPE, OCI_MEDIA_TYPE, WASM_LAYER_MEDIA_TYPE],
-                OciArtifactCacheUpdate::Update,
             )
             .await
             .context("failed to fetch OCI path")?;
@@ -248,12 +334,11 @@ impl OciFetcher {
         oci_ref: impl AsRef<str>,
         host_id: impl AsRef<str>,
     ) -> anyhow::Result<(PathBuf, Option<jwt::Token<jwt::CapabilityProvider>>)> {
+        let _pin = self.pin(&oci_ref);
         let (path, cache) = self
-            .fetch_path(
-                oci_cache_dir().await?,
+            .fetch_cached_path(
                 oci_ref.as_ref(),
                 vec![PROVIDER_ARCHIVE_MEDIA_TYPE, OCI_MEDIA_TYPE],
-                OciArtifactCacheUpdate::Update,
             )
             .await
             .context("failed to fetch OCI path")?;
@@ -266,6 +351,18 @@ impl OciFetcher {
             .with_context(|| format!("failed to read `{}`", path.display()))
     }
 
+    /// Used to set the trust policy for signatures attached to fetched components and providers
+    pub fn with_signature_policy(mut self, policy: SignaturePolicy) -> Self {
+        self.signature_policy = policy;
+        self
+    }
+
+    /// Used to set the cache in which fetched artifacts are stored
+    pub fn with_cache(mut self, cache: ArtifactCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
     /// Used to set additional CA paths that will be used as part of fetching components and providers
     pub fn with_additional_ca_paths(mut self, paths: &[impl AsRef<Path>]) -> Self {
         self.additional_ca_paths = paths.iter().map(AsRef::as_ref).map(PathBuf::from).collect();
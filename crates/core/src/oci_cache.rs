@@ -0,0 +1,537 @@
+//! Content-addressed cache of OCI artifacts fetched by [`crate::OciFetcher`].
+//!
+//! Artifacts are stored under the digest of their manifest, so a mutable tag can never resolve
+//! to stale content. Each artifact is accompanied by a metadata file recording the references it
+//! was fetched under and a digest of the cached content, which is checked on every read.
+//!
+//! The cache may be bounded in size, in which case [`ArtifactCache::gc`] evicts the least
+//! recently used artifacts that are not in use. Artifacts being fetched are protected from
+//! eviction by [pinning](ArtifactCache::pin) the reference they are fetched under.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::env::temp_dir;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, PoisonError};
+use std::time::SystemTime;
+
+use anyhow::Context as _;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+const BLOBS_DIR: &str = "blobs";
+const METADATA_EXTENSION: &str = "json";
+
+/// Metadata of an artifact stored in an [`ArtifactCache`]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CachedArtifact {
+    /// Digest of the artifact manifest
+    pub digest: String,
+    /// SHA-256 digest of the cached artifact content
+    pub content_digest: String,
+    /// Size of the cached artifact content in bytes
+    pub size: u64,
+    /// References the artifact was fetched under
+    pub references: BTreeSet<String>,
+    /// Time the artifact was last read from or written to the cache
+    #[serde(skip)]
+    pub last_used: Option<SystemTime>,
+}
+
+/// Result of a garbage collection of an [`ArtifactCache`]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct CacheGcResult {
+    /// Artifacts removed from the cache
+    pub evicted: Vec<CachedArtifact>,
+    /// Total size of the artifacts remaining in the cache in bytes
+    pub remaining_size: u64,
+}
+
+/// Content-addressed, optionally size-bounded cache of OCI artifacts.
+///
+/// Modifications of the cache are serialized across clones of an [`ArtifactCache`], so a single
+/// instance should be shared by all users of a cache directory within a process.
+#[derive(Clone, Debug)]
+pub struct ArtifactCache {
+    root: PathBuf,
+    max_size: Option<u64>,
+    lock: Arc<Mutex<()>>,
+    /// Number of [`ArtifactPin`]s held for each reference
+    pins: Arc<std::sync::Mutex<HashMap<String, usize>>>,
+}
+
+/// Guard returned by [`ArtifactCache::pin`], which keeps the artifacts fetched under a reference
+/// from being evicted until it is dropped
+#[derive(Debug)]
+#[must_use = "the reference is only pinned until the guard is dropped"]
+pub struct ArtifactPin {
+    reference: String,
+    pins: Arc<std::sync::Mutex<HashMap<String, usize>>>,
+}
+
+impl Drop for ArtifactPin {
+    fn drop(&mut self) {
+        let mut pins = self.pins.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(n) = pins.get_mut(&self.reference) {
+            *n -= 1;
+            if *n == 0 {
+                pins.remove(&self.reference);
+            }
+        }
+    }
+}
+
+impl PartialEq for ArtifactCache {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root && self.max_size == other.max_size
+    }
+}
+
+impl Eq for ArtifactCache {}
+
+impl Default for ArtifactCache {
+    fn default() -> Self {
+        Self::new(Self::default_dir(), None)
+    }
+}
+
+/// Compute the SHA256 digest of the given content, of the form `sha256:<hex>`
+#[must_use]
+pub fn content_digest(content: &[u8]) -> String {
+    format_digest(&ring::digest::digest(&ring::digest::SHA256, content))
+}
+
+/// Compute the SHA256 digest of the content of the file at `path`, of the form `sha256:<hex>`,
+/// without reading the whole file into memory
+async fn file_digest(path: &Path) -> std::io::Result<String> {
+    let mut file = fs::File::open(path).await?;
+    let mut ctx = ring::digest::Context::new(&ring::digest::SHA256);
+    let mut buf = vec![0; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        ctx.update(&buf[..n]);
+    }
+    Ok(format_digest(&ctx.finish()))
+}
+
+fn format_digest(digest: &ring::digest::Digest) -> String {
+    let hex = digest
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    format!("sha256:{hex}")
+}
+
+impl ArtifactCache {
+    /// Construct a cache rooted at `root`. If `max_size` is set, [`ArtifactCache::gc`] evicts
+    /// artifacts until the cache is no larger than `max_size` bytes.
+    pub fn new(root: impl Into<PathBuf>, max_size: Option<u64>) -> Self {
+        Self {
+            root: root.into(),
+            max_size,
+            lock: Arc::default(),
+            pins: Arc::default(),
+        }
+    }
+
+    /// Pin the artifacts stored under `reference`, so that they are not evicted by
+    /// [`ArtifactCache::gc`] or [`ArtifactCache::prune`] of this cache or any of its clones,
+    /// until the returned guard is dropped.
+    ///
+    /// Callers should pin the reference they fetch an artifact under before looking it up, and
+    /// hold the pin until they have read the cached content.
+    pub fn pin(&self, reference: impl Into<String>) -> ArtifactPin {
+        let reference = reference.into();
+        *self
+            .pins
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(reference.clone())
+            .or_default() += 1;
+        ArtifactPin {
+            reference,
+            pins: Arc::clone(&self.pins),
+        }
+    }
+
+    /// Default directory in which OCI artifacts are cached
+    pub fn default_dir() -> PathBuf {
+        temp_dir().join("wasmcloud_ocicache")
+    }
+
+    /// Directory the cache is rooted at
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Maximum size of the cache in bytes, if bounded
+    pub fn max_size(&self) -> Option<u64> {
+        self.max_size
+    }
+
+    fn blob_path(&self, digest: &str) -> anyhow::Result<PathBuf> {
+        let (algorithm, hex) = digest
+            .split_once(':')
+            .with_context(|| format!("invalid artifact digest `{digest}`"))?;
+        anyhow::ensure!(
+            !algorithm.is_empty()
+                && !hex.is_empty()
+                && algorithm
+                    .chars()
+                    .chain(hex.chars())
+                    .all(|c| c.is_ascii_alphanumeric()),
+            "invalid artifact digest `{digest}`"
+        );
+        Ok(self.root.join(BLOBS_DIR).join(format!("{algorithm}-{hex}")))
+    }
+
+    async fn read_metadata(blob: &Path) -> anyhow::Result<CachedArtifact> {
+        let buf = fs::read(blob.with_extension(METADATA_EXTENSION))
+            .await
+            .context("failed to read artifact metadata")?;
+        let mut artifact: CachedArtifact =
+            serde_json::from_slice(&buf).context("failed to parse artifact metadata")?;
+        artifact.last_used = fs::metadata(blob).await.and_then(|md| md.modified()).ok();
+        Ok(artifact)
+    }
+
+    /// Remove the content and metadata of `blob`.
+    ///
+    /// Files are removed synchronously, so that [`ArtifactCache::prune`] can do so while holding
+    /// the lock of the pins.
+    fn remove_blob(blob: &Path) -> std::io::Result<()> {
+        match std::fs::remove_file(blob.with_extension(METADATA_EXTENSION)) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        match std::fs::remove_file(blob) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Look up the artifact with manifest `digest`, recording `reference` as one of its
+    /// references. Returns the path of the cached content, if present and intact.
+    ///
+    /// Artifacts failing the integrity check are removed from the cache. Artifacts evicted
+    /// concurrently are reported as not present, unless `reference` is [pinned](Self::pin).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be read
+    pub async fn get(&self, digest: &str, reference: &str) -> anyhow::Result<Option<PathBuf>> {
+        let blob = self.blob_path(digest)?;
+        if !fs::try_exists(&blob).await? {
+            return Ok(None);
+        }
+        let mut artifact = match Self::read_metadata(&blob).await {
+            Ok(artifact) => artifact,
+            Err(err) => {
+                warn!(?err, %digest, "removing cached OCI artifact with invalid metadata");
+                let _lock = self.lock.lock().await;
+                Self::remove_blob(&blob)?;
+                return Ok(None);
+            }
+        };
+        let digest_of_content = match file_digest(&blob).await {
+            Ok(digest) => digest,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to read `{}`", blob.display()))
+            }
+        };
+        if digest_of_content != artifact.content_digest {
+            warn!(%digest, "removing corrupted cached OCI artifact");
+            let _lock = self.lock.lock().await;
+            Self::remove_blob(&blob)?;
+            return Ok(None);
+        }
+        if artifact.references.insert(reference.to_string()) {
+            let _lock = self.lock.lock().await;
+            // The artifact may have been evicted before it was recorded under `reference`
+            if !fs::try_exists(&blob).await? {
+                return Ok(None);
+            }
+            self.write_metadata(&blob, &artifact).await?;
+        }
+        // The modification time of the content tracks when it was last used
+        let file = match fs::OpenOptions::new().append(true).open(&blob).await {
+            Ok(file) => file.into_std().await,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        if let Err(err) = file.set_modified(SystemTime::now()) {
+            debug!(?err, %digest, "failed to update cached OCI artifact use time");
+        }
+        Ok(Some(blob))
+    }
+
+    async fn write_metadata(&self, blob: &Path, artifact: &CachedArtifact) -> anyhow::Result<()> {
+        let buf = serde_json::to_vec(artifact).context("failed to encode artifact metadata")?;
+        write_atomic(&blob.with_extension(METADATA_EXTENSION), &buf)
+            .await
+            .context("failed to write artifact metadata")
+    }
+
+    /// Store `content` of the artifact with manifest `digest`, fetched under `reference`.
+    /// Returns the path of the cached content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be written to
+    pub async fn put(
+        &self,
+        digest: &str,
+        reference: &str,
+        content: &[u8],
+    ) -> anyhow::Result<PathBuf> {
+        let blob = self.blob_path(digest)?;
+        let _lock = self.lock.lock().await;
+        fs::create_dir_all(self.root.join(BLOBS_DIR))
+            .await
+            .with_context(|| format!("failed to create `{}`", self.root.display()))?;
+        let mut references = Self::read_metadata(&blob)
+            .await
+            .map(|artifact| artifact.references)
+            .unwrap_or_default();
+        references.insert(reference.to_string());
+        write_atomic(&blob, content)
+            .await
+            .with_context(|| format!("failed to write `{}`", blob.display()))?;
+        let artifact = CachedArtifact {
+            digest: digest.to_string(),
+            content_digest: content_digest(content),
+            size: content.len() as u64,
+            references,
+            last_used: None,
+        };
+        self.write_metadata(&blob, &artifact).await?;
+        Ok(blob)
+    }
+
+    /// List all artifacts in the cache, least recently used first
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be read
+    pub async fn list(&self) -> anyhow::Result<Vec<CachedArtifact>> {
+        let dir = self.root.join(BLOBS_DIR);
+        let mut entries = match fs::read_dir(&dir).await {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to read `{}`", dir.display()))
+            }
+        };
+        let mut artifacts = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if path
+                .extension()
+                .is_some_and(|ext| ext == METADATA_EXTENSION)
+            {
+                continue;
+            }
+            match Self::read_metadata(&path).await {
+                Ok(artifact) => artifacts.push(artifact),
+                Err(err) => debug!(?err, path = %path.display(), "skipping cache entry"),
+            }
+        }
+        artifacts.sort_by_key(|artifact| artifact.last_used);
+        Ok(artifacts)
+    }
+
+    /// Evict least recently used artifacts until the cache is within its maximum size.
+    /// Artifacts whose digest or any of whose references are contained in `in_use` or
+    /// [pinned](Self::pin) are never evicted. This is a no-op for an unbounded cache.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be read or artifacts cannot be removed
+    pub async fn gc(&self, in_use: &HashSet<String>) -> anyhow::Result<CacheGcResult> {
+        match self.max_size {
+            Some(max_size) => self.prune(max_size, in_use).await,
+            None => Ok(CacheGcResult {
+                evicted: Vec::new(),
+                remaining_size: self.list().await?.iter().map(|a| a.size).sum(),
+            }),
+        }
+    }
+
+    /// Evict least recently used artifacts until the cache is no larger than `max_size` bytes.
+    /// Artifacts whose digest or any of whose references are contained in `in_use` or
+    /// [pinned](Self::pin) are never evicted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cache cannot be read or artifacts cannot be removed
+    pub async fn prune(
+        &self,
+        max_size: u64,
+        in_use: &HashSet<String>,
+    ) -> anyhow::Result<CacheGcResult> {
+        let _lock = self.lock.lock().await;
+        let artifacts = self.list().await?;
+        let mut remaining_size: u64 = artifacts.iter().map(|a| a.size).sum();
+        let mut evicted = Vec::new();
+        for artifact in artifacts {
+            if remaining_size <= max_size {
+                break;
+            }
+            if in_use.contains(&artifact.digest)
+                || artifact.references.iter().any(|r| in_use.contains(r))
+            {
+                continue;
+            }
+            let blob = self.blob_path(&artifact.digest)?;
+            // Hold the lock of the pins while removing, so that an artifact cannot be pinned
+            // between the check and its removal
+            let pins = self.pins.lock().unwrap_or_else(PoisonError::into_inner);
+            if pins.contains_key(&artifact.digest)
+                || artifact.references.iter().any(|r| pins.contains_key(r))
+            {
+                continue;
+            }
+            Self::remove_blob(&blob)
+                .with_context(|| format!("failed to remove artifact `{}`", artifact.digest))?;
+            drop(pins);
+            debug!(digest = %artifact.digest, "evicted cached OCI artifact");
+            remaining_size = remaining_size.saturating_sub(artifact.size);
+            evicted.push(artifact);
+        }
+        Ok(CacheGcResult {
+            evicted,
+            remaining_size,
+        })
+    }
+}
+
+/// Write `buf` to a temporary file next to `path` and rename it into place, so that readers never
+/// observe a partially written file
+async fn write_atomic(path: &Path, buf: &[u8]) -> std::io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{}.tmp", std::process::id()));
+    let tmp = PathBuf::from(tmp);
+    let mut file = fs::File::create(&tmp).await?;
+    file.write_all(buf).await?;
+    file.flush().await?;
+    drop(file);
+    fs::rename(&tmp, path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anyhow::Result;
+
+    const DIGEST_A: &str = "sha256:aaaa";
+    const DIGEST_B: &str = "sha256:bbbb";
+
+    #[tokio::test]
+    async fn test_get_put() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = ArtifactCache::new(dir.path(), None);
+        assert_eq!(cache.get(DIGEST_A, "foo:0.1.0").await?, None);
+
+        let path = cache.put(DIGEST_A, "foo:0.1.0", b"hello").await?;
+        assert_eq!(fs::read(&path).await?, b"hello");
+        assert_eq!(cache.get(DIGEST_A, "foo:latest").await?, Some(path.clone()));
+        let artifacts = cache.list().await?;
+        assert_eq!(artifacts.len(), 1);
+        assert_eq!(
+            artifacts[0].references,
+            BTreeSet::from(["foo:0.1.0".to_string(), "foo:latest".to_string()])
+        );
+
+        // Corrupted content is detected and evicted
+        fs::write(&path, b"goodbye").await?;
+        assert_eq!(cache.get(DIGEST_A, "foo:0.1.0").await?, None);
+        assert!(cache.list().await?.is_empty());
+
+        assert!(cache.get("../../etc/passwd", "foo").await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_put() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = ArtifactCache::new(dir.path(), None);
+        let other = cache.clone();
+        let (a, b) = tokio::join!(
+            cache.put(DIGEST_A, "foo:0.1.0", b"hello"),
+            other.put(DIGEST_A, "foo:latest", b"hello"),
+        );
+        assert_eq!(a?, b?);
+        let artifacts = cache.list().await?;
+        assert_eq!(artifacts.len(), 1);
+        assert_eq!(
+            artifacts[0].references,
+            BTreeSet::from(["foo:0.1.0".to_string(), "foo:latest".to_string()])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_gc() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = ArtifactCache::new(dir.path(), Some(6));
+        cache.put(DIGEST_A, "foo:0.1.0", b"hello").await?;
+        cache.put(DIGEST_B, "bar:0.1.0", b"world").await?;
+
+        // Artifacts in use are kept even if the cache is over its maximum size
+        let res = cache
+            .gc(&HashSet::from([
+                "foo:0.1.0".to_string(),
+                DIGEST_B.to_string(),
+            ]))
+            .await?;
+        assert!(res.evicted.is_empty());
+        assert_eq!(res.remaining_size, 10);
+
+        let res = cache.gc(&HashSet::from([DIGEST_B.to_string()])).await?;
+        assert_eq!(res.evicted.len(), 1);
+        assert_eq!(res.evicted[0].digest, DIGEST_A);
+        assert_eq!(res.remaining_size, 5);
+        assert_eq!(cache.get(DIGEST_A, "foo:0.1.0").await?, None);
+
+        let res = cache.prune(0, &HashSet::new()).await?;
+        assert_eq!(res.evicted.len(), 1);
+        assert!(cache.list().await?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_gc_pinned() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let cache = ArtifactCache::new(dir.path(), Some(0));
+        // Pins are shared with clones, like the cache of another host of a group
+        let other = cache.clone();
+        let pin = cache.pin("foo:latest");
+        let path = cache.put(DIGEST_A, "foo:0.1.0", b"hello").await?;
+        assert_eq!(cache.get(DIGEST_A, "foo:latest").await?, Some(path.clone()));
+
+        // Pinned artifacts are kept until the pin is dropped
+        let res = other.gc(&HashSet::new()).await?;
+        assert!(res.evicted.is_empty());
+        assert_eq!(fs::read(&path).await?, b"hello");
+
+        // Pins are counted per reference
+        let second = other.pin("foo:latest");
+        drop(pin);
+        assert!(other.gc(&HashSet::new()).await?.evicted.is_empty());
+        drop(second);
+        let res = other.gc(&HashSet::new()).await?;
+        assert_eq!(res.evicted.len(), 1);
+        assert_eq!(cache.get(DIGEST_A, "foo:latest").await?, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,608 @@
+//! Reading and writing artifacts in an [OCI image layout], either as a directory or as a
+//! (`docker save`-style) tarball of one, to support environments without access to a registry.
+//!
+//! Artifacts in a layout are addressed by a [`LayoutReference`] of the form `path:tag` or
+//! `path@digest`. All blobs read from a layout are verified against their digest.
+//!
+//! [OCI image layout]: https://github.com/opencontainers/image-spec/blob/main/image-layout.md
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, ensure, Context as _};
+use async_compression::tokio::bufread::GzipDecoder;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tokio::io::{AsyncRead, AsyncWriteExt as _, BufReader};
+use tokio_stream::StreamExt as _;
+use tokio_tar::Archive;
+use wascap::jwt;
+
+use crate::oci::{OCI_MEDIA_TYPE, PROVIDER_ARCHIVE_MEDIA_TYPE, WASM_MEDIA_TYPE};
+
+/// Annotation of a manifest descriptor in `index.json` holding its tag
+pub const REF_NAME_ANNOTATION: &str = "org.opencontainers.image.ref.name";
+/// Annotation set by `docker save` and containerd holding the full image name
+const CONTAINERD_IMAGE_NAME_ANNOTATION: &str = "io.containerd.image.name";
+
+const OCI_LAYOUT_FILE: &str = "oci-layout";
+const INDEX_FILE: &str = "index.json";
+const BLOBS_DIR: &str = "blobs";
+const IMAGE_LAYOUT_VERSION: &str = "1.0.0";
+const IMAGE_INDEX_MEDIA_TYPE: &str = "application/vnd.oci.image.index.v1+json";
+const IMAGE_MANIFEST_MEDIA_TYPE: &str = "application/vnd.oci.image.manifest.v1+json";
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// A reference to an artifact within an OCI image layout
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LayoutReference {
+    /// Path of the layout directory or tarball
+    pub path: PathBuf,
+    /// Tag or digest of the artifact within the layout. If not set, the layout must contain
+    /// exactly one artifact.
+    pub selector: Option<LayoutSelector>,
+}
+
+/// Selects an artifact within an OCI image layout
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LayoutSelector {
+    /// Select by tag, i.e. the `org.opencontainers.image.ref.name` annotation
+    Tag(String),
+    /// Select by manifest digest
+    Digest(String),
+}
+
+impl std::fmt::Display for LayoutSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tag(tag) => write!(f, "tag `{tag}`"),
+            Self::Digest(digest) => write!(f, "digest `{digest}`"),
+        }
+    }
+}
+
+impl LayoutReference {
+    /// Parse a reference of the form `path`, `path:tag` or `path@digest`
+    pub fn parse(s: &str) -> anyhow::Result<Self> {
+        ensure!(!s.is_empty(), "empty OCI layout reference");
+        if let Some((path, digest)) = s.rsplit_once('@') {
+            ensure!(
+                digest.contains(':'),
+                "invalid digest `{digest}` in OCI layout reference"
+            );
+            return Ok(Self {
+                path: PathBuf::from(path),
+                selector: Some(LayoutSelector::Digest(digest.to_string())),
+            });
+        }
+        // A tag cannot contain a path separator, which distinguishes it from e.g. a drive letter
+        match s.rsplit_once(':') {
+            Some((path, tag))
+                if !path.is_empty() && !tag.is_empty() && !tag.contains(['/', '\\']) =>
+            {
+                Ok(Self {
+                    path: PathBuf::from(path),
+                    selector: Some(LayoutSelector::Tag(tag.to_string())),
+                })
+            }
+            _ => Ok(Self {
+                path: PathBuf::from(s),
+                selector: None,
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    media_type: String,
+    digest: String,
+    size: u64,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    annotations: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Index {
+    schema_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    media_type: Option<String>,
+    manifests: Vec<Descriptor>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Manifest {
+    schema_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    media_type: Option<String>,
+    config: Descriptor,
+    layers: Vec<Descriptor>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    annotations: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LayoutFile {
+    image_layout_version: String,
+}
+
+fn sha256_digest(buf: &[u8]) -> String {
+    let digest = ring::digest::digest(&ring::digest::SHA256, buf);
+    let hex = digest
+        .as_ref()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    format!("sha256:{hex}")
+}
+
+fn blob_path(digest: &str) -> anyhow::Result<String> {
+    let (algorithm, hex) = digest
+        .split_once(':')
+        .with_context(|| format!("invalid digest `{digest}`"))?;
+    ensure!(
+        algorithm == "sha256",
+        "unsupported digest algorithm `{algorithm}`"
+    );
+    ensure!(
+        !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        "invalid digest `{digest}`"
+    );
+    Ok(format!("{BLOBS_DIR}/{algorithm}/{hex}"))
+}
+
+enum Source {
+    Dir(PathBuf),
+    Tar(HashMap<String, Vec<u8>>),
+}
+
+/// An OCI image layout, read from a directory or a tarball
+pub struct OciLayout {
+    source: Source,
+}
+
+impl OciLayout {
+    /// Open the OCI image layout at `path`, which may be a directory or a (possibly gzipped)
+    /// tarball
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` cannot be read or is not an OCI image layout
+    pub async fn open(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let md = fs::metadata(path)
+            .await
+            .with_context(|| format!("failed to read OCI layout `{}`", path.display()))?;
+        let source = if md.is_dir() {
+            Source::Dir(path.to_path_buf())
+        } else {
+            let buf = fs::read(path)
+                .await
+                .with_context(|| format!("failed to read OCI layout `{}`", path.display()))?;
+            Source::Tar(read_tar(&buf).await.with_context(|| {
+                format!("failed to read OCI layout tarball `{}`", path.display())
+            })?)
+        };
+        let layout = Self { source };
+        let LayoutFile {
+            image_layout_version,
+        } = serde_json::from_slice(&layout.read(OCI_LAYOUT_FILE).await?)
+            .context("failed to parse `oci-layout` file")?;
+        ensure!(
+            image_layout_version == IMAGE_LAYOUT_VERSION,
+            "unsupported OCI image layout version `{image_layout_version}`"
+        );
+        Ok(layout)
+    }
+
+    async fn read(&self, name: &str) -> anyhow::Result<Vec<u8>> {
+        match &self.source {
+            Source::Dir(dir) => fs::read(dir.join(name))
+                .await
+                .with_context(|| format!("failed to read `{name}` from OCI layout")),
+            Source::Tar(files) => files
+                .get(name)
+                .cloned()
+                .with_context(|| format!("`{name}` not found in OCI layout")),
+        }
+    }
+
+    async fn read_blob(&self, descriptor: &Descriptor) -> anyhow::Result<Vec<u8>> {
+        let buf = self.read(&blob_path(&descriptor.digest)?).await?;
+        ensure!(
+            buf.len() as u64 == descriptor.size,
+            "size of blob `{}` does not match its descriptor",
+            descriptor.digest
+        );
+        ensure!(
+            sha256_digest(&buf) == descriptor.digest,
+            "content of blob `{}` does not match its digest",
+            descriptor.digest
+        );
+        Ok(buf)
+    }
+
+    async fn resolve(&self, selector: Option<&LayoutSelector>) -> anyhow::Result<Descriptor> {
+        let index: Index = serde_json::from_slice(&self.read(INDEX_FILE).await?)
+            .context("failed to parse `index.json`")?;
+        let mut matches = index
+            .manifests
+            .into_iter()
+            .filter(|desc| match selector {
+                None => true,
+                Some(LayoutSelector::Digest(digest)) => desc.digest == *digest,
+                Some(LayoutSelector::Tag(tag)) => {
+                    desc.annotations.get(REF_NAME_ANNOTATION) == Some(tag)
+                        || desc
+                            .annotations
+                            .get(CONTAINERD_IMAGE_NAME_ANNOTATION)
+                            .and_then(|name| name.rsplit_once(':'))
+                            .is_some_and(|(_, name_tag)| name_tag == tag)
+                }
+            })
+            .collect::<Vec<_>>();
+        let desc = match (matches.pop(), matches.is_empty(), selector) {
+            (Some(desc), true, _) => desc,
+            (None, _, Some(selector)) => bail!("no artifact with {selector} in OCI layout"),
+            (None, _, None) => bail!("OCI layout contains no artifacts"),
+            (Some(_), false, _) => {
+                bail!("OCI layout contains multiple artifacts, a tag or digest must be specified")
+            }
+        };
+        if desc.media_type != IMAGE_INDEX_MEDIA_TYPE {
+            return Ok(desc);
+        }
+        // Nested indexes, e.g. multi-platform images, must contain a single manifest
+        let nested: Index = serde_json::from_slice(&self.read_blob(&desc).await?)
+            .context("failed to parse nested image index")?;
+        match <[Descriptor; 1]>::try_from(nested.manifests) {
+            Ok([desc]) => Ok(desc),
+            Err(manifests) => bail!(
+                "nested image index `{}` must contain exactly one manifest, found {}",
+                desc.digest,
+                manifests.len()
+            ),
+        }
+    }
+
+    /// Read the artifact selected by `selector`, returning its manifest digest and the
+    /// concatenated content of its layers with one of `accepted_media_types`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the artifact cannot be found, fails verification or does not contain
+    /// any layers with an accepted media type
+    pub async fn fetch(
+        &self,
+        selector: Option<&LayoutSelector>,
+        accepted_media_types: &[&str],
+    ) -> anyhow::Result<(String, Vec<u8>)> {
+        let desc = self.resolve(selector).await?;
+        let manifest: Manifest = serde_json::from_slice(&self.read_blob(&desc).await?)
+            .context("failed to parse image manifest")?;
+        let mut content = Vec::new();
+        let mut found = false;
+        for layer in manifest
+            .layers
+            .iter()
+            .filter(|layer| accepted_media_types.contains(&layer.media_type.as_str()))
+        {
+            content.extend(self.read_blob(layer).await?);
+            found = true;
+        }
+        ensure!(
+            found,
+            "artifact `{}` contains no layers of an accepted media type",
+            desc.digest
+        );
+        Ok((desc.digest, content))
+    }
+
+    /// Read the component selected by `selector`, returning its manifest digest and the
+    /// component
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the component cannot be read, see [`OciLayout::fetch`]
+    pub async fn fetch_component(
+        &self,
+        selector: Option<&LayoutSelector>,
+    ) -> anyhow::Result<(String, Vec<u8>)> {
+        self.fetch(
+            selector,
+            &[
+                WASM_MEDIA_TYPE,
+                OCI_MEDIA_TYPE,
+                oci_wasm::WASM_LAYER_MEDIA_TYPE,
+            ],
+        )
+        .await
+    }
+
+    /// Read the provider archive selected by `selector`, returning its manifest digest and the
+    /// archive
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive cannot be read, see [`OciLayout::fetch`]
+    pub async fn fetch_provider_archive(
+        &self,
+        selector: Option<&LayoutSelector>,
+    ) -> anyhow::Result<(String, Vec<u8>)> {
+        self.fetch(selector, &[PROVIDER_ARCHIVE_MEDIA_TYPE, OCI_MEDIA_TYPE])
+            .await
+    }
+}
+
+/// Read all regular files of a (possibly gzipped) tarball into memory, keyed by their normalized
+/// path
+async fn read_tar(buf: &[u8]) -> anyhow::Result<HashMap<String, Vec<u8>>> {
+    let reader = if buf.starts_with(&GZIP_MAGIC) {
+        Box::new(GzipDecoder::new(BufReader::new(Cursor::new(buf))))
+            as Box<dyn AsyncRead + Unpin + Send + Sync + '_>
+    } else {
+        Box::new(Cursor::new(buf)) as Box<dyn AsyncRead + Unpin + Send + Sync + '_>
+    };
+    let mut archive = Archive::new(reader);
+    let mut entries = archive.entries()?;
+    let mut files = HashMap::new();
+    while let Some(entry) = entries.next().await {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path()?;
+        let name = path
+            .components()
+            .filter_map(|c| match c {
+                std::path::Component::Normal(c) => c.to_str(),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        let mut data = Vec::new();
+        tokio::io::copy(&mut entry, &mut data).await?;
+        files.insert(name, data);
+    }
+    Ok(files)
+}
+
+/// A blob to be written to an OCI image layout
+#[derive(Clone, Debug)]
+pub struct LayoutBlob {
+    /// Media type of the blob
+    pub media_type: String,
+    /// Content of the blob
+    pub data: Vec<u8>,
+}
+
+impl LayoutBlob {
+    fn descriptor(&self) -> Descriptor {
+        Descriptor {
+            media_type: self.media_type.clone(),
+            digest: sha256_digest(&self.data),
+            size: self.data.len() as u64,
+            annotations: BTreeMap::new(),
+        }
+    }
+}
+
+/// Write an artifact consisting of `config` and `layers` to the OCI image layout at `path` under
+/// `tag`, returning the digest of its manifest.
+///
+/// If `path` ends in `.tar`, a tarball containing a new layout is written. Otherwise, the artifact
+/// is added to the layout directory at `path`, which is created if it does not exist, replacing
+/// any artifact previously tagged `tag`.
+///
+/// # Errors
+///
+/// Returns an error if the layout cannot be written
+pub async fn write_oci_layout(
+    path: impl AsRef<Path>,
+    tag: &str,
+    manifest_media_type: Option<&str>,
+    config: LayoutBlob,
+    layers: Vec<LayoutBlob>,
+    annotations: BTreeMap<String, String>,
+) -> anyhow::Result<String> {
+    let path = path.as_ref();
+    let manifest = Manifest {
+        schema_version: 2,
+        media_type: Some(
+            manifest_media_type
+                .unwrap_or(IMAGE_MANIFEST_MEDIA_TYPE)
+                .to_string(),
+        ),
+        config: config.descriptor(),
+        layers: layers.iter().map(LayoutBlob::descriptor).collect(),
+        annotations,
+    };
+    let manifest = LayoutBlob {
+        media_type: IMAGE_MANIFEST_MEDIA_TYPE.to_string(),
+        data: serde_json::to_vec(&manifest).context("failed to encode image manifest")?,
+    };
+    let mut manifest_desc = manifest.descriptor();
+    manifest_desc
+        .annotations
+        .insert(REF_NAME_ANNOTATION.to_string(), tag.to_string());
+    let digest = manifest_desc.digest.clone();
+
+    let is_tar = path.extension().is_some_and(|ext| ext == "tar");
+    let mut index = Index {
+        schema_version: 2,
+        media_type: Some(IMAGE_INDEX_MEDIA_TYPE.to_string()),
+        manifests: Vec::new(),
+    };
+    if !is_tar {
+        if let Ok(buf) = fs::read(path.join(INDEX_FILE)).await {
+            index =
+                serde_json::from_slice(&buf).context("failed to parse existing `index.json`")?;
+        }
+    }
+    index.manifests.retain(|desc| {
+        desc.annotations
+            .get(REF_NAME_ANNOTATION)
+            .map(String::as_str)
+            != Some(tag)
+    });
+    index.manifests.push(manifest_desc);
+
+    let mut files = vec![
+        (
+            OCI_LAYOUT_FILE.to_string(),
+            serde_json::to_vec(&LayoutFile {
+                image_layout_version: IMAGE_LAYOUT_VERSION.to_string(),
+            })?,
+        ),
+        (INDEX_FILE.to_string(), serde_json::to_vec(&index)?),
+    ];
+    for blob in [config, manifest].into_iter().chain(layers) {
+        files.push((blob_path(&sha256_digest(&blob.data))?, blob.data));
+    }
+
+    if is_tar {
+        let file = fs::File::create(path)
+            .await
+            .with_context(|| format!("failed to create `{}`", path.display()))?;
+        let mut tar = tokio_tar::Builder::new(file);
+        for (name, data) in files {
+            let mut header = tokio_tar::Header::new_gnu();
+            header.set_path(&name)?;
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append_data(&mut header, &name, Cursor::new(data))
+                .await?;
+        }
+        let mut file = tar.into_inner().await?;
+        file.flush().await?;
+    } else {
+        for (name, data) in files {
+            let file_path = path.join(&name);
+            if let Some(parent) = file_path.parent() {
+                fs::create_dir_all(parent)
+                    .await
+                    .with_context(|| format!("failed to create `{}`", parent.display()))?;
+            }
+            fs::write(&file_path, data)
+                .await
+                .with_context(|| format!("failed to write `{}`", file_path.display()))?;
+        }
+    }
+    Ok(digest)
+}
+
+/// Read the provider archive referenced by `reference` from an OCI image layout into `cache` and
+/// read it, see [`crate::par::read`]
+///
+/// # Errors
+///
+/// Returns an error if the archive cannot be read from the layout or is not a valid provider
+/// archive
+pub async fn read_provider_from_layout(
+    reference: &str,
+    host_id: impl AsRef<str>,
+    cache: &crate::ArtifactCache,
+) -> anyhow::Result<(PathBuf, Option<jwt::Token<jwt::CapabilityProvider>>)> {
+    let LayoutReference { path, selector } = LayoutReference::parse(reference)?;
+    let (digest, par) = OciLayout::open(&path)
+        .await?
+        .fetch_provider_archive(selector.as_ref())
+        .await?;
+    let path = cache
+        .put(&digest, reference, &par)
+        .await
+        .context("failed to cache provider archive")?;
+    crate::par::read(&path, host_id, reference, crate::UseParFileCache::Ignore)
+        .await
+        .with_context(|| format!("failed to read `{}`", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anyhow::Result;
+
+    fn artifact(data: &[u8]) -> (LayoutBlob, Vec<LayoutBlob>) {
+        (
+            LayoutBlob {
+                media_type: "application/vnd.wasm.config.v0+json".to_string(),
+                data: b"{}".to_vec(),
+            },
+            vec![LayoutBlob {
+                media_type: oci_wasm::WASM_LAYER_MEDIA_TYPE.to_string(),
+                data: data.to_vec(),
+            }],
+        )
+    }
+
+    #[test]
+    fn test_parse_reference() -> Result<()> {
+        assert_eq!(
+            LayoutReference::parse("/srv/bundle:0.1.0")?,
+            LayoutReference {
+                path: "/srv/bundle".into(),
+                selector: Some(LayoutSelector::Tag("0.1.0".into())),
+            }
+        );
+        assert_eq!(
+            LayoutReference::parse("bundle.tar@sha256:abcd")?,
+            LayoutReference {
+                path: "bundle.tar".into(),
+                selector: Some(LayoutSelector::Digest("sha256:abcd".into())),
+            }
+        );
+        assert_eq!(
+            LayoutReference::parse("/srv/bundle")?,
+            LayoutReference {
+                path: "/srv/bundle".into(),
+                selector: None,
+            }
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_layout_roundtrip() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        for path in [dir.path().join("layout"), dir.path().join("layout.tar")] {
+            let (config, layers) = artifact(b"foo");
+            let foo = write_oci_layout(&path, "foo", None, config, layers, BTreeMap::new()).await?;
+            let layout = OciLayout::open(&path).await?;
+            let (digest, content) = layout
+                .fetch_component(Some(&LayoutSelector::Tag("foo".into())))
+                .await?;
+            assert_eq!(digest, foo);
+            assert_eq!(content, b"foo");
+            let (_, content) = layout.fetch_component(None).await?;
+            assert_eq!(content, b"foo");
+            assert!(layout.fetch_provider_archive(None).await.is_err());
+        }
+
+        // Directories accumulate artifacts
+        let path = dir.path().join("layout");
+        let (config, layers) = artifact(b"bar");
+        let bar = write_oci_layout(&path, "bar", None, config, layers, BTreeMap::new()).await?;
+        let layout = OciLayout::open(&path).await?;
+        assert!(layout.fetch_component(None).await.is_err());
+        let (_, content) = layout
+            .fetch_component(Some(&LayoutSelector::Digest(bar)))
+            .await?;
+        assert_eq!(content, b"bar");
+
+        // Tampered blobs are rejected
+        let blob = path.join(blob_path(&sha256_digest(b"bar"))?);
+        fs::write(&blob, b"baz").await?;
+        assert!(layout
+            .fetch_component(Some(&LayoutSelector::Tag("bar".into())))
+            .await
+            .is_err());
+        Ok(())
+    }
+}
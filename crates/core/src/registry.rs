@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use anyhow::{Context as _, Result};
+use serde::{Deserialize, Serialize};
 
 /// The type of a registry
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
@@ -55,6 +56,29 @@ impl From<RegistryAuth> for oci_client::secrets::RegistryAuth {
     }
 }
 
+/// Trust policy for signatures attached to artifacts in a registry
+///
+/// Signatures are expected in the layout produced by `cosign sign --key`, i.e. stored in the same
+/// repository as the artifact under a `sha256-<digest>.sig` tag.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SignaturePolicy {
+    /// Paths to PEM-encoded public keys trusted to sign artifacts. An artifact is accepted if any
+    /// of these keys produced a valid signature over it. Verification is disabled if empty.
+    pub public_keys: Vec<PathBuf>,
+    /// Paths to PEM-encoded transparency log (Rekor) public keys. If not empty, an accepted
+    /// signature must also carry a transparency log bundle, which is verified offline against
+    /// these keys.
+    pub rekor_public_keys: Vec<PathBuf>,
+}
+
+impl SignaturePolicy {
+    /// Whether signature verification is required by this policy
+    pub fn is_enabled(&self) -> bool {
+        !self.public_keys.is_empty()
+    }
+}
+
 /// Credentials for a registry containing wasmCloud artifacts
 #[derive(Debug, Default)]
 #[non_exhaustive]
@@ -69,6 +93,8 @@ pub struct RegistryConfig {
     pub(crate) allow_insecure: bool,
     /// Additional CAs to include in the OCI client configuration
     pub(crate) additional_ca_paths: Vec<PathBuf>,
+    /// Trust policy for signatures attached to artifacts fetched from the registry
+    pub(crate) signature_policy: SignaturePolicy,
 }
 
 /// Builder for constructing a [`RegistryConfig`]
@@ -83,6 +109,7 @@ pub struct RegistryConfigBuilder {
     allow_latest: Option<bool>,
     allow_insecure: Option<bool>,
     additional_ca_paths: Option<Vec<PathBuf>>,
+    signature_policy: Option<SignaturePolicy>,
 }
 
 impl RegistryConfigBuilder {
@@ -111,6 +138,11 @@ impl RegistryConfigBuilder {
         self
     }
 
+    pub fn signature_policy(mut self, policy: SignaturePolicy) -> Self {
+        self.signature_policy = Some(policy);
+        self
+    }
+
     pub fn build(self) -> Result<RegistryConfig> {
         let allow_insecure = self.allow_insecure.unwrap_or_default();
         Ok(RegistryConfig {
@@ -123,6 +155,7 @@ impl RegistryConfigBuilder {
             allow_latest: self.allow_insecure.unwrap_or_default(),
             allow_insecure,
             additional_ca_paths: self.additional_ca_paths.unwrap_or_default(),
+            signature_policy: self.signature_policy.unwrap_or_default(),
         })
     }
 }
@@ -167,4 +200,12 @@ impl RegistryConfig {
     pub fn set_additional_ca_paths(&mut self, value: Vec<PathBuf>) {
         self.additional_ca_paths = value;
     }
+
+    pub fn signature_policy(&self) -> &SignaturePolicy {
+        &self.signature_policy
+    }
+
+    pub fn set_signature_policy(&mut self, value: SignaturePolicy) {
+        self.signature_policy = value;
+    }
 }
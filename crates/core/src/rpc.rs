@@ -9,6 +9,20 @@
 
 use serde::{Deserialize, Serialize};
 
+/// wRPC invocation header carrying the time, in milliseconds, the caller is willing to wait for
+/// the invocation to complete. Receivers use it to derive the execution deadline of the invocation.
+pub const WRPC_TIMEOUT_HEADER: &str = "timeout-ms";
+
+/// Generate the prefix of the NATS inboxes, on which hosts and providers of a lattice receive wRPC
+/// invocation parameters and results
+///
+/// Hosts and providers use the default NATS inbox prefix, so inboxes are currently not scoped to
+/// the lattice and the prefix is the same for all lattices.
+#[must_use]
+pub fn rpc_inbox_prefix(_lattice: &str) -> String {
+    "_INBOX".to_string()
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct HealthCheckRequest {}
 
@@ -66,3 +80,14 @@ pub fn shutdown_subject(lattice: &str, provider_key: &str, link_name: &str) -> S
 pub fn provider_config_update_subject(lattice: &str, provider_key: &str) -> String {
     format!("wasmbus.rpc.{lattice}.{provider_key}.config.update")
 }
+
+/// Generate the wasmbus RPC subject for delivering secret updates to a given provider
+///
+/// Messages published on this subject contain the JSON-serialized secrets of the provider, sealed
+/// with the provider xkey, e.g. a rotated X.509-SVID of the provider.
+///
+/// NOTE that the NATS message body limits (default 1MiB) apply to these messages
+#[must_use]
+pub fn provider_secrets_update_subject(lattice: &str, provider_key: &str) -> String {
+    format!("wasmbus.rpc.{lattice}.{provider_key}.secrets.update")
+}
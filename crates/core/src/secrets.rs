@@ -2,6 +2,15 @@ use std::fmt::{Debug, Formatter, Result};
 
 use serde::{Deserialize, Serialize};
 
+/// Secret holding the PEM-encoded X.509-SVID certificate chain of a provider for its own identity.
+/// Given to providers on start and whenever the SVID is rotated, if enabled on the host
+pub const WORKLOAD_X509_SVID_SECRET: &str = "wasmcloud.dev/x509-svid";
+/// Secret holding the PEM-encoded PKCS#8 private key of the X.509-SVID in [`WORKLOAD_X509_SVID_SECRET`]
+pub const WORKLOAD_X509_SVID_KEY_SECRET: &str = "wasmcloud.dev/x509-svid-key";
+/// Secret holding the PEM-encoded X.509 trust bundle of the trust domain of the X.509-SVID in
+/// [`WORKLOAD_X509_SVID_SECRET`]
+pub const WORKLOAD_X509_BUNDLE_SECRET: &str = "wasmcloud.dev/x509-bundle";
+
 #[derive(Deserialize, Serialize, Clone)]
 // This tagging allows deserializers to know whether the secret is a string or bytes.
 // This is especially necessary for languages where strings and bytes are treated very similarly.
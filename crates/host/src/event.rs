@@ -1,4 +1,8 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+
 use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::Duration;
 
 use serde_json::json;
 use wascap::jwt;
@@ -29,6 +33,40 @@ pub trait EventPublisher: Send + Sync {
 pub struct DefaultEventPublisher {}
 impl EventPublisher for DefaultEventPublisher {}
 
+/// An [EventPublisher], which adds a `sequence` number to the data of all events published by a
+/// host before publishing them using the wrapped publisher.
+///
+/// Sequence numbers start at 1 and are incremented by 1 for every event, before it is published.
+/// An event, which failed to be published, therefore shows up as a gap in the sequence, which
+/// allows consumers to detect missed events. Events published concurrently may arrive out of order,
+/// consumers should order them by their sequence numbers.
+pub(crate) struct SequencedEventPublisher {
+    inner: Arc<dyn EventPublisher>,
+    sequence: Arc<AtomicU64>,
+}
+
+impl SequencedEventPublisher {
+    /// Wrap `inner`, storing the sequence number of the last event assigned in `sequence`
+    pub(crate) fn new(inner: Arc<dyn EventPublisher>, sequence: Arc<AtomicU64>) -> Self {
+        Self { inner, sequence }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventPublisher for SequencedEventPublisher {
+    async fn publish_event(
+        &self,
+        event_name: &str,
+        mut data: serde_json::Value,
+    ) -> anyhow::Result<()> {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(data) = data.as_object_mut() {
+            data.insert("sequence".into(), sequence.into());
+        }
+        self.inner.publish_event(event_name, data).await
+    }
+}
+
 fn format_component_claims(claims: &jwt::Claims<jwt::Component>) -> serde_json::Value {
     let issuer = &claims.issuer;
     let not_before_human = claims
@@ -355,16 +393,42 @@ pub fn provider_health_check(
     })
 }
 
+/// Generates an event payload for when a provider is crash-looping and is no longer restarted
+///
+/// # Arguments
+/// * `host_id` - ID of the host supervising the provider
+/// * `provider_id` - Unique identifier for the provider
+/// * `restarts` - Number of restarts of the provider within `window`
+/// * `window` - Period of time, within which restarts were counted
+///
+/// # Returns
+/// JSON object containing crash-loop details
+pub fn provider_crashloop(
+    host_id: impl AsRef<str>,
+    provider_id: impl AsRef<str>,
+    restarts: usize,
+    window: Duration,
+) -> serde_json::Value {
+    json!({
+        "host_id": host_id.as_ref(),
+        "provider_id": provider_id.as_ref(),
+        "restarts": restarts,
+        "window_seconds": window.as_secs(),
+    })
+}
+
 /// Generates an event payload for when a config is set
 ///
 /// # Arguments
 /// * `config_name` - Name of the configuration being set
+/// * `revision` - Revision assigned to the configuration by the config store, if it keeps revisions
 ///
 /// # Returns
 /// JSON object containing config set details
-pub fn config_set(config_name: impl AsRef<str>) -> serde_json::Value {
+pub fn config_set(config_name: impl AsRef<str>, revision: Option<u64>) -> serde_json::Value {
     json!({
         "config_name": config_name.as_ref(),
+        "revision": revision,
     })
 }
 
@@ -398,3 +462,46 @@ pub fn labels_changed(
         "labels": labels.into(),
     })
 }
+
+/// Generates an event payload for when a host is cordoned or uncordoned
+///
+/// # Arguments
+/// * `host_id` - ID of the host
+/// * `cordoned` - Whether the host is now cordoned
+///
+/// # Returns
+/// JSON object containing the cordon state of the host
+pub fn host_cordoned(host_id: impl AsRef<str>, cordoned: bool) -> serde_json::Value {
+    json!({
+        "host_id": host_id.as_ref(),
+        "cordoned": cordoned,
+    })
+}
+
+/// Generates an event payload for when a host starts draining its workloads
+///
+/// # Arguments
+/// * `host_id` - ID of the draining host
+/// * `timeout_ms` - Optional time in milliseconds given to in-flight invocations to finish
+///
+/// # Returns
+/// JSON object containing drain details
+pub fn host_draining(host_id: impl AsRef<str>, timeout_ms: Option<u64>) -> serde_json::Value {
+    json!({
+        "host_id": host_id.as_ref(),
+        "timeout_ms": timeout_ms,
+    })
+}
+
+/// Generates an event payload for when a host has stopped all of its workloads after draining
+///
+/// # Arguments
+/// * `host_id` - ID of the drained host
+///
+/// # Returns
+/// JSON object containing the host ID
+pub fn host_drained(host_id: impl AsRef<str>) -> serde_json::Value {
+    json!({
+        "host_id": host_id.as_ref(),
+    })
+}
@@ -0,0 +1,353 @@
+//! The HTTP/JSON implementation of the control interface.
+//!
+//! Control interface operations are served under `/api/v1/ctl/`, where the path segments are the
+//! tokens of the NATS subject of the operation following the lattice, e.g. a `POST` request to
+//! `/api/v1/ctl/provider/start/{host_id}` starts a provider. Request and response bodies are the
+//! same JSON documents exchanged over NATS. Events published by the host are streamed as
+//! Server-Sent Events from `/api/v1/events`, optionally filtered by repeated `type` query
+//! parameters. All requests must be authenticated with a bearer token.
+
+use core::convert::Infallible;
+use core::time::Duration;
+
+use std::sync::{Arc, OnceLock, Weak};
+
+use anyhow::Context as _;
+use bytes::Bytes;
+use cloudevents::{EventBuilder, EventBuilderV10};
+use http::{header, HeaderMap, HeaderValue, Method, Request, Response, StatusCode};
+use http_body_util::combinators::UnsyncBoxBody;
+use http_body_util::{BodyExt as _, Full, LengthLimitError, Limited, StreamBody};
+use hyper::body::{Frame, Incoming};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tokio::sync::broadcast;
+use tracing::{debug, instrument, warn};
+use ulid::Ulid;
+use uuid::Uuid;
+use wasmcloud_control_interface::CtlResponse;
+
+use crate::event::EventPublisher;
+use crate::wasmbus::Host;
+
+/// Path prefix of control interface operations
+const CTL_PATH_PREFIX: &str = "/api/v1/ctl/";
+
+/// Path of the host event stream
+const EVENTS_PATH: &str = "/api/v1/events";
+
+/// Maximum size of a control interface request body
+const MAX_REQUEST_SIZE: usize = 4 * 1024 * 1024;
+
+/// Interval at which comments are sent on idle event streams to keep connections open
+const EVENTS_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Number of events buffered for each event stream, after which the oldest events are dropped
+const EVENTS_CAPACITY: usize = 1024;
+
+/// Kinds of control interface operations, which target a single host by its ID
+const HOST_SCOPED_KINDS: [&str; 4] = ["component", "provider", "label", "host"];
+
+/// Body of responses served on the HTTP administration endpoint
+pub(crate) type ResponseBody = UnsyncBoxBody<Bytes, Infallible>;
+
+/// An event published by the host, encoded as a Server-Sent Event
+#[derive(Debug)]
+struct Event {
+    name: String,
+    frame: Bytes,
+}
+
+/// A control interface server that receives HTTP/JSON requests on the host HTTP administration
+/// endpoint and dispatches them to the host for processing.
+#[derive(Debug)]
+pub struct HttpControlInterfaceServer {
+    token: String,
+    host: OnceLock<Weak<Host>>,
+    events: broadcast::Sender<Arc<Event>>,
+}
+
+impl HttpControlInterfaceServer {
+    /// Create a new HTTP control interface server.
+    ///
+    /// # Arguments
+    /// * `token` - The bearer token all requests must be authenticated with.
+    pub fn new(token: impl Into<String>) -> Self {
+        let (events, _) = broadcast::channel(EVENTS_CAPACITY);
+        Self {
+            token: token.into(),
+            host: OnceLock::new(),
+            events,
+        }
+    }
+
+    /// Whether a request for `path` on the HTTP administration endpoint is handled by the control
+    /// interface server
+    pub(crate) fn handles(path: &str) -> bool {
+        path.starts_with(CTL_PATH_PREFIX) || path == EVENTS_PATH
+    }
+
+    /// Attach the host requests are dispatched to. Requests are rejected until a host is attached
+    pub(crate) fn attach(&self, host: &Arc<Host>) {
+        let _ = self.host.set(Arc::downgrade(host));
+    }
+
+    /// Wrap `publisher`, such that events published by the host with ID `host_id` are also sent
+    /// on the event streams of this server
+    pub(crate) fn event_publisher(
+        &self,
+        publisher: Arc<dyn EventPublisher>,
+        host_id: String,
+    ) -> Arc<dyn EventPublisher> {
+        Arc::new(HttpEventPublisher {
+            inner: publisher,
+            event_builder: EventBuilderV10::new().source(host_id),
+            events: self.events.clone(),
+        })
+    }
+
+    /// Handle a request for a path handled by the control interface server
+    #[instrument(level = "debug", skip_all, fields(method = %req.method(), path = req.uri().path()))]
+    pub(crate) async fn handle(&self, req: Request<Incoming>) -> Response<ResponseBody> {
+        if !self.authorized(req.headers()) {
+            let mut res = error_response(StatusCode::UNAUTHORIZED, "unauthorized");
+            res.headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+            return res;
+        }
+        let Some(host) = self.host.get().and_then(Weak::upgrade) else {
+            return error_response(StatusCode::SERVICE_UNAVAILABLE, "host is not running");
+        };
+        if req.uri().path() == EVENTS_PATH {
+            if req.method() != Method::GET {
+                return error_response(
+                    StatusCode::METHOD_NOT_ALLOWED,
+                    &format!("method `{}` not supported for event streams", req.method()),
+                );
+            }
+            return self.stream_events(req.uri().query());
+        }
+        handle_ctl(host, req).await
+    }
+
+    fn authorized(&self, headers: &HeaderMap) -> bool {
+        headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| constant_time_eq(token.as_bytes(), self.token.as_bytes()))
+    }
+
+    /// Stream events published by the host, with a type in the `type` query parameters if any
+    fn stream_events(&self, query: Option<&str>) -> Response<ResponseBody> {
+        let types: Vec<String> = query
+            .into_iter()
+            .flat_map(|query| query.split('&'))
+            .filter_map(|param| param.strip_prefix("type="))
+            .filter(|ty| !ty.is_empty())
+            .map(String::from)
+            .collect();
+        let mut keepalive = tokio::time::interval(EVENTS_KEEPALIVE_INTERVAL);
+        keepalive.reset();
+        let events = futures::stream::unfold(
+            (self.events.subscribe(), keepalive, types),
+            |(mut events, mut keepalive, types)| async move {
+                loop {
+                    let frame = tokio::select! {
+                        _ = keepalive.tick() => Bytes::from_static(b": keep-alive\n\n"),
+                        event = events.recv() => match event {
+                            Ok(event) if types.is_empty() || types.contains(&event.name) => {
+                                event.frame.clone()
+                            }
+                            Ok(_) => continue,
+                            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                                warn!(skipped, "event stream lagged behind, events were dropped");
+                                continue;
+                            }
+                            Err(broadcast::error::RecvError::Closed) => return None,
+                        },
+                    };
+                    return Some((
+                        Ok::<_, Infallible>(Frame::data(frame)),
+                        (events, keepalive, types),
+                    ));
+                }
+            },
+        );
+        let mut res = Response::new(StreamBody::new(events).boxed_unsync());
+        res.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/event-stream"),
+        );
+        res.headers_mut()
+            .insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        res
+    }
+}
+
+/// Dispatch a control interface operation to the host
+async fn handle_ctl(host: Arc<Host>, req: Request<Incoming>) -> Response<ResponseBody> {
+    if req.method() != Method::GET && req.method() != Method::POST {
+        return error_response(
+            StatusCode::METHOD_NOT_ALLOWED,
+            &format!(
+                "method `{}` not supported for control interface operations",
+                req.method()
+            ),
+        );
+    }
+    let segments: Vec<&str> = req
+        .uri()
+        .path()
+        .trim_start_matches(CTL_PATH_PREFIX)
+        .trim_end_matches('/')
+        .split('/')
+        .collect();
+    if segments
+        .iter()
+        .any(|segment| segment.is_empty() || segment.contains(['.', '*', '>']))
+    {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "invalid control interface operation",
+        );
+    }
+    // Reject operations targeting other hosts, which would otherwise be handled by this host
+    if let [kind, _, target] = segments.as_slice() {
+        if HOST_SCOPED_KINDS.contains(kind) && *target != host.id() {
+            return error_response(StatusCode::NOT_FOUND, &format!("unknown host `{target}`"));
+        }
+    }
+    let operation = segments.join(".");
+
+    let payload = match Limited::new(req.into_body(), MAX_REQUEST_SIZE)
+        .collect()
+        .await
+    {
+        Ok(body) => body.to_bytes(),
+        Err(err) if err.is::<LengthLimitError>() => {
+            return error_response(StatusCode::PAYLOAD_TOO_LARGE, "request body is too large");
+        }
+        Err(err) => {
+            debug!(?err, "failed to read control interface request body");
+            return error_response(StatusCode::BAD_REQUEST, "failed to read request body");
+        }
+    };
+    match host.handle_ctl_request(&operation, payload).await {
+        Some(body) => json_response(StatusCode::OK, body),
+        // The host does not respond to this request, e.g. an auction it does not participate in
+        None => {
+            let mut res = Response::new(Full::default().boxed_unsync());
+            *res.status_mut() = StatusCode::NO_CONTENT;
+            res
+        }
+    }
+}
+
+fn json_response(status: StatusCode, body: Bytes) -> Response<ResponseBody> {
+    let mut res = Response::new(Full::new(body).boxed_unsync());
+    *res.status_mut() = status;
+    res.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    res
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<ResponseBody> {
+    let body = serde_json::to_vec(&CtlResponse::<()>::error(message)).unwrap_or_default();
+    json_response(status, body.into())
+}
+
+/// Compare two byte strings in time independent of their contents
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// An [`EventPublisher`], which sends events to the event streams of an
+/// [`HttpControlInterfaceServer`] in addition to publishing them using the wrapped publisher
+struct HttpEventPublisher {
+    inner: Arc<dyn EventPublisher>,
+    event_builder: EventBuilderV10,
+    events: broadcast::Sender<Arc<Event>>,
+}
+
+impl HttpEventPublisher {
+    /// Encode an event with a CloudEvents payload envelope as a Server-Sent Event
+    fn encode(&self, name: &str, data: serde_json::Value) -> anyhow::Result<Event> {
+        let now = OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .context("failed to format current time")?;
+        let ev = self
+            .event_builder
+            .clone()
+            .ty(format!("com.wasmcloud.lattice.{name}"))
+            .id(Uuid::from_u128(Ulid::new().into()).to_string())
+            .time(now)
+            .data("application/json", data)
+            .build()
+            .context("failed to build cloud event")?;
+        let ev = serde_json::to_string(&ev).context("failed to serialize event")?;
+        Ok(Event {
+            name: name.to_string(),
+            frame: format!("event: {name}\ndata: {ev}\n\n").into(),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl EventPublisher for HttpEventPublisher {
+    async fn publish_event(&self, name: &str, data: serde_json::Value) -> anyhow::Result<()> {
+        // Avoid encoding events if there are no event streams
+        if self.events.receiver_count() > 0 {
+            match self.encode(name, data.clone()) {
+                Ok(event) => {
+                    let _ = self.events.send(Arc::new(event));
+                }
+                Err(err) => warn!(
+                    ?err,
+                    event = name,
+                    "failed to encode event for event streams"
+                ),
+            }
+        }
+        self.inner.publish_event(name, data).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handles() {
+        assert!(HttpControlInterfaceServer::handles(
+            "/api/v1/ctl/host/get/NABC"
+        ));
+        assert!(HttpControlInterfaceServer::handles("/api/v1/events"));
+        assert!(!HttpControlInterfaceServer::handles("/api/v1/ctl"));
+        assert!(!HttpControlInterfaceServer::handles("/livez"));
+    }
+
+    #[test]
+    fn test_authorized() {
+        let server = HttpControlInterfaceServer::new("secret");
+        let mut headers = HeaderMap::new();
+        assert!(!server.authorized(&headers));
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer wrong"),
+        );
+        assert!(!server.authorized(&headers));
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Basic secret"),
+        );
+        assert!(!server.authorized(&headers));
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer secret"),
+        );
+        assert!(server.authorized(&headers));
+    }
+}
@@ -0,0 +1,5 @@
+//! HTTP implementations of wasmCloud [crate::wasmbus::Host] extension traits, served on the host
+//! HTTP administration endpoint
+
+/// HTTP/JSON implementation of the wasmCloud control interface
+pub mod ctl;
@@ -10,6 +10,10 @@ pub mod config;
 /// [crate::event::EventPublisher] trait for receiving and publishing events from the host
 pub mod event;
 
+/// HTTP implementations of wasmCloud host extension traits, such as the control interface, served
+/// on the host HTTP administration endpoint
+pub mod http;
+
 /// NATS implementations of [crate::policy::PolicyManager], [crate::secrets::SecretsManager], and
 /// [crate::store::StoreManager] traits for the wasmCloud host.
 pub mod nats;
@@ -52,7 +56,8 @@ use tokio::fs;
 use tracing::{debug, instrument, warn};
 use url::Url;
 use wascap::jwt;
-use wasmcloud_core::{OciFetcher, RegistryConfig};
+use wasmcloud_core::oci_layout::{read_provider_from_layout, LayoutReference, OciLayout};
+use wasmcloud_core::{ArtifactCache, CacheResult, OciFetcher, RegistryConfig};
 
 /// A reference to a resource, either a file, an OCI image, or a builtin provider
 #[derive(PartialEq)]
@@ -61,6 +66,9 @@ pub enum ResourceRef<'a> {
     File(PathBuf),
     /// An OCI reference
     Oci(&'a str),
+    /// A reference to an artifact in a local OCI image layout directory or tarball, of the form
+    /// `path`, `path:tag` or `path@digest`
+    OciLayout(&'a str),
     /// A builtin provider reference
     Builtin(&'a str),
 }
@@ -72,6 +80,7 @@ impl AsRef<str> for ResourceRef<'_> {
             // valid string
             ResourceRef::File(path) => path.to_str().expect("invalid file reference URL"),
             ResourceRef::Oci(s) => s,
+            ResourceRef::OciLayout(s) => s,
             ResourceRef::Builtin(s) => s,
         }
     }
@@ -94,6 +103,11 @@ impl<'a> TryFrom<&'a str> for ResourceRef<'a> {
                             .map(Self::Oci)
                             .context("invalid OCI reference")
                     }
+                    "oci-layout" => s
+                        .strip_prefix("oci-layout://")
+                        .filter(|s| !s.is_empty())
+                        .map(Self::OciLayout)
+                        .context("invalid OCI layout reference"),
                     "wasmcloud+builtin" => s
                         .strip_prefix("wasmcloud+builtin://")
                         .map(Self::Builtin)
@@ -132,18 +146,20 @@ impl ResourceRef<'_> {
                 let (l, _) = s.split_once('/')?;
                 Some(l)
             }
+            ResourceRef::OciLayout(_) => None,
             ResourceRef::Builtin(_) => None,
         }
     }
 }
 
 /// Fetch an component from a reference.
-#[instrument(level = "debug", skip(allow_file_load, registry_config))]
+#[instrument(level = "debug", skip(allow_file_load, registry_config, cache))]
 pub async fn fetch_component(
     component_ref: &str,
     allow_file_load: bool,
     additional_ca_paths: &Vec<PathBuf>,
     registry_config: &HashMap<String, RegistryConfig>,
+    cache: &ArtifactCache,
 ) -> anyhow::Result<Vec<u8>> {
     match ResourceRef::try_from(component_ref)? {
         ResourceRef::File(component_ref) => {
@@ -161,23 +177,40 @@ pub async fn fetch_component(
             .map(OciFetcher::from)
             .unwrap_or_default()
             .with_additional_ca_paths(additional_ca_paths)
+            .with_cache(cache.clone())
             .fetch_component(component_ref)
             .await
             .with_context(|| {
                 format!("failed to fetch component under OCI reference `{component_ref}`")
             }),
+        ResourceRef::OciLayout(layout_ref) => {
+            ensure!(
+                allow_file_load,
+                "unable to start component from OCI layout, file loading is disabled"
+            );
+            let LayoutReference { path, selector } = LayoutReference::parse(layout_ref)?;
+            let (_, component) = OciLayout::open(&path)
+                .await?
+                .fetch_component(selector.as_ref())
+                .await
+                .with_context(|| {
+                    format!("failed to read component under OCI layout reference `{layout_ref}`")
+                })?;
+            Ok(component)
+        }
         ResourceRef::Builtin(..) => bail!("nothing to fetch for a builtin"),
     }
 }
 
 /// Fetch a provider from a reference.
-#[instrument(skip(registry_config, host_id), fields(provider_ref = %provider_ref.as_ref()))]
+#[instrument(skip(registry_config, host_id, cache), fields(provider_ref = %provider_ref.as_ref()))]
 pub async fn fetch_provider(
     provider_ref: &ResourceRef<'_>,
     host_id: impl AsRef<str>,
     allow_file_load: bool,
     additional_ca_paths: &Vec<PathBuf>,
     registry_config: &HashMap<String, RegistryConfig>,
+    cache: &ArtifactCache,
 ) -> anyhow::Result<(PathBuf, Option<jwt::Token<jwt::CapabilityProvider>>)> {
     match provider_ref {
         ResourceRef::File(provider_path) => {
@@ -200,15 +233,51 @@ pub async fn fetch_provider(
             .map(OciFetcher::from)
             .unwrap_or_default()
             .with_additional_ca_paths(additional_ca_paths)
+            .with_cache(cache.clone())
             .fetch_provider(provider_ref, host_id)
             .await
             .with_context(|| {
                 format!("failed to fetch provider under OCI reference `{provider_ref}`")
             }),
+        ResourceRef::OciLayout(layout_ref) => {
+            ensure!(
+                allow_file_load,
+                "unable to start provider from OCI layout, file loading is disabled"
+            );
+            read_provider_from_layout(layout_ref, host_id, cache)
+                .await
+                .with_context(|| {
+                    format!("failed to read provider under OCI layout reference `{layout_ref}`")
+                })
+        }
         ResourceRef::Builtin(..) => bail!("nothing to fetch for a builtin"),
     }
 }
 
+/// Fetch an OCI artifact into the artifact cache ahead of time, so that starting a component or
+/// provider from it later does not need to download it.
+#[instrument(level = "debug", skip(registry_config, cache))]
+pub async fn prefetch_artifact(
+    artifact_ref: &str,
+    additional_ca_paths: &Vec<PathBuf>,
+    registry_config: &HashMap<String, RegistryConfig>,
+    cache: &ArtifactCache,
+) -> anyhow::Result<CacheResult> {
+    match ResourceRef::try_from(artifact_ref)? {
+        ref oci_ref @ ResourceRef::Oci(artifact_ref) => oci_ref
+            .authority()
+            .and_then(|authority| registry_config.get(authority))
+            .map(OciFetcher::from)
+            .unwrap_or_default()
+            .with_additional_ca_paths(additional_ca_paths)
+            .with_cache(cache.clone())
+            .prefetch(artifact_ref)
+            .await
+            .with_context(|| format!("failed to prefetch OCI reference `{artifact_ref}`")),
+        _ => bail!("only OCI references can be prefetched"),
+    }
+}
+
 #[test]
 fn parse_references() -> anyhow::Result<()> {
     // file:// URL
@@ -226,6 +295,13 @@ fn parse_references() -> anyhow::Result<()> {
         "OCI reference should be parsed as OCI and stripped of scheme"
     );
 
+    // oci-layout:// URL
+    ensure!(
+        ResourceRef::try_from("oci-layout:///srv/bundle.tar:0.1.0").expect("failed to parse")
+            == ResourceRef::OciLayout("/srv/bundle.tar:0.1.0"),
+        "OCI layout reference should be parsed as OCI layout and stripped of scheme"
+    );
+
     // http URL
     ensure!(
         ResourceRef::try_from("http://127.0.0.1:5000/v2/foo:0.1.0").expect("failed to parse")
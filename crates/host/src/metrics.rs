@@ -2,6 +2,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use sysinfo::System;
+use tokio::sync::watch;
 use tokio::task::JoinHandle;
 use wasmcloud_tracing::{
     Counter, Gauge, Histogram, KeyValue, Meter, ObservableGauge, UpDownCounter,
@@ -19,10 +20,16 @@ pub struct HostMetrics {
     pub component_invocations: Counter<u64>,
     /// The count of the number of times an component invocation resulted in an error.
     pub component_errors: Counter<u64>,
+    /// Represents the fuel consumed by each component invocation, if fuel metering is enabled.
+    pub component_invocation_fuel: Histogram<u64>,
     /// The number of active instances of a component.
     pub component_active_instances: UpDownCounter<i64>,
     /// The maximum number of instances of a component.
     pub component_max_instances: Gauge<u64>,
+    /// The count of component invocations handled by a pre-instantiated instance.
+    pub component_instance_pool_hits: Counter<u64>,
+    /// The count of component invocations, which found no pre-instantiated instance in the pool.
+    pub component_instance_pool_misses: Counter<u64>,
 
     /// The total amount of available system memory in bytes.
     pub system_total_memory_bytes: ObservableGauge<u64>,
@@ -36,15 +43,18 @@ pub struct HostMetrics {
     // but we don't really have a way of getting at those. We should figure out a way to get at that
     // information so we don't have to duplicate it here.
     pub host_id: String,
-    /// The host's lattice ID.
-    // Eventually a host will be able to support multiple lattices, so this will need to either be
-    // removed or metrics will need to be scoped per-lattice.
+    /// The host's lattice ID. Processes joined to multiple lattices run a host, and therefore
+    /// record metrics, per lattice.
     pub lattice_id: String,
 
+    /// The most recently refreshed system metrics.
+    system_metrics: watch::Receiver<SystemMetrics>,
+
     // Task handle for dropping when the metrics are no longer needed.
     _refresh_task_handle: Arc<RefreshWrapper>,
 }
 
+#[derive(Debug)]
 struct SystemMetrics {
     system_total_memory_bytes: u64,
     /// The total amount of used system memory in bytes.
@@ -94,6 +104,12 @@ impl HostMetrics {
             .with_description("Number of component errors")
             .build();
 
+        let component_invocation_fuel = meter
+            .u64_histogram("wasmcloud_host.component.invocation.fuel")
+            .with_description("Fuel consumed by each component invocation")
+            .with_unit("fuel")
+            .build();
+
         let component_active_instances = meter
             .i64_up_down_counter("wasmcloud_host.component.active_instances")
             .with_description("Number of active component instances")
@@ -104,6 +120,16 @@ impl HostMetrics {
             .with_description("Maximum number of component instances")
             .build();
 
+        let component_instance_pool_hits = meter
+            .u64_counter("wasmcloud_host.component.instance_pool.hits")
+            .with_description("Number of invocations handled by a pre-instantiated instance")
+            .build();
+
+        let component_instance_pool_misses = meter
+            .u64_counter("wasmcloud_host.component.instance_pool.misses")
+            .with_description("Number of invocations, which found no pre-instantiated instance")
+            .build();
+
         let mut system = System::new();
         // Get the initial metrics
         system.refresh_memory();
@@ -113,7 +139,7 @@ impl HostMetrics {
             system_used_memory_bytes: system.used_memory(),
             system_cpu_usage: system.global_cpu_usage() as f64,
         };
-        let (tx, rx) = tokio::sync::watch::channel(initial_metrics);
+        let (tx, rx) = watch::channel(initial_metrics);
 
         let refresh_time = refresh_time.unwrap_or(DEFAULT_REFRESH_TIME);
 
@@ -175,17 +201,34 @@ impl HostMetrics {
             handle_rpc_message_duration_ns: wasmcloud_host_handle_rpc_message_duration_ns,
             component_invocations: component_invocation_count,
             component_errors: component_error_count,
+            component_invocation_fuel,
             component_active_instances,
             component_max_instances,
+            component_instance_pool_hits,
+            component_instance_pool_misses,
             system_total_memory_bytes: system_memory_total_bytes,
             system_used_memory_bytes: system_memory_used_bytes,
             system_cpu_usage,
             host_id,
             lattice_id,
+            system_metrics: rx,
             _refresh_task_handle: Arc::new(RefreshWrapper(refresh_task_handle)),
         })
     }
 
+    /// The total amount of system memory in bytes, as of the last refresh.
+    pub(crate) fn total_memory_bytes(&self) -> u64 {
+        self.system_metrics.borrow().system_total_memory_bytes
+    }
+
+    /// The amount of system memory in bytes, which is not in use, as of the last refresh.
+    pub(crate) fn available_memory_bytes(&self) -> u64 {
+        let metrics = self.system_metrics.borrow();
+        metrics
+            .system_total_memory_bytes
+            .saturating_sub(metrics.system_used_memory_bytes)
+    }
+
     /// Increment the number of active instances of a component.
     pub(crate) fn increment_active_instance(&self, attributes: &[KeyValue]) {
         self.component_active_instances.add(1, attributes);
@@ -201,15 +244,29 @@ impl HostMetrics {
         self.component_max_instances.record(max, attributes);
     }
 
-    /// Record the result of invoking a component, including the elapsed time, any attributes, and whether the invocation resulted in an error.
+    /// Record whether a component invocation was handled by a pre-instantiated instance.
+    pub(crate) fn record_instance_pool(&self, hit: bool, attributes: &[KeyValue]) {
+        if hit {
+            self.component_instance_pool_hits.add(1, attributes);
+        } else {
+            self.component_instance_pool_misses.add(1, attributes);
+        }
+    }
+
+    /// Record the result of invoking a component, including the elapsed time, the fuel consumed (if metered), any attributes, and whether the invocation resulted in an error.
     pub(crate) fn record_component_invocation(
         &self,
         elapsed: u64,
+        fuel_consumed: Option<u64>,
         attributes: &[KeyValue],
         error: bool,
     ) {
         self.handle_rpc_message_duration_ns
             .record(elapsed, attributes);
+        if let Some(fuel_consumed) = fuel_consumed {
+            self.component_invocation_fuel
+                .record(fuel_consumed, attributes);
+        }
         self.component_invocations.add(1, attributes);
         if error {
             self.component_errors.add(1, attributes);
@@ -28,7 +28,7 @@ use crate::{
 
 const DEFAULT_CTL_TOPIC_PREFIX: &str = "wasmbus.ctl";
 
-use super::{create_bucket, ctl::NatsControlInterfaceServer};
+use super::{create_bucket, ctl::NatsControlInterfaceServer, CONFIG_HISTORY};
 
 /// Opinionated [crate::wasmbus::HostBuilder] that uses NATS as the primary transport and implementations
 /// for the [crate::wasmbus::Host] extension traits.
@@ -50,6 +50,7 @@ pub struct NatsHostBuilder {
     // Trait implementations for NATS
     config_store: Arc<dyn StoreManager>,
     data_store: Store,
+    cron_store: Arc<dyn StoreManager>,
     policy_manager: Option<Arc<dyn PolicyManager>>,
     secrets_manager: Option<Arc<dyn SecretsManager>>,
     event_publisher: Option<Arc<dyn EventPublisher>>,
@@ -75,10 +76,13 @@ impl NatsHostBuilder {
             async_nats::jetstream::new(ctl_nats.clone())
         };
         let bucket = format!("LATTICEDATA_{}", lattice);
-        let data_store = create_bucket(&ctl_jetstream, &bucket).await?;
+        let data_store = create_bucket(&ctl_jetstream, &bucket, 1).await?;
 
         let config_bucket = format!("CONFIGDATA_{}", lattice);
-        let config_data = create_bucket(&ctl_jetstream, &config_bucket).await?;
+        let config_data = create_bucket(&ctl_jetstream, &config_bucket, CONFIG_HISTORY).await?;
+
+        let cron_bucket = format!("CRONDATA_{}", lattice);
+        let cron_data = create_bucket(&ctl_jetstream, &cron_bucket, 1).await?;
 
         let supplemental_config = if config_service_enabled {
             load_supplemental_config(&ctl_nats, &lattice, &labels).await?
@@ -103,6 +107,7 @@ impl NatsHostBuilder {
             registry_config,
             config_store: Arc::new(config_data),
             data_store,
+            cron_store: Arc::new(cron_data),
             policy_manager: None,
             secrets_manager: None,
             event_publisher: None,
@@ -185,7 +190,8 @@ impl NatsHostBuilder {
                 .with_secrets_manager(self.secrets_manager)
                 .with_bundle_generator(Some(self.config_generator))
                 .with_config_store(Some(self.config_store))
-                .with_data_store(Some(Arc::new(self.data_store.clone()))),
+                .with_data_store(Some(Arc::new(self.data_store.clone())))
+                .with_cron_store(Some(self.cron_store)),
             NatsControlInterfaceServer::new(
                 self.ctl_nats,
                 self.data_store,
@@ -5,7 +5,7 @@ use std::{sync::Arc, time::Duration};
 use anyhow::{bail, Context as _};
 use async_nats::jetstream::kv::Store;
 use nkeys::KeyPair;
-use tracing::{info, instrument};
+use tracing::{info, instrument, warn};
 
 use crate::workload_identity::{
     setup_workload_identity_nats_connect_options, WorkloadIdentityConfig,
@@ -82,20 +82,28 @@ pub async fn connect_nats(
         .context("failed to connect to NATS")
 }
 
+/// Number of revisions of each named configuration kept by the configuration bucket of a lattice
+pub(crate) const CONFIG_HISTORY: i64 = 16;
+
 #[instrument(level = "debug", skip_all)]
 pub(crate) async fn create_bucket(
     jetstream: &async_nats::jetstream::Context,
     bucket: &str,
+    history: i64,
 ) -> anyhow::Result<Store> {
     // Don't create the bucket if it already exists
     if let Ok(store) = jetstream.get_key_value(bucket).await {
         info!(%bucket, "bucket already exists. Skipping creation.");
+        if history > 1 {
+            ensure_bucket_history(jetstream, bucket, history).await;
+        }
         return Ok(store);
     }
 
     match jetstream
         .create_key_value(async_nats::jetstream::kv::Config {
             bucket: bucket.to_string(),
+            history,
             ..Default::default()
         })
         .await
@@ -109,3 +117,33 @@ pub(crate) async fn create_bucket(
         }
     }
 }
+
+/// Raise the number of revisions kept per key by an existing bucket to `history`, as the bucket
+/// may have been created by a host keeping fewer revisions
+#[instrument(level = "debug", skip(jetstream))]
+async fn ensure_bucket_history(
+    jetstream: &async_nats::jetstream::Context,
+    bucket: &str,
+    history: i64,
+) {
+    let mut stream = match jetstream.get_stream(format!("KV_{bucket}")).await {
+        Ok(stream) => stream,
+        Err(err) => {
+            warn!(%bucket, ?err, "failed to get bucket stream, unable to verify its history");
+            return;
+        }
+    };
+    let mut config = match stream.info().await {
+        Ok(info) if info.config.max_messages_per_subject >= history => return,
+        Ok(info) => info.config.clone(),
+        Err(err) => {
+            warn!(%bucket, ?err, "failed to get bucket stream info, unable to verify its history");
+            return;
+        }
+    };
+    config.max_messages_per_subject = history;
+    match jetstream.update_stream(&config).await {
+        Ok(_) => info!(%bucket, history, "increased bucket history"),
+        Err(err) => warn!(%bucket, ?err, "failed to increase bucket history"),
+    }
+}
@@ -3,7 +3,7 @@
 use std::{collections::HashMap, sync::Arc};
 
 use anyhow::{anyhow, ensure, Context as _};
-use async_nats::jetstream::kv::{Entry as KvEntry, Operation, Store};
+use async_nats::jetstream::kv::{CreateErrorKind, Entry as KvEntry, Operation, Store};
 use bytes::Bytes;
 use futures::{StreamExt as _, TryStreamExt as _};
 use tokio::{
@@ -14,7 +14,7 @@ use tracing::{debug, error, instrument, warn};
 
 use crate::{
     config::ConfigManager,
-    store::StoreManager,
+    store::{StoreManager, StoreRevision},
     wasmbus::{
         claims::{Claims, StoredClaims},
         ComponentSpecification,
@@ -44,6 +44,47 @@ impl StoreManager for Store {
             .await
             .map_err(|err| anyhow::anyhow!("Failed to delete config: {}", err))
     }
+
+    #[instrument(level = "debug", skip(self, value))]
+    async fn create(&self, key: &str, value: Bytes) -> anyhow::Result<bool> {
+        match Store::create(self, key, value).await {
+            Ok(_) => Ok(true),
+            Err(err) if err.kind() == CreateErrorKind::AlreadyExists => Ok(false),
+            Err(err) => Err(anyhow::anyhow!("Failed to create entry: {}", err)),
+        }
+    }
+
+    #[instrument(level = "debug", skip(self, value))]
+    async fn put_revision(&self, key: &str, value: Bytes) -> anyhow::Result<Option<u64>> {
+        self.put(key, value)
+            .await
+            .map(Some)
+            .map_err(|err| anyhow::anyhow!("Failed to set config: {}", err))
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn history(&self, key: &str) -> anyhow::Result<Vec<StoreRevision>> {
+        self.history(key)
+            .await
+            .map_err(|err| anyhow::anyhow!("Failed to get history: {}", err))?
+            .map_ok(|entry| StoreRevision {
+                revision: entry.revision,
+                created_at: Some(entry.created),
+                value: matches!(entry.operation, Operation::Put).then_some(entry.value),
+            })
+            .try_collect()
+            .await
+            .map_err(|err| anyhow::anyhow!("Failed to read history: {}", err))
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn get_revision(&self, key: &str, revision: u64) -> anyhow::Result<Option<Bytes>> {
+        match self.entry_for_revision(key, revision).await {
+            Ok(Some(entry)) if matches!(entry.operation, Operation::Put) => Ok(Some(entry.value)),
+            Ok(_) => Ok(None),
+            Err(err) => Err(anyhow::anyhow!("Failed to get revision: {}", err)),
+        }
+    }
 }
 
 #[async_trait::async_trait]
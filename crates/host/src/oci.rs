@@ -1,9 +1,11 @@
 // Adapted from
 // https://github.com/wasmCloud/wasmcloud-otp/blob/5f13500646d9e077afa1fca67a3fe9c8df5f3381/host_core/native/hostcore_wasmcloud_native/src/oci.rs
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
+use wasmcloud_core::{ArtifactCache, SignaturePolicy};
 
 /// Configuration options for OCI operations.
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
@@ -20,4 +22,27 @@ pub struct Config {
     pub oci_user: Option<String>,
     /// Password for the OCI registry specified by `oci_registry`.
     pub oci_password: Option<String>,
+    /// Signature trust policies keyed by OCI registry. Artifacts fetched from a registry with a
+    /// policy are only started if they carry a signature accepted by that policy.
+    pub signature_policies: HashMap<String, SignaturePolicy>,
+    /// Directory in which fetched OCI artifacts are cached, defaults to a directory in the
+    /// system temporary directory
+    pub cache_dir: Option<PathBuf>,
+    /// Maximum size of the OCI artifact cache in bytes. Least recently used artifacts which are
+    /// not in use by the host are evicted once this is exceeded. Unbounded if not set.
+    pub cache_max_size: Option<u64>,
+    /// OCI references of artifacts to fetch into the cache when the host starts
+    pub prefetch: Vec<String>,
+}
+
+impl Config {
+    /// Returns the [`ArtifactCache`] described by this configuration
+    pub fn artifact_cache(&self) -> ArtifactCache {
+        ArtifactCache::new(
+            self.cache_dir
+                .clone()
+                .unwrap_or_else(ArtifactCache::default_dir),
+            self.cache_max_size,
+        )
+    }
 }
@@ -1,7 +1,7 @@
 use std::collections::{hash_map::Entry, HashMap};
 
 use anyhow::Result;
-use tracing::{debug, instrument, warn};
+use tracing::{debug, error, instrument, warn};
 use wasmcloud_control_interface::RegistryCredential;
 use wasmcloud_core::{RegistryAuth, RegistryConfig, RegistryType};
 
@@ -99,6 +99,37 @@ pub async fn merge_registry_config(
         }
     });
 
+    // update or create entry for all registries with a signature trust policy
+    oci_opts
+        .signature_policies
+        .into_iter()
+        .for_each(|(reg, policy)| match registry_config.entry(reg.clone()) {
+            Entry::Occupied(mut entry) => {
+                debug!(oci_registry_url = %reg, "set signature policy");
+                entry.get_mut().set_signature_policy(policy);
+            }
+            Entry::Vacant(entry) => {
+                debug!(oci_registry_url = %reg, "set signature policy");
+                match RegistryConfig::builder()
+                    .reg_type(RegistryType::Oci)
+                    .auth(RegistryAuth::Anonymous)
+                    .signature_policy(policy)
+                    .build()
+                {
+                    Ok(config) => {
+                        entry.insert(config);
+                    }
+                    Err(err) => {
+                        error!(
+                            ?err,
+                            oci_registry_url = %reg,
+                            "failed to build registry config, signature policy is not enforced"
+                        );
+                    }
+                }
+            }
+        });
+
     // update allow_latest for all registries
     registry_config.iter_mut().for_each(|(url, config)| {
         if !additional_ca_paths.is_empty() {
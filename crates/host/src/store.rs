@@ -1,10 +1,37 @@
 //! Module with structs for use in managing and accessing data used by various wasmCloud entities
-use std::collections::HashMap;
+use core::fmt;
+
+use std::collections::{hash_map, HashMap};
 
 use bytes::Bytes;
+use time::OffsetDateTime;
 use tokio::sync::RwLock;
 use tracing::instrument;
 
+/// A revision of the value of a key in a store keeping a history of values
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoreRevision {
+    /// Revision number assigned by the store, increasing with every change
+    pub revision: u64,
+    /// When the revision was created
+    pub created_at: Option<OffsetDateTime>,
+    /// The value of the key at this revision, or `None` if the key was deleted
+    pub value: Option<Bytes>,
+}
+
+/// Error returned by [`StoreManager::create`] for stores which do not support atomically
+/// inserting a key
+#[derive(Debug)]
+pub struct CreateNotSupported;
+
+impl fmt::Display for CreateNotSupported {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("atomic create not supported")
+    }
+}
+
+impl std::error::Error for CreateNotSupported {}
+
 #[async_trait::async_trait]
 /// A trait for managing a store of data, such as a config store or a data store.
 pub trait StoreManager: Send + Sync {
@@ -16,6 +43,39 @@ pub trait StoreManager: Send + Sync {
 
     /// Deletes a key from the config store.
     async fn del(&self, key: &str) -> anyhow::Result<()>;
+
+    /// Atomically inserts a key-value pair only if the key does not exist, returning whether it
+    /// was inserted.
+    ///
+    /// The default implementation returns a [`CreateNotSupported`] error, for stores which cannot
+    /// insert keys atomically.
+    async fn create(&self, _key: &str, _value: Bytes) -> anyhow::Result<bool> {
+        Err(CreateNotSupported.into())
+    }
+
+    /// Inserts or updates a key-value pair, returning the revision assigned to the value if the
+    /// store keeps a history of values.
+    async fn put_revision(&self, key: &str, value: Bytes) -> anyhow::Result<Option<u64>> {
+        self.put(key, value).await?;
+        Ok(None)
+    }
+
+    /// Retrieves the history of values of a key, ordered from oldest to newest.
+    ///
+    /// The default implementation returns an empty history, for stores which do not keep one.
+    async fn history(&self, _key: &str) -> anyhow::Result<Vec<StoreRevision>> {
+        Ok(Vec::new())
+    }
+
+    /// Retrieves the value of a key at the given revision, if it is still kept by the store.
+    async fn get_revision(&self, key: &str, revision: u64) -> anyhow::Result<Option<Bytes>> {
+        Ok(self
+            .history(key)
+            .await?
+            .into_iter()
+            .find(|entry| entry.revision == revision)
+            .and_then(|entry| entry.value))
+    }
 }
 
 /// A struct that implements the StoreManager trait, storing data in an in-memory HashMap.
@@ -42,4 +102,15 @@ impl StoreManager for DefaultStore {
         self.store.write().await.remove(key);
         Ok(())
     }
+
+    #[instrument(skip(self, value))]
+    async fn create(&self, key: &str, value: Bytes) -> anyhow::Result<bool> {
+        match self.store.write().await.entry(key.to_string()) {
+            hash_map::Entry::Occupied(_) => Ok(false),
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert(value);
+                Ok(true)
+            }
+        }
+    }
 }
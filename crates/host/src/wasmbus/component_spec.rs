@@ -4,6 +4,7 @@
 use anyhow::Context as _;
 use serde::{Deserialize, Serialize};
 use tracing::{error, instrument, warn};
+use wascap::jwt;
 use wasmcloud_control_interface::Link;
 
 use crate::wasmbus::component_import_links;
@@ -19,13 +20,18 @@ pub struct ComponentSpecification {
     /// All outbound links from this component to other components, used for routing when calling a component `import`
     #[serde(default)]
     pub(crate) links: Vec<Link>,
+    /// The claims embedded in the component, if present
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) claims: Option<jwt::Claims<jwt::Component>>,
+    /// SHA256 digest of the component, used for checking uniqueness of component IDs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) digest: Option<String>,
+    /// Names of the configuration the component was scaled with
+    #[serde(default)]
+    pub(crate) config: Vec<String>,
     ////
     // Possible additions in the future, left in as comments to facilitate discussion
     ////
-    // /// The claims embedded in the component, if present
-    // claims: Option<Claims>,
-    // /// SHA256 digest of the component, used for checking uniqueness of component IDs
-    // digest: String
     // /// (Advanced) Additional routing topics to subscribe on in addition to the component ID.
     // routing_groups: Vec<String>,
 }
@@ -35,9 +41,29 @@ impl ComponentSpecification {
     pub fn new(url: impl AsRef<str>) -> Self {
         Self {
             url: url.as_ref().to_string(),
-            links: Vec::new(),
+            ..Default::default()
         }
     }
+
+    /// The URL of the component
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The claims embedded in the component, if present
+    pub fn claims(&self) -> Option<&jwt::Claims<jwt::Component>> {
+        self.claims.as_ref()
+    }
+
+    /// SHA256 digest of the component, of the form `sha256:<hex>`, if recorded
+    pub fn digest(&self) -> Option<&str> {
+        self.digest.as_deref()
+    }
+
+    /// Names of the configuration the component was scaled with
+    pub fn config(&self) -> &[String] {
+        &self.config
+    }
 }
 
 impl super::Host {
@@ -80,6 +106,25 @@ impl super::Host {
         Ok(())
     }
 
+    /// Apply `update` to the stored component specification, if one exists, and store it
+    #[instrument(level = "debug", skip_all)]
+    pub(crate) async fn update_component_spec(
+        &self,
+        id: impl AsRef<str>,
+        update: impl FnOnce(&mut ComponentSpecification),
+    ) -> anyhow::Result<()> {
+        let id = id.as_ref();
+        let Some(mut spec) = self.get_component_spec(id).await? else {
+            warn!(
+                component_id = id,
+                "component spec not found, skipping update"
+            );
+            return Ok(());
+        };
+        update(&mut spec);
+        self.store_component_spec(id, &spec).await
+    }
+
     #[instrument(level = "debug", skip_all)]
     pub(crate) async fn delete_component_spec(&self, id: impl AsRef<str>) -> anyhow::Result<()> {
         let id = id.as_ref();
@@ -0,0 +1,228 @@
+//! Validation of the configuration of components and providers against the JSON schema they declare
+
+use std::collections::{BTreeSet, HashMap};
+
+use anyhow::ensure;
+use serde_json::Value;
+
+/// Ensures that the configuration of the component or provider `id` matches the JSON schema it
+/// declares, if any, failing with a description of every violation otherwise
+pub(crate) fn ensure_valid(
+    id: &str,
+    schema: Option<&Value>,
+    config: &HashMap<String, String>,
+) -> anyhow::Result<()> {
+    let Some(schema) = schema else {
+        return Ok(());
+    };
+    let errors = validate(schema, config);
+    ensure!(
+        errors.is_empty(),
+        "configuration of `{id}` does not match its schema: {}",
+        errors.join("; ")
+    );
+    Ok(())
+}
+
+/// Validates configuration against a JSON schema describing it.
+///
+/// Configuration values are always strings, so a value matches a JSON schema `type` if it can be
+/// parsed as such, e.g. `"8080"` is a valid `integer` and `"true"` a valid `boolean`.
+/// The supported subset of JSON schema keywords is `properties`, `required` and
+/// `additionalProperties` on the top-level object and `type`, `enum`, `minimum`, `maximum`,
+/// `minLength` and `maxLength` on the properties. Other keywords are ignored.
+///
+/// Returns a description of every violation found, which is empty if the configuration is valid
+pub(crate) fn validate(schema: &Value, config: &HashMap<String, String>) -> Vec<String> {
+    let Some(schema) = schema.as_object() else {
+        return Vec::default();
+    };
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let mut errors = Vec::default();
+    if let Some(required) = schema.get("required").and_then(Value::as_array) {
+        for key in required.iter().filter_map(Value::as_str) {
+            if !config.contains_key(key) {
+                errors.push(format!("required key `{key}` is missing"));
+            }
+        }
+    }
+    // Sort the keys to report violations in a stable order
+    let keys: BTreeSet<_> = config.keys().collect();
+    for key in keys {
+        let value = &config[key];
+        match properties.and_then(|properties| properties.get(key)) {
+            Some(property) => {
+                if let Err(e) = validate_value(property, value) {
+                    errors.push(format!("key `{key}` {e}"));
+                }
+            }
+            None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                errors.push(format!("key `{key}` is not allowed by the schema"));
+            }
+            None => {}
+        }
+    }
+    errors
+}
+
+/// Validates a single configuration value against the schema of its property
+fn validate_value(schema: &Value, value: &str) -> Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        return Ok(());
+    };
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(tys)) => tys.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::default(),
+    };
+    if !types.is_empty() && !types.iter().any(|ty| is_type(ty, value)) {
+        return Err(format!(
+            "must be of type `{}`, got `{value}`",
+            types.join("` or `")
+        ));
+    }
+    if let Some(variants) = schema.get("enum").and_then(Value::as_array) {
+        let parsed = serde_json::from_str::<Value>(value).ok();
+        if !variants
+            .iter()
+            .any(|v| v.as_str() == Some(value) || parsed.as_ref() == Some(v))
+        {
+            let variants = variants
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(format!("must be one of {variants}, got `{value}`"));
+        }
+    }
+    if let Ok(n) = value.parse::<f64>() {
+        if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+            if n < min {
+                return Err(format!("must be at least {min}, got `{value}`"));
+            }
+        }
+        if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+            if n > max {
+                return Err(format!("must be at most {max}, got `{value}`"));
+            }
+        }
+    }
+    let len = value.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+        if len < min {
+            return Err(format!("must be at least {min} characters long"));
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+        if len > max {
+            return Err(format!("must be at most {max} characters long"));
+        }
+    }
+    Ok(())
+}
+
+/// Returns whether a configuration value can be interpreted as the JSON schema type `ty`
+fn is_type(ty: &str, value: &str) -> bool {
+    match ty {
+        "string" => true,
+        "integer" => value.parse::<i64>().is_ok() || value.parse::<u64>().is_ok(),
+        "number" => value.parse::<f64>().is_ok_and(f64::is_finite),
+        "boolean" => value == "true" || value == "false",
+        "null" => value == "null",
+        "object" => serde_json::from_str::<Value>(value).is_ok_and(|v| v.is_object()),
+        "array" => serde_json::from_str::<Value>(value).is_ok_and(|v| v.is_array()),
+        // Unknown types are not enforced
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn config(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "url": { "type": "string", "minLength": 1 },
+                "port": { "type": "integer", "minimum": 1, "maximum": 65535 },
+                "tls": { "type": "boolean" },
+                "mode": { "enum": ["fast", "safe"] },
+                "tags": { "type": ["array", "null"] },
+            },
+            "required": ["url", "port"],
+        });
+
+        assert!(validate(&schema, &config(&[("url", "nats://x"), ("port", "4222")])).is_empty());
+        assert!(validate(
+            &schema,
+            &config(&[
+                ("url", "nats://x"),
+                ("port", "4222"),
+                ("tls", "true"),
+                ("mode", "safe"),
+                ("tags", "[\"a\"]"),
+                ("extra", "ignored"),
+            ])
+        )
+        .is_empty());
+
+        assert_eq!(
+            validate(&schema, &config(&[("port", "http")])),
+            [
+                "required key `url` is missing",
+                "key `port` must be of type `integer`, got `http`",
+            ]
+        );
+        assert_eq!(
+            validate(
+                &schema,
+                &config(&[
+                    ("url", ""),
+                    ("port", "70000"),
+                    ("tls", "yes"),
+                    ("mode", "slow"),
+                    ("tags", "a"),
+                ])
+            ),
+            [
+                "key `mode` must be one of \"fast\", \"safe\", got `slow`",
+                "key `port` must be at most 65535, got `70000`",
+                "key `tags` must be of type `array` or `null`, got `a`",
+                "key `tls` must be of type `boolean`, got `yes`",
+                "key `url` must be at least 1 characters long",
+            ]
+        );
+
+        let strict = json!({ "properties": { "url": {} }, "additionalProperties": false });
+        assert_eq!(
+            validate(&strict, &config(&[("url", "x"), ("extra", "y")])),
+            ["key `extra` is not allowed by the schema"]
+        );
+        assert!(validate(&json!(true), &config(&[("extra", "y")])).is_empty());
+    }
+
+    #[test]
+    fn test_ensure_valid() {
+        let schema = json!({ "required": ["url"] });
+        ensure_valid("id", None, &config(&[])).expect("no schema should accept any config");
+        ensure_valid("id", Some(&schema), &config(&[("url", "x")]))
+            .expect("config should be valid");
+        let err = ensure_valid("id", Some(&schema), &config(&[]))
+            .expect_err("missing key should be rejected");
+        assert_eq!(
+            err.to_string(),
+            "configuration of `id` does not match its schema: required key `url` is missing"
+        );
+    }
+}
@@ -8,15 +8,18 @@ use std::time::Duration;
 use anyhow::{anyhow, bail, Context as _};
 use bytes::Bytes;
 use futures::join;
+use serde::Serialize;
 use serde_json::json;
+use time::format_description::well_known::Rfc3339;
 use tokio::spawn;
 use tokio::time::Instant;
 use tracing::{debug, error, info, instrument, trace, warn};
 use wasmcloud_control_interface::{
-    ComponentAuctionAck, ComponentAuctionRequest, CtlResponse,
-    DeleteInterfaceLinkDefinitionRequest, HostInventory, HostLabel, HostLabelIdentifier, Link,
-    ProviderAuctionAck, ProviderAuctionRequest, RegistryCredential, ScaleComponentCommand,
-    StartProviderCommand, StopHostCommand, StopProviderCommand, UpdateComponentCommand,
+    ComponentAuctionAck, ComponentAuctionRequest, ConfigRevision, CordonHostCommand, CtlResponse,
+    DeleteInterfaceLinkDefinitionRequest, DrainHostCommand, GetProviderLogsCommand, HostInventory,
+    HostLabel, HostLabelIdentifier, Link, ProviderAuctionAck, ProviderAuctionRequest, ProviderLogs,
+    RegistryCredential, RollbackConfigCommand, ScaleComponentCommand, StartProviderCommand,
+    StopHostCommand, StopProviderCommand, UpdateComponentCommand,
 };
 use wasmcloud_core::shutdown_subject;
 use wasmcloud_tracing::context::TraceContextInjector;
@@ -51,6 +54,21 @@ pub trait ControlInterfaceServer: Send + Sync {
     /// or failure.
     async fn handle_stop_host(&self, request: StopHostCommand) -> anyhow::Result<CtlResponse<()>>;
 
+    /// Handle a request to cordon or uncordon the host. A cordoned host should not respond to
+    /// auction requests. This method should return a response indicating success or failure.
+    async fn handle_cordon_host(
+        &self,
+        request: CordonHostCommand,
+    ) -> anyhow::Result<CtlResponse<()>>;
+
+    /// Handle a request to drain the host. The host should be cordoned and stop all of its
+    /// workloads once in-flight invocations finished. This method should return a response
+    /// indicating whether draining was started.
+    async fn handle_drain_host(
+        self: Arc<Self>,
+        request: DrainHostCommand,
+    ) -> anyhow::Result<CtlResponse<()>>;
+
     /// Handle a request to scale a component. This method should return a response indicating success
     /// or failure.
     async fn handle_scale_component(
@@ -79,6 +97,13 @@ pub trait ControlInterfaceServer: Send + Sync {
         request: StopProviderCommand,
     ) -> anyhow::Result<CtlResponse<()>>;
 
+    /// Handle a request to get the most recent output of a provider. This method should return a
+    /// response containing the buffered log lines of the provider.
+    async fn handle_get_provider_logs(
+        &self,
+        request: GetProviderLogsCommand,
+    ) -> anyhow::Result<CtlResponse<ProviderLogs>>;
+
     /// Handle a request to get the host inventory. This method should return a response containing
     /// the host inventory.
     async fn handle_inventory(&self) -> anyhow::Result<CtlResponse<HostInventory>>;
@@ -141,6 +166,29 @@ pub trait ControlInterfaceServer: Send + Sync {
         data: Bytes,
     ) -> anyhow::Result<CtlResponse<()>>;
 
+    /// Handle a request to get the configuration for a specific key at the given revision. This method
+    /// should return a response containing the configuration.
+    async fn handle_config_get_revision(
+        &self,
+        config_name: &str,
+        revision: u64,
+    ) -> anyhow::Result<CtlResponse<HashMap<String, String>>>;
+
+    /// Handle a request to get the revision history of the configuration for a specific key. This method
+    /// should return a response containing the revisions, ordered from oldest to newest.
+    async fn handle_config_history(
+        &self,
+        config_name: &str,
+    ) -> anyhow::Result<CtlResponse<Vec<ConfigRevision>>>;
+
+    /// Handle a request to restore the configuration for a specific key to a previous revision. This
+    /// method should return a response containing the new revision of the configuration.
+    async fn handle_config_rollback(
+        &self,
+        config_name: &str,
+        request: RollbackConfigCommand,
+    ) -> anyhow::Result<CtlResponse<ConfigRevision>>;
+
     /// Handle a request to ping all hosts in the lattice. This method should return a response containing
     /// the host data.
     async fn handle_ping_hosts(
@@ -166,25 +214,53 @@ impl ControlInterfaceServer for Host {
             "handling auction for component"
         );
 
+        if self.cordoned.load(Ordering::Relaxed) {
+            debug!(component_id, "host is cordoned, skipping component auction");
+            return Ok(None);
+        }
+
         let host_labels = self.labels.read().await;
         let constraints_satisfied = constraints
             .iter()
             .all(|(k, v)| host_labels.get(k).is_some_and(|hv| hv == v));
-        let component_id_running = self.components.read().await.contains_key(component_id);
-
-        // This host can run the component if all constraints are satisfied and the component is not already running
-        if constraints_satisfied && !component_id_running {
-            Ok(Some(CtlResponse::ok(
-                ComponentAuctionAck::from_component_host_and_constraints(
-                    component_ref,
-                    component_id,
-                    &self.host_key.public_key(),
-                    constraints.clone(),
-                ),
-            )))
-        } else {
-            Ok(None)
+        let components = self.components.read().await;
+        let component_id_running = components.contains_key(component_id);
+        let component_ref_running = components
+            .values()
+            .any(|component| &*component.image_reference == component_ref);
+        drop(components);
+        let max_slots = u64::from(self.host_config.max_components);
+        let free_slots = max_slots.saturating_sub(self.used_component_slots().await);
+        let available_memory = self.metrics.available_memory_bytes();
+        let memory_satisfied =
+            request.min_available_memory().unwrap_or_default() <= available_memory;
+
+        // This host can run the component if all constraints are satisfied, the component is not
+        // already running and the host has capacity left for it
+        if !constraints_satisfied
+            || component_id_running
+            || free_slots == 0
+            || !memory_satisfied
+            || (request.anti_affinity() && component_ref_running)
+        {
+            return Ok(None);
         }
+        let score = auction_score(
+            (percentage(free_slots, max_slots)
+                + percentage(available_memory, self.metrics.total_memory_bytes()))
+                / 2,
+            component_ref_running,
+        );
+        Ok(Some(CtlResponse::ok(
+            ComponentAuctionAck::builder()
+                .component_ref(component_ref.into())
+                .component_id(component_id.into())
+                .host_id(self.host_key.public_key())
+                .constraints(constraints.clone())
+                .score(score)
+                .build()
+                .map_err(|e| anyhow!("failed to build component auction ack: {e}"))?,
+        )))
     }
 
     #[instrument(level = "debug", skip_all)]
@@ -203,25 +279,45 @@ impl ControlInterfaceServer for Host {
             "handling auction for provider"
         );
 
+        if self.cordoned.load(Ordering::Relaxed) {
+            debug!(provider_id, "host is cordoned, skipping provider auction");
+            return Ok(None);
+        }
+
         let host_labels = self.labels.read().await;
         let constraints_satisfied = constraints
             .iter()
             .all(|(k, v)| host_labels.get(k).is_some_and(|hv| hv == v));
         let providers = self.providers.read().await;
         let provider_running = providers.contains_key(provider_id);
-        if constraints_satisfied && !provider_running {
-            Ok(Some(CtlResponse::ok(
-                ProviderAuctionAck::builder()
-                    .provider_ref(provider_ref.into())
-                    .provider_id(provider_id.into())
-                    .constraints(constraints.clone())
-                    .host_id(self.host_key.public_key())
-                    .build()
-                    .map_err(|e| anyhow!("failed to build provider auction ack: {e}"))?,
-            )))
-        } else {
-            Ok(None)
+        let provider_ref_running = providers
+            .values()
+            .any(|provider| provider.image_ref == provider_ref);
+        drop(providers);
+        let available_memory = self.metrics.available_memory_bytes();
+        let memory_satisfied =
+            request.min_available_memory().unwrap_or_default() <= available_memory;
+        if !constraints_satisfied
+            || provider_running
+            || !memory_satisfied
+            || (request.anti_affinity() && provider_ref_running)
+        {
+            return Ok(None);
         }
+        let score = auction_score(
+            percentage(available_memory, self.metrics.total_memory_bytes()),
+            provider_ref_running,
+        );
+        Ok(Some(CtlResponse::ok(
+            ProviderAuctionAck::builder()
+                .provider_ref(provider_ref.into())
+                .provider_id(provider_id.into())
+                .constraints(constraints.clone())
+                .host_id(self.host_key.public_key())
+                .score(score)
+                .build()
+                .map_err(|e| anyhow!("failed to build provider auction ack: {e}"))?,
+        )))
     }
 
     #[instrument(level = "debug", skip_all)]
@@ -241,6 +337,88 @@ impl ControlInterfaceServer for Host {
         ))
     }
 
+    #[instrument(level = "debug", skip_all)]
+    async fn handle_cordon_host(
+        &self,
+        request: CordonHostCommand,
+    ) -> anyhow::Result<CtlResponse<()>> {
+        let cordoned = request.cordoned();
+
+        info!(cordoned, "handling cordon host");
+
+        if !cordoned && self.draining.load(Ordering::Relaxed) {
+            return Ok(CtlResponse::error(
+                "cannot uncordon host while it is draining",
+            ));
+        }
+        self.cordoned.store(cordoned, Ordering::Relaxed);
+        self.event_publisher
+            .publish_event(
+                "host_cordoned",
+                crate::event::host_cordoned(self.host_key.public_key(), cordoned),
+            )
+            .await?;
+
+        Ok(CtlResponse::<()>::success(
+            "successfully handled cordon host".into(),
+        ))
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn handle_drain_host(
+        self: Arc<Self>,
+        request: DrainHostCommand,
+    ) -> anyhow::Result<CtlResponse<()>> {
+        let timeout = request.timeout();
+        let stop_host = request.stop_host();
+
+        info!(?timeout, stop_host, "handling drain host");
+
+        if self.draining.swap(true, Ordering::Relaxed) {
+            return Ok(CtlResponse::error("host is already draining"));
+        }
+        self.cordoned.store(true, Ordering::Relaxed);
+        let host_id = self.host_key.public_key();
+        if let Err(err) = self
+            .event_publisher
+            .publish_event(
+                "host_draining",
+                crate::event::host_draining(&host_id, timeout),
+            )
+            .await
+        {
+            self.draining.store(false, Ordering::Relaxed);
+            return Err(err);
+        }
+
+        let deadline =
+            timeout.and_then(|timeout| Instant::now().checked_add(Duration::from_millis(timeout)));
+        spawn(async move {
+            if let Err(err) = self.drain_workloads(deadline).await {
+                error!(?err, "failed to drain host");
+            } else {
+                info!("host drained");
+                if let Err(err) = self
+                    .event_publisher
+                    .publish_event("host_drained", crate::event::host_drained(&host_id))
+                    .await
+                {
+                    error!(?err, "failed to publish host drained event");
+                }
+            }
+            self.draining.store(false, Ordering::Relaxed);
+            if stop_host {
+                self.ready.store(false, Ordering::Relaxed);
+                self.heartbeat.abort();
+                self.stop_tx.send_replace(None);
+            }
+        });
+
+        Ok(CtlResponse::<()>::success(
+            "successfully handled drain host".into(),
+        ))
+    }
+
     #[instrument(level = "debug", skip_all)]
     async fn handle_scale_component(
         self: Arc<Self>,
@@ -253,12 +431,19 @@ impl ControlInterfaceServer for Host {
         let config = request.config().clone();
         let allow_update = request.allow_update();
         let host_id = request.host_id();
+        let limits = request.limits().cloned().unwrap_or_default();
+        self.validate_component_limits(&limits)
+            .context("invalid component resource limits")?;
 
         debug!(
             component_ref,
             max_instances, component_id, "handling scale component"
         );
 
+        if max_instances > 0 && self.draining.load(Ordering::Relaxed) {
+            return Ok(CtlResponse::error("host is draining"));
+        }
+
         let host_id = host_id.to_string();
         let annotations: Annotations = annotations
             .cloned()
@@ -349,12 +534,14 @@ impl ControlInterfaceServer for Host {
                     Arc::clone(&component_id),
                     &host_id,
                     max_instances,
+                    limits,
                     &annotations,
                     config,
                     wasm.ok_or_else(|| {
                         retrieval_error.unwrap_or_else(|| anyhow!("unexpected missing wasm binary"))
                     }),
                     claims_token.as_ref(),
+                    allow_update,
                 )
                 .await
             {
@@ -469,6 +656,10 @@ impl ControlInterfaceServer for Host {
         self: Arc<Self>,
         request: StartProviderCommand,
     ) -> anyhow::Result<Option<CtlResponse<()>>> {
+        if self.draining.load(Ordering::Relaxed) {
+            return Ok(Some(CtlResponse::error("host is draining")));
+        }
+
         if self
             .providers
             .read()
@@ -498,6 +689,14 @@ impl ControlInterfaceServer for Host {
                 );
                 return Ok(None);
             }
+            if !self.experimental_features.builtin_cron && name == "cron" {
+                debug!(
+                    provider_ref = request.provider_ref(),
+                    provider_id = request.provider_id(),
+                    "skipping start provider for disabled builtin cron provider"
+                );
+                return Ok(None);
+            }
         }
 
         // NOTE: We log at info since starting providers can take a while
@@ -619,6 +818,29 @@ impl ControlInterfaceServer for Host {
     }
 
     #[instrument(level = "debug", skip_all)]
+    async fn handle_get_provider_logs(
+        &self,
+        request: GetProviderLogsCommand,
+    ) -> anyhow::Result<CtlResponse<ProviderLogs>> {
+        let provider_id = request.provider_id();
+
+        trace!(provider_id, "handling get provider logs");
+
+        let providers = self.providers.read().await;
+        let Some(Provider { logs, .. }) = providers.get(provider_id) else {
+            return Ok(CtlResponse::error("provider with that ID is not running"));
+        };
+        let (lines, truncated) = logs.get(request.since(), request.max_lines());
+        let logs = ProviderLogs::builder()
+            .provider_id(provider_id)
+            .lines(lines)
+            .truncated(truncated)
+            .build()
+            .map_err(|e| anyhow!("failed to build provider logs: {e}"))?;
+        Ok(CtlResponse::ok(logs))
+    }
+
+    #[instrument(level = "trace", skip_all)]
     async fn handle_inventory(&self) -> anyhow::Result<CtlResponse<HostInventory>> {
         trace!("handling inventory");
         let inventory = self.inventory().await;
@@ -783,6 +1005,7 @@ impl ControlInterfaceServer for Host {
                     .iter()
                     .chain(request.target_config())
             ).await?;
+            self.validate_link_config_schema(&request).await?;
 
             let mut component_spec = self
                 .get_component_spec(source_id)
@@ -973,19 +1196,113 @@ impl ControlInterfaceServer for Host {
         // Validate that the data is of the proper type by deserialing it
         serde_json::from_slice::<HashMap<String, String>>(&data)
             .context("config data should be a map of string -> string")?;
-        self.config_store
-            .put(config_name, data)
+        let revision = self
+            .config_store
+            .put_revision(config_name, data)
             .await
             .context("unable to store config data")?;
         // We don't write it into the cached data and instead let the caching thread handle it as we
         // won't need it immediately.
         self.event_publisher
-            .publish_event("config_set", crate::event::config_set(config_name))
+            .publish_event(
+                "config_set",
+                crate::event::config_set(config_name, revision),
+            )
             .await?;
 
         Ok(CtlResponse::<()>::success("successfully put config".into()))
     }
 
+    #[instrument(level = "trace", skip(self))]
+    async fn handle_config_get_revision(
+        &self,
+        config_name: &str,
+        revision: u64,
+    ) -> anyhow::Result<CtlResponse<HashMap<String, String>>> {
+        trace!(%config_name, revision, "handling get config revision");
+        let Some(config_bytes) = self
+            .config_store
+            .get_revision(config_name, revision)
+            .await?
+        else {
+            bail!("revision {revision} of config `{config_name}` not found");
+        };
+        let config_map: HashMap<String, String> = serde_json::from_slice(&config_bytes)
+            .context("config data should be a map of string -> string")?;
+        Ok(CtlResponse::ok(config_map))
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn handle_config_history(
+        &self,
+        config_name: &str,
+    ) -> anyhow::Result<CtlResponse<Vec<ConfigRevision>>> {
+        trace!(%config_name, "handling get config history");
+        let history = self
+            .config_store
+            .history(config_name)
+            .await
+            .context("failed to get config history")?
+            .into_iter()
+            .map(|entry| {
+                let revision = ConfigRevision::builder()
+                    .name(config_name)
+                    .revision(entry.revision)
+                    .deleted(entry.value.is_none());
+                let revision = match entry
+                    .created_at
+                    .and_then(|created_at| created_at.format(&Rfc3339).ok())
+                {
+                    Some(created_at) => revision.created_at(&created_at),
+                    None => revision,
+                };
+                revision
+                    .build()
+                    .map_err(|e| anyhow!("failed to build config revision: {e}"))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(CtlResponse::ok(history))
+    }
+
+    #[instrument(level = "debug", skip_all, fields(%config_name))]
+    async fn handle_config_rollback(
+        &self,
+        config_name: &str,
+        request: RollbackConfigCommand,
+    ) -> anyhow::Result<CtlResponse<ConfigRevision>> {
+        debug!(revision = request.revision(), "handle config rollback");
+        let Some(data) = self
+            .config_store
+            .get_revision(config_name, request.revision())
+            .await
+            .context("failed to get config revision")?
+        else {
+            bail!(
+                "revision {} of config `{config_name}` not found",
+                request.revision()
+            );
+        };
+        let revision = self
+            .config_store
+            .put_revision(config_name, data)
+            .await
+            .context("unable to store config data")?
+            .context("config store does not keep revisions")?;
+        self.event_publisher
+            .publish_event(
+                "config_set",
+                crate::event::config_set(config_name, Some(revision)),
+            )
+            .await?;
+
+        let revision = ConfigRevision::builder()
+            .name(config_name)
+            .revision(revision)
+            .build()
+            .map_err(|e| anyhow!("failed to build config revision: {e}"))?;
+        Ok(CtlResponse::ok(revision))
+    }
+
     #[instrument(level = "debug", skip_all)]
     async fn handle_ping_hosts(
         &self,
@@ -1015,3 +1332,230 @@ impl ControlInterfaceServer for Host {
         Ok(CtlResponse::ok(host))
     }
 }
+
+impl Host {
+    /// Handle a control interface request for `operation`, which are the tokens of the NATS
+    /// subject following the lattice, e.g. `component.scale.{host_id}`. Returns the serialized
+    /// response, or `None` if the host does not respond to the request.
+    pub(crate) async fn handle_ctl_request(
+        self: Arc<Self>,
+        operation: &str,
+        payload: Bytes,
+    ) -> Option<Bytes> {
+        // This response is a wrapped Result<Option<Result<Vec<u8>>>> for a good reason.
+        // The outer Result is for reporting protocol errors in handling the request, e.g. failing to
+        //    deserialize the request payload.
+        // The Option is for the case where the request is handled successfully, but the handler
+        //    doesn't want to send a response back to the client, like with an auction.
+        // The inner Result is purely for the success or failure of serializing the [CtlResponse], which
+        //    should never fail but it's a result we must handle.
+        // And finally, the Vec<u8> is the serialized [CtlResponse] that we'll send back to the client
+        let mut parts = operation.split('.');
+        let ctl_response = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            // Component commands
+            (Some("component"), Some("auction"), None, None) => self
+                .handle_auction_component(payload)
+                .await
+                .map(serialize_ctl_response),
+            (Some("component"), Some("scale"), Some(_host_id), None) => Arc::clone(&self)
+                .handle_scale_component(payload)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            (Some("component"), Some("update"), Some(_host_id), None) => Arc::clone(&self)
+                .handle_update_component(payload)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            // Provider commands
+            (Some("provider"), Some("auction"), None, None) => self
+                .handle_auction_provider(payload)
+                .await
+                .map(serialize_ctl_response),
+            (Some("provider"), Some("start"), Some(_host_id), None) => Arc::clone(&self)
+                .handle_start_provider(payload)
+                .await
+                .map(serialize_ctl_response),
+            (Some("provider"), Some("stop"), Some(_host_id), None) => self
+                .handle_stop_provider(payload)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            (Some("provider"), Some("logs"), Some(_host_id), None) => self
+                .handle_get_provider_logs(payload)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            // Host commands
+            (Some("host"), Some("get"), Some(_host_id), None) => self
+                .handle_inventory()
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            (Some("host"), Some("ping"), None, None) => self
+                .handle_ping_hosts()
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            (Some("host"), Some("stop"), Some(host_id), None) => self
+                .handle_stop_host(payload, host_id)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            (Some("host"), Some("cordon"), Some(host_id), None) => self
+                .handle_cordon_host(payload, host_id)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            (Some("host"), Some("drain"), Some(host_id), None) => Arc::clone(&self)
+                .handle_drain_host(payload, host_id)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            // Claims commands
+            (Some("claims"), Some("get"), None, None) => self
+                .handle_claims()
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            // Link commands
+            (Some("link"), Some("del"), None, None) => self
+                .handle_link_del(payload)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            (Some("link"), Some("get"), None, None) => {
+                // Explicitly returning a Vec<u8> for non-cloning efficiency within handle_links
+                self.handle_links().await.map(|bytes| Some(Ok(bytes)))
+            }
+            (Some("link"), Some("put"), None, None) => self
+                .handle_link_put(payload)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            // Label commands
+            (Some("label"), Some("del"), Some(host_id), None) => self
+                .handle_label_del(host_id, payload)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            (Some("label"), Some("put"), Some(host_id), None) => self
+                .handle_label_put(host_id, payload)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            // Registry commands
+            (Some("registry"), Some("put"), None, None) => self
+                .handle_registries_put(payload)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            // Config commands
+            (Some("config"), Some("get"), Some(config_name), None) => self
+                .handle_config_get(config_name)
+                .await
+                .map(|bytes| Some(Ok(bytes))),
+            (Some("config"), Some("put"), Some(config_name), None) => self
+                .handle_config_put(config_name, payload)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            (Some("config"), Some("del"), Some(config_name), None) => self
+                .handle_config_delete(config_name)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            (Some("config"), Some("get"), Some(config_name), Some(revision)) => self
+                .handle_config_get_revision(config_name, revision)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            (Some("config"), Some("history"), Some(config_name), None) => self
+                .handle_config_history(config_name)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            (Some("config"), Some("rollback"), Some(config_name), None) => self
+                .handle_config_rollback(config_name, payload)
+                .await
+                .map(Some)
+                .map(serialize_ctl_response),
+            // Topic fallback
+            _ => {
+                warn!(%operation, "received unsupported control interface request");
+                Ok(serialize_ctl_response(Some(CtlResponse::error(
+                    "unsupported operation",
+                ))))
+            }
+        };
+
+        if let Err(err) = &ctl_response {
+            error!(%operation, ?err, "failed to handle control interface request");
+        } else {
+            trace!(%operation, "handled control interface request");
+        }
+
+        match ctl_response {
+            Ok(Some(Ok(payload))) => Some(payload.into()),
+            // No response from the host (e.g. auctioning provider)
+            Ok(None) => None,
+            Err(e) => Some(
+                serde_json::to_vec(&CtlResponse::error(&e.to_string()))
+                    .context("failed to encode control interface response")
+                    // This should never fail to serialize, but the fallback ensures that we send
+                    // something back to the client even if we somehow fail.
+                    .unwrap_or_else(|_| format!(r#"{{"success":false,"error":"{e}"}}"#).into())
+                    .into(),
+            ),
+            // This would only occur if we failed to serialize a valid CtlResponse. This is
+            // programmer error.
+            Ok(Some(Err(e))) => Some(
+                serde_json::to_vec(&CtlResponse::error(&e.to_string()))
+                    .context("failed to encode control interface response")
+                    .unwrap_or_else(|_| format!(r#"{{"success":false,"error":"{e}"}}"#).into())
+                    .into(),
+            ),
+        }
+    }
+}
+
+/// Returns `part` as a percentage of `total`, from `0` to `100`
+fn percentage(part: u64, total: u64) -> u32 {
+    if total == 0 {
+        return 0;
+    }
+    let percentage = u128::from(part.min(total)) * 100 / u128::from(total);
+    u32::try_from(percentage).unwrap_or(100)
+}
+
+/// Computes the score of an auction bid from the percentage of free host capacity. Bids of hosts
+/// already running an instance of the same workload are halved to spread instances across hosts
+fn auction_score(free_capacity: u32, colocated: bool) -> u32 {
+    if colocated {
+        free_capacity / 2
+    } else {
+        free_capacity
+    }
+}
+
+/// Helper function to serialize `CtlResponse`<T> into a Vec<u8> if the response is Some
+fn serialize_ctl_response<T: Serialize>(
+    ctl_response: Option<CtlResponse<T>>,
+) -> Option<anyhow::Result<Vec<u8>>> {
+    ctl_response.map(|resp| serde_json::to_vec(&resp).map_err(anyhow::Error::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{auction_score, percentage};
+
+    #[test]
+    fn score_auction_bids() {
+        assert_eq!(percentage(1, 4), 25);
+        assert_eq!(percentage(5, 4), 100);
+        assert_eq!(percentage(u64::MAX, u64::MAX), 100);
+        assert_eq!(percentage(1, 0), 0);
+        assert_eq!(auction_score(80, false), 80);
+        assert_eq!(auction_score(80, true), 40);
+    }
+}
@@ -10,6 +10,9 @@ pub struct Features {
     /// Enable the built-in NATS Messaging capability provider
     /// that can be started with the reference wasmcloud+builtin://messaging-nats
     pub(crate) builtin_messaging_nats: bool,
+    /// Enable the built-in cron capability provider
+    /// that can be started with the reference wasmcloud+builtin://cron
+    pub(crate) builtin_cron: bool,
     /// Enable the wasmcloud:messaging@v3 interface support in the host
     pub(crate) wasmcloud_messaging_v3: bool,
     /// Enable workload identity in the host that will be used for authenticating
@@ -19,6 +22,8 @@ pub struct Features {
     pub(crate) workload_identity_interface: bool,
     /// Enable the wrpc:rpc interface support in the runtime
     pub(crate) rpc_interface: bool,
+    /// Enable the wasmcloud:blobstore extension interfaces support in the runtime
+    pub(crate) blobstore_extensions: bool,
 }
 
 impl Features {
@@ -39,6 +44,12 @@ impl Features {
         self
     }
 
+    /// Enable the built-in cron capability provider
+    pub fn enable_builtin_cron(mut self) -> Self {
+        self.builtin_cron = true;
+        self
+    }
+
     /// Enable the wasmcloud:messaging@v3 interface support in the host
     pub fn enable_wasmcloud_messaging_v3(mut self) -> Self {
         self.wasmcloud_messaging_v3 = true;
@@ -63,6 +74,12 @@ impl Features {
         self
     }
 
+    /// Enable wasmcloud:blobstore extension interfaces support in the runtime
+    pub fn enable_blobstore_extensions(mut self) -> Self {
+        self.blobstore_extensions = true;
+        self
+    }
+
     /// Check if the built-in HTTP server capability provider is enabled
     pub fn builtin_http_server_enabled(&self) -> bool {
         self.builtin_http_server
@@ -73,6 +90,11 @@ impl Features {
         self.builtin_messaging_nats
     }
 
+    /// Check if the built-in cron capability provider is enabled
+    pub fn builtin_cron_enabled(&self) -> bool {
+        self.builtin_cron
+    }
+
     /// Check if the wasmcloud:messaging@v3 interface support is enabled
     pub fn wasmcloud_messaging_v3_enabled(&self) -> bool {
         self.wasmcloud_messaging_v3
@@ -92,6 +114,11 @@ impl Features {
     pub fn rpc_interface_enabled(&self) -> bool {
         self.rpc_interface
     }
+
+    /// Check if the wasmcloud:blobstore extension interfaces support is enabled
+    pub fn blobstore_extensions_enabled(&self) -> bool {
+        self.blobstore_extensions
+    }
 }
 
 /// This enables unioning feature flags together
@@ -102,11 +129,13 @@ impl std::ops::BitOr for Features {
         Self {
             builtin_http_server: self.builtin_http_server || rhs.builtin_http_server,
             builtin_messaging_nats: self.builtin_messaging_nats || rhs.builtin_messaging_nats,
+            builtin_cron: self.builtin_cron || rhs.builtin_cron,
             wasmcloud_messaging_v3: self.wasmcloud_messaging_v3 || rhs.wasmcloud_messaging_v3,
             workload_identity_auth: self.workload_identity_auth || rhs.workload_identity_auth,
             workload_identity_interface: self.workload_identity_interface
                 || rhs.workload_identity_interface,
             rpc_interface: self.rpc_interface || rhs.rpc_interface,
+            blobstore_extensions: self.blobstore_extensions || rhs.blobstore_extensions,
         }
     }
 }
@@ -130,6 +159,7 @@ impl From<&str> for Features {
             "builtin-messaging-nats" | "builtin_messaging_nats" => {
                 Self::new().enable_builtin_messaging_nats()
             }
+            "builtin-cron" | "builtin_cron" => Self::new().enable_builtin_cron(),
             "wasmcloud-messaging-v3" | "wasmcloud_messaging_v3" => {
                 Self::new().enable_wasmcloud_messaging_v3()
             }
@@ -140,6 +170,9 @@ impl From<&str> for Features {
                 Self::new().enable_workload_identity_interface()
             }
             "rpc-interface" | "rpc_interface" => Self::new().enable_rpc_interface(),
+            "blobstore-extensions" | "blobstore_extensions" => {
+                Self::new().enable_blobstore_extensions()
+            }
             _ => {
                 warn!(%s, "unknown feature flag");
                 Self::new()
@@ -155,6 +188,7 @@ impl From<Features> for wasmcloud_runtime::experimental::Features {
             wasmcloud_messaging_v3: f.wasmcloud_messaging_v3,
             workload_identity_interface: f.workload_identity_interface,
             rpc_interface: f.rpc_interface,
+            blobstore_extensions: f.blobstore_extensions,
         }
     }
 }
@@ -0,0 +1,117 @@
+use core::time::Duration;
+
+use std::sync::{Arc, Mutex, Weak};
+
+use anyhow::{anyhow, ensure};
+use wasmcloud_core::ArtifactCache;
+use wasmcloud_runtime::Runtime;
+
+use super::{Host, HostConfig};
+
+/// Configuration of the runtime shared by a [HostGroup], which must be identical for all hosts
+/// of the group
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct RuntimeConfig {
+    max_execution_time: Duration,
+    epoch_tick_interval: Duration,
+    fuel_metering: bool,
+    max_linear_memory: u64,
+    max_component_size: u64,
+    max_components: u32,
+    max_core_instances_per_component: u32,
+    experimental_features: wasmcloud_runtime::experimental::Features,
+}
+
+impl From<&HostConfig> for RuntimeConfig {
+    fn from(config: &HostConfig) -> Self {
+        Self {
+            max_execution_time: config.max_execution_time,
+            epoch_tick_interval: config.epoch_tick_interval,
+            fuel_metering: config.fuel_metering,
+            max_linear_memory: config.max_linear_memory,
+            max_component_size: config.max_component_size,
+            max_components: config.max_components,
+            max_core_instances_per_component: config.max_core_instances_per_component,
+            experimental_features: config.experimental_features.into(),
+        }
+    }
+}
+
+/// A group of hosts running in the same process, each joined to its own lattice.
+///
+/// Hosts built with the same group execute components on a single wasmtime [Runtime] and share a
+/// single OCI artifact cache, taking each other's running components and providers into account
+/// when evicting artifacts from it. All hosts of a group must be configured with the same runtime
+/// limits and artifact cache, runtime limits such as `max_components` apply to the group as a
+/// whole. Components, providers, links, configuration, policy and secrets remain isolated per host.
+#[derive(Clone, Default)]
+pub struct HostGroup {
+    runtime: Arc<Mutex<Option<(RuntimeConfig, Runtime)>>>,
+    artifact_cache: Arc<Mutex<Option<ArtifactCache>>>,
+    hosts: Arc<Mutex<Vec<Weak<Host>>>>,
+}
+
+impl HostGroup {
+    /// Create a new, empty [HostGroup]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the runtime shared by the group, building it with `build` if no host of the group
+    /// has been built yet. Fails if `config` differs from the configuration of the shared runtime
+    pub(crate) fn runtime(
+        &self,
+        config: RuntimeConfig,
+        build: impl FnOnce() -> anyhow::Result<Runtime>,
+    ) -> anyhow::Result<Runtime> {
+        let mut runtime = self
+            .runtime
+            .lock()
+            .map_err(|_| anyhow!("host group runtime lock poisoned"))?;
+        if let Some((group_config, runtime)) = runtime.as_ref() {
+            ensure!(
+                *group_config == config,
+                "host runtime configuration differs from the runtime configuration of its host group"
+            );
+            return Ok(runtime.clone());
+        }
+        let built = build()?;
+        *runtime = Some((config, built.clone()));
+        Ok(built)
+    }
+
+    /// Returns the artifact cache shared by the group, which is `cache` if no host of the group
+    /// has been built yet. Fails if `cache` differs from the shared artifact cache
+    pub(crate) fn artifact_cache(&self, cache: ArtifactCache) -> anyhow::Result<ArtifactCache> {
+        let mut artifact_cache = self
+            .artifact_cache
+            .lock()
+            .map_err(|_| anyhow!("host group artifact cache lock poisoned"))?;
+        if let Some(artifact_cache) = artifact_cache.as_ref() {
+            ensure!(
+                *artifact_cache == cache,
+                "host OCI artifact cache configuration differs from the artifact cache of its host group"
+            );
+            return Ok(artifact_cache.clone());
+        }
+        *artifact_cache = Some(cache.clone());
+        Ok(cache)
+    }
+
+    /// Add a host to the group
+    pub(crate) fn register(&self, host: &Arc<Host>) {
+        if let Ok(mut hosts) = self.hosts.lock() {
+            hosts.retain(|host| host.strong_count() > 0);
+            hosts.push(Arc::downgrade(host));
+        }
+    }
+
+    /// Returns all hosts of the group which are still running
+    pub(crate) fn hosts(&self) -> Vec<Arc<Host>> {
+        self.hosts
+            .lock()
+            .map(|hosts| hosts.iter().filter_map(Weak::upgrade).collect())
+            .unwrap_or_default()
+    }
+}
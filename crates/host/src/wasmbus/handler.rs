@@ -10,35 +10,25 @@ use async_nats::header::{IntoHeaderName as _, IntoHeaderValue as _};
 use async_trait::async_trait;
 use bytes::Bytes;
 use secrecy::SecretBox;
-#[cfg(unix)]
-use spire_api::{
-    selectors::Selector, DelegateAttestationRequest::Selectors, DelegatedIdentityClient,
-};
 use tokio::sync::RwLock;
 use tracing::{error, instrument, warn};
+use wasmcloud_core::WRPC_TIMEOUT_HEADER;
 use wasmcloud_runtime::capability::logging::logging;
 use wasmcloud_runtime::capability::secrets::store::SecretValue;
 use wasmcloud_runtime::capability::{
     self, identity, messaging0_2_0, messaging0_3_0, secrets, CallTargetInterface,
 };
 use wasmcloud_runtime::component::{
-    Bus, Bus1_0_0, Config, Error, Identity, InvocationErrorIntrospect, InvocationErrorKind,
-    Logging, Messaging0_2, Messaging0_3, MessagingClient0_3, MessagingGuestMessage0_3,
-    MessagingHostMessage0_3, ReplacedInstanceTarget, Secrets,
+    Bus, Bus1_0_0, Config, DeadlineExceeded, Error, Identity, InvocationErrorIntrospect,
+    InvocationErrorKind, Logging, Messaging0_2, Messaging0_3, MessagingClient0_3,
+    MessagingGuestMessage0_3, MessagingHostMessage0_3, ReplacedInstanceTarget, Secrets,
 };
 use wasmcloud_tracing::context::TraceContextInjector;
 use wrpc_transport::InvokeExt as _;
 
 use super::config::ConfigBundle;
 use super::{injector_to_headers, Features};
-
-// The key used to represent a wasmCloud-specific selector:
-// https://github.com/spiffe/spire-api-sdk/blob/3c6b1447f3d82210b91462d003f6c2774ffbe472/proto/spire/api/types/selector.proto#L6-L8
-//
-// Similar to existing types defined in the spire-api crate: https://github.com/maxlambrecht/rust-spiffe/blob/929a090f99d458dd67fa499b74afbeb2fc44b114/spire-api/src/selectors.rs#L4-L5
-const WASMCLOUD_SELECTOR_TYPE: &str = "wasmcloud";
-// Similar to the existing Kubernetes types: https://github.com/maxlambrecht/rust-spiffe/blob/929a090f99d458dd67fa499b74afbeb2fc44b114/spire-api/src/selectors.rs#L38-L39
-const WASMCLOUD_SELECTOR_COMPONENT: &str = "component";
+use crate::workload_identity::{fetch_jwt_svid, Workload};
 
 #[derive(Clone, Debug)]
 pub struct Handler {
@@ -78,6 +68,8 @@ pub struct Handler {
     pub experimental_features: Features,
     /// Labels associated with the wasmCloud Host the component is running on
     pub host_labels: Arc<RwLock<BTreeMap<String, String>>>,
+    /// SPIFFE trust domain the SPIFFE ID of the component is derived in, if configured
+    pub workload_identity_trust_domain: Option<Arc<str>>,
 }
 
 impl Handler {
@@ -96,6 +88,7 @@ impl Handler {
             invocation_timeout: self.invocation_timeout,
             experimental_features: self.experimental_features,
             host_labels: self.host_labels.clone(),
+            workload_identity_trust_domain: self.workload_identity_trust_domain.clone(),
         }
     }
 }
@@ -196,9 +189,14 @@ impl wrpc_transport::Invoke for Handler {
         let targets = self.targets.read().await;
 
         let target_instance = match target_instance {
+            // The `wasmcloud:blobstore` extensions are served by the same provider as
+            // `wasi:blobstore`, so they share its link
             Some(
                 ReplacedInstanceTarget::BlobstoreBlobstore
-                | ReplacedInstanceTarget::BlobstoreContainer,
+                | ReplacedInstanceTarget::BlobstoreContainer
+                | ReplacedInstanceTarget::BlobstoreMultipart
+                | ReplacedInstanceTarget::BlobstoreObjects
+                | ReplacedInstanceTarget::BlobstorePresign,
             ) => "wasi:blobstore/blobstore",
             Some(ReplacedInstanceTarget::KeyvalueAtomics) => "wasi:keyvalue/atomics",
             Some(ReplacedInstanceTarget::KeyvalueStore) => "wasi:keyvalue/store",
@@ -241,6 +239,10 @@ impl wrpc_transport::Invoke for Handler {
         let mut headers = injector_to_headers(&TraceContextInjector::default_with_span());
         headers.insert("source-id", &*self.component_id);
         headers.insert("link-name", link_name);
+        headers.insert(
+            WRPC_TIMEOUT_HEADER,
+            self.invocation_timeout.as_millis().to_string().as_str(),
+        );
         let nats = wrpc_transport_nats::Client::new(
             Arc::clone(&self.nats),
             format!("{}.{id}", &self.lattice),
@@ -1141,137 +1143,42 @@ impl Messaging0_3 for Handler {
 
 #[async_trait]
 impl Identity for Handler {
-    #[cfg(unix)]
     #[instrument(level = "debug", skip_all)]
     async fn get(
         &self,
         audience: &str,
     ) -> anyhow::Result<Result<Option<String>, identity::store::Error>> {
-        let mut client = match DelegatedIdentityClient::default().await {
-            Ok(client) => client,
-            Err(err) => {
-                return Ok(Err(identity::store::Error::Io(format!(
-                    "Unable to connect to workload identity service: {err}"
-                ))));
-            }
-        };
-
-        let mut selectors =
-            parse_selectors_from_host_labels(self.host_labels.read().await.deref()).await;
-        // "wasmcloud", "component:{component_id}" is inserted at the end to make sure it can't be overridden.
-        selectors.push(Selector::Generic((
-            WASMCLOUD_SELECTOR_TYPE.to_string(),
-            format!("{}:{}", WASMCLOUD_SELECTOR_COMPONENT, self.component_id),
-        )));
-
-        let svids = match client
-            .fetch_jwt_svids(&[audience], Selectors(selectors))
-            .await
+        match fetch_jwt_svid(
+            Workload::Component(&self.component_id),
+            self.host_labels.read().await.deref(),
+            &self.lattice,
+            self.workload_identity_trust_domain.as_deref(),
+            audience,
+        )
+        .await
         {
-            Ok(svids) => svids,
-            Err(err) => {
-                return Ok(Err(identity::store::Error::Io(format!(
-                    "Unable to query workload identity service: {err}"
-                ))));
-            }
-        };
-
-        if !svids.is_empty() {
-            // TODO: Is there a better way to determine which SVID to return here?
-            let svid = svids.first().map(|svid| svid.token()).unwrap_or_default();
-            Ok(Ok(Some(svid.to_string())))
-        } else {
-            Ok(Err(identity::store::Error::NotFound))
+            Ok(Some(svid)) => Ok(Ok(Some(svid))),
+            Ok(None) => Ok(Err(identity::store::Error::NotFound)),
+            Err(err) => Ok(Err(identity::store::Error::Io(format!("{err:#}")))),
         }
     }
-
-    #[cfg(target_family = "windows")]
-    #[instrument(level = "debug", skip_all)]
-    async fn get(
-        &self,
-        _audience: &str,
-    ) -> anyhow::Result<Result<Option<String>, identity::store::Error>> {
-        Ok(Err(identity::store::Error::Other(
-            "workload identity is not supported on Windows".to_string(),
-        )))
-    }
 }
 
 impl InvocationErrorIntrospect for Handler {
     fn invocation_error_kind(&self, err: &anyhow::Error) -> InvocationErrorKind {
+        if err
+            .chain()
+            .any(|err| err.is::<DeadlineExceeded>() || err.is::<tokio::time::error::Elapsed>())
+        {
+            return InvocationErrorKind::Timeout;
+        }
         if let Some(err) = err.root_cause().downcast_ref::<std::io::Error>() {
-            if err.kind() == std::io::ErrorKind::NotConnected {
-                return InvocationErrorKind::NotFound;
+            match err.kind() {
+                std::io::ErrorKind::NotConnected => return InvocationErrorKind::NotFound,
+                std::io::ErrorKind::TimedOut => return InvocationErrorKind::Timeout,
+                _ => {}
             }
         }
         InvocationErrorKind::Trap
     }
 }
-
-// TODO(joonas): Make this more generalized so we can support non-wasmcloud-specific
-// selectors as well.
-//
-// environment variable -> WASMCLOUD_LABEL_wasmcloud__ns=my-namespace-goes-here
-// becomes:
-// SPIRE Selector -> wasmcloud:ns:my-namespace-goes-here
-#[cfg(unix)]
-async fn parse_selectors_from_host_labels(host_labels: &BTreeMap<String, String>) -> Vec<Selector> {
-    let mut selectors = vec![];
-
-    for (key, value) in host_labels.iter() {
-        // Ensure the label starts with `wasmcloud__` and doesn't end in `__`, i.e. just `wasmcloud__`
-        if key.starts_with("wasmcloud__") && !key.ends_with("__") {
-            let selector = key
-                // Replace all __ with :
-                .replace("__", ":")
-                // Remove the leading "wasmcloud"
-                .split_once(":")
-                // Map the remaining part of the label key together with the value `` to make it a selector
-                .map(|(_, selector)| format!("{}:{}", selector, value))
-                // This should never get triggered, but just in case.
-                .unwrap_or("unknown".to_string());
-
-            selectors.push(Selector::Generic((
-                WASMCLOUD_SELECTOR_TYPE.to_string(),
-                selector,
-            )));
-        }
-    }
-
-    selectors
-}
-
-#[cfg(unix)]
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::env::consts::{ARCH, FAMILY, OS};
-
-    #[tokio::test]
-    async fn test_parse_selectors_from_host_labels() {
-        let labels = BTreeMap::from([
-            ("hostcore.arch".into(), ARCH.into()),
-            ("hostcore.os".into(), OS.into()),
-            ("hostcore.osfamily".into(), FAMILY.into()),
-            ("wasmcloud__lattice".into(), "default".into()),
-        ]);
-
-        let selectors = parse_selectors_from_host_labels(&labels).await;
-
-        assert_eq!(selectors.len(), 1);
-
-        let (selector_type, selector_value) = match selectors.first() {
-            Some(Selector::Generic(pair)) => pair,
-            _ => &("wrong-value".into(), "wrong-value".into()),
-        };
-        assert_eq!(selector_type, WASMCLOUD_SELECTOR_TYPE);
-        assert_eq!(selector_value, "lattice:default");
-    }
-
-    #[tokio::test]
-    async fn test_parse_selectors_from_host_labels_defaults_to_no_selectors() {
-        let no_labels = BTreeMap::new();
-        let selectors = parse_selectors_from_host_labels(&no_labels).await;
-        assert_eq!(selectors.len(), 0);
-    }
-}
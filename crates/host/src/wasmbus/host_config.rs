@@ -1,16 +1,20 @@
 use crate::OciConfig;
 
 use core::net::SocketAddr;
+use core::str::FromStr;
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
+use anyhow::bail;
 use nkeys::KeyPair;
 use url::Url;
 use wasmcloud_core::{logging::Level as LogLevel, OtelConfig};
 use wasmcloud_runtime::{
-    DEFAULT_MAX_CORE_INSTANCES_PER_COMPONENT, MAX_COMPONENTS, MAX_COMPONENT_SIZE, MAX_LINEAR_MEMORY,
+    DEFAULT_EPOCH_TICK_INTERVAL, DEFAULT_MAX_CORE_INSTANCES_PER_COMPONENT, MAX_COMPONENTS,
+    MAX_COMPONENT_SIZE, MAX_LINEAR_MEMORY,
 };
 
 use crate::wasmbus::experimental::Features;
@@ -56,6 +60,11 @@ pub struct Host {
     pub version: String,
     /// The maximum execution time for a component instance
     pub max_execution_time: Duration,
+    /// The interval at which execution deadlines of component invocations are checked
+    pub epoch_tick_interval: Duration,
+    /// Whether to meter the fuel consumed by component invocations. Required for per-component
+    /// fuel budgets to be enforced
+    pub fuel_metering: bool,
     /// The maximum linear memory that a component instance can allocate
     pub max_linear_memory: u64,
     /// The maximum size of a component binary that can be loaded
@@ -68,6 +77,22 @@ pub struct Host {
     pub heartbeat_interval: Option<Duration>,
     /// Experimental features that can be enabled in the host
     pub experimental_features: Features,
+    /// SPIFFE trust domain in which SPIFFE IDs of components and providers are derived, of the form
+    /// `spiffe://{trust_domain}/wasmcloud/{lattice}/{component|provider}/{id}`. If set, workloads
+    /// only receive SVIDs issued for their derived SPIFFE ID
+    pub workload_identity_trust_domain: Option<String>,
+    /// Whether capability providers receive the X.509-SVID issued for their own SPIFFE ID as
+    /// secrets on start and whenever it is rotated. Requires the workload identity interface
+    /// experimental feature
+    pub provider_x509_svids: bool,
+    /// Isolation applied to native capability provider processes
+    pub provider_sandbox: ProviderSandbox,
+    /// The number of most recent output lines retained per provider, queryable via the control interface
+    pub provider_log_buffer_lines: usize,
+    /// When native capability provider processes are restarted after exiting
+    pub provider_restart_policy: ProviderRestartPolicy,
+    /// Health checks performed on capability providers
+    pub provider_health_check: ProviderHealthCheck,
     /// HTTP administration endpoint address
     pub http_admin: Option<SocketAddr>,
     /// Whether component auctions are enabled
@@ -87,6 +112,186 @@ pub struct PolicyService {
     pub policy_timeout_ms: Option<Duration>,
 }
 
+/// Syscall filter applied to sandboxed capability providers
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SeccompProfile {
+    /// Deny syscalls, which allow to escape the sandbox or affect the host system, e.g. `mount`,
+    /// `ptrace`, `bpf` or `init_module`
+    #[default]
+    Default,
+    /// Do not filter syscalls
+    Unconfined,
+}
+
+impl FromStr for SeccompProfile {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "default" => Ok(Self::Default),
+            "unconfined" => Ok(Self::Unconfined),
+            _ => bail!("unknown seccomp profile `{s}`, expected `default` or `unconfined`"),
+        }
+    }
+}
+
+/// A range of host user or group IDs, e.g. as assigned to a user in `/etc/subuid` or `/etc/subgid`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IdRange {
+    /// First ID of the range
+    pub start: u32,
+    /// Number of IDs in the range
+    pub count: u32,
+}
+
+impl FromStr for IdRange {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((start, count)) = s.split_once(':') else {
+            bail!("invalid ID range `{s}`, expected `START:COUNT`");
+        };
+        let (Ok(start), Ok(count)) = (start.parse(), count.parse()) else {
+            bail!("invalid ID range `{s}`, expected `START:COUNT`");
+        };
+        Ok(Self { start, count })
+    }
+}
+
+/// Configuration for isolation of native capability provider processes, only supported on Linux.
+///
+/// Sandboxed providers run in a separate user namespace as an unprivileged user, with a read-only
+/// filesystem and a private, writable `/tmp`. Limits can be further tightened for individual
+/// providers using `wasmcloud.dev/sandbox*` annotations on start provider commands.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProviderSandbox {
+    /// Whether all providers are sandboxed. If not set, providers may still opt in using the
+    /// `wasmcloud.dev/sandbox` annotation
+    pub enabled: bool,
+    /// Host user ID to run sandboxed providers as, requires the host to be able to change its user ID
+    pub uid: Option<u32>,
+    /// Host group ID to run sandboxed providers as, requires the host to be able to change its group ID
+    pub gid: Option<u32>,
+    /// Range of host user IDs mapped into the user namespace of sandboxed providers. Defaults to
+    /// the range assigned in `/etc/subuid` to the user sandboxed providers are run as
+    pub uid_range: Option<IdRange>,
+    /// Range of host group IDs mapped into the user namespace of sandboxed providers. Defaults to
+    /// the range assigned in `/etc/subgid` to the user sandboxed providers are run as
+    pub gid_range: Option<IdRange>,
+    /// Syscall filter applied to sandboxed providers
+    pub seccomp: SeccompProfile,
+    /// Path of a network namespace to run sandboxed providers in, e.g. `/var/run/netns/providers`.
+    /// The namespace must be able to reach the lattice
+    pub network_namespace: Option<PathBuf>,
+    /// Maximum amount of memory in bytes a sandboxed provider may use
+    pub max_memory: Option<u64>,
+    /// Maximum amount of CPU a sandboxed provider may use, in thousandths of a CPU
+    pub max_cpu_millis: Option<u64>,
+    /// cgroup v2 directory, in which a cgroup is created for each sandboxed provider when memory or
+    /// CPU limits are set
+    pub cgroup_root: PathBuf,
+    /// Size of the private, writable `/tmp` of sandboxed providers in bytes
+    pub scratch_size: u64,
+}
+
+impl Default for ProviderSandbox {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            uid: None,
+            gid: None,
+            uid_range: None,
+            gid_range: None,
+            seccomp: SeccompProfile::default(),
+            network_namespace: None,
+            max_memory: None,
+            max_cpu_millis: None,
+            cgroup_root: PathBuf::from("/sys/fs/cgroup/wasmcloud"),
+            // 64 MiB
+            scratch_size: 64 * 1024 * 1024,
+        }
+    }
+}
+
+/// Which exited capability provider processes are restarted
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ProviderRestartMode {
+    /// Never restart providers
+    Never,
+    /// Restart providers, which exited unsuccessfully or were stopped after failing health checks
+    OnFailure,
+    /// Always restart providers
+    #[default]
+    Always,
+}
+
+impl FromStr for ProviderRestartMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "never" => Ok(Self::Never),
+            "on-failure" => Ok(Self::OnFailure),
+            "always" => Ok(Self::Always),
+            _ => bail!("unknown restart policy `{s}`, expected `never`, `on-failure` or `always`"),
+        }
+    }
+}
+
+/// Restart policy of native capability provider processes supervised by the host.
+///
+/// Restarts are delayed by an exponentially increasing backoff. A provider restarted more than
+/// `max_restarts` times within `window` is considered to be crash-looping, it is marked as failed
+/// and no longer restarted. The mode can be overridden for individual providers using the
+/// `wasmcloud.dev/restart-policy` annotation on start provider commands.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProviderRestartPolicy {
+    /// Which exited providers are restarted
+    pub mode: ProviderRestartMode,
+    /// Maximum number of restarts within `window`, `0` disables crash-loop detection
+    pub max_restarts: u32,
+    /// Period of time, within which restarts are counted
+    pub window: Duration,
+    /// Delay before the first restart within `window`, doubled for every subsequent restart
+    pub initial_backoff: Duration,
+    /// Maximum delay before a restart
+    pub max_backoff: Duration,
+}
+
+impl Default for ProviderRestartPolicy {
+    fn default() -> Self {
+        Self {
+            mode: ProviderRestartMode::default(),
+            max_restarts: 5,
+            window: Duration::from_secs(5 * 60),
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Configuration for health checks of capability providers
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProviderHealthCheck {
+    /// Interval at which provider health is checked
+    pub interval: Duration,
+    /// Amount of time to wait for a health check response, after which the check is failed
+    pub timeout: Duration,
+    /// Number of consecutive failed health checks, after which a provider process is stopped and
+    /// restarted according to the restart policy. If not set, failed health checks are only reported
+    pub restart_after: Option<u32>,
+}
+
+impl Default for ProviderHealthCheck {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            timeout: Duration::from_secs(5),
+            restart_after: None,
+        }
+    }
+}
+
 impl Default for Host {
     fn default() -> Self {
         Self {
@@ -109,6 +314,8 @@ impl Default for Host {
             otel_config: OtelConfig::default(),
             version: env!("CARGO_PKG_VERSION").to_string(),
             max_execution_time: Duration::from_millis(10 * 60 * 1000),
+            epoch_tick_interval: DEFAULT_EPOCH_TICK_INTERVAL,
+            fuel_metering: false,
             // 10 MB
             max_linear_memory: MAX_LINEAR_MEMORY,
             // 50 MB
@@ -117,6 +324,12 @@ impl Default for Host {
             max_components: MAX_COMPONENTS,
             heartbeat_interval: None,
             experimental_features: Features::default(),
+            workload_identity_trust_domain: None,
+            provider_x509_svids: false,
+            provider_sandbox: ProviderSandbox::default(),
+            provider_log_buffer_lines: 1000,
+            provider_restart_policy: ProviderRestartPolicy::default(),
+            provider_health_check: ProviderHealthCheck::default(),
             http_admin: None,
             enable_component_auction: true,
             enable_provider_auction: true,
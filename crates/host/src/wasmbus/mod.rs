@@ -2,14 +2,14 @@
 
 use core::sync::atomic::Ordering;
 
-use std::collections::{hash_map, BTreeMap, HashMap};
+use std::collections::{hash_map, BTreeMap, HashMap, HashSet};
 use std::env::consts::{ARCH, FAMILY, OS};
 use std::future::Future;
 use std::num::NonZeroUsize;
 use std::ops::Deref;
 use std::pin::Pin;
 use std::str::FromStr;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicU64};
 use std::sync::Arc;
 use std::task::{Context, Poll};
 use std::time::Duration;
@@ -17,11 +17,12 @@ use std::time::Duration;
 use anyhow::{anyhow, bail, ensure, Context as _};
 use bytes::{BufMut, Bytes, BytesMut};
 use claims::{Claims, StoredClaims};
+use futures::future::join_all;
 use futures::stream::{AbortHandle, Abortable};
 use futures::{join, stream, Stream, StreamExt, TryStreamExt};
 use hyper_util::rt::{TokioExecutor, TokioIo};
 use nkeys::{KeyPair, KeyPairType, XKey};
-use providers::Provider;
+use providers::{Provider, ProviderLogBuffer};
 use secrecy::SecretBox;
 use serde_json::json;
 use sysinfo::System;
@@ -30,27 +31,33 @@ use tokio::net::TcpListener;
 use tokio::spawn;
 use tokio::sync::{mpsc, watch, RwLock, Semaphore};
 use tokio::task::{JoinHandle, JoinSet};
-use tokio::time::{interval_at, timeout, Instant};
+use tokio::time::{interval_at, timeout, timeout_at, Instant};
 use tokio_stream::wrappers::IntervalStream;
 use tracing::{debug, debug_span, error, info, instrument, trace, warn, Instrument as _};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 use wascap::jwt;
 use wasmcloud_control_interface::{
-    ComponentAuctionAck, ComponentAuctionRequest, ComponentDescription, CtlResponse,
-    DeleteInterfaceLinkDefinitionRequest, HostInventory, HostLabel, HostLabelIdentifier, Link,
-    ProviderAuctionAck, ProviderAuctionRequest, ProviderDescription, RegistryCredential,
-    ScaleComponentCommand, StartProviderCommand, StopHostCommand, StopProviderCommand,
-    UpdateComponentCommand,
+    ComponentAuctionAck, ComponentAuctionRequest, ComponentDescription, ComponentResourceLimits,
+    ConfigRevision, CordonHostCommand, CtlResponse, DeleteInterfaceLinkDefinitionRequest,
+    DrainHostCommand, GetProviderLogsCommand, HostInventory, HostLabel, HostLabelIdentifier, Link,
+    ProviderAuctionAck, ProviderAuctionRequest, ProviderDescription, ProviderLogs,
+    RegistryCredential, RollbackConfigCommand, ScaleComponentCommand, StartProviderCommand,
+    StopHostCommand, StopProviderCommand, UpdateComponentCommand,
+};
+use wasmcloud_core::{
+    content_digest, ArtifactCache, CacheResult, ComponentId, WRPC_TIMEOUT_HEADER,
 };
-use wasmcloud_core::ComponentId;
 use wasmcloud_runtime::capability::secrets::store::SecretValue;
-use wasmcloud_runtime::component::WrpcServeEvent;
-use wasmcloud_runtime::Runtime;
+use wasmcloud_runtime::component::{
+    DeadlineExceeded, InstanceReuse, InvocationDeadline, WrpcServeEvent,
+};
+use wasmcloud_runtime::{Runtime, MAX_TABLE_ELEMENTS};
 use wasmcloud_secrets_types::SECRET_PREFIX;
 use wasmcloud_tracing::context::TraceContextInjector;
 use wasmcloud_tracing::{global, InstrumentationScope, KeyValue};
 
-use crate::event::{DefaultEventPublisher, EventPublisher};
+use crate::event::{DefaultEventPublisher, EventPublisher, SequencedEventPublisher};
+use crate::http::ctl::HttpControlInterfaceServer;
 use crate::metrics::HostMetrics;
 use crate::nats::connect_nats;
 use crate::nats::provider::NatsProviderManager;
@@ -59,10 +66,14 @@ use crate::secrets::{DefaultSecretsManager, SecretsManager};
 use crate::store::{DefaultStore, StoreManager};
 use crate::wasmbus::ctl::ControlInterfaceServer;
 use crate::workload_identity::WorkloadIdentityConfig;
-use crate::{fetch_component, PolicyManager, PolicyResponse, RegistryConfig, ResourceRef};
+use crate::{
+    fetch_component, prefetch_artifact, PolicyManager, PolicyResponse, RegistryConfig, ResourceRef,
+};
 
 mod component_spec;
+mod config_schema;
 mod experimental;
+mod group;
 mod handler;
 
 pub(crate) mod claims;
@@ -80,6 +91,7 @@ pub mod host_config;
 pub use self::experimental::Features;
 pub use self::host_config::Host as HostConfig;
 pub use component_spec::ComponentSpecification;
+pub use group::HostGroup;
 pub use providers::ProviderManager;
 
 use self::config::{BundleGenerator, ConfigBundle};
@@ -88,6 +100,15 @@ use self::handler::Handler;
 const MAX_INVOCATION_CHANNEL_SIZE: usize = 5000;
 const MIN_INVOCATION_CHANNEL_SIZE: usize = 256;
 
+/// Annotation setting the maximum amount of fuel a single invocation of a component may consume
+const FUEL_BUDGET_ANNOTATION: &str = "wasmcloud.dev/fuel-budget";
+
+/// Annotation setting the minimum number of pre-instantiated, idle instances kept for a component
+const MIN_IDLE_INSTANCES_ANNOTATION: &str = "wasmcloud.dev/min-idle-instances";
+
+/// Annotation setting the instance reuse policy of a component, either `never` or `stateless`
+const INSTANCE_REUSE_ANNOTATION: &str = "wasmcloud.dev/instance-reuse";
+
 #[derive(Clone, Default)]
 struct AsyncBytesMut(Arc<std::sync::Mutex<BytesMut>>);
 
@@ -141,9 +162,16 @@ struct Component {
     annotations: Annotations,
     /// Maximum number of instances of this component that can be running at once
     max_instances: NonZeroUsize,
+    /// Resource limits applied to the instances of this component
+    limits: ComponentResourceLimits,
     image_reference: Arc<str>,
+    /// SHA256 digest of the component bytes, of the form `sha256:<hex>`
+    digest: Arc<str>,
     events: mpsc::Sender<WrpcServeEvent<<WrpcServer as wrpc_transport::Serve>::Context>>,
     permits: Arc<Semaphore>,
+    /// Number of invocations of this component, which can be handled concurrently, i.e. the
+    /// total number of `permits`
+    concurrency: usize,
 }
 
 impl Deref for Component {
@@ -169,6 +197,14 @@ struct InvocationContext {
     start_at: Instant,
     attributes: Vec<KeyValue>,
     span: tracing::Span,
+    /// Deadline derived from the timeout of the caller, if specified
+    deadline: Option<Instant>,
+}
+
+impl InvocationDeadline for InvocationContext {
+    fn deadline(&self) -> Option<std::time::Instant> {
+        self.deadline.map(Instant::into_std)
+    }
 }
 
 impl Deref for InvocationContext {
@@ -223,6 +259,12 @@ impl wrpc_transport::Serve for WrpcServer {
             let policy_manager = Arc::clone(&policy_manager);
             let span = tracing::info_span!("component_invocation", func = %func, id = %id, instance = %instance);
             async move {
+                let start_at = Instant::now();
+                let deadline = cx
+                    .as_ref()
+                    .and_then(|cx| cx.get(WRPC_TIMEOUT_HEADER))
+                    .and_then(|timeout| timeout.to_string().parse().ok())
+                    .map(|timeout| start_at + Duration::from_millis(timeout));
                 if let Some(ref cx) = cx {
                     // Coerce the HashMap<String, Vec<String>> into a Vec<(String, String)> by
                     // flattening the values
@@ -260,7 +302,7 @@ impl wrpc_transport::Serve for WrpcServer {
 
                 Ok((
                     InvocationContext{
-                        start_at: Instant::now(),
+                        start_at,
                         // TODO(metrics): insert information about the source once we have concrete context data
                         attributes: vec![
                             KeyValue::new("component.ref", image_reference),
@@ -269,6 +311,7 @@ impl wrpc_transport::Serve for WrpcServer {
                             KeyValue::new("operation", format!("{instance}/{func}")),
                         ],
                         span,
+                        deadline,
                     },
                     tx,
                     rx,
@@ -318,6 +361,12 @@ pub struct Host {
     /// Indicates whether the host is ready to process requests.
     ready: Arc<AtomicBool>,
 
+    /// Indicates whether the host is cordoned, i.e. does not respond to auctions.
+    cordoned: AtomicBool,
+
+    /// Indicates whether the host is currently draining its workloads.
+    draining: AtomicBool,
+
     /// The encryption key used to secure secrets when transmitting over NATS.
     secrets_xkey: Arc<XKey>,
 
@@ -346,6 +395,9 @@ pub struct Host {
     /// Optional overrides for registry configuration settings.
     registry_config: RwLock<HashMap<String, RegistryConfig>>,
 
+    /// The cache of artifacts fetched from OCI registries.
+    artifact_cache: ArtifactCache,
+
     /// The NATS client used for making RPC calls.
     rpc_nats: Arc<async_nats::Client>,
 
@@ -358,6 +410,9 @@ pub struct Host {
     /// The event publisher used for emitting events from the host.
     pub(crate) event_publisher: Arc<dyn EventPublisher>,
 
+    /// The sequence number of the last event emitted by the host.
+    event_sequence: Arc<AtomicU64>,
+
     /// The policy manager used for evaluating policy decisions.
     policy_manager: Arc<dyn PolicyManager>,
 
@@ -370,9 +425,15 @@ pub struct Host {
     /// The data store for managing links, claims, and component specifications.
     data_store: Arc<dyn StoreManager>,
 
+    /// The store for coordinating scheduled runs of the builtin cron provider between hosts.
+    cron_store: Arc<dyn StoreManager>,
+
     /// The generator for creating configuration bundles.
     config_generator: BundleGenerator,
 
+    /// The group of hosts in this process sharing the runtime and artifact cache, if any.
+    host_group: Option<HostGroup>,
+
     /// A set of tasks managed by the host.
     #[allow(unused)]
     tasks: JoinSet<()>,
@@ -392,12 +453,18 @@ pub struct HostBuilder {
     config_store: Option<Arc<dyn StoreManager>>,
     /// The data store to use for managing data
     data_store: Option<Arc<dyn StoreManager>>,
+    /// The store to use for coordinating scheduled runs of the builtin cron provider
+    cron_store: Option<Arc<dyn StoreManager>>,
     /// The event publisher to use for sending events
     event_publisher: Option<Arc<dyn EventPublisher>>,
     /// The policy manager to use for evaluating policy decisions
     policy_manager: Option<Arc<dyn PolicyManager>>,
     /// The secrets manager to use for managing secrets
     secrets_manager: Option<Arc<dyn SecretsManager>>,
+    /// The control interface server to mount on the HTTP administration endpoint
+    http_control_interface: Option<Arc<HttpControlInterfaceServer>>,
+    /// The group of hosts to share the runtime and artifact cache with
+    host_group: Option<HostGroup>,
 }
 
 impl HostBuilder {
@@ -485,6 +552,32 @@ impl HostBuilder {
         Self { data_store, ..self }
     }
 
+    /// Initialize the host with the given store for coordinating scheduled runs of the builtin
+    /// cron provider
+    pub fn with_cron_store(self, cron_store: Option<Arc<dyn StoreManager>>) -> Self {
+        Self { cron_store, ..self }
+    }
+
+    /// Initialize the host with the given control interface server mounted on the HTTP
+    /// administration endpoint, which must be enabled
+    pub fn with_http_control_interface(
+        self,
+        http_control_interface: Option<HttpControlInterfaceServer>,
+    ) -> Self {
+        Self {
+            http_control_interface: http_control_interface.map(Arc::new),
+            ..self
+        }
+    }
+
+    /// Initialize the host as a member of the given [HostGroup], sharing the group's runtime and
+    /// artifact cache with the other hosts in this process. Building the host fails if its runtime
+    /// or artifact cache configuration differs from the other hosts of the group. Runtime limits
+    /// such as `max_components` apply to the group as a whole
+    pub fn with_host_group(self, host_group: Option<HostGroup>) -> Self {
+        Self { host_group, ..self }
+    }
+
     /// Initialize the host with the given configuration watching bundle
     pub fn with_bundle_generator(self, bundle_generator: Option<BundleGenerator>) -> Self {
         Self {
@@ -499,6 +592,10 @@ impl HostBuilder {
         self,
     ) -> anyhow::Result<(Arc<Host>, impl Future<Output = anyhow::Result<()>>)> {
         ensure!(self.config.host_key.key_pair_type() == KeyPairType::Server);
+        ensure!(
+            self.http_control_interface.is_none() || self.config.http_admin.is_some(),
+            "HTTP control interface requires the HTTP administration endpoint to be enabled"
+        );
 
         let mut labels = BTreeMap::from([
             ("hostcore.arch".into(), ARCH.into()),
@@ -549,15 +646,32 @@ impl HostBuilder {
 
         let (stop_tx, stop_rx) = watch::channel(None);
 
-        let (runtime, _epoch) = Runtime::builder()
-            .max_execution_time(self.config.max_execution_time)
-            .max_linear_memory(self.config.max_linear_memory)
-            .max_components(self.config.max_components)
-            .max_core_instances_per_component(self.config.max_core_instances_per_component)
-            .max_component_size(self.config.max_component_size)
-            .experimental_features(self.config.experimental_features.into())
-            .build()
-            .context("failed to build runtime")?;
+        let artifact_cache = if let Some(group) = &self.host_group {
+            group.artifact_cache(self.config.oci_opts.artifact_cache())?
+        } else {
+            self.config.oci_opts.artifact_cache()
+        };
+
+        let build_runtime = || -> anyhow::Result<Runtime> {
+            let mut runtime = Runtime::builder()
+                .max_execution_time(self.config.max_execution_time)
+                .epoch_tick_interval(self.config.epoch_tick_interval)
+                .max_linear_memory(self.config.max_linear_memory)
+                .max_components(self.config.max_components)
+                .max_core_instances_per_component(self.config.max_core_instances_per_component)
+                .max_component_size(self.config.max_component_size)
+                .experimental_features(self.config.experimental_features.into());
+            if self.config.fuel_metering {
+                runtime = runtime.fuel_metering();
+            }
+            let (runtime, _epoch) = runtime.build().context("failed to build runtime")?;
+            Ok(runtime)
+        };
+        let runtime = if let Some(group) = &self.host_group {
+            group.runtime((&self.config).into(), build_runtime)?
+        } else {
+            build_runtime()?
+        };
 
         let scope = InstrumentationScope::builder("wasmcloud-host")
             .with_version(self.config.version.clone())
@@ -593,13 +707,20 @@ impl HostBuilder {
                 .await
                 .context("failed to bind on HTTP administration endpoint")?;
             let ready = Arc::clone(&ready);
+            let http_control_interface = self.http_control_interface.clone();
             let svc = hyper::service::service_fn(move |req| {
                 const OK: &str = r#"{"status":"ok"}"#;
                 const FAIL: &str = r#"{"status":"failure"}"#;
                 let ready = Arc::clone(&ready);
+                let http_control_interface = http_control_interface.clone();
                 async move {
+                    if let Some(ctl) = http_control_interface
+                        .filter(|_| HttpControlInterfaceServer::handles(req.uri().path()))
+                    {
+                        return Ok(ctl.handle(req).await);
+                    }
                     let (http::request::Parts { method, uri, .. }, _) = req.into_parts();
-                    match (method.as_str(), uri.path()) {
+                    let res = match (method.as_str(), uri.path()) {
                         ("HEAD", "/livez") => Ok(http::Response::default()),
                         ("GET", "/livez") => Ok(http::Response::new(http_body_util::Full::new(
                             Bytes::from(OK),
@@ -639,12 +760,16 @@ impl HostBuilder {
                             .body(http_body_util::Full::new(Bytes::from(format!(
                                 "unknown endpoint `{path}`"
                             )))),
-                    }
+                    };
+                    res.map(|res| res.map(http_body_util::BodyExt::boxed_unsync))
                 }
             });
             let srv = hyper_util::server::conn::auto::Builder::new(TokioExecutor::new());
             tasks.spawn(async move {
+                // Connections are served concurrently, since event streams are long-lived
+                let mut conns = JoinSet::new();
                 loop {
+                    while conns.try_join_next().is_some() {}
                     let stream = match socket.accept().await {
                         Ok((stream, _)) => stream,
                         Err(err) => {
@@ -653,9 +778,12 @@ impl HostBuilder {
                         }
                     };
                     let svc = svc.clone();
-                    if let Err(err) = srv.serve_connection(TokioIo::new(stream), svc).await {
-                        error!(?err, "failed to serve HTTP administration connection");
-                    }
+                    let srv = srv.clone();
+                    conns.spawn(async move {
+                        if let Err(err) = srv.serve_connection(TokioIo::new(stream), svc).await {
+                            error!(?err, "failed to serve HTTP administration connection");
+                        }
+                    });
                 }
             });
         }
@@ -663,6 +791,20 @@ impl HostBuilder {
         let (heartbeat_abort, heartbeat_abort_reg) = AbortHandle::new_pair();
         let start_at = Instant::now();
 
+        let event_publisher = self
+            .event_publisher
+            .unwrap_or_else(|| Arc::new(DefaultEventPublisher::default()));
+        let event_publisher = if let Some(ctl) = &self.http_control_interface {
+            ctl.event_publisher(event_publisher, self.config.host_key.public_key())
+        } else {
+            event_publisher
+        };
+        let event_sequence = Arc::default();
+        let event_publisher = Arc::new(SequencedEventPublisher::new(
+            event_publisher,
+            Arc::clone(&event_sequence),
+        ));
+
         let host = Host {
             components: Arc::new(RwLock::new(HashMap::new())),
             providers: RwLock::new(HashMap::new()),
@@ -684,13 +826,16 @@ impl HostBuilder {
             max_execution_time: self.config.max_execution_time,
             messaging_links: Arc::default(),
             ready: Arc::clone(&ready),
+            cordoned: AtomicBool::default(),
+            draining: AtomicBool::default(),
+            host_group: self.host_group.clone(),
             tasks,
             rpc_nats: Arc::clone(&rpc_nats),
             registry_config: RwLock::new(self.registry_config),
+            artifact_cache,
             // Extension traits that we fallback to defaults for
-            event_publisher: self
-                .event_publisher
-                .unwrap_or_else(|| Arc::new(DefaultEventPublisher::default())),
+            event_publisher,
+            event_sequence,
             policy_manager: self
                 .policy_manager
                 .unwrap_or_else(|| Arc::new(DefaultPolicyManager)),
@@ -700,6 +845,9 @@ impl HostBuilder {
             data_store: self
                 .data_store
                 .unwrap_or_else(|| Arc::new(DefaultStore::default())),
+            cron_store: self
+                .cron_store
+                .unwrap_or_else(|| Arc::new(DefaultStore::default())),
             config_store: self
                 .config_store
                 .unwrap_or_else(|| Arc::new(DefaultStore::default())),
@@ -717,6 +865,12 @@ impl HostBuilder {
         };
 
         let host = Arc::new(host);
+        if let Some(ctl) = &self.http_control_interface {
+            ctl.attach(&host);
+        }
+        if let Some(group) = &self.host_group {
+            group.register(&host);
+        }
 
         let heartbeat_interval = host
             .host_config
@@ -766,6 +920,16 @@ impl HostBuilder {
             }
         });
 
+        if !host.host_config.oci_opts.prefetch.is_empty() {
+            spawn({
+                let host = Arc::clone(&host);
+                async move {
+                    host.prefetch_artifacts(&host.host_config.oci_opts.prefetch)
+                        .await;
+                }
+            });
+        }
+
         let start_evt = json!({
             "id": host.host_key.public_key(),
             "friendly_name": host.friendly_name,
@@ -853,6 +1017,8 @@ impl Host {
     #[instrument(level = "debug", skip_all)]
     async fn inventory(&self) -> HostInventory {
         trace!("generating host inventory");
+        // The sequence is loaded first, so that the inventory reflects at least all events up to it
+        let sequence = self.event_sequence.load(Ordering::Relaxed);
         let components: Vec<_> = {
             let components = self.components.read().await;
             stream::iter(components.iter())
@@ -878,6 +1044,10 @@ impl Host {
                     {
                         description = description.name(name);
                     };
+                    description = description.digest(component.digest.to_string());
+                    if let Some(claims) = component.claims() {
+                        description = description.claims(claims_summary(claims));
+                    }
 
                     Some(
                         description
@@ -901,6 +1071,7 @@ impl Host {
                         annotations,
                         claims_token,
                         image_ref,
+                        failed,
                         ..
                     },
                 )| {
@@ -928,6 +1099,7 @@ impl Host {
                                 .and_then(|jwt::CapabilityProvider { rev, .. }| *rev)
                                 .unwrap_or_default(),
                         )
+                        .failed(failed.load(Ordering::Relaxed))
                         .build()
                         .expect("failed to build provider description")
                 },
@@ -944,6 +1116,8 @@ impl Host {
             .uptime_seconds(uptime.as_secs())
             .version(self.host_config.version.clone())
             .host_id(self.host_key.public_key())
+            .sequence(sequence)
+            .cordoned(self.cordoned.load(Ordering::Relaxed))
             .build()
             .expect("failed to build host inventory")
     }
@@ -954,6 +1128,36 @@ impl Host {
         Ok(serde_json::to_value(self.inventory().await)?)
     }
 
+    /// Validates requested component resource limits against the maximum settings of this host
+    fn validate_component_limits(&self, limits: &ComponentResourceLimits) -> anyhow::Result<()> {
+        if let Some(max) = limits.max_linear_memory() {
+            ensure!(
+                max <= self.host_config.max_linear_memory,
+                "requested max linear memory of {max} bytes exceeds host maximum of {} bytes",
+                self.host_config.max_linear_memory
+            );
+        }
+        if let Some(max) = limits.max_execution_time_ms() {
+            let max = Duration::from_millis(max);
+            ensure!(
+                max <= self.max_execution_time,
+                "requested max execution time of {max:?} exceeds host maximum of {:?}",
+                self.max_execution_time
+            );
+        }
+        if let Some(max) = limits.max_table_elements() {
+            ensure!(
+                max <= MAX_TABLE_ELEMENTS,
+                "requested max table elements of {max} exceeds host maximum of {MAX_TABLE_ELEMENTS}"
+            );
+        }
+        ensure!(
+            limits.max_concurrency() != Some(0),
+            "requested max concurrency must be greater than zero"
+        );
+        Ok(())
+    }
+
     /// Instantiate a component
     #[allow(clippy::too_many_arguments)] // TODO: refactor into a config struct
     #[instrument(level = "debug", skip_all)]
@@ -961,19 +1165,55 @@ impl Host {
         &self,
         annotations: &Annotations,
         image_reference: Arc<str>,
+        digest: Arc<str>,
         id: Arc<str>,
         max_instances: NonZeroUsize,
+        limits: ComponentResourceLimits,
         mut component: wasmcloud_runtime::Component<Handler>,
         handler: Handler,
     ) -> anyhow::Result<Arc<Component>> {
         trace!(
             component_ref = ?image_reference,
             max_instances,
+            ?limits,
             "instantiating component"
         );
 
-        let max_execution_time = self.max_execution_time;
+        let max_execution_time = limits
+            .max_execution_time_ms()
+            .map_or(self.max_execution_time, Duration::from_millis);
         component.set_max_execution_time(max_execution_time);
+        component.set_max_linear_memory(limits.max_linear_memory());
+        component.set_max_table_elements(limits.max_table_elements());
+
+        let max_fuel = annotations
+            .get(FUEL_BUDGET_ANNOTATION)
+            .map(|budget| {
+                budget.parse::<u64>().with_context(|| {
+                    format!("invalid `{FUEL_BUDGET_ANNOTATION}` annotation value `{budget}`")
+                })
+            })
+            .transpose()?;
+        if max_fuel.is_some() && !component.fuel_metering() {
+            warn!(
+                component_id = ?id,
+                "`{FUEL_BUDGET_ANNOTATION}` annotation ignored, fuel metering is not enabled on this host"
+            );
+        }
+        component.set_max_fuel(max_fuel);
+
+        if let Some(min_idle) = annotations.get(MIN_IDLE_INSTANCES_ANNOTATION) {
+            let min_idle = min_idle.parse::<usize>().with_context(|| {
+                format!("invalid `{MIN_IDLE_INSTANCES_ANNOTATION}` annotation value `{min_idle}`")
+            })?;
+            component.set_min_idle_instances(min_idle.min(max_instances.get()));
+        }
+        if let Some(reuse) = annotations.get(INSTANCE_REUSE_ANNOTATION) {
+            let reuse = reuse.parse::<InstanceReuse>().with_context(|| {
+                format!("invalid `{INSTANCE_REUSE_ANNOTATION}` annotation value")
+            })?;
+            component.set_instance_reuse(reuse);
+        }
 
         let (events_tx, mut events_rx) = mpsc::channel(
             max_instances
@@ -1002,9 +1242,13 @@ impl Host {
                 events_tx.clone(),
             )
             .await?;
-        let permits = Arc::new(Semaphore::new(
-            usize::from(max_instances).min(Semaphore::MAX_PERMITS),
-        ));
+        component.prewarm(&handler);
+        let concurrency = limits
+            .max_concurrency()
+            .and_then(|max| usize::try_from(max).ok())
+            .map_or(max_instances.get(), |max| max.min(max_instances.get()))
+            .min(Semaphore::MAX_PERMITS);
+        let permits = Arc::new(Semaphore::new(concurrency));
         let component_attributes = Arc::new(vec![
             KeyValue::new("component.id", id.to_string()),
             KeyValue::new("component.ref", image_reference.to_string()),
@@ -1021,6 +1265,7 @@ impl Host {
             handler,
             events: events_tx,
             permits: Arc::clone(&permits),
+            concurrency,
             exports: spawn(async move {
                 // Since we are joining two `move` closures, we need two separate `Arc`s
                 let metrics_left = Arc::clone(&metrics);
@@ -1054,6 +1299,17 @@ impl Host {
                                                     debug!("successfully handled invocation");
                                                     Ok(())
                                                 }
+                                                Ok(Err(err))
+                                                    if err.chain().any(|err| {
+                                                        err.is::<DeadlineExceeded>()
+                                                    }) =>
+                                                {
+                                                    warn!(
+                                                        ?err,
+                                                        "component invocation exceeded its execution deadline"
+                                                    );
+                                                    Err(err)
+                                                }
                                                 Ok(Err(err)) => {
                                                     warn!(?err, "failed to handle invocation");
                                                     Err(err)
@@ -1085,6 +1341,8 @@ impl Host {
                                             ..
                                         },
                                     success,
+                                    fuel_consumed,
+                                    pool_hit,
                                 }
                                 | WrpcServeEvent::MessagingHandlerHandleMessageReturned {
                                     context:
@@ -1094,6 +1352,8 @@ impl Host {
                                             ..
                                         },
                                     success,
+                                    fuel_consumed,
+                                    pool_hit,
                                 }
                                 | WrpcServeEvent::DynamicExportReturned {
                                     context:
@@ -1103,12 +1363,20 @@ impl Host {
                                             ..
                                         },
                                     success,
-                                } => metrics_right.record_component_invocation(
-                                    u64::try_from(start_at.elapsed().as_nanos())
-                                        .unwrap_or_default(),
-                                    attributes,
-                                    !success,
-                                ),
+                                    fuel_consumed,
+                                    pool_hit,
+                                } => {
+                                    metrics_right.record_component_invocation(
+                                        u64::try_from(start_at.elapsed().as_nanos())
+                                            .unwrap_or_default(),
+                                        fuel_consumed,
+                                        attributes,
+                                        !success,
+                                    );
+                                    if let Some(hit) = pool_hit {
+                                        metrics_right.record_instance_pool(hit, attributes);
+                                    }
+                                }
                             }
                         }
                         debug!("serving event stream is done");
@@ -1118,7 +1386,9 @@ impl Host {
             }),
             annotations: annotations.clone(),
             max_instances,
+            limits,
             image_reference: Arc::clone(&image_reference),
+            digest,
         }))
     }
 
@@ -1132,22 +1402,37 @@ impl Host {
         component_ref: Arc<str>,
         component_id: Arc<str>,
         max_instances: NonZeroUsize,
+        limits: ComponentResourceLimits,
         annotations: &Annotations,
         config: ConfigBundle,
         secrets: HashMap<String, SecretBox<SecretValue>>,
+        allow_update: bool,
     ) -> anyhow::Result<&'a mut Arc<Component>> {
         debug!(?component_ref, ?max_instances, "starting new component");
 
+        let digest = content_digest(wasm);
+        let mut component_spec = self
+            .get_component_spec(&component_id)
+            .await?
+            .unwrap_or_else(|| ComponentSpecification::new(&component_ref));
+        // A component ID identifies exactly one set of bytes in the lattice, unless an update is requested
+        if let Some(spec_digest) = component_spec.digest() {
+            ensure!(
+                allow_update || spec_digest == digest,
+                "component `{component_id}` is already specified with digest `{spec_digest}`, refusing to start `{component_ref}` with digest `{digest}` without allowing an update"
+            );
+        }
+
         if let Some(ref claims) = claims {
             self.store_claims(Claims::Component(claims.clone()))
                 .await
                 .context("failed to store claims")?;
         }
 
-        let component_spec = self
-            .get_component_spec(&component_id)
-            .await?
-            .unwrap_or_else(|| ComponentSpecification::new(&component_ref));
+        component_spec.url = component_ref.to_string();
+        component_spec.digest = Some(digest.clone());
+        component_spec.claims.clone_from(&claims);
+        component_spec.config.clone_from(config.config_names());
         self.store_component_spec(&component_id, &component_spec)
             .await?;
 
@@ -1167,14 +1452,21 @@ impl Host {
             invocation_timeout: Duration::from_secs(10), // TODO: Make this configurable
             experimental_features: self.experimental_features,
             host_labels: Arc::clone(&self.labels),
+            workload_identity_trust_domain: self
+                .host_config
+                .workload_identity_trust_domain
+                .as_deref()
+                .map(Arc::from),
         };
         let component = wasmcloud_runtime::Component::new(&self.runtime, wasm)?;
         let component = self
             .instantiate_component(
                 annotations,
                 Arc::clone(&component_ref),
+                Arc::from(digest),
                 Arc::clone(&component_id),
                 max_instances,
+                limits,
                 component,
                 handler,
             )
@@ -1208,17 +1500,193 @@ impl Host {
         Ok(())
     }
 
+    /// Stops all components and providers running on the host. Components stop accepting new
+    /// invocations immediately, but invocations already in flight are given until `deadline` to
+    /// finish before providers are stopped.
+    #[instrument(level = "debug", skip_all)]
+    async fn drain_workloads(&self, deadline: Option<Instant>) -> anyhow::Result<()> {
+        let host_id = self.host_key.public_key();
+        let components: Vec<_> = self
+            .components
+            .write()
+            .await
+            .drain()
+            .map(|(_, component)| component)
+            .collect();
+        for component in &components {
+            self.stop_component(component, &host_id)
+                .await
+                .context("failed to stop component while draining")?;
+        }
+
+        // Each in-flight invocation holds a permit, so all permits can only be acquired once all
+        // invocations have finished
+        let in_flight = join_all(components.iter().map(|component| {
+            component
+                .permits
+                .acquire_many(u32::try_from(component.concurrency).unwrap_or(u32::MAX))
+        }));
+        if let Some(deadline) = deadline {
+            if timeout_at(deadline, in_flight).await.is_err() {
+                warn!("in-flight invocations did not finish before the drain deadline");
+            }
+        } else {
+            in_flight.await;
+        }
+
+        for component in &components {
+            info!(component_id = %component.id, "component stopped");
+            self.event_publisher
+                .publish_event(
+                    "component_scaled",
+                    crate::event::component_scaled(
+                        component.claims(),
+                        &component.annotations,
+                        &host_id,
+                        0_usize,
+                        &component.image_reference,
+                        &component.id,
+                    ),
+                )
+                .await?;
+        }
+
+        let provider_ids: Vec<_> = self.providers.read().await.keys().cloned().collect();
+        for provider_id in provider_ids {
+            let request = StopProviderCommand::builder()
+                .host_id(&host_id)
+                .provider_id(&provider_id)
+                .build()
+                .map_err(|e| anyhow!(e))
+                .context("failed to build stop provider command")?;
+            <Self as ControlInterfaceServer>::handle_stop_provider(self, request).await?;
+        }
+        Ok(())
+    }
+
     #[instrument(level = "trace", skip_all)]
     async fn fetch_component(&self, component_ref: &str) -> anyhow::Result<Vec<u8>> {
         let registry_config = self.registry_config.read().await;
-        fetch_component(
+        let component = fetch_component(
             component_ref,
             self.host_config.allow_file_load,
             &self.host_config.oci_opts.additional_ca_paths,
             &registry_config,
+            &self.artifact_cache,
         )
         .await
-        .context("failed to fetch component")
+        .context("failed to fetch component")?;
+        self.gc_artifact_cache(Some(component_ref)).await;
+        Ok(component)
+    }
+
+    /// Fetch OCI artifacts into the host's artifact cache ahead of time, so that later starting
+    /// components or providers from them does not need to download them. Returns the result of
+    /// fetching each artifact, in order.
+    #[instrument(level = "debug", skip_all)]
+    pub async fn prefetch_artifacts(
+        &self,
+        artifact_refs: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Vec<anyhow::Result<CacheResult>> {
+        let registry_config = self.registry_config.read().await;
+        let mut results = Vec::new();
+        for artifact_ref in artifact_refs {
+            let artifact_ref = artifact_ref.as_ref();
+            let res = prefetch_artifact(
+                artifact_ref,
+                &self.host_config.oci_opts.additional_ca_paths,
+                &registry_config,
+                &self.artifact_cache,
+            )
+            .await;
+            match &res {
+                Ok(cache) => debug!(artifact_ref, ?cache, "prefetched artifact"),
+                Err(err) => warn!(artifact_ref, ?err, "failed to prefetch artifact"),
+            }
+            results.push(res);
+        }
+        results
+    }
+
+    /// Returns the image references of all components and providers running on this host
+    async fn image_refs_in_use(&self) -> HashSet<String> {
+        let mut in_use: HashSet<String> = HashSet::new();
+        in_use.extend(
+            self.components
+                .read()
+                .await
+                .values()
+                .map(|component| component.image_reference.to_string()),
+        );
+        in_use.extend(
+            self.providers
+                .read()
+                .await
+                .values()
+                .map(|provider| provider.image_ref.clone()),
+        );
+        in_use
+    }
+
+    /// Returns the number of component instance slots in use on the runtime of this host, which
+    /// includes the components of all other hosts of its [HostGroup] sharing the runtime
+    async fn used_component_slots(&self) -> u64 {
+        fn slots(components: &HashMap<ComponentId, Arc<Component>>) -> u64 {
+            // Every running component reserves a slot for each of its concurrent instances
+            components
+                .values()
+                .map(|component| u64::try_from(component.concurrency).unwrap_or(u64::MAX))
+                .fold(0, u64::saturating_add)
+        }
+
+        let mut used = slots(&*self.components.read().await);
+        if let Some(group) = &self.host_group {
+            for host in group.hosts() {
+                if !core::ptr::eq(Arc::as_ptr(&host), self) {
+                    used = used.saturating_add(slots(&*host.components.read().await));
+                }
+            }
+        }
+        used
+    }
+
+    /// Evict artifacts from the artifact cache which are not in use by this host or any other host
+    /// of its [HostGroup], if it exceeds its maximum size. `fetched_ref` is considered in use, as
+    /// it is about to be started.
+    #[instrument(level = "debug", skip(self))]
+    async fn gc_artifact_cache(&self, fetched_ref: Option<&str>) {
+        if self.artifact_cache.max_size().is_none() {
+            return;
+        }
+        let mut in_use = self.image_refs_in_use().await;
+        if let Some(group) = &self.host_group {
+            for host in group.hosts() {
+                if !core::ptr::eq(Arc::as_ptr(&host), self) {
+                    in_use.extend(host.image_refs_in_use().await);
+                }
+            }
+        }
+        in_use.extend(fetched_ref.map(ToString::to_string));
+        // Artifacts are cached under their bare OCI reference
+        let in_use = in_use
+            .iter()
+            .filter_map(
+                |image_ref| match ResourceRef::try_from(image_ref.as_str()) {
+                    Ok(ResourceRef::Oci(oci_ref)) => Some(oci_ref.to_lowercase()),
+                    Ok(ResourceRef::OciLayout(layout_ref)) => Some(layout_ref.to_string()),
+                    _ => None,
+                },
+            )
+            .collect();
+        match self.artifact_cache.gc(&in_use).await {
+            Ok(res) if !res.evicted.is_empty() => debug!(
+                evicted = res.evicted.len(),
+                remaining_size = res.remaining_size,
+                "evicted artifacts from OCI artifact cache"
+            ),
+            Ok(_) => {}
+            Err(err) => warn!(?err, "failed to garbage collect OCI artifact cache"),
+        }
     }
 
     #[instrument(level = "debug", skip_all)]
@@ -1287,6 +1755,64 @@ impl Host {
         .await
     }
 
+    #[instrument(level = "debug", skip_all)]
+    pub(crate) async fn handle_cordon_host(
+        &self,
+        payload: impl AsRef<[u8]>,
+        transport_host_id: &str,
+    ) -> anyhow::Result<CtlResponse<()>> {
+        anyhow::ensure!(
+            transport_host_id == self.host_key.public_key(),
+            "invalid host_id [{transport_host_id}]"
+        );
+        // Allow an empty payload to be used for cordoning hosts
+        let request = if payload.as_ref().is_empty() {
+            CordonHostCommand::builder()
+                .host_id(transport_host_id)
+                .build()
+                .map_err(|e| anyhow!(e))
+                .context("failed to build cordon host command")?
+        } else {
+            serde_json::from_slice::<CordonHostCommand>(payload.as_ref())
+                .context("failed to deserialize cordon command")?
+        };
+        let host_id = request.host_id();
+        anyhow::ensure!(
+            host_id.is_empty() || host_id == transport_host_id,
+            "invalid host_id [{host_id}]"
+        );
+        <Self as ControlInterfaceServer>::handle_cordon_host(self, request).await
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    pub(crate) async fn handle_drain_host(
+        self: Arc<Self>,
+        payload: impl AsRef<[u8]>,
+        transport_host_id: &str,
+    ) -> anyhow::Result<CtlResponse<()>> {
+        anyhow::ensure!(
+            transport_host_id == self.host_key.public_key(),
+            "invalid host_id [{transport_host_id}]"
+        );
+        // Allow an empty payload to be used for draining hosts
+        let request = if payload.as_ref().is_empty() {
+            DrainHostCommand::builder()
+                .host_id(transport_host_id)
+                .build()
+                .map_err(|e| anyhow!(e))
+                .context("failed to build drain host command")?
+        } else {
+            serde_json::from_slice::<DrainHostCommand>(payload.as_ref())
+                .context("failed to deserialize drain command")?
+        };
+        let host_id = request.host_id();
+        anyhow::ensure!(
+            host_id.is_empty() || host_id == transport_host_id,
+            "invalid host_id [{host_id}]"
+        );
+        <Self as ControlInterfaceServer>::handle_drain_host(self, request).await
+    }
+
     #[instrument(level = "debug", skip_all)]
     pub(crate) async fn handle_scale_component(
         self: Arc<Self>,
@@ -1307,10 +1833,12 @@ impl Host {
         component_id: Arc<str>,
         host_id: &str,
         max_instances: u32,
+        limits: ComponentResourceLimits,
         annotations: &Annotations,
         config: Vec<String>,
         wasm: anyhow::Result<Vec<u8>>,
         claims_token: Option<&jwt::Token<jwt::Component>>,
+        allow_update: bool,
     ) -> anyhow::Result<()> {
         trace!(?component_ref, max_instances, "scale component task");
 
@@ -1367,6 +1895,11 @@ impl Host {
                     .await?;
                 match &wasm {
                     Ok(wasm) => {
+                        config_schema::ensure_valid(
+                            &component_id,
+                            wasmcloud_runtime::component::config_schema(wasm)?.as_ref(),
+                            &config.get_config().await,
+                        )?;
                         self.start_component(
                             entry,
                             wasm,
@@ -1374,9 +1907,11 @@ impl Host {
                             Arc::clone(&component_ref),
                             Arc::clone(&component_id),
                             max,
+                            limits,
                             annotations,
                             config,
                             secrets,
+                            allow_update,
                         )
                         .await?;
 
@@ -1419,6 +1954,14 @@ impl Host {
                 self.stop_component(&component, host_id)
                     .await
                     .context("failed to stop component in response to scale to zero")?;
+                // The digest is only enforced while the component is running, so that it can be
+                // started with different bytes once it has been scaled to zero
+                self.update_component_spec(&component_id, |spec| {
+                    if spec.digest.as_deref() == Some(&*component.digest) {
+                        spec.digest = None;
+                    }
+                })
+                .await?;
 
                 info!(?component_ref, "component stopped");
                 crate::event::component_scaled(
@@ -1447,8 +1990,8 @@ impl Host {
                     &component.id,
                 );
 
-                // Modify scale only if the requested max differs from the current max or if the configuration has changed
-                if component.max_instances != max || config_changed {
+                // Modify scale only if the requested max, the resource limits or the configuration have changed
+                if component.max_instances != max || component.limits != limits || config_changed {
                     // We must partially clone the handler as we can't be sharing the targets between components
                     let handler = component.handler.copy_for_new();
                     if config_changed {
@@ -1459,6 +2002,11 @@ impl Host {
                                 annotations.get("wasmcloud.dev/appspec"),
                             )
                             .await?;
+                        config_schema::ensure_valid(
+                            &component_id,
+                            component.config_schema(),
+                            &config.get_config().await,
+                        )?;
                         *handler.config_data.write().await = config;
                         *handler.secrets.write().await = secrets;
                     }
@@ -1466,8 +2014,10 @@ impl Host {
                         .instantiate_component(
                             annotations,
                             Arc::clone(&component_ref),
+                            Arc::clone(&component.digest),
                             Arc::clone(&component.id),
                             max,
+                            limits,
                             component.component.clone(),
                             handler,
                         )
@@ -1479,6 +2029,12 @@ impl Host {
                         .context("failed to stop component after scaling")?;
 
                     info!(?component_ref, ?max, "component scaled");
+                    if config_changed {
+                        self.update_component_spec(&component_id, |spec| {
+                            spec.config.clone_from(&config);
+                        })
+                        .await?;
+                    }
                 } else {
                     debug!(?component_ref, ?max, "component already at desired scale");
                 }
@@ -1529,22 +2085,42 @@ impl Host {
             }
 
             let new_component = self.fetch_component(&new_component_ref).await?;
+            let new_digest = content_digest(&new_component);
             let new_component = wasmcloud_runtime::Component::new(&self.runtime, &new_component)
                 .context("failed to initialize component")?;
+            config_schema::ensure_valid(
+                &component_id,
+                new_component.config_schema(),
+                &*existing_component
+                    .handler
+                    .config_data
+                    .read()
+                    .await
+                    .get_config()
+                    .await,
+            )?;
             let new_claims = new_component.claims().cloned();
             if let Some(ref claims) = new_claims {
                 self.store_claims(Claims::Component(claims.clone()))
                     .await
                     .context("failed to store claims")?;
             }
+            self.update_component_spec(&component_id, |spec| {
+                spec.url = new_component_ref.to_string();
+                spec.digest = Some(new_digest.clone());
+                spec.claims.clone_from(&new_claims);
+            })
+            .await?;
 
             let max = existing_component.max_instances;
             let Ok(component) = self
                 .instantiate_component(
                     &annotations,
                     Arc::clone(&new_component_ref),
+                    Arc::from(new_digest),
                     Arc::clone(&component_id),
                     max,
+                    existing_component.limits.clone(),
                     new_component,
                     existing_component.handler.copy_for_new(),
                 )
@@ -1629,9 +2205,11 @@ impl Host {
                     self.host_config.allow_file_load,
                     &self.host_config.oci_opts.additional_ca_paths,
                     &registry_config,
+                    &self.artifact_cache,
                 )
                 .await
                 .context("failed to fetch provider")?;
+                self.gc_artifact_cache(Some(provider_ref.as_ref())).await;
                 (Some(path), claims_token)
             }
         };
@@ -1689,10 +2267,22 @@ impl Host {
                     &annotations,
                 )
                 .await?;
+            config_schema::ensure_valid(
+                provider_id,
+                claims
+                    .as_ref()
+                    .and_then(|claims| claims.metadata.as_ref())
+                    .and_then(|metadata| metadata.provider_config_schema.as_ref()),
+                &config_bundle.get_config().await,
+            )?;
             let config_bundle = Arc::new(RwLock::new(config_bundle));
             // Used by provider child tasks (health check, config watch, process restarter) to
             // know when to shutdown.
             let shutdown = Arc::new(AtomicBool::new(false));
+            let logs = Arc::new(ProviderLogBuffer::new(
+                self.host_config.provider_log_buffer_lines,
+            ));
+            let failed = Arc::new(AtomicBool::new(false));
             let tasks = match (path, &provider_ref) {
                 (Some(path), ..) => {
                     Arc::clone(&self)
@@ -1707,6 +2297,8 @@ impl Host {
                             claims_token.clone(),
                             annotations.clone(),
                             shutdown.clone(),
+                            Arc::clone(&logs),
+                            Arc::clone(&failed),
                         )
                         .await?
                 }
@@ -1725,6 +2317,13 @@ impl Host {
                     "messaging-nats" => {
                         bail!("feature `builtin-messaging-nats` is not enabled, denying start")
                     }
+                    "cron" if self.experimental_features.builtin_cron => {
+                        self.start_cron_provider(host_data, provider_xkey, provider_id)
+                            .await?
+                    }
+                    "cron" => {
+                        bail!("feature `builtin-cron` is not enabled, denying start")
+                    }
                     _ => bail!("unknown builtin name: {name}"),
                 },
                 _ => bail!("invalid provider reference"),
@@ -1755,6 +2354,8 @@ impl Host {
                 image_ref: provider_ref.as_ref().to_string(),
                 xkey,
                 shutdown,
+                logs,
+                failed,
             });
         } else {
             bail!("provider is already running with that ID")
@@ -1773,6 +2374,16 @@ impl Host {
         <Self as ControlInterfaceServer>::handle_stop_provider(self, cmd).await
     }
 
+    #[instrument(level = "trace", skip_all)]
+    pub(crate) async fn handle_get_provider_logs(
+        &self,
+        payload: impl AsRef<[u8]>,
+    ) -> anyhow::Result<CtlResponse<ProviderLogs>> {
+        let cmd = serde_json::from_slice::<GetProviderLogsCommand>(payload.as_ref())
+            .context("failed to deserialize provider logs request")?;
+        <Self as ControlInterfaceServer>::handle_get_provider_logs(self, cmd).await
+    }
+
     #[instrument(level = "debug", skip_all)]
     pub(crate) async fn handle_inventory(&self) -> anyhow::Result<CtlResponse<HostInventory>> {
         <Self as ControlInterfaceServer>::handle_inventory(self).await
@@ -1864,6 +2475,38 @@ impl Host {
         <Self as ControlInterfaceServer>::handle_config_put(self, config_name, data).await
     }
 
+    #[instrument(level = "debug", skip_all, fields(%config_name))]
+    pub(crate) async fn handle_config_get_revision(
+        &self,
+        config_name: &str,
+        revision: &str,
+    ) -> anyhow::Result<CtlResponse<HashMap<String, String>>> {
+        let revision = revision
+            .parse()
+            .with_context(|| format!("invalid config revision `{revision}`"))?;
+        <Self as ControlInterfaceServer>::handle_config_get_revision(self, config_name, revision)
+            .await
+    }
+
+    #[instrument(level = "debug", skip_all, fields(%config_name))]
+    pub(crate) async fn handle_config_history(
+        &self,
+        config_name: &str,
+    ) -> anyhow::Result<CtlResponse<Vec<ConfigRevision>>> {
+        <Self as ControlInterfaceServer>::handle_config_history(self, config_name).await
+    }
+
+    #[instrument(level = "debug", skip_all, fields(%config_name))]
+    pub(crate) async fn handle_config_rollback(
+        &self,
+        config_name: &str,
+        payload: impl AsRef<[u8]>,
+    ) -> anyhow::Result<CtlResponse<ConfigRevision>> {
+        let request = serde_json::from_slice::<RollbackConfigCommand>(payload.as_ref())
+            .context("failed to deserialize config rollback command")?;
+        <Self as ControlInterfaceServer>::handle_config_rollback(self, config_name, request).await
+    }
+
     #[instrument(level = "debug", skip_all, fields(%config_name))]
     pub(crate) async fn handle_config_delete(
         &self,
@@ -2050,6 +2693,60 @@ impl Host {
         Ok(())
     }
 
+    /// Validates the configuration of the ends of a link, which are running on this host and
+    /// declare a JSON schema of their configuration. Components are validated with the
+    /// configuration the link supplies to them merged over their own, providers with the
+    /// configuration the link supplies to them against the link configuration schema in their
+    /// claims.
+    async fn validate_link_config_schema(&self, link: &Link) -> anyhow::Result<()> {
+        for (id, link_config) in [
+            (link.source_id(), link.source_config()),
+            (link.target(), link.target_config()),
+        ] {
+            let component = self.components.read().await.get(id).cloned();
+            let (schema, mut config) = if let Some(component) = component {
+                let Some(schema) = component.config_schema().cloned() else {
+                    continue;
+                };
+                let config = component
+                    .handler
+                    .config_data
+                    .read()
+                    .await
+                    .get_config()
+                    .await
+                    .clone();
+                (schema, config)
+            } else {
+                let schema = self.providers.read().await.get(id).and_then(|provider| {
+                    provider
+                        .claims_token
+                        .as_ref()
+                        .and_then(|token| token.claims.metadata.as_ref())
+                        .and_then(|metadata| metadata.config_schema.clone())
+                });
+                let Some(schema) = schema else {
+                    continue;
+                };
+                (schema, HashMap::default())
+            };
+            let link_config = self
+                .config_generator
+                .generate(
+                    link_config
+                        .iter()
+                        .filter(|name| !name.starts_with(SECRET_PREFIX))
+                        .cloned()
+                        .collect(),
+                )
+                .await
+                .context("Unable to fetch requested config")?;
+            config.extend(link_config.get_config().await.clone());
+            config_schema::ensure_valid(id, Some(&schema), &config)?;
+        }
+        Ok(())
+    }
+
     /// Transform a [`wasmcloud_control_interface::Link`] into a [`wasmcloud_core::InterfaceLinkDefinition`]
     /// by fetching the source and target configurations and secrets, and encrypting the secrets.
     async fn resolve_link_config(
@@ -2173,6 +2870,39 @@ fn human_friendly_uptime(uptime: Duration) -> String {
     .to_string()
 }
 
+/// Summarize component claims as reported in the host inventory
+fn claims_summary(claims: &jwt::Claims<jwt::Component>) -> BTreeMap<String, String> {
+    let mut summary = BTreeMap::from([
+        ("issuer".to_string(), claims.issuer.clone()),
+        ("subject".to_string(), claims.subject.clone()),
+        ("issued_at".to_string(), claims.issued_at.to_string()),
+    ]);
+    if let Some(expires) = claims.expires {
+        summary.insert("expires".to_string(), expires.to_string());
+    }
+    if let Some(not_before) = claims.not_before {
+        summary.insert("not_before".to_string(), not_before.to_string());
+    }
+    if let Some(jwt::Component {
+        ver,
+        call_alias,
+        tags,
+        ..
+    }) = &claims.metadata
+    {
+        if let Some(ver) = ver {
+            summary.insert("version".to_string(), ver.clone());
+        }
+        if let Some(call_alias) = call_alias {
+            summary.insert("call_alias".to_string(), call_alias.clone());
+        }
+        if let Some(tags) = tags.as_ref().filter(|tags| !tags.is_empty()) {
+            summary.insert("tags".to_string(), tags.join(","));
+        }
+    }
+    summary
+}
+
 /// Helper function to inject trace context into NATS headers
 pub fn injector_to_headers(injector: &TraceContextInjector) -> async_nats::header::HeaderMap {
     injector
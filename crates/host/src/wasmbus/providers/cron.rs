@@ -0,0 +1,848 @@
+//! Built-in cron capability provider
+//!
+//! The provider invokes the `wasmcloud:messaging/handler.handle-message` export of linked
+//! components on a schedule, configured by the source config of the link:
+//!
+//! - `cron`: a 5-field cron expression (`minute hour day-of-month month day-of-week`) evaluated in
+//!   UTC, or one of `@yearly`, `@monthly`, `@weekly`, `@daily` and `@hourly`
+//! - `interval`: a fixed interval, like `30s` or `5m`, aligned to the UNIX epoch. Exactly one of
+//!   `cron` and `interval` must be set
+//! - `subject`: the subject of the delivered message, defaults to the link name
+//! - `payload`: the body of the delivered message, defaults to a JSON description of the tick
+//! - `jitter`: maximum random delay added to each run, like `5s`
+//! - `overlap`: `skip` (default) to skip runs while the previous run is still executing on the
+//!   host, or `allow`
+//! - `missed`: `skip` (default) to drop missed runs, or `run-once` to execute a single catch-up
+//!   run for runs that were skipped due to overlap or while no host was running the schedule
+//! - `coordination`: `lattice` (default) to execute each run exactly once across all hosts
+//!   running the provider and the linked component, or `none` to run on every host. Lattice
+//!   coordination requires the cron store of the host to support [`StoreManager::create`]
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, ensure, Context as _};
+use bytes::Bytes;
+use nkeys::XKey;
+use rand::Rng as _;
+use serde::{Deserialize, Serialize};
+use time::{Date, Month, OffsetDateTime, Time};
+use tokio::sync::{broadcast, Mutex, RwLock};
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+use tracing::{debug, error, instrument, trace, trace_span, warn, Instrument as _, Span};
+use wasmcloud_core::HostData;
+use wasmcloud_provider_sdk::provider::{
+    handle_provider_commands, receive_link_for_provider, ProviderCommandReceivers,
+};
+use wasmcloud_provider_sdk::{LinkConfig, LinkDeleteInfo, ProviderConnection};
+use wasmcloud_runtime::capability::wrpc;
+use wasmcloud_tracing::KeyValue;
+
+use crate::store::{CreateNotSupported, StoreManager};
+use crate::wasmbus::{Component, InvocationContext};
+
+/// A set of allowed values of a cron field, as a bitmask
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct CronField(u64);
+
+impl CronField {
+    fn parse(field: &str, min: u32, max: u32) -> anyhow::Result<Self> {
+        let mut bits = 0;
+        for part in field.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => {
+                    let step: u32 = step
+                        .parse()
+                        .with_context(|| format!("invalid step `{step}`"))?;
+                    ensure!(step > 0, "step must be greater than zero");
+                    (range, step)
+                }
+                None => (part, 1),
+            };
+            let (start, end) = match range {
+                "*" => (min, max),
+                range => match range.split_once('-') {
+                    Some((start, end)) => (
+                        start
+                            .parse()
+                            .with_context(|| format!("invalid value `{start}`"))?,
+                        end.parse()
+                            .with_context(|| format!("invalid value `{end}`"))?,
+                    ),
+                    // `n/step` means every `step` starting at `n`
+                    None if part.contains('/') => (
+                        range
+                            .parse()
+                            .with_context(|| format!("invalid value `{range}`"))?,
+                        max,
+                    ),
+                    None => {
+                        let value = range
+                            .parse()
+                            .with_context(|| format!("invalid value `{range}`"))?;
+                        (value, value)
+                    }
+                },
+            };
+            ensure!(
+                min <= start && start <= end && end <= max,
+                "`{part}` is out of range {min}-{max}"
+            );
+            for value in (start..=end).step_by(step as usize) {
+                bits |= 1 << value;
+            }
+        }
+        Ok(Self(bits))
+    }
+
+    fn contains(&self, value: u32) -> bool {
+        self.0 & (1 << value) != 0
+    }
+}
+
+/// A parsed 5-field cron expression, evaluated in UTC
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct CronSchedule {
+    minutes: CronField,
+    hours: CronField,
+    days_of_month: CronField,
+    months: CronField,
+    days_of_week: CronField,
+    /// Whether both day of month and day of week are restricted, in which case a day matches if
+    /// either matches
+    day_or: bool,
+}
+
+impl CronSchedule {
+    fn parse(expr: &str) -> anyhow::Result<Self> {
+        let expr = match expr.trim() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            expr => expr,
+        };
+        let [minutes, hours, days_of_month, months, days_of_week] = expr
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("cron expression `{expr}` must have 5 fields"))?;
+        let day_or = !days_of_month.starts_with('*') && !days_of_week.starts_with('*');
+        let mut days_of_week = CronField::parse(days_of_week, 0, 7)
+            .with_context(|| format!("invalid day of week `{days_of_week}`"))?;
+        // Both 0 and 7 are Sunday
+        if days_of_week.contains(7) {
+            days_of_week.0 |= 1;
+        }
+        Ok(Self {
+            minutes: CronField::parse(minutes, 0, 59)
+                .with_context(|| format!("invalid minute `{minutes}`"))?,
+            hours: CronField::parse(hours, 0, 23)
+                .with_context(|| format!("invalid hour `{hours}`"))?,
+            days_of_month: CronField::parse(days_of_month, 1, 31)
+                .with_context(|| format!("invalid day of month `{days_of_month}`"))?,
+            months: CronField::parse(months, 1, 12)
+                .with_context(|| format!("invalid month `{months}`"))?,
+            days_of_week,
+            day_or,
+        })
+    }
+
+    fn matches_day(&self, date: Date) -> bool {
+        let dom = self.days_of_month.contains(date.day().into());
+        let dow = self
+            .days_of_week
+            .contains(date.weekday().number_days_from_sunday().into());
+        if self.day_or {
+            dom || dow
+        } else {
+            dom && dow
+        }
+    }
+
+    /// Returns the first time strictly after `after` matching the schedule, if one exists within
+    /// the next 5 years
+    fn next_after(&self, after: OffsetDateTime) -> Option<OffsetDateTime> {
+        let limit = after + time::Duration::days(5 * 366);
+        let mut t =
+            after.replace_second(0).ok()?.replace_nanosecond(0).ok()? + time::Duration::minutes(1);
+        while t <= limit {
+            if !self.months.contains(u8::from(t.month()).into()) {
+                let (year, month) = match t.month() {
+                    Month::December => (t.year() + 1, Month::January),
+                    month => (t.year(), month.next()),
+                };
+                t = t
+                    .replace_date(Date::from_calendar_date(year, month, 1).ok()?)
+                    .replace_time(Time::MIDNIGHT);
+            } else if !self.matches_day(t.date()) {
+                t = t
+                    .replace_date(t.date().next_day()?)
+                    .replace_time(Time::MIDNIGHT);
+            } else if !self.hours.contains(t.hour().into()) {
+                t = t.replace_time(Time::from_hms(t.hour(), 0, 0).ok()?) + time::Duration::HOUR;
+            } else if !self.minutes.contains(t.minute().into()) {
+                t += time::Duration::MINUTE;
+            } else {
+                return Some(t);
+            }
+        }
+        None
+    }
+}
+
+/// When a job runs
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Schedule {
+    Cron(CronSchedule),
+    /// Fixed interval, aligned to the UNIX epoch so that all hosts agree on run times
+    Interval(Duration),
+}
+
+impl Schedule {
+    /// Returns the first scheduled run strictly after `after`
+    fn next_after(&self, after: OffsetDateTime) -> Option<OffsetDateTime> {
+        match self {
+            Self::Cron(cron) => cron.next_after(after),
+            Self::Interval(interval) => {
+                let interval = i128::try_from(interval.as_nanos()).ok()?;
+                let after = after.unix_timestamp_nanos();
+                let next = (after.div_euclid(interval) + 1) * interval;
+                OffsetDateTime::from_unix_timestamp_nanos(next).ok()
+            }
+        }
+    }
+}
+
+/// What to do when a run is scheduled while the previous run is still executing
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum OverlapPolicy {
+    #[default]
+    Skip,
+    Allow,
+}
+
+/// What to do with runs that were missed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum MissedRunPolicy {
+    #[default]
+    Skip,
+    RunOnce,
+}
+
+/// Configuration of a scheduled job, parsed from link config
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct JobConfig {
+    schedule: Schedule,
+    subject: String,
+    payload: Option<Bytes>,
+    jitter: Duration,
+    overlap: OverlapPolicy,
+    missed: MissedRunPolicy,
+    coordinate: bool,
+}
+
+impl JobConfig {
+    fn from_map(link_name: &str, config: &HashMap<String, String>) -> anyhow::Result<Self> {
+        let schedule = match (config.get("cron"), config.get("interval")) {
+            (Some(cron), None) => Schedule::Cron(CronSchedule::parse(cron)?),
+            (None, Some(interval)) => {
+                let interval = humantime::parse_duration(interval)
+                    .with_context(|| format!("invalid interval `{interval}`"))?;
+                ensure!(!interval.is_zero(), "interval must be greater than zero");
+                Schedule::Interval(interval)
+            }
+            (Some(_), Some(_)) => bail!("only one of `cron` and `interval` may be set"),
+            (None, None) => bail!("one of `cron` or `interval` must be set"),
+        };
+        let jitter = config
+            .get("jitter")
+            .map(|jitter| {
+                humantime::parse_duration(jitter)
+                    .with_context(|| format!("invalid jitter `{jitter}`"))
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let overlap = match config.get("overlap").map(String::as_str) {
+            None | Some("skip") => OverlapPolicy::Skip,
+            Some("allow") => OverlapPolicy::Allow,
+            Some(overlap) => bail!("invalid overlap policy `{overlap}`"),
+        };
+        let missed = match config.get("missed").map(String::as_str) {
+            None | Some("skip") => MissedRunPolicy::Skip,
+            Some("run-once") => MissedRunPolicy::RunOnce,
+            Some(missed) => bail!("invalid missed run policy `{missed}`"),
+        };
+        let coordinate = match config.get("coordination").map(String::as_str) {
+            None | Some("lattice") => true,
+            Some("none") => false,
+            Some(coordination) => bail!("invalid coordination `{coordination}`"),
+        };
+        Ok(Self {
+            schedule,
+            subject: config
+                .get("subject")
+                .cloned()
+                .unwrap_or_else(|| link_name.to_string()),
+            payload: config.get("payload").cloned().map(Bytes::from),
+            jitter,
+            overlap,
+            missed,
+            coordinate,
+        })
+    }
+}
+
+/// The last run of a job, stored in the host cron store to coordinate across the lattice
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct LastRun {
+    /// UNIX timestamp of the scheduled time of the last run
+    scheduled_at: i64,
+}
+
+/// Body of the message delivered to components, if no `payload` is configured
+#[derive(Debug, Serialize)]
+struct Tick<'a> {
+    job: &'a str,
+    scheduled_at: i64,
+}
+
+/// A scheduled job for a link from the provider to a component
+struct Job {
+    config: JobConfig,
+    /// Name of the job in the cron store, unique to the provider, target and link
+    key: String,
+    target_id: Arc<str>,
+    link_name: Box<str>,
+    components: Arc<RwLock<HashMap<String, Arc<Component>>>>,
+    cron_store: Arc<dyn StoreManager>,
+    lattice_id: Arc<str>,
+    host_id: Arc<str>,
+    running: AtomicBool,
+    pending: AtomicBool,
+}
+
+impl Job {
+    fn last_run_key(&self) -> String {
+        format!("LASTRUN_{}", self.key)
+    }
+
+    async fn last_run(&self) -> anyhow::Result<Option<LastRun>> {
+        self.cron_store
+            .get(&self.last_run_key())
+            .await?
+            .map(|last_run| serde_json::from_slice(&last_run))
+            .transpose()
+            .context("failed to decode last run")
+    }
+
+    /// Claim the run scheduled at `scheduled_at` across the lattice, returning `false` if another
+    /// host has claimed it
+    async fn claim(&self, scheduled_at: i64) -> anyhow::Result<bool> {
+        let claim_key = format!("CLAIM_{}.{scheduled_at}", self.key);
+        if !self
+            .cron_store
+            .create(&claim_key, Bytes::from(self.host_id.to_string()))
+            .await?
+        {
+            return Ok(false);
+        }
+        // The claim is only held while recording the last run, so check that this run has not
+        // already been recorded by a host which held the claim before
+        let claimed = match self.last_run().await {
+            Ok(Some(LastRun {
+                scheduled_at: last_scheduled_at,
+            })) if last_scheduled_at >= scheduled_at => Ok(false),
+            Ok(_) => match serde_json::to_vec(&LastRun { scheduled_at }) {
+                Ok(last_run) => self
+                    .cron_store
+                    .put(&self.last_run_key(), last_run.into())
+                    .await
+                    .map(|()| true),
+                Err(err) => Err(anyhow::Error::new(err).context("failed to encode last run")),
+            },
+            Err(err) => Err(err),
+        };
+        if let Err(err) = self.cron_store.del(&claim_key).await {
+            warn!(?err, claim_key, "failed to release cron run claim");
+        }
+        claimed
+    }
+
+    fn message(&self, scheduled_at: i64) -> wrpc::wasmcloud::messaging0_2_0::types::BrokerMessage {
+        let body = self.config.payload.clone().unwrap_or_else(|| {
+            serde_json::to_vec(&Tick {
+                job: self.link_name.as_ref(),
+                scheduled_at,
+            })
+            .map(Bytes::from)
+            .unwrap_or_default()
+        });
+        wrpc::wasmcloud::messaging0_2_0::types::BrokerMessage {
+            subject: self.config.subject.clone(),
+            body,
+            reply_to: None,
+        }
+    }
+
+    #[instrument(level = "debug", skip(self), fields(target_id = %self.target_id, link_name = %self.link_name))]
+    async fn invoke(&self, scheduled_at: i64) {
+        use wrpc::exports::wasmcloud::messaging0_2_0::handler::Handler as _;
+
+        let component = {
+            let components = self.components.read().await;
+            let Some(component) = components.get(self.target_id.as_ref()) else {
+                warn!("linked component not found");
+                return;
+            };
+            Arc::clone(component)
+        };
+        let _permit = match component
+            .permits
+            .acquire()
+            .instrument(trace_span!("acquire_message_permit"))
+            .await
+        {
+            Ok(permit) => permit,
+            Err(err) => {
+                error!(?err, "failed to acquire execution permit");
+                return;
+            }
+        };
+        match component
+            .instantiate(component.handler.copy_for_new(), component.events.clone())
+            .handle_message(
+                InvocationContext {
+                    span: Span::current(),
+                    start_at: Instant::now(),
+                    attributes: vec![
+                        KeyValue::new("component.ref", Arc::clone(&component.image_reference)),
+                        KeyValue::new("lattice", Arc::clone(&self.lattice_id)),
+                        KeyValue::new("host", Arc::clone(&self.host_id)),
+                    ],
+                    deadline: None,
+                },
+                self.message(scheduled_at),
+            )
+            .await
+        {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                warn!(?err, "component failed to handle scheduled run")
+            }
+            Err(err) => {
+                warn!(?err, "failed to call component")
+            }
+        }
+    }
+
+    /// Execute the run scheduled at `scheduled_at`, if this host is responsible for it
+    async fn run(self: Arc<Self>, scheduled_at: i64) {
+        // Only hosts running the component take part in the schedule
+        if !self
+            .components
+            .read()
+            .await
+            .contains_key(self.target_id.as_ref())
+        {
+            trace!(target_id = %self.target_id, "linked component not running on host, skipping run");
+            return;
+        }
+        if self.config.coordinate {
+            match self.claim(scheduled_at).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    trace!(scheduled_at, "run claimed by another host");
+                    return;
+                }
+                Err(err) => {
+                    error!(?err, scheduled_at, "failed to claim scheduled run");
+                    return;
+                }
+            }
+        }
+        if self.config.overlap == OverlapPolicy::Skip && self.running.swap(true, Ordering::AcqRel) {
+            debug!(scheduled_at, "previous run still executing, skipping run");
+            if self.config.missed == MissedRunPolicy::RunOnce {
+                self.pending.store(true, Ordering::Release);
+            }
+            return;
+        }
+        self.invoke(scheduled_at).await;
+        // Catch up on a single run missed while this one was executing
+        while self.config.overlap == OverlapPolicy::Skip
+            && self.pending.swap(false, Ordering::AcqRel)
+        {
+            debug!("executing missed run");
+            self.invoke(OffsetDateTime::now_utc().unix_timestamp())
+                .await;
+        }
+        self.running.store(false, Ordering::Release);
+    }
+
+    /// Run the job on its schedule until the task is aborted
+    async fn schedule(self: Arc<Self>) {
+        let mut tasks = JoinSet::new();
+        if self.config.missed == MissedRunPolicy::RunOnce && self.config.coordinate {
+            match self.last_run().await {
+                Ok(Some(LastRun { scheduled_at })) => {
+                    let missed = OffsetDateTime::from_unix_timestamp(scheduled_at)
+                        .ok()
+                        .and_then(|last| self.config.schedule.next_after(last))
+                        .filter(|next| *next < OffsetDateTime::now_utc());
+                    if let Some(missed) = missed {
+                        debug!(%missed, "executing run missed while no host was scheduling the job");
+                        tasks.spawn(Arc::clone(&self).run(missed.unix_timestamp()));
+                    }
+                }
+                Ok(None) => {}
+                Err(err) => warn!(?err, "failed to get last run of job"),
+            }
+        }
+        loop {
+            let now = OffsetDateTime::now_utc();
+            let Some(next) = self.config.schedule.next_after(now) else {
+                warn!(target_id = %self.target_id, link_name = %self.link_name, "schedule has no future runs");
+                return;
+            };
+            let mut delay = Duration::try_from(next - now).unwrap_or_default();
+            if !self.config.jitter.is_zero() {
+                delay += rand::rng().random_range(Duration::ZERO..=self.config.jitter);
+            }
+            tokio::time::sleep(delay).await;
+            // Reap finished runs
+            while tasks.try_join_next().is_some() {}
+            tasks.spawn(Arc::clone(&self).run(next.unix_timestamp()));
+        }
+    }
+}
+
+/// Build the cron store key of a job. Keys may only contain `[-/_=.a-zA-Z0-9]`
+fn job_key(provider_id: &str, target_id: &str, link_name: &str) -> String {
+    [provider_id, target_id, link_name]
+        .map(|s| {
+            s.chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || matches!(c, '-' | '=') {
+                        c
+                    } else {
+                        '-'
+                    }
+                })
+                .collect::<String>()
+        })
+        .join(".")
+}
+
+/// Ensure that `store` supports [`StoreManager::create`], which lattice coordination relies on to
+/// claim runs, by creating and removing a probe key of the job with `job_key`
+async fn ensure_atomic_create(store: &dyn StoreManager, job_key: &str) -> anyhow::Result<()> {
+    let probe_key = format!("CLAIM_{job_key}.probe");
+    match store.create(&probe_key, Bytes::new()).await {
+        Ok(true) => {
+            if let Err(err) = store.del(&probe_key).await {
+                warn!(?err, probe_key, "failed to remove cron store probe key");
+            }
+            Ok(())
+        }
+        Ok(false) => Ok(()),
+        Err(err) if err.is::<CreateNotSupported>() => bail!(
+            "`coordination=lattice` requires a cron store supporting atomic create, use `coordination=none` instead"
+        ),
+        Err(err) => {
+            // Transient store errors are reported when claiming runs
+            warn!(?err, "failed to check cron store for atomic create support");
+            Ok(())
+        }
+    }
+}
+
+struct Provider {
+    components: Arc<RwLock<HashMap<String, Arc<Component>>>>,
+    cron_store: Arc<dyn StoreManager>,
+    jobs: Mutex<HashMap<Arc<str>, HashMap<Box<str>, JoinSet<()>>>>,
+    provider_id: Arc<str>,
+    lattice_id: Arc<str>,
+    host_id: Arc<str>,
+}
+
+impl wasmcloud_provider_sdk::Provider for Provider {
+    #[instrument(level = "debug", skip_all)]
+    async fn receive_link_config_as_source(
+        &self,
+        LinkConfig {
+            target_id,
+            config,
+            link_name,
+            ..
+        }: LinkConfig<'_>,
+    ) -> anyhow::Result<()> {
+        let config = JobConfig::from_map(link_name, config)
+            .with_context(|| format!("invalid schedule for link `{link_name}` to `{target_id}`"))?;
+        let key = job_key(&self.provider_id, target_id, link_name);
+        if config.coordinate {
+            ensure_atomic_create(self.cron_store.as_ref(), &key)
+                .await
+                .with_context(|| {
+                    format!("cannot coordinate schedule for link `{link_name}` to `{target_id}`")
+                })?;
+        }
+        let job = Arc::new(Job {
+            config,
+            key,
+            target_id: Arc::from(target_id),
+            link_name: link_name.into(),
+            components: Arc::clone(&self.components),
+            cron_store: Arc::clone(&self.cron_store),
+            lattice_id: Arc::clone(&self.lattice_id),
+            host_id: Arc::clone(&self.host_id),
+            running: AtomicBool::default(),
+            pending: AtomicBool::default(),
+        });
+        let mut tasks = JoinSet::new();
+        tasks.spawn(job.schedule());
+        // Replacing an existing schedule for the link aborts it
+        self.jobs
+            .lock()
+            .await
+            .entry(Arc::from(target_id))
+            .or_default()
+            .insert(link_name.into(), tasks);
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip_all)]
+    async fn delete_link_as_source(&self, info: impl LinkDeleteInfo) -> anyhow::Result<()> {
+        let target_id = info.get_target_id();
+        let link_name = info.get_link_name();
+        self.jobs
+            .lock()
+            .await
+            .get_mut(target_id)
+            .map(|links| links.remove(link_name));
+        Ok(())
+    }
+}
+
+impl crate::wasmbus::Host {
+    #[instrument(level = "debug", skip_all)]
+    pub(crate) async fn start_cron_provider(
+        &self,
+        host_data: HostData,
+        provider_xkey: XKey,
+        provider_id: &str,
+    ) -> anyhow::Result<JoinSet<()>> {
+        let host_id = self.host_key.public_key();
+
+        let (quit_tx, quit_rx) = broadcast::channel(1);
+        let commands = ProviderCommandReceivers::new(
+            Arc::clone(&self.rpc_nats),
+            &quit_tx,
+            &self.host_config.lattice,
+            provider_id,
+            provider_id,
+            &host_id,
+        )
+        .await?;
+        let conn = ProviderConnection::new(
+            Arc::clone(&self.rpc_nats),
+            Arc::from(provider_id),
+            Arc::clone(&self.host_config.lattice),
+            host_id.to_string(),
+            host_data.config,
+            provider_xkey,
+            Arc::clone(&self.secrets_xkey),
+        )
+        .context("failed to establish provider connection")?;
+        let provider = Provider {
+            components: Arc::clone(&self.components),
+            cron_store: Arc::clone(&self.cron_store),
+            jobs: Mutex::default(),
+            provider_id: Arc::from(provider_id),
+            host_id: Arc::from(host_id),
+            lattice_id: Arc::clone(&self.host_config.lattice),
+        };
+        for ld in host_data.link_definitions {
+            if let Err(e) = receive_link_for_provider(&provider, &conn, ld).await {
+                error!(
+                    error = %e,
+                    "failed to initialize link during provider startup",
+                );
+            }
+        }
+        let mut tasks = JoinSet::new();
+        tasks.spawn(async move {
+            handle_provider_commands(provider, &conn, quit_rx, quit_tx, commands).await
+        });
+
+        Ok(tasks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::DefaultStore;
+
+    fn at(date: (i32, u8, u8), hour: u8, minute: u8, second: u8) -> OffsetDateTime {
+        let (year, month, day) = date;
+        Date::from_calendar_date(year, Month::try_from(month).unwrap(), day)
+            .unwrap()
+            .with_hms(hour, minute, second)
+            .unwrap()
+            .assume_utc()
+    }
+
+    #[test]
+    fn test_cron_next_after() -> anyhow::Result<()> {
+        let every_15 = CronSchedule::parse("*/15 * * * *")?;
+        assert_eq!(
+            every_15.next_after(at((2024, 1, 1), 0, 7, 30)),
+            Some(at((2024, 1, 1), 0, 15, 0))
+        );
+        assert_eq!(
+            every_15.next_after(at((2024, 1, 1), 0, 15, 0)),
+            Some(at((2024, 1, 1), 0, 30, 0))
+        );
+
+        let weekdays = CronSchedule::parse("30 9 * * 1-5")?;
+        // 2024-01-06 is a Saturday
+        assert_eq!(
+            weekdays.next_after(at((2024, 1, 6), 12, 0, 0)),
+            Some(at((2024, 1, 8), 9, 30, 0))
+        );
+
+        let leap_day = CronSchedule::parse("0 0 29 2 *")?;
+        assert_eq!(
+            leap_day.next_after(at((2024, 3, 1), 0, 0, 0)),
+            Some(at((2028, 2, 29), 0, 0, 0))
+        );
+
+        // Day of month or day of week when both are restricted
+        let first_or_sunday = CronSchedule::parse("0 0 1 * 7")?;
+        assert_eq!(
+            first_or_sunday.next_after(at((2024, 1, 1), 12, 0, 0)),
+            Some(at((2024, 1, 7), 0, 0, 0))
+        );
+
+        assert_eq!(
+            CronSchedule::parse("@daily")?,
+            CronSchedule::parse("0 0 * * *")?
+        );
+        assert_eq!(
+            CronSchedule::parse("0 0 31 2 *")?.next_after(at((2024, 1, 1), 0, 0, 0)),
+            None
+        );
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("* * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_interval_next_after() {
+        let schedule = Schedule::Interval(Duration::from_secs(30));
+        assert_eq!(
+            schedule.next_after(at((2024, 1, 1), 0, 0, 10)),
+            Some(at((2024, 1, 1), 0, 0, 30))
+        );
+        assert_eq!(
+            schedule.next_after(at((2024, 1, 1), 0, 0, 30)),
+            Some(at((2024, 1, 1), 0, 1, 0))
+        );
+    }
+
+    #[test]
+    fn test_job_config() -> anyhow::Result<()> {
+        let config = JobConfig::from_map(
+            "cleanup",
+            &HashMap::from([
+                ("interval".into(), "5m".into()),
+                ("jitter".into(), "10s".into()),
+                ("missed".into(), "run-once".into()),
+            ]),
+        )?;
+        assert_eq!(
+            config,
+            JobConfig {
+                schedule: Schedule::Interval(Duration::from_secs(300)),
+                subject: "cleanup".into(),
+                payload: None,
+                jitter: Duration::from_secs(10),
+                overlap: OverlapPolicy::Skip,
+                missed: MissedRunPolicy::RunOnce,
+                coordinate: true,
+            }
+        );
+        assert!(JobConfig::from_map("cleanup", &HashMap::default()).is_err());
+        assert!(JobConfig::from_map(
+            "cleanup",
+            &HashMap::from([
+                ("interval".into(), "5m".into()),
+                ("cron".into(), "@hourly".into()),
+            ]),
+        )
+        .is_err());
+        assert!(JobConfig::from_map(
+            "cleanup",
+            &HashMap::from([
+                ("cron".into(), "@hourly".into()),
+                ("overlap".into(), "queue".into()),
+            ]),
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_job_key() {
+        assert_eq!(
+            job_key("cron", "my_component", "default"),
+            "cron.my-component.default"
+        );
+    }
+
+    /// A store, which does not support atomic creation of keys
+    #[derive(Default)]
+    struct NonAtomicStore(DefaultStore);
+
+    #[async_trait::async_trait]
+    impl StoreManager for NonAtomicStore {
+        async fn get(&self, key: &str) -> anyhow::Result<Option<Bytes>> {
+            self.0.get(key).await
+        }
+
+        async fn put(&self, key: &str, value: Bytes) -> anyhow::Result<()> {
+            self.0.put(key, value).await
+        }
+
+        async fn del(&self, key: &str) -> anyhow::Result<()> {
+            self.0.del(key).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ensure_atomic_create() -> anyhow::Result<()> {
+        let store = DefaultStore::default();
+        ensure_atomic_create(&store, "cron.my-component.default").await?;
+        assert_eq!(
+            store.get("CLAIM_cron.my-component.default.probe").await?,
+            None,
+            "probe key should have been removed"
+        );
+
+        let store = NonAtomicStore::default();
+        assert!(store
+            .create("CLAIM_cron.my-component.default.1", Bytes::new())
+            .await
+            .is_err_and(|err| err.is::<CreateNotSupported>()));
+        assert!(ensure_atomic_create(&store, "cron.my-component.default")
+            .await
+            .is_err());
+        Ok(())
+    }
+}
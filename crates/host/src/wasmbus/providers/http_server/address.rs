@@ -2,6 +2,7 @@ use core::net::SocketAddr;
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Context as _;
 use http::header::HOST;
@@ -43,8 +44,12 @@ impl wasmcloud_provider_sdk::Provider for Provider {
             ..
         }: LinkConfig<'_>,
     ) -> anyhow::Result<()> {
-        let ServiceSettings { address, .. } =
-            load_settings(Some(self.address), config).context("failed to load settings")?;
+        let ServiceSettings {
+            address,
+            timeout_ms,
+            ..
+        } = load_settings(Some(self.address), config).context("failed to load settings")?;
+        let timeout = timeout_ms.map(Duration::from_millis);
 
         let components = Arc::clone(&self.components);
         let host_id = Arc::clone(&self.host_id);
@@ -58,6 +63,7 @@ impl wasmcloud_provider_sdk::Provider for Provider {
                 let lattice_id = Arc::clone(&lattice_id);
                 let target_id = Arc::clone(&target_id);
                 async move {
+                    let deadline = timeout.map(|timeout| Instant::now() + timeout);
                     let component = {
                         let components = components.read().await;
                         let component = components
@@ -123,6 +129,7 @@ impl wasmcloud_provider_sdk::Provider for Provider {
                                     KeyValue::new("lattice", Arc::clone(&lattice_id)),
                                     KeyValue::new("host", Arc::clone(&host_id)),
                                 ],
+                                deadline,
                             },
                             req,
                         )
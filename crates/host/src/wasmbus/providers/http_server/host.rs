@@ -2,6 +2,7 @@ use core::net::SocketAddr;
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{bail, Context as _, Result};
 use http::header::HOST;
@@ -143,6 +144,7 @@ impl Provider {
         components: Arc<RwLock<HashMap<String, Arc<Component>>>>,
         lattice_id: Arc<str>,
         host_id: Arc<str>,
+        timeout: Option<Duration>,
         host_header: Option<String>,
     ) -> Result<Self> {
         let host_router = Arc::new(RwLock::new(Router {
@@ -158,6 +160,7 @@ impl Provider {
                 let components = Arc::clone(&components);
                 let host_router = Arc::clone(&host_router);
                 async move {
+                    let deadline = timeout.map(|timeout| Instant::now() + timeout);
                     let (
                         http::request::Parts {
                             method,
@@ -254,6 +257,7 @@ impl Provider {
                                     KeyValue::new("lattice", Arc::clone(&lattice_id)),
                                     KeyValue::new("host", Arc::clone(&host_id)),
                                 ],
+                                deadline,
                             },
                             req,
                         )
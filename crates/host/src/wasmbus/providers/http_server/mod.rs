@@ -3,6 +3,7 @@ use core::str::FromStr as _;
 
 use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{bail, Context as _};
 use hyper_util::rt::{TokioExecutor, TokioIo};
@@ -43,6 +44,12 @@ impl crate::wasmbus::Host {
             .transpose()
             .context("failed to parse default_address")?
             .unwrap_or_else(default_listen_address);
+        let timeout = host_data
+            .config
+            .get("timeout_ms")
+            .map(|s| s.parse().map(Duration::from_millis))
+            .transpose()
+            .context("failed to parse timeout_ms")?;
 
         let provider = match host_data.config.get("routing_mode").map(String::as_str) {
             // Run provider in address mode by default
@@ -60,6 +67,7 @@ impl crate::wasmbus::Host {
                     Arc::clone(&self.components),
                     Arc::from(host_id.as_str()),
                     Arc::clone(&self.host_config.lattice),
+                    timeout,
                 )
                 .await?,
             ),
@@ -69,6 +77,7 @@ impl crate::wasmbus::Host {
                     Arc::clone(&self.components),
                     Arc::from(host_id.as_str()),
                     Arc::clone(&self.host_config.lattice),
+                    timeout,
                     host_data.config.get("header").cloned(),
                 )
                 .await?,
@@ -2,6 +2,7 @@ use core::net::SocketAddr;
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{bail, Context as _};
 use http::header::HOST;
@@ -126,6 +127,7 @@ impl Provider {
         components: Arc<RwLock<HashMap<String, Arc<Component>>>>,
         lattice_id: Arc<str>,
         host_id: Arc<str>,
+        timeout: Option<Duration>,
     ) -> anyhow::Result<Self> {
         let path_router: Arc<RwLock<Router>> = Arc::default();
         let handle = listen(address, {
@@ -136,6 +138,7 @@ impl Provider {
                 let components = Arc::clone(&components);
                 let path_router = Arc::clone(&path_router);
                 async move {
+                    let deadline = timeout.map(|timeout| Instant::now() + timeout);
                     let (
                         http::request::Parts {
                             method,
@@ -223,6 +226,7 @@ impl Provider {
                                     KeyValue::new("lattice", Arc::clone(&lattice_id)),
                                     KeyValue::new("host", Arc::clone(&host_id)),
                                 ],
+                                deadline,
                             },
                             req,
                         )
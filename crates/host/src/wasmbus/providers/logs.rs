@@ -0,0 +1,284 @@
+//! Capture of the output of native capability provider processes
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tokio::io::{AsyncBufReadExt as _, AsyncRead, AsyncReadExt as _, BufReader};
+use tracing::{debug, event, trace, Level};
+use wasmcloud_control_interface::{ProviderLogLine, ProviderLogStream};
+
+/// Maximum length of a line written by a provider in bytes, longer lines are split
+const MAX_LINE_LENGTH: usize = 16 * 1024;
+
+/// Number of consecutive failed reads, after which capture of provider output is stopped
+const MAX_READ_ERRORS: usize = 16;
+
+/// Bounded buffer of the most recent lines written by a provider process. Shared by all processes
+/// spawned for a provider, such that output is retained across restarts.
+#[derive(Debug)]
+pub(crate) struct ProviderLogBuffer {
+    capacity: usize,
+    inner: Mutex<ProviderLogBufferInner>,
+}
+
+#[derive(Debug, Default)]
+struct ProviderLogBufferInner {
+    lines: VecDeque<ProviderLogLine>,
+    next_sequence: u64,
+}
+
+impl ProviderLogBuffer {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::default(),
+        }
+    }
+
+    /// Appends a line to the buffer, evicting the oldest line if the buffer is full
+    pub(crate) fn push(&self, stream: ProviderLogStream, line: &str) {
+        if self.capacity == 0 {
+            return;
+        }
+        let Ok(mut inner) = self.inner.lock() else {
+            return;
+        };
+        let timestamp = OffsetDateTime::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_default();
+        let Ok(line) = ProviderLogLine::builder()
+            .sequence(inner.next_sequence)
+            .timestamp(&timestamp)
+            .stream(stream)
+            .line(line)
+            .build()
+        else {
+            return;
+        };
+        inner.next_sequence += 1;
+        if inner.lines.len() == self.capacity {
+            inner.lines.pop_front();
+        }
+        inner.lines.push_back(line);
+    }
+
+    /// Returns up to `max_lines` of the most recent lines with a sequence number greater than
+    /// `since`, along with whether any such lines were already evicted
+    pub(crate) fn get(
+        &self,
+        since: Option<u64>,
+        max_lines: Option<usize>,
+    ) -> (Vec<ProviderLogLine>, bool) {
+        let Ok(inner) = self.inner.lock() else {
+            return (Vec::default(), false);
+        };
+        let first = since.map_or(0, |since| since.saturating_add(1));
+        let truncated = inner
+            .lines
+            .front()
+            .map_or(inner.next_sequence, ProviderLogLine::sequence)
+            > first;
+        let lines = inner
+            .lines
+            .iter()
+            .skip_while(|line| line.sequence() < first)
+            .collect::<Vec<_>>();
+        let skip = max_lines.map_or(0, |max| lines.len().saturating_sub(max));
+        (lines.into_iter().skip(skip).cloned().collect(), truncated)
+    }
+}
+
+/// Parses the level of a line written by a provider not using structured logging, e.g.
+/// `2024-01-01T00:00:00.000000Z  INFO wasmcloud_provider_sdk: message`
+fn parse_text_level(line: &str) -> Option<Level> {
+    line.split_whitespace()
+        .take(3)
+        .filter(|word| word.chars().all(|c| c.is_ascii_alphabetic()))
+        .find_map(|word| word.parse().ok())
+}
+
+/// Re-emits a line written by the provider with `provider_id` as a tracing event. Lines written
+/// by providers using structured logging are JSON objects, the level, message and fields of which
+/// are preserved.
+fn emit(provider_id: &str, stream: ProviderLogStream, line: &str) {
+    macro_rules! emit_at {
+        ($level:expr, $($args:tt)+) => {{
+            // Event levels must be constant
+            let level = $level;
+            if level == Level::ERROR {
+                event!(Level::ERROR, $($args)+)
+            } else if level == Level::WARN {
+                event!(Level::WARN, $($args)+)
+            } else if level == Level::INFO {
+                event!(Level::INFO, $($args)+)
+            } else if level == Level::DEBUG {
+                event!(Level::DEBUG, $($args)+)
+            } else {
+                event!(Level::TRACE, $($args)+)
+            }
+        }};
+    }
+
+    let stream = stream.as_str();
+    if let Ok(serde_json::Value::Object(mut fields)) = serde_json::from_str(line) {
+        let level = fields
+            .remove("level")
+            .and_then(|level| level.as_str().and_then(|level| level.parse().ok()))
+            .unwrap_or(Level::INFO);
+        let target = fields.remove("target");
+        let target = target.as_ref().and_then(serde_json::Value::as_str);
+        let message = match fields.get_mut("fields") {
+            Some(serde_json::Value::Object(fields)) => fields.remove("message"),
+            _ => fields.remove("message"),
+        };
+        let message = message
+            .as_ref()
+            .map(|message| {
+                message
+                    .as_str()
+                    .map_or_else(|| message.to_string(), str::to_string)
+            })
+            .unwrap_or_default();
+        let fields = serde_json::Value::Object(fields);
+        emit_at!(
+            level,
+            provider_id,
+            stream,
+            provider_target = target,
+            %fields,
+            "{message}"
+        );
+    } else {
+        let level = parse_text_level(line).unwrap_or(Level::INFO);
+        emit_at!(level, provider_id, stream, "{line}");
+    }
+}
+
+/// Reads lines written by the provider with `provider_id` to `stream` until the stream is
+/// closed, emitting them as tracing events and appending them to `buffer`. Lines, which are not
+/// valid UTF-8, are decoded lossily, such that the provider never blocks on a full pipe.
+pub(crate) async fn capture(
+    provider_id: Arc<str>,
+    stream: ProviderLogStream,
+    reader: impl AsyncRead + Unpin,
+    buffer: Arc<ProviderLogBuffer>,
+) {
+    let mut reader = BufReader::new(reader);
+    let mut buf = Vec::with_capacity(1024);
+    let mut errors = 0;
+    loop {
+        buf.clear();
+        match (&mut reader)
+            .take(MAX_LINE_LENGTH as u64)
+            .read_until(b'\n', &mut buf)
+            .await
+        {
+            Ok(n) if n > 0 => {
+                errors = 0;
+                if buf.last() == Some(&b'\n') {
+                    buf.pop();
+                    if buf.last() == Some(&b'\r') {
+                        buf.pop();
+                    }
+                }
+                let line = String::from_utf8_lossy(&buf);
+                emit(&provider_id, stream, &line);
+                buffer.push(stream, &line);
+            }
+            Ok(_) => {
+                trace!(
+                    ?provider_id,
+                    stream = stream.as_str(),
+                    "provider output closed"
+                );
+                return;
+            }
+            Err(err) => {
+                debug!(
+                    ?err,
+                    ?provider_id,
+                    stream = stream.as_str(),
+                    "failed to read provider output"
+                );
+                errors += 1;
+                if errors == MAX_READ_ERRORS {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequences(lines: &[ProviderLogLine]) -> Vec<u64> {
+        lines.iter().map(ProviderLogLine::sequence).collect()
+    }
+
+    #[test]
+    fn test_provider_log_buffer() {
+        let buffer = ProviderLogBuffer::new(3);
+        let (lines, truncated) = buffer.get(None, None);
+        assert!(lines.is_empty());
+        assert!(!truncated);
+
+        for line in ["a", "b", "c", "d", "e"] {
+            buffer.push(ProviderLogStream::Stdout, line);
+        }
+        let (lines, truncated) = buffer.get(None, None);
+        assert_eq!(sequences(&lines), [2, 3, 4]);
+        assert_eq!(lines[0].line(), "c");
+        assert!(truncated);
+
+        let (lines, truncated) = buffer.get(Some(2), None);
+        assert_eq!(sequences(&lines), [3, 4]);
+        assert!(!truncated);
+
+        let (lines, truncated) = buffer.get(Some(4), None);
+        assert!(lines.is_empty());
+        assert!(!truncated);
+
+        let (lines, _) = buffer.get(None, Some(1));
+        assert_eq!(sequences(&lines), [4]);
+    }
+
+    #[tokio::test]
+    async fn test_capture() {
+        let buffer = Arc::new(ProviderLogBuffer::new(10));
+        let mut output = b"first\r\ninvalid \xff utf-8\n".to_vec();
+        output.extend(vec![b'a'; MAX_LINE_LENGTH + 1]);
+        output.extend(b"\nlast");
+        capture(
+            "provider".into(),
+            ProviderLogStream::Stdout,
+            output.as_slice(),
+            Arc::clone(&buffer),
+        )
+        .await;
+        let (lines, _) = buffer.get(None, None);
+        let lines: Vec<_> = lines.iter().map(ProviderLogLine::line).collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "first");
+        assert_eq!(lines[1], "invalid \u{fffd} utf-8");
+        assert_eq!(lines[2].len(), MAX_LINE_LENGTH);
+        assert_eq!(lines[3], "a");
+        assert_eq!(lines[4], "last");
+    }
+
+    #[test]
+    fn test_parse_text_level() {
+        assert_eq!(
+            parse_text_level("2024-01-01T00:00:00.000000Z  WARN provider: message"),
+            Some(Level::WARN)
+        );
+        assert_eq!(
+            parse_text_level("ERROR something failed"),
+            Some(Level::ERROR)
+        );
+        assert_eq!(parse_text_level("listening on port 8080"), None);
+    }
+}
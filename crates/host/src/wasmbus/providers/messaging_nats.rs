@@ -127,6 +127,7 @@ async fn handle_message(
                     KeyValue::new("lattice", lattice_id),
                     KeyValue::new("host", host_id),
                 ],
+                deadline: None,
             },
             wrpc::wasmcloud::messaging0_2_0::types::BrokerMessage {
                 subject: msg.subject.into_string(),
@@ -2,13 +2,14 @@
 //!
 //! The root of this module includes functionality for running and managing provider binaries. The
 //! submodules contain builtin implementations of wasmCloud capabilities providers.
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::env;
 use std::path::{Path, PathBuf};
+use std::pin::pin;
 use std::process::Stdio;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::{bail, Context as _};
 use async_nats::Client;
@@ -19,26 +20,40 @@ use futures::{stream, Future, StreamExt};
 use nkeys::XKey;
 use tokio::io::AsyncWriteExt;
 use tokio::process;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, RwLock};
 use tokio::task::JoinSet;
-use tracing::{error, instrument, trace, warn};
+use tracing::{error, info, instrument, trace, warn};
 use uuid::Uuid;
 use wascap::jwt::{CapabilityProvider, Token};
+use wasmcloud_control_interface::ProviderLogStream;
 use wasmcloud_core::{
-    health_subject, provider_config_update_subject, HealthCheckResponse, HostData, OtelConfig,
+    health_subject, provider_config_update_subject, provider_secrets_update_subject,
+    HealthCheckResponse, HostData, OtelConfig,
 };
 use wasmcloud_runtime::capability::secrets::store::SecretValue;
 use wasmcloud_tracing::context::TraceContextInjector;
 
 use crate::event::EventPublisher;
 use crate::jwt;
+use crate::wasmbus::host_config::ProviderHealthCheck;
 use crate::wasmbus::injector_to_headers;
 use crate::wasmbus::{config::ConfigBundle, Annotations};
+use crate::workload_identity::{fetch_x509_svid_secrets, stream_x509_svid_secrets, Workload};
 
+pub(crate) use self::logs::ProviderLogBuffer;
+use self::restart::{restart_policy, Restarts};
+use self::sandbox::{sandbox_config, Sandbox};
 use super::Host;
 
+/// Delay before reopening the stream of X.509-SVIDs of a provider after it ended or failed
+const X509_SVID_RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+mod cron;
 mod http_server;
+mod logs;
 mod messaging_nats;
+mod restart;
+mod sandbox;
 
 /// A trait for sending and receiving messages to/from a provider
 #[async_trait::async_trait]
@@ -70,9 +85,20 @@ pub(crate) struct Provider {
     pub(crate) shutdown: Arc<AtomicBool>,
     /// Tasks running the provider, health check, and config watcher
     pub(crate) tasks: JoinSet<()>,
+    /// Most recent output of the provider process, empty for builtin providers
+    pub(crate) logs: Arc<ProviderLogBuffer>,
+    /// Set to `true` when the provider process is no longer restarted after exiting unsuccessfully,
+    /// e.g. because it is crash-looping
+    pub(crate) failed: Arc<AtomicBool>,
 }
 
 impl Host {
+    /// Whether capability providers receive the X.509-SVID issued for their own SPIFFE ID
+    fn provider_x509_svids_enabled(&self) -> bool {
+        self.experimental_features.workload_identity_interface
+            && self.host_config.provider_x509_svids
+    }
+
     /// Fetch configuration and secrets for a capability provider, forming the host configuration
     /// with links, config and secrets to pass to that provider. Also returns the config bundle
     /// which is used to watch for changes to the configuration, or can be discarded if
@@ -134,7 +160,7 @@ impl Host {
             .collect::<Vec<wasmcloud_core::InterfaceLinkDefinition>>()
             .await;
 
-        let secrets = {
+        let mut secrets: HashMap<_, _> = {
             // NOTE(brooksmtownsend): This trait import is used here to ensure we're only exposing secret
             // values when we need them.
             use secrecy::ExposeSecret;
@@ -152,6 +178,19 @@ impl Host {
                 })
                 .collect()
         };
+        if self.provider_x509_svids_enabled() {
+            match fetch_x509_svid_secrets(
+                Workload::Provider(provider_id),
+                &*self.labels.read().await,
+                &self.host_config.lattice,
+                self.host_config.workload_identity_trust_domain.as_deref(),
+            )
+            .await
+            {
+                Ok(svid_secrets) => secrets.extend(svid_secrets),
+                Err(err) => warn!(?err, provider_id, "failed to fetch X.509-SVID for provider"),
+            }
+        }
         let host_config = config.get_config().await.clone();
         let lattice_rpc_user_seed = self
             .host_config
@@ -229,9 +268,18 @@ impl Host {
         claims_token: Option<Token<CapabilityProvider>>,
         annotations: BTreeMap<String, String>,
         shutdown: Arc<AtomicBool>,
+        logs: Arc<ProviderLogBuffer>,
+        failed: Arc<AtomicBool>,
     ) -> anyhow::Result<JoinSet<()>> {
         trace!("spawn provider process");
 
+        let restarts = restart_policy(&self.host_config.provider_restart_policy, &annotations)
+            .map(Restarts::new)
+            .context("invalid provider restart policy")?;
+        // Failed health checks only cause the provider to be restarted if the restart policy allows it
+        let (unhealthy_tx, unhealthy_rx) = mpsc::channel(1);
+        let unhealthy_tx = restarts.should_restart(false).then_some(unhealthy_tx);
+
         let mut tasks = JoinSet::new();
 
         // Spawn a task to ensure the provider is restarted if it exits prematurely,
@@ -248,23 +296,30 @@ impl Host {
                     claims_token,
                     annotations,
                     shutdown.clone(),
+                    logs,
+                    failed,
+                    restarts,
+                    unhealthy_rx,
                 )
                 .await?,
         );
 
-        // Spawn a task to check the health of the provider every 30 seconds
+        // Spawn a task to periodically check the health of the provider
         tasks.spawn(check_health(
             Arc::clone(&self.rpc_nats),
             self.event_publisher.clone(),
             Arc::clone(&self.host_config.lattice),
             self.host_key.public_key(),
             provider_id.to_string(),
+            self.host_config.provider_health_check.clone(),
+            unhealthy_tx,
         ));
 
         Ok(tasks)
     }
 
-    /// Run and supervise a binary provider, restarting it if it exits prematurely.
+    /// Run and supervise a binary provider, restarting it according to its restart policy if it
+    /// exits prematurely or a restart is requested due to failed health checks.
     #[allow(clippy::too_many_arguments)]
     async fn run_provider(
         self: Arc<Self>,
@@ -277,13 +332,22 @@ impl Host {
         claims_token: Option<Token<CapabilityProvider>>,
         annotations: BTreeMap<String, String>,
         shutdown: Arc<AtomicBool>,
+        logs: Arc<ProviderLogBuffer>,
+        failed: Arc<AtomicBool>,
+        mut restarts: Restarts,
+        mut unhealthy: mpsc::Receiver<()>,
     ) -> anyhow::Result<impl Future<Output = ()>> {
         let host_data =
             serde_json::to_vec(&host_data).context("failed to serialize provider data")?;
+        let sandbox = sandbox_config(&self.host_config.provider_sandbox, &annotations)
+            .context("invalid provider sandbox configuration")?
+            .map(|config| Sandbox::new(config, &self.host_key.public_key(), &provider_id))
+            .transpose()
+            .context("failed to prepare provider sandbox")?;
 
         // If there's any issues starting the provider, we want to exit immediately
         let child = Arc::new(RwLock::new(
-            provider_command(&path, host_data)
+            provider_command(&path, host_data, sandbox.as_ref(), &provider_id, &logs)
                 .await
                 .context("failed to configure binary provider command")?,
         ));
@@ -299,9 +363,43 @@ impl Host {
                 Arc::clone(&lattice),
                 provider_id.clone(),
             ));
+            // The X.509-SVID watcher is not restarted along with the provider, since restarted
+            // providers are started with the current X.509-SVID
+            let mut svid_task = JoinSet::new();
+            if self.provider_x509_svids_enabled() {
+                match XKey::from_public_key(&provider_xkey.public_key()) {
+                    Ok(xkey) => {
+                        svid_task
+                            .spawn(Arc::clone(&self).watch_x509_svid(provider_id.clone(), xkey));
+                    }
+                    Err(err) => {
+                        error!(
+                            ?err,
+                            ?provider_id,
+                            "failed to create XKey from provider public key xkey"
+                        );
+                    }
+                }
+            }
             loop {
                 let mut child = child.write().await;
-                match child.wait().await {
+                let status = tokio::select! {
+                    status = child.wait() => Some(status),
+                    Some(()) = unhealthy.recv() => None,
+                };
+                let status = if let Some(status) = status {
+                    status
+                } else {
+                    warn!(
+                        path = ?path.display(),
+                        "stopping provider that failed health checks",
+                    );
+                    if let Err(err) = child.start_kill() {
+                        warn!(?err, path = ?path.display(), "failed to kill unhealthy provider");
+                    }
+                    child.wait().await
+                };
+                match status {
                     Ok(status) => {
                         // When the provider is shutting down, don't restart it
                         if shutdown.load(Ordering::Relaxed) {
@@ -315,11 +413,67 @@ impl Host {
                             continue;
                         }
 
+                        if !restarts.should_restart(status.success()) {
+                            if status.success() {
+                                info!(
+                                    path = ?path.display(),
+                                    "provider exited and will not be restarted due to its restart policy",
+                                );
+                            } else {
+                                error!(
+                                    path = ?path.display(),
+                                    status = ?status,
+                                    "provider failed and will not be restarted due to its restart policy",
+                                );
+                                failed.store(true, Ordering::Relaxed);
+                            }
+                            shutdown.store(true, Ordering::Relaxed);
+                            return;
+                        }
+
+                        let Some(backoff) = restarts.next_backoff(Instant::now()) else {
+                            let policy = restarts.policy();
+                            error!(
+                                path = ?path.display(),
+                                status = ?status,
+                                restarts = restarts.count(),
+                                window = ?policy.window,
+                                "provider is crash-looping and will not be restarted",
+                            );
+                            failed.store(true, Ordering::Relaxed);
+                            shutdown.store(true, Ordering::Relaxed);
+                            if let Err(e) = self
+                                .event_publisher
+                                .publish_event(
+                                    "provider_crashloop",
+                                    crate::event::provider_crashloop(
+                                        self.host_key.public_key(),
+                                        &provider_id,
+                                        restarts.count(),
+                                        policy.window,
+                                    ),
+                                )
+                                .await
+                            {
+                                warn!(
+                                    ?e,
+                                    ?provider_id,
+                                    "failed to publish provider crashloop event"
+                                );
+                            }
+                            return;
+                        };
+
                         warn!(
                             path = ?path.display(),
                             status = ?status,
+                            ?backoff,
                             "restarting provider that exited while being supervised",
                         );
+                        tokio::time::sleep(backoff).await;
+                        if shutdown.load(Ordering::Relaxed) {
+                            continue;
+                        }
 
                         let (host_data, new_config_bundle) = match self
                             .prepare_provider_config(
@@ -363,16 +517,22 @@ impl Host {
 
                         // Restart the provider by attempting to re-execute the binary with the same
                         // host data
-                        let Ok(child_cmd) = provider_command(&path, host_data).await else {
+                        let Ok(child_cmd) = provider_command(
+                            &path,
+                            host_data,
+                            sandbox.as_ref(),
+                            &provider_id,
+                            &logs,
+                        )
+                        .await
+                        else {
                             error!(path = ?path.display(), "failed to restart provider");
                             shutdown.store(true, Ordering::Relaxed);
                             return;
                         };
                         *child = child_cmd;
-
-                        // To avoid a tight loop, we wait 5 seconds after restarting. In the worst case,
-                        // the provider will continually execute and exit every 5 seconds.
-                        tokio::time::sleep(Duration::from_secs(5)).await;
+                        // Discard restart requests caused by health checks of the previous process
+                        while unhealthy.try_recv().is_ok() {}
                     }
                     Err(e) => {
                         error!(
@@ -388,12 +548,84 @@ impl Host {
             }
         })
     }
+
+    /// Watch for rotations of the X.509-SVID of a provider and send them to the provider
+    ///
+    /// The current X.509-SVID is sent as well, in case it was rotated after the provider was
+    /// started. The stream of X.509-SVIDs is reopened if it ends, e.g. when the SPIRE Agent restarts.
+    async fn watch_x509_svid(self: Arc<Self>, provider_id: String, provider_xkey: XKey) {
+        let subject = provider_secrets_update_subject(&self.host_config.lattice, &provider_id);
+        trace!(?provider_id, "starting X.509-SVID rotation listener");
+        loop {
+            match stream_x509_svid_secrets(
+                Workload::Provider(&provider_id),
+                &*self.labels.read().await,
+                &self.host_config.lattice,
+                self.host_config.workload_identity_trust_domain.as_deref(),
+            )
+            .await
+            {
+                Ok(svids) => {
+                    let mut svids = pin!(svids);
+                    while let Some(secrets) = svids.next().await {
+                        let secrets = match secrets {
+                            Ok(secrets) => secrets,
+                            Err(err) => {
+                                warn!(
+                                    ?err,
+                                    ?provider_id,
+                                    "failed to receive X.509-SVID for provider"
+                                );
+                                continue;
+                            }
+                        };
+                        let sealed = match serde_json::to_vec(&secrets)
+                            .context("failed to serialize secrets")
+                            .and_then(|secrets| {
+                                self.secrets_xkey
+                                    .seal(&secrets, &provider_xkey)
+                                    .context("failed to encrypt secrets")
+                            }) {
+                            Ok(sealed) => sealed,
+                            Err(err) => {
+                                error!(?err, ?provider_id, "failed to prepare X.509-SVID update");
+                                continue;
+                            }
+                        };
+                        trace!(?provider_id, subject, "publishing X.509-SVID update");
+                        if let Err(err) = self
+                            .rpc_nats
+                            .publish(subject.clone(), Bytes::from(sealed))
+                            .await
+                        {
+                            error!(%err, ?provider_id, "failed to publish X.509-SVID update to provider");
+                        }
+                    }
+                    warn!(?provider_id, "X.509-SVID stream ended, reconnecting");
+                }
+                Err(err) => {
+                    warn!(
+                        ?err,
+                        ?provider_id,
+                        "failed to stream X.509-SVIDs for provider"
+                    );
+                }
+            }
+            tokio::time::sleep(X509_SVID_RECONNECT_DELAY).await;
+        }
+    }
 }
 
-/// Using the provided path as the provider binary, start the provider process and
-/// pass the host data to it over stdin. Returns the child process handle which
-/// has already been spawned.
-async fn provider_command(path: &Path, host_data: Vec<u8>) -> anyhow::Result<process::Child> {
+/// Using the provided path as the provider binary, start the provider process, within the
+/// sandbox if one is given, and pass the host data to it over stdin. Output of the provider is
+/// captured into `logs`. Returns the child process handle which has already been spawned.
+async fn provider_command(
+    path: &Path,
+    host_data: Vec<u8>,
+    sandbox: Option<&Sandbox>,
+    provider_id: &str,
+    logs: &Arc<ProviderLogBuffer>,
+) -> anyhow::Result<process::Child> {
     let mut child_cmd = process::Command::new(path);
     // Prevent the provider from inheriting the host's environment, with the exception of
     // the following variables we manually add back
@@ -419,11 +651,37 @@ async fn provider_command(path: &Path, host_data: Vec<u8>) -> anyhow::Result<pro
         }
     }
 
+    if let Some(sandbox) = sandbox {
+        sandbox
+            .apply(&mut child_cmd)
+            .context("failed to sandbox provider process")?;
+    }
+
     let mut child = child_cmd
         .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
         .kill_on_drop(true)
         .spawn()
         .context("failed to spawn provider process")?;
+    // Capture tasks complete once the provider process exits and its output is closed
+    let provider_id: Arc<str> = Arc::from(provider_id);
+    if let Some(stdout) = child.stdout.take() {
+        tokio::spawn(logs::capture(
+            Arc::clone(&provider_id),
+            ProviderLogStream::Stdout,
+            stdout,
+            Arc::clone(logs),
+        ));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        tokio::spawn(logs::capture(
+            provider_id,
+            ProviderLogStream::Stderr,
+            stderr,
+            Arc::clone(logs),
+        ));
+    }
     let mut stdin = child.stdin.take().context("failed to take stdin")?;
     stdin
         .write_all(STANDARD.encode(host_data).as_bytes())
@@ -441,32 +699,36 @@ async fn provider_command(path: &Path, host_data: Vec<u8>) -> anyhow::Result<pro
 /// Watch for health check responses from the provider
 ///
 /// Returns a future that should be polled to continually check provider
-/// health every 30 seconds until the health receiver gets a message to stop
+/// health at the configured interval until the health receiver gets a message to stop.
+/// If `unhealthy` is set, a restart of the provider is requested on it after the configured
+/// number of consecutive failed health checks
 fn check_health(
     rpc_nats: Arc<Client>,
     event_publisher: Arc<dyn EventPublisher + Send + Sync>,
     lattice: Arc<str>,
     host_id: String,
     provider_id: String,
+    config: ProviderHealthCheck,
+    unhealthy: Option<mpsc::Sender<()>>,
 ) -> impl Future<Output = ()> {
     let health_subject = async_nats::Subject::from(health_subject(&lattice, &provider_id));
 
-    // Check the health of the provider every 30 seconds
-    let mut health_check = tokio::time::interval(Duration::from_secs(30));
+    let mut health_check = tokio::time::interval(config.interval);
     let mut previous_healthy = false;
+    let mut failures = 0u32;
     // Allow the provider 5 seconds to initialize
     health_check.reset_after(Duration::from_secs(5));
     async move {
         loop {
             let _ = health_check.tick().await;
             trace!(?provider_id, "performing provider health check");
-            let request =
-                async_nats::Request::new()
-                    .payload(Bytes::new())
-                    .headers(injector_to_headers(
-                        &TraceContextInjector::default_with_span(),
-                    ));
-            if let Ok(async_nats::Message { payload, .. }) =
+            let request = async_nats::Request::new()
+                .payload(Bytes::new())
+                .timeout(Some(config.timeout))
+                .headers(injector_to_headers(
+                    &TraceContextInjector::default_with_span(),
+                ));
+            let healthy = if let Ok(async_nats::Message { payload, .. }) =
                 rpc_nats.send_request(health_subject.clone(), request).await
             {
                 match (
@@ -489,6 +751,7 @@ fn check_health(
                                 "failed to publish provider health check succeeded event",
                             );
                         }
+                        true
                     }
                     (Ok(HealthCheckResponse { healthy: false, .. }), true) => {
                         trace!(?provider_id, "provider health check failed");
@@ -506,9 +769,10 @@ fn check_health(
                                 "failed to publish provider health check failed event",
                             );
                         }
+                        false
                     }
                     // If the provider health status didn't change, we simply publish a health check status event
-                    (Ok(_), _) => {
+                    (Ok(HealthCheckResponse { healthy, .. }), _) => {
                         if let Err(e) = event_publisher
                             .publish_event(
                                 "health_check_status",
@@ -522,17 +786,41 @@ fn check_health(
                                 "failed to publish provider health check status event",
                             );
                         }
+                        healthy
+                    }
+                    _ => {
+                        warn!(
+                            ?provider_id,
+                            "failed to deserialize provider health check response"
+                        );
+                        false
                     }
-                    _ => warn!(
-                        ?provider_id,
-                        "failed to deserialize provider health check response"
-                    ),
                 }
             } else {
                 warn!(
                     ?provider_id,
-                    "failed to request provider health, retrying in 30 seconds"
+                    interval = ?config.interval,
+                    "failed to request provider health, retrying after interval"
                 );
+                false
+            };
+            if healthy {
+                failures = 0;
+                continue;
+            }
+            failures = failures.saturating_add(1);
+            if let (Some(unhealthy), Some(restart_after)) = (&unhealthy, config.restart_after) {
+                if failures >= restart_after {
+                    warn!(
+                        ?provider_id,
+                        failures, "requesting restart of provider that failed health checks"
+                    );
+                    failures = 0;
+                    previous_healthy = false;
+                    let _ = unhealthy.try_send(());
+                    // Allow the restarted provider 5 seconds to initialize
+                    health_check.reset_after(Duration::from_secs(5));
+                }
             }
         }
     }
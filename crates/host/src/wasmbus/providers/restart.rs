@@ -0,0 +1,150 @@
+//! Restart policy of native capability provider processes supervised by the host
+
+use std::collections::{BTreeMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use anyhow::Context as _;
+
+use crate::wasmbus::host_config::{ProviderRestartMode, ProviderRestartPolicy};
+
+/// Annotation overriding the restart policy mode of a provider, one of `never`, `on-failure` or
+/// `always`
+const RESTART_POLICY_ANNOTATION: &str = "wasmcloud.dev/restart-policy";
+
+/// Determines the restart policy of a provider from the host configuration and the provider
+/// annotations
+pub(crate) fn restart_policy(
+    host: &ProviderRestartPolicy,
+    annotations: &BTreeMap<String, String>,
+) -> anyhow::Result<ProviderRestartPolicy> {
+    let mut policy = host.clone();
+    if let Some(mode) = annotations.get(RESTART_POLICY_ANNOTATION) {
+        policy.mode = mode.parse().with_context(|| {
+            format!("invalid `{RESTART_POLICY_ANNOTATION}` annotation value `{mode}`")
+        })?;
+    }
+    Ok(policy)
+}
+
+/// Restarts of a supervised provider, used to compute the backoff before restarting it and to
+/// detect crash loops
+#[derive(Debug)]
+pub(crate) struct Restarts {
+    policy: ProviderRestartPolicy,
+    restarts: VecDeque<Instant>,
+}
+
+impl Restarts {
+    pub(crate) fn new(policy: ProviderRestartPolicy) -> Self {
+        Self {
+            policy,
+            restarts: VecDeque::default(),
+        }
+    }
+
+    /// Returns the policy restarts are tracked for
+    pub(crate) fn policy(&self) -> &ProviderRestartPolicy {
+        &self.policy
+    }
+
+    /// Returns the number of restarts within the window of the policy, as of the last restart
+    pub(crate) fn count(&self) -> usize {
+        self.restarts.len()
+    }
+
+    /// Whether a provider, which exited successfully if `success` is set, should be restarted
+    pub(crate) fn should_restart(&self, success: bool) -> bool {
+        match self.policy.mode {
+            ProviderRestartMode::Never => false,
+            ProviderRestartMode::OnFailure => !success,
+            ProviderRestartMode::Always => true,
+        }
+    }
+
+    /// Records a restart at `now` and returns the delay before restarting the provider, or `None`
+    /// if the provider is crash-looping and should not be restarted
+    pub(crate) fn next_backoff(&mut self, now: Instant) -> Option<Duration> {
+        while self
+            .restarts
+            .front()
+            .is_some_and(|restart| now.saturating_duration_since(*restart) > self.policy.window)
+        {
+            self.restarts.pop_front();
+        }
+        let previous = self.restarts.len();
+        if self.policy.max_restarts > 0
+            && previous >= usize::try_from(self.policy.max_restarts).unwrap_or(usize::MAX)
+        {
+            return None;
+        }
+        self.restarts.push_back(now);
+        let backoff = u32::try_from(previous)
+            .ok()
+            .and_then(|previous| 2u32.checked_pow(previous))
+            .and_then(|factor| self.policy.initial_backoff.checked_mul(factor))
+            .unwrap_or(self.policy.max_backoff);
+        Some(backoff.min(self.policy.max_backoff))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_policy() {
+        let host = ProviderRestartPolicy::default();
+        let policy = restart_policy(&host, &BTreeMap::default()).expect("failed to get policy");
+        assert_eq!(policy, host);
+
+        let annotations = BTreeMap::from([(
+            RESTART_POLICY_ANNOTATION.to_string(),
+            "on-failure".to_string(),
+        )]);
+        let policy = restart_policy(&host, &annotations).expect("failed to get policy");
+        assert_eq!(policy.mode, ProviderRestartMode::OnFailure);
+
+        let annotations = BTreeMap::from([(
+            RESTART_POLICY_ANNOTATION.to_string(),
+            "sometimes".to_string(),
+        )]);
+        assert!(restart_policy(&host, &annotations).is_err());
+    }
+
+    #[test]
+    fn test_restarts_backoff() {
+        let mut restarts = Restarts::new(ProviderRestartPolicy {
+            mode: ProviderRestartMode::Always,
+            max_restarts: 4,
+            window: Duration::from_secs(60),
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(5),
+        });
+        let start = Instant::now();
+        assert_eq!(restarts.next_backoff(start), Some(Duration::from_secs(1)));
+        assert_eq!(restarts.next_backoff(start), Some(Duration::from_secs(2)));
+        assert_eq!(restarts.next_backoff(start), Some(Duration::from_secs(4)));
+        assert_eq!(restarts.next_backoff(start), Some(Duration::from_secs(5)));
+        assert_eq!(restarts.next_backoff(start), None);
+        assert_eq!(restarts.count(), 4);
+
+        // Restarts outside of the window are forgotten
+        let later = start + Duration::from_secs(61);
+        assert_eq!(restarts.next_backoff(later), Some(Duration::from_secs(1)));
+        assert_eq!(restarts.count(), 1);
+    }
+
+    #[test]
+    fn test_restarts_mode() {
+        let mut policy = ProviderRestartPolicy::default();
+        assert!(Restarts::new(policy.clone()).should_restart(true));
+
+        policy.mode = ProviderRestartMode::OnFailure;
+        let restarts = Restarts::new(policy.clone());
+        assert!(!restarts.should_restart(true));
+        assert!(restarts.should_restart(false));
+
+        policy.mode = ProviderRestartMode::Never;
+        assert!(!Restarts::new(policy).should_restart(false));
+    }
+}
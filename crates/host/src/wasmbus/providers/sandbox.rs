@@ -0,0 +1,1125 @@
+//! Opt-in isolation of native capability provider processes.
+//!
+//! Sandboxed providers are spawned in a new user and mount namespace, in which they run as an
+//! unprivileged user mapped to a subordinate ID range of the host, or to the unprivileged user the
+//! host runs them as. All mounts are remounted read-only and a private `tmpfs` is mounted at
+//! `/tmp`. Optionally, a seccomp syscall filter is applied,
+//! providers join a preconfigured network namespace, and memory and CPU usage are limited using a
+//! cgroup v2 created for each provider.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::{bail, ensure, Context as _};
+
+use crate::wasmbus::host_config::{ProviderSandbox, SeccompProfile};
+
+/// Annotation opting a provider in to or out of sandboxing, either `true` or `false`. Providers
+/// cannot opt out if sandboxing is enabled for the host
+const SANDBOX_ANNOTATION: &str = "wasmcloud.dev/sandbox";
+
+/// Annotation setting the seccomp profile of a sandboxed provider, either `default` or `unconfined`
+const SANDBOX_SECCOMP_ANNOTATION: &str = "wasmcloud.dev/sandbox-seccomp";
+
+/// Annotation setting the path of a network namespace to run a sandboxed provider in
+const SANDBOX_NETWORK_NAMESPACE_ANNOTATION: &str = "wasmcloud.dev/sandbox-network-namespace";
+
+/// Annotation setting the maximum amount of memory in bytes a sandboxed provider may use
+const SANDBOX_MAX_MEMORY_ANNOTATION: &str = "wasmcloud.dev/sandbox-max-memory";
+
+/// Annotation setting the maximum amount of CPU, in thousandths of a CPU, a sandboxed provider may use
+const SANDBOX_MAX_CPU_ANNOTATION: &str = "wasmcloud.dev/sandbox-max-cpu-millis";
+
+/// Determines the sandbox of a provider from the host configuration and the provider annotations.
+///
+/// Annotations may only tighten the sandbox configured for the host. Returns `None` if the
+/// provider is not sandboxed.
+pub(crate) fn sandbox_config(
+    host: &ProviderSandbox,
+    annotations: &BTreeMap<String, String>,
+) -> anyhow::Result<Option<ProviderSandbox>> {
+    let enabled = match annotations.get(SANDBOX_ANNOTATION).map(String::as_str) {
+        None => host.enabled,
+        Some("true") => true,
+        Some("false") => {
+            ensure!(!host.enabled, "providers must be sandboxed on this host");
+            false
+        }
+        Some(enabled) => bail!("invalid `{SANDBOX_ANNOTATION}` annotation value `{enabled}`"),
+    };
+    if !enabled {
+        return Ok(None);
+    }
+    let mut config = host.clone();
+    if let Some(seccomp) = annotations.get(SANDBOX_SECCOMP_ANNOTATION) {
+        let seccomp = seccomp.parse().with_context(|| {
+            format!("invalid `{SANDBOX_SECCOMP_ANNOTATION}` annotation value `{seccomp}`")
+        })?;
+        ensure!(
+            seccomp == SeccompProfile::Default || host.seccomp == SeccompProfile::Unconfined,
+            "providers must use the default seccomp profile on this host"
+        );
+        config.seccomp = seccomp;
+    }
+    if let Some(netns) = annotations.get(SANDBOX_NETWORK_NAMESPACE_ANNOTATION) {
+        let netns = PathBuf::from(netns);
+        if let Some(host_netns) = &host.network_namespace {
+            ensure!(
+                *host_netns == netns,
+                "providers must run in network namespace `{}` on this host",
+                host_netns.display()
+            );
+        }
+        config.network_namespace = Some(netns);
+    }
+    if let Some(max_memory) = annotations.get(SANDBOX_MAX_MEMORY_ANNOTATION) {
+        let max_memory = max_memory.parse().with_context(|| {
+            format!("invalid `{SANDBOX_MAX_MEMORY_ANNOTATION}` annotation value `{max_memory}`")
+        })?;
+        config.max_memory = Some(
+            host.max_memory
+                .map_or(max_memory, |max| max.min(max_memory)),
+        );
+    }
+    if let Some(max_cpu) = annotations.get(SANDBOX_MAX_CPU_ANNOTATION) {
+        let max_cpu = max_cpu.parse().with_context(|| {
+            format!("invalid `{SANDBOX_MAX_CPU_ANNOTATION}` annotation value `{max_cpu}`")
+        })?;
+        config.max_cpu_millis = Some(host.max_cpu_millis.map_or(max_cpu, |max| max.min(max_cpu)));
+    }
+    ensure!(
+        config.max_memory != Some(0) && config.max_cpu_millis != Some(0),
+        "sandbox limits must be greater than 0"
+    );
+    Ok(Some(config))
+}
+
+/// Sandbox of a provider, shared by all processes spawned for it. The cgroup of the provider, if
+/// any, is removed on drop.
+#[derive(Debug)]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub(crate) struct Sandbox {
+    config: ProviderSandbox,
+    cgroup: Option<PathBuf>,
+    #[cfg(target_os = "linux")]
+    ids: linux::IdMapping,
+}
+
+#[cfg(not(target_os = "linux"))]
+impl Sandbox {
+    pub(crate) fn new(
+        _config: ProviderSandbox,
+        _host_id: &str,
+        _provider_id: &str,
+    ) -> anyhow::Result<Self> {
+        bail!("provider sandboxing is only supported on Linux")
+    }
+
+    pub(crate) fn apply(&self, _cmd: &mut tokio::process::Command) -> anyhow::Result<()> {
+        bail!("provider sandboxing is only supported on Linux")
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use core::ffi::{c_char, c_int, CStr};
+    use core::mem::offset_of;
+    use core::ptr;
+
+    use std::env;
+    use std::ffi::CString;
+    use std::io;
+    use std::os::unix::ffi::OsStrExt as _;
+    use std::path::{Path, PathBuf};
+
+    use anyhow::{bail, ensure, Context as _};
+    use nix::libc;
+    use nix::mount::{mount, MsFlags};
+    use nix::sched::{unshare, CloneFlags};
+    use nix::unistd::{chdir, geteuid, getgid, getuid, setgid, setgroups, setuid, Gid, Uid, User};
+    use tokio::process;
+    use tracing::{debug, warn};
+
+    use super::Sandbox;
+    use crate::wasmbus::host_config::{IdRange, ProviderSandbox, SeccompProfile};
+
+    /// User and group ID sandboxed providers run as within their user namespace
+    const SANDBOX_ID: u32 = 65534;
+
+    /// Namespace flags of `clone`, which are denied by [`SeccompProfile::Default`]
+    const CLONE_NAMESPACE_FLAGS: u32 = (libc::CLONE_NEWNS
+        | libc::CLONE_NEWCGROUP
+        | libc::CLONE_NEWUTS
+        | libc::CLONE_NEWIPC
+        | libc::CLONE_NEWUSER
+        | libc::CLONE_NEWPID
+        | libc::CLONE_NEWNET) as u32;
+
+    /// `BPF_LD | BPF_W | BPF_ABS`
+    const BPF_LD_W_ABS: u16 = 0x20;
+    /// `BPF_JMP | BPF_JEQ | BPF_K`
+    const BPF_JMP_JEQ_K: u16 = 0x15;
+    /// `BPF_JMP | BPF_JGE | BPF_K`
+    #[cfg(target_arch = "x86_64")]
+    const BPF_JMP_JGE_K: u16 = 0x35;
+    /// `BPF_JMP | BPF_JSET | BPF_K`
+    const BPF_JMP_JSET_K: u16 = 0x45;
+    /// `BPF_RET | BPF_K`
+    const BPF_RET_K: u16 = 0x06;
+
+    const SECCOMP_RET_KILL_PROCESS: u32 = 0x8000_0000;
+    const SECCOMP_RET_ERRNO: u32 = 0x0005_0000;
+    const SECCOMP_RET_ALLOW: u32 = 0x7fff_0000;
+
+    #[cfg(target_arch = "x86_64")]
+    const AUDIT_ARCH: u32 = 0xc000_003e;
+    #[cfg(target_arch = "aarch64")]
+    const AUDIT_ARCH: u32 = 0xc000_00b7;
+
+    /// Syscalls denied by [`SeccompProfile::Default`], which allow to escape the sandbox,
+    /// inspect other processes or affect the host system. `clone` is only denied if it creates new
+    /// namespaces and `clone3` is reported to be unavailable
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    const DENIED_SYSCALLS: &[libc::c_long] = &[
+        libc::SYS_acct,
+        libc::SYS_add_key,
+        libc::SYS_adjtimex,
+        libc::SYS_bpf,
+        libc::SYS_clock_adjtime,
+        libc::SYS_clock_settime,
+        libc::SYS_delete_module,
+        libc::SYS_finit_module,
+        libc::SYS_init_module,
+        libc::SYS_kexec_load,
+        libc::SYS_keyctl,
+        libc::SYS_mount,
+        libc::SYS_name_to_handle_at,
+        libc::SYS_open_by_handle_at,
+        libc::SYS_perf_event_open,
+        libc::SYS_pivot_root,
+        libc::SYS_process_vm_readv,
+        libc::SYS_process_vm_writev,
+        libc::SYS_ptrace,
+        libc::SYS_quotactl,
+        libc::SYS_reboot,
+        libc::SYS_request_key,
+        libc::SYS_setdomainname,
+        libc::SYS_sethostname,
+        libc::SYS_setns,
+        libc::SYS_settimeofday,
+        libc::SYS_swapoff,
+        libc::SYS_swapon,
+        libc::SYS_syslog,
+        libc::SYS_umount2,
+        libc::SYS_unshare,
+        libc::SYS_userfaultfd,
+    ];
+
+    fn bpf_stmt(code: u16, k: u32) -> libc::sock_filter {
+        libc::sock_filter {
+            code,
+            jt: 0,
+            jf: 0,
+            k,
+        }
+    }
+
+    fn bpf_jump(code: u16, k: u32, jt: u8, jf: u8) -> libc::sock_filter {
+        libc::sock_filter { code, jt, jf, k }
+    }
+
+    /// Builds the BPF program of [`SeccompProfile::Default`]
+    #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+    pub(super) fn seccomp_filter() -> anyhow::Result<Vec<libc::sock_filter>> {
+        let errno = SECCOMP_RET_ERRNO | libc::EPERM as u32;
+        let mut filter = vec![
+            // Kill processes using a syscall ABI other than the native one
+            bpf_stmt(BPF_LD_W_ABS, offset_of!(libc::seccomp_data, arch) as u32),
+            bpf_jump(BPF_JMP_JEQ_K, AUDIT_ARCH, 1, 0),
+            bpf_stmt(BPF_RET_K, SECCOMP_RET_KILL_PROCESS),
+            bpf_stmt(BPF_LD_W_ABS, offset_of!(libc::seccomp_data, nr) as u32),
+        ];
+        // Deny x32 syscalls, which share the x86_64 audit architecture
+        #[cfg(target_arch = "x86_64")]
+        filter.extend([
+            bpf_jump(BPF_JMP_JGE_K, 0x4000_0000, 0, 1),
+            bpf_stmt(BPF_RET_K, errno),
+        ]);
+        for nr in DENIED_SYSCALLS {
+            filter.extend([
+                bpf_jump(BPF_JMP_JEQ_K, *nr as u32, 0, 1),
+                bpf_stmt(BPF_RET_K, errno),
+            ]);
+        }
+        filter.extend([
+            // The flags of `clone3` are passed in memory, which cannot be inspected. Report it to
+            // be unavailable, such that the C library falls back to `clone`
+            bpf_jump(BPF_JMP_JEQ_K, libc::SYS_clone3 as u32, 0, 1),
+            bpf_stmt(BPF_RET_K, SECCOMP_RET_ERRNO | libc::ENOSYS as u32),
+            // Deny `clone` creating new namespaces, the flags are the lower half of the first argument
+            bpf_jump(BPF_JMP_JEQ_K, libc::SYS_clone as u32, 0, 3),
+            bpf_stmt(BPF_LD_W_ABS, offset_of!(libc::seccomp_data, args) as u32),
+            bpf_jump(BPF_JMP_JSET_K, CLONE_NAMESPACE_FLAGS, 0, 1),
+            bpf_stmt(BPF_RET_K, errno),
+        ]);
+        filter.push(bpf_stmt(BPF_RET_K, SECCOMP_RET_ALLOW));
+        Ok(filter)
+    }
+
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    pub(super) fn seccomp_filter() -> anyhow::Result<Vec<libc::sock_filter>> {
+        anyhow::bail!("the default seccomp profile is not supported on this architecture")
+    }
+
+    /// How host user and group IDs are mapped into the user namespace of sandboxed providers
+    #[derive(Debug)]
+    pub(super) enum IdMapping {
+        /// The unprivileged user and group providers are run as are mapped to [`SANDBOX_ID`]. The
+        /// ID maps are written by the provider process itself
+        Own { uid: u32, gid: u32 },
+        /// Subordinate ID ranges are mapped, in which providers switch to [`SANDBOX_ID`]. The ID
+        /// maps are written by a helper process, which retains the privileges of the host
+        Subordinate {
+            uid: IdRange,
+            gid: IdRange,
+            /// Paths of `newuidmap` and `newgidmap`, used to write the ID maps if the host is
+            /// unprivileged
+            newidmap: Option<[PathBuf; 2]>,
+        },
+    }
+
+    impl IdMapping {
+        /// Determines the ID mapping of providers sandboxed using `config`. Refuses to map the
+        /// root user of the host into the sandbox
+        pub(super) fn new(config: &ProviderSandbox) -> anyhow::Result<Self> {
+            let uid = config.uid.unwrap_or_else(|| getuid().as_raw());
+            let gid = config.gid.unwrap_or_else(|| getgid().as_raw());
+            let user = User::from_uid(Uid::from_raw(uid)).ok().flatten();
+            let name = user.as_ref().map(|user| user.name.as_str());
+            let uid_range = config
+                .uid_range
+                .or_else(|| read_subordinate_range("/etc/subuid", name, uid));
+            let gid_range = config
+                .gid_range
+                .or_else(|| read_subordinate_range("/etc/subgid", name, uid));
+            match (uid_range, gid_range) {
+                (Some(uid), Some(gid)) => {
+                    ensure!(
+                        uid.count > SANDBOX_ID && gid.count > SANDBOX_ID,
+                        "subordinate ID ranges of sandboxed providers must contain more than {SANDBOX_ID} IDs"
+                    );
+                    let newidmap = if geteuid().is_root() {
+                        None
+                    } else {
+                        Some([find_program("newuidmap")?, find_program("newgidmap")?])
+                    };
+                    Ok(Self::Subordinate { uid, gid, newidmap })
+                }
+                (None, None) => {
+                    ensure!(
+                        uid != 0 && gid != 0,
+                        "sandboxed providers must not run as root, configure subordinate ID ranges in `/etc/subuid` and `/etc/subgid` or a sandbox user and group"
+                    );
+                    Ok(Self::Own { uid, gid })
+                }
+                _ => bail!(
+                    "both a subordinate user and group ID range must be configured for sandboxed providers"
+                ),
+            }
+        }
+    }
+
+    /// Finds the subordinate ID range assigned to the user with `name` or `id` in the contents of
+    /// `/etc/subuid` or `/etc/subgid`
+    pub(super) fn subordinate_range(
+        contents: &str,
+        name: Option<&str>,
+        id: u32,
+    ) -> Option<IdRange> {
+        let id = id.to_string();
+        contents.lines().find_map(|line| {
+            let mut fields = line.trim().split(':');
+            let owner = fields.next()?;
+            if owner != id && Some(owner) != name {
+                return None;
+            }
+            Some(IdRange {
+                start: fields.next()?.parse().ok()?,
+                count: fields.next()?.parse().ok()?,
+            })
+        })
+    }
+
+    fn read_subordinate_range(path: &str, name: Option<&str>, id: u32) -> Option<IdRange> {
+        let contents = std::fs::read_to_string(path).ok()?;
+        subordinate_range(&contents, name, id)
+    }
+
+    fn find_program(name: &str) -> anyhow::Result<PathBuf> {
+        env::split_paths(&env::var_os("PATH").unwrap_or_default())
+            .map(|dir| dir.join(name))
+            .find(|path| path.is_file())
+            .with_context(|| {
+                format!("`{name}` is required to map subordinate IDs into the sandbox of providers")
+            })
+    }
+
+    /// Parses the mount points, which are remounted read-only, along with their flags, which must
+    /// be preserved when remounting, from the contents of `/proc/self/mountinfo`
+    pub(super) fn read_only_remounts(mountinfo: &str) -> Vec<(String, MsFlags)> {
+        let mut remounts: Vec<(String, MsFlags)> = Vec::new();
+        for line in mountinfo.lines() {
+            let mut fields = line.split(' ').skip(4);
+            let (Some(mount_point), Some(options)) = (fields.next(), fields.next()) else {
+                continue;
+            };
+            let mount_point = unescape_mount_point(mount_point);
+            let flags = options
+                .split(',')
+                .fold(MsFlags::empty(), |flags, option| match option {
+                    "nosuid" => flags | MsFlags::MS_NOSUID,
+                    "nodev" => flags | MsFlags::MS_NODEV,
+                    "noexec" => flags | MsFlags::MS_NOEXEC,
+                    "noatime" => flags | MsFlags::MS_NOATIME,
+                    "nodiratime" => flags | MsFlags::MS_NODIRATIME,
+                    "relatime" => flags | MsFlags::MS_RELATIME,
+                    _ => flags,
+                });
+            // Only the topmost mount at a mount point can be remounted
+            remounts.retain(|(other, _)| *other != mount_point);
+            remounts.push((mount_point, flags));
+        }
+        remounts
+    }
+
+    /// Unescapes the octal escape sequences (e.g. `\040` for a space) in a mount point
+    fn unescape_mount_point(s: &str) -> String {
+        let mut out = Vec::with_capacity(s.len());
+        let mut bytes = s.as_bytes();
+        while let Some((&c, rest)) = bytes.split_first() {
+            let escaped = rest
+                .get(..3)
+                .filter(|_| c == b'\\')
+                .and_then(|escape| core::str::from_utf8(escape).ok())
+                .and_then(|escape| u8::from_str_radix(escape, 8).ok());
+            if let Some(escaped) = escaped {
+                out.push(escaped);
+                bytes = &rest[3..];
+            } else {
+                out.push(c);
+                bytes = rest;
+            }
+        }
+        String::from_utf8_lossy(&out).into_owned()
+    }
+
+    /// Returns the name of a new cgroup for the provider with `provider_id` on the host with
+    /// `host_id`. Names are unique, even if provider IDs only differ in characters not allowed in
+    /// cgroup names or the same provider is sandboxed again before its previous cgroup is removed.
+    pub(super) fn cgroup_name(host_id: &str, provider_id: &str) -> String {
+        let provider_id: String = provider_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .take(64)
+            .collect();
+        format!("{host_id}-{provider_id}-{}", uuid::Uuid::new_v4())
+    }
+
+    fn path_cstring(path: &Path) -> anyhow::Result<CString> {
+        CString::new(path.as_os_str().as_bytes())
+            .with_context(|| format!("invalid path `{}`", path.display()))
+    }
+
+    /// Writes `contents` to the file at `path`. Does not allocate, as required after `fork`
+    fn write_file(path: &CStr, contents: &[u8]) -> io::Result<()> {
+        write_file_at(libc::AT_FDCWD, path, contents)
+    }
+
+    /// Writes `contents` to the file at `path` relative to the directory `dir`. Does not allocate
+    fn write_file_at(dir: c_int, path: &CStr, contents: &[u8]) -> io::Result<()> {
+        // SAFETY: `path` is a valid NUL-terminated string
+        let fd = unsafe { libc::openat(dir, path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: `fd` is a valid file descriptor and `contents` is valid for `contents.len()` bytes
+        let n = unsafe { libc::write(fd, contents.as_ptr().cast(), contents.len()) };
+        let err = io::Error::last_os_error();
+        // SAFETY: `fd` is a valid file descriptor, which is not used afterwards
+        unsafe { libc::close(fd) };
+        match usize::try_from(n) {
+            Ok(n) if n == contents.len() => Ok(()),
+            Ok(..) => Err(io::ErrorKind::WriteZero.into()),
+            Err(..) => Err(err),
+        }
+    }
+
+    /// Formats `n` as a NUL-terminated decimal number into `buf`. Does not allocate
+    pub(super) fn format_decimal(mut n: u32, buf: &mut [u8; 11]) -> &CStr {
+        let mut i = buf.len() - 1;
+        buf[i] = 0;
+        loop {
+            i -= 1;
+            buf[i] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        // SAFETY: `buf[i..]` contains digits followed by a single NUL byte
+        unsafe { CStr::from_bytes_with_nul_unchecked(&buf[i..]) }
+    }
+
+    /// Invocation of `newuidmap` or `newgidmap`, without the leading PID argument
+    struct NewIdMap {
+        program: CString,
+        args: [CString; 3],
+    }
+
+    impl NewIdMap {
+        fn new(program: &Path, range: IdRange) -> anyhow::Result<Self> {
+            Ok(Self {
+                program: path_cstring(program)?,
+                args: [
+                    c"0".into(),
+                    CString::new(range.start.to_string())?,
+                    CString::new(range.count.to_string())?,
+                ],
+            })
+        }
+
+        /// Maps the IDs of process `pid` and waits for the program to exit. Does not allocate
+        fn run(&self, pid: &CStr) -> io::Result<()> {
+            let argv: [*const c_char; 6] = [
+                self.program.as_ptr(),
+                pid.as_ptr(),
+                self.args[0].as_ptr(),
+                self.args[1].as_ptr(),
+                self.args[2].as_ptr(),
+                ptr::null(),
+            ];
+            // SAFETY: the child only executes the program or exits
+            match unsafe { libc::fork() } {
+                -1 => Err(io::Error::last_os_error()),
+                0 => {
+                    // SAFETY: `argv` is a NULL-terminated array of valid NUL-terminated strings
+                    unsafe {
+                        libc::execv(self.program.as_ptr(), argv.as_ptr());
+                        libc::_exit(127)
+                    }
+                }
+                child => wait_success(child),
+            }
+        }
+    }
+
+    /// Waits for the child process `pid` to exit, failing unless it exited successfully
+    fn wait_success(pid: libc::pid_t) -> io::Result<()> {
+        let mut status = 0;
+        // SAFETY: `status` is valid for writes
+        if unsafe { libc::waitpid(pid, &mut status, 0) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::from_raw_os_error(libc::EPERM))
+        }
+    }
+
+    /// Helper process writing the ID maps of a sandboxed provider, which requires `CAP_SETUID` and
+    /// `CAP_SETGID` outside of its user namespace to map subordinate IDs. It is forked before the
+    /// provider drops the privileges of the host and creates its user namespace.
+    struct IdHelper {
+        pid: libc::pid_t,
+        /// Write end of the pipe used to notify the helper once the user namespace exists
+        ready: c_int,
+    }
+
+    impl IdHelper {
+        /// Forks the helper writing the ID maps of the calling process. Does not allocate
+        fn fork(spawn: &Spawn) -> io::Result<Self> {
+            let mut fds = [0; 2];
+            // SAFETY: `fds` is valid for writes of two file descriptors
+            if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let [ready_rx, ready_tx] = fds;
+            // `/proc/self` refers to the calling process, even when opened by the helper
+            // SAFETY: the path is a valid NUL-terminated string
+            let proc_dir = unsafe {
+                libc::open(
+                    c"/proc/self".as_ptr(),
+                    libc::O_RDONLY | libc::O_DIRECTORY | libc::O_CLOEXEC,
+                )
+            };
+            if proc_dir < 0 {
+                let err = io::Error::last_os_error();
+                // SAFETY: both file descriptors are valid and not used afterwards
+                unsafe {
+                    libc::close(ready_rx);
+                    libc::close(ready_tx);
+                }
+                return Err(err);
+            }
+            // SAFETY: `getpid` is always safe to call
+            let target = unsafe { libc::getpid() };
+            // SAFETY: the helper only performs syscalls and exits
+            match unsafe { libc::fork() } {
+                -1 => {
+                    let err = io::Error::last_os_error();
+                    // SAFETY: all file descriptors are valid and not used afterwards
+                    unsafe {
+                        libc::close(ready_rx);
+                        libc::close(ready_tx);
+                        libc::close(proc_dir);
+                    }
+                    Err(err)
+                }
+                0 => {
+                    // SAFETY: `ready_tx` is a valid file descriptor, which is not used afterwards
+                    unsafe { libc::close(ready_tx) };
+                    let res = Self::map_ids(spawn, proc_dir, ready_rx, target);
+                    // SAFETY: `_exit` is always safe to call
+                    unsafe { libc::_exit(if res.is_ok() { 0 } else { 1 }) }
+                }
+                pid => {
+                    // SAFETY: both file descriptors are valid and not used afterwards
+                    unsafe {
+                        libc::close(ready_rx);
+                        libc::close(proc_dir);
+                    }
+                    Ok(Self {
+                        pid,
+                        ready: ready_tx,
+                    })
+                }
+            }
+        }
+
+        /// Waits for the process `target` to create its user namespace and writes its ID maps.
+        /// Called in the helper
+        fn map_ids(
+            spawn: &Spawn,
+            proc_dir: c_int,
+            ready: c_int,
+            target: libc::pid_t,
+        ) -> io::Result<()> {
+            let mut buf = [0u8; 1];
+            // SAFETY: `ready` is a valid file descriptor and `buf` is valid for writes of one byte
+            if unsafe { libc::read(ready, buf.as_mut_ptr().cast(), 1) } != 1 {
+                // The process failed before creating its user namespace
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+            if let Some([newuidmap, newgidmap]) = &spawn.newidmap {
+                let mut pid = [0; 11];
+                let pid = format_decimal(target.unsigned_abs(), &mut pid);
+                newuidmap.run(pid)?;
+                newgidmap.run(pid)
+            } else {
+                write_file_at(proc_dir, c"uid_map", &spawn.uid_map)?;
+                write_file_at(proc_dir, c"gid_map", &spawn.gid_map)
+            }
+        }
+
+        /// Notifies the helper, that the user namespace of the calling process exists, and
+        /// waits for it to write the ID maps. Does not allocate
+        fn finish(self) -> io::Result<()> {
+            // SAFETY: `ready` is a valid file descriptor and the buffer is valid for one byte
+            let n = unsafe { libc::write(self.ready, [0u8].as_ptr().cast(), 1) };
+            let err = io::Error::last_os_error();
+            // SAFETY: `ready` is a valid file descriptor, which is not used afterwards
+            unsafe { libc::close(self.ready) };
+            if n != 1 {
+                return Err(err);
+            }
+            wait_success(self.pid)
+        }
+    }
+
+    /// State computed ahead of spawning a sandboxed provider, such that entering the sandbox in
+    /// the forked child does not need to allocate
+    struct Spawn {
+        cgroup_procs: Option<CString>,
+        network_namespace: Option<CString>,
+        uid: Option<Uid>,
+        gid: Option<Gid>,
+        uid_map: Vec<u8>,
+        gid_map: Vec<u8>,
+        /// Whether the ID maps are written by an [`IdHelper`], which is required to map
+        /// subordinate IDs
+        id_helper: bool,
+        /// `newuidmap` and `newgidmap` invocations used by the [`IdHelper`], if the host is unprivileged
+        newidmap: Option<[NewIdMap; 2]>,
+        remounts: Vec<(CString, MsFlags)>,
+        scratch_options: CString,
+        seccomp: Option<Vec<libc::sock_filter>>,
+    }
+
+    impl Spawn {
+        /// Enters the sandbox. Called in the forked child, right before executing the provider
+        fn enter(&self) -> io::Result<()> {
+            // Fork the helper writing the ID maps while the host privileges are still held
+            let id_helper = if self.id_helper {
+                Some(IdHelper::fork(self)?)
+            } else {
+                None
+            };
+            // Join the cgroup and network namespace while the host privileges are still held
+            if let Some(cgroup_procs) = &self.cgroup_procs {
+                write_file(cgroup_procs, b"0")?;
+            }
+            if let Some(network_namespace) = &self.network_namespace {
+                // SAFETY: `network_namespace` is a valid NUL-terminated string
+                let fd = unsafe {
+                    libc::open(network_namespace.as_ptr(), libc::O_RDONLY | libc::O_CLOEXEC)
+                };
+                if fd < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                // SAFETY: `fd` is a valid file descriptor
+                let res = unsafe { libc::setns(fd, libc::CLONE_NEWNET) };
+                let err = io::Error::last_os_error();
+                // SAFETY: `fd` is a valid file descriptor, which is not used afterwards
+                unsafe { libc::close(fd) };
+                if res < 0 {
+                    return Err(err);
+                }
+            }
+            if let Some(gid) = self.gid {
+                setgroups(&[])?;
+                setgid(gid)?;
+            }
+            if let Some(uid) = self.uid {
+                setuid(uid)?;
+            }
+
+            unshare(CloneFlags::CLONE_NEWUSER | CloneFlags::CLONE_NEWNS)?;
+            if let Some(id_helper) = id_helper {
+                id_helper.finish()?;
+            } else {
+                write_file(c"/proc/self/setgroups", b"deny")?;
+                write_file(c"/proc/self/uid_map", &self.uid_map)?;
+                write_file(c"/proc/self/gid_map", &self.gid_map)?;
+            }
+
+            // Capabilities within the new user namespace are held until the provider is executed
+            mount(
+                None::<&CStr>,
+                c"/",
+                None::<&CStr>,
+                MsFlags::MS_REC | MsFlags::MS_PRIVATE,
+                None::<&CStr>,
+            )?;
+            for (mount_point, flags) in &self.remounts {
+                mount(
+                    None::<&CStr>,
+                    mount_point.as_c_str(),
+                    None::<&CStr>,
+                    MsFlags::MS_BIND | MsFlags::MS_REMOUNT | MsFlags::MS_RDONLY | *flags,
+                    None::<&CStr>,
+                )?;
+            }
+            mount(
+                Some(c"tmpfs"),
+                c"/tmp",
+                Some(c"tmpfs"),
+                MsFlags::MS_NOSUID | MsFlags::MS_NODEV,
+                Some(self.scratch_options.as_c_str()),
+            )?;
+            chdir(c"/tmp")?;
+
+            if self.id_helper {
+                // Switch to the unprivileged user within the subordinate ID ranges, the
+                // capabilities within the user namespace are dropped once the provider is executed
+                setgroups(&[])?;
+                setgid(Gid::from_raw(SANDBOX_ID))?;
+                setuid(Uid::from_raw(SANDBOX_ID))?;
+            }
+
+            if let Some(filter) = &self.seccomp {
+                let prog = libc::sock_fprog {
+                    len: filter.len() as libc::c_ushort,
+                    filter: filter.as_ptr().cast_mut(),
+                };
+                // SAFETY: arguments are valid for `PR_SET_NO_NEW_PRIVS`
+                if unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) } < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                // SAFETY: `prog` points to a valid BPF program, which outlives the call
+                if unsafe {
+                    libc::prctl(
+                        libc::PR_SET_SECCOMP,
+                        libc::SECCOMP_MODE_FILTER,
+                        &prog as *const libc::sock_fprog,
+                    )
+                } < 0
+                {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+            Ok(())
+        }
+    }
+
+    impl Sandbox {
+        /// Prepares the sandbox for the provider with `provider_id` on the host with `host_id`,
+        /// creating its cgroup if memory or CPU limits are set
+        pub(crate) fn new(
+            config: ProviderSandbox,
+            host_id: &str,
+            provider_id: &str,
+        ) -> anyhow::Result<Self> {
+            if config.seccomp == SeccompProfile::Default {
+                // Ensure the profile is supported, before a provider is spawned
+                seccomp_filter()?;
+            }
+            let ids = IdMapping::new(&config)?;
+            if config.max_memory.is_none() && config.max_cpu_millis.is_none() {
+                return Ok(Self {
+                    config,
+                    cgroup: None,
+                    ids,
+                });
+            }
+            std::fs::create_dir_all(&config.cgroup_root).with_context(|| {
+                format!(
+                    "failed to create cgroup root `{}`",
+                    config.cgroup_root.display()
+                )
+            })?;
+            if let Err(err) = std::fs::write(
+                config.cgroup_root.join("cgroup.subtree_control"),
+                "+cpu +memory",
+            ) {
+                debug!(?err, "failed to enable cgroup controllers");
+            }
+            let cgroup = config.cgroup_root.join(cgroup_name(host_id, provider_id));
+            // An existing cgroup may be in use by another provider, so it must never be reused
+            std::fs::create_dir(&cgroup)
+                .with_context(|| format!("failed to create cgroup `{}`", cgroup.display()))?;
+            if let Some(max_memory) = config.max_memory {
+                std::fs::write(cgroup.join("memory.max"), max_memory.to_string())
+                    .context("failed to set cgroup memory limit")?;
+            }
+            if let Some(max_cpu_millis) = config.max_cpu_millis {
+                // Quota is in microseconds per 100ms period
+                std::fs::write(
+                    cgroup.join("cpu.max"),
+                    format!("{} 100000", max_cpu_millis.saturating_mul(100)),
+                )
+                .context("failed to set cgroup CPU limit")?;
+            }
+            Ok(Self {
+                config,
+                cgroup: Some(cgroup),
+                ids,
+            })
+        }
+
+        /// Configures `cmd` to spawn the provider within the sandbox
+        pub(crate) fn apply(&self, cmd: &mut process::Command) -> anyhow::Result<()> {
+            let mountinfo =
+                std::fs::read_to_string("/proc/self/mountinfo").context("failed to read mounts")?;
+            let remounts = read_only_remounts(&mountinfo)
+                .into_iter()
+                .map(|(mount_point, flags)| {
+                    CString::new(mount_point)
+                        .map(|mount_point| (mount_point, flags))
+                        .context("invalid mount point")
+                })
+                .collect::<anyhow::Result<_>>()?;
+            let (uid_map, gid_map, newidmap) = match &self.ids {
+                IdMapping::Own { uid, gid } => (
+                    format!("{SANDBOX_ID} {uid} 1\n"),
+                    format!("{SANDBOX_ID} {gid} 1\n"),
+                    None,
+                ),
+                IdMapping::Subordinate { uid, gid, newidmap } => (
+                    format!("0 {} {}\n", uid.start, uid.count),
+                    format!("0 {} {}\n", gid.start, gid.count),
+                    newidmap
+                        .as_ref()
+                        .map(|[newuidmap, newgidmap]| {
+                            anyhow::Ok([
+                                NewIdMap::new(newuidmap, *uid)?,
+                                NewIdMap::new(newgidmap, *gid)?,
+                            ])
+                        })
+                        .transpose()
+                        .context("invalid ID map program")?,
+                ),
+            };
+            let (uid_map, gid_map) = (uid_map.into_bytes(), gid_map.into_bytes());
+            let spawn = Spawn {
+                cgroup_procs: self
+                    .cgroup
+                    .as_ref()
+                    .map(|cgroup| path_cstring(&cgroup.join("cgroup.procs")))
+                    .transpose()?,
+                network_namespace: self
+                    .config
+                    .network_namespace
+                    .as_deref()
+                    .map(path_cstring)
+                    .transpose()?,
+                uid: self.config.uid.map(Uid::from_raw),
+                gid: self.config.gid.map(Gid::from_raw),
+                uid_map,
+                gid_map,
+                id_helper: matches!(self.ids, IdMapping::Subordinate { .. }),
+                newidmap,
+                remounts,
+                scratch_options: CString::new(format!(
+                    "size={},mode=1777",
+                    self.config.scratch_size
+                ))
+                .context("invalid scratch size")?,
+                seccomp: match self.config.seccomp {
+                    SeccompProfile::Default => Some(seccomp_filter()?),
+                    SeccompProfile::Unconfined => None,
+                },
+            };
+            cmd.env("TMPDIR", "/tmp");
+            // SAFETY: `Spawn::enter` only performs syscalls and does not allocate
+            unsafe {
+                cmd.pre_exec(move || spawn.enter());
+            }
+            Ok(())
+        }
+    }
+
+    impl Drop for Sandbox {
+        fn drop(&mut self) {
+            if let Some(cgroup) = &self.cgroup {
+                if let Err(err) = std::fs::remove_dir(cgroup) {
+                    warn!(?err, cgroup = ?cgroup.display(), "failed to remove provider cgroup");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sandbox_config() {
+        let host = ProviderSandbox::default();
+        assert_eq!(sandbox_config(&host, &BTreeMap::new()).unwrap(), None);
+
+        let config = sandbox_config(
+            &host,
+            &BTreeMap::from([
+                (SANDBOX_ANNOTATION.into(), "true".into()),
+                (SANDBOX_MAX_MEMORY_ANNOTATION.into(), "1048576".into()),
+                (SANDBOX_MAX_CPU_ANNOTATION.into(), "500".into()),
+            ]),
+        )
+        .unwrap()
+        .expect("provider should be sandboxed");
+        assert_eq!(config.max_memory, Some(1_048_576));
+        assert_eq!(config.max_cpu_millis, Some(500));
+        assert_eq!(config.seccomp, SeccompProfile::Default);
+
+        assert!(sandbox_config(
+            &host,
+            &BTreeMap::from([(SANDBOX_ANNOTATION.into(), "yes".into())]),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_sandbox_config_cannot_loosen_host() {
+        let host = ProviderSandbox {
+            enabled: true,
+            max_memory: Some(1024),
+            network_namespace: Some("/var/run/netns/providers".into()),
+            ..Default::default()
+        };
+        assert!(sandbox_config(
+            &host,
+            &BTreeMap::from([(SANDBOX_ANNOTATION.into(), "false".into())]),
+        )
+        .is_err());
+        assert!(sandbox_config(
+            &host,
+            &BTreeMap::from([(SANDBOX_SECCOMP_ANNOTATION.into(), "unconfined".into())]),
+        )
+        .is_err());
+        assert!(sandbox_config(
+            &host,
+            &BTreeMap::from([(
+                SANDBOX_NETWORK_NAMESPACE_ANNOTATION.into(),
+                "/proc/1/ns/net".into()
+            )]),
+        )
+        .is_err());
+        let config = sandbox_config(
+            &host,
+            &BTreeMap::from([(SANDBOX_MAX_MEMORY_ANNOTATION.into(), "4096".into())]),
+        )
+        .unwrap()
+        .expect("provider should be sandboxed");
+        assert_eq!(config.max_memory, Some(1024));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_read_only_remounts() {
+        use nix::mount::MsFlags;
+
+        let mountinfo = "\
+22 1 259:2 / / rw,relatime shared:1 - ext4 /dev/nvme0n1p2 rw
+23 22 0:21 / /proc rw,nosuid,nodev,noexec,relatime shared:12 - proc proc rw
+24 22 0:5 / /dev rw,nosuid shared:2 - devtmpfs devtmpfs rw
+25 24 0:22 / /dev/shm rw,nosuid,nodev shared:3 - tmpfs tmpfs rw
+26 22 0:23 / /devices rw,nosuid,nodev shared:4 - tmpfs tmpfs rw
+27 22 259:3 / /mnt/my\\040data rw,noexec shared:5 - ext4 /dev/nvme0n1p3 rw";
+        assert_eq!(
+            linux::read_only_remounts(mountinfo),
+            vec![
+                ("/".to_string(), MsFlags::MS_RELATIME),
+                (
+                    "/proc".to_string(),
+                    MsFlags::MS_NOSUID
+                        | MsFlags::MS_NODEV
+                        | MsFlags::MS_NOEXEC
+                        | MsFlags::MS_RELATIME
+                ),
+                ("/dev".to_string(), MsFlags::MS_NOSUID),
+                (
+                    "/dev/shm".to_string(),
+                    MsFlags::MS_NOSUID | MsFlags::MS_NODEV
+                ),
+                (
+                    "/devices".to_string(),
+                    MsFlags::MS_NOSUID | MsFlags::MS_NODEV
+                ),
+                ("/mnt/my data".to_string(), MsFlags::MS_NOEXEC),
+            ]
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_subordinate_range() {
+        use crate::wasmbus::host_config::IdRange;
+
+        let subuid = "\
+alice:100000:65536
+1001:165536:65536
+bob:231072:invalid";
+        assert_eq!(
+            linux::subordinate_range(subuid, Some("alice"), 1000),
+            Some(IdRange {
+                start: 100_000,
+                count: 65536
+            })
+        );
+        assert_eq!(
+            linux::subordinate_range(subuid, None, 1001),
+            Some(IdRange {
+                start: 165_536,
+                count: 65536
+            })
+        );
+        assert_eq!(linux::subordinate_range(subuid, Some("bob"), 1002), None);
+        assert_eq!(linux::subordinate_range(subuid, Some("root"), 0), None);
+
+        assert_eq!(
+            "100000:65536".parse::<IdRange>().unwrap(),
+            IdRange {
+                start: 100_000,
+                count: 65536
+            }
+        );
+        assert!("100000".parse::<IdRange>().is_err());
+        assert!("100000:-1".parse::<IdRange>().is_err());
+    }
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    #[test]
+    fn test_seccomp_filter_clone() {
+        use core::mem::offset_of;
+
+        use nix::libc;
+
+        /// Evaluates the subset of classic BPF used by the seccomp filter
+        fn run(filter: &[libc::sock_filter], nr: libc::c_long, arg0: u64) -> u32 {
+            let load = |offset: u32| match offset as usize {
+                o if o == offset_of!(libc::seccomp_data, nr) => nr as u32,
+                o if o == offset_of!(libc::seccomp_data, arch) => 0xc000_003e,
+                o if o == offset_of!(libc::seccomp_data, args) => arg0 as u32,
+                o => panic!("unexpected load at offset {o}"),
+            };
+            let (mut pc, mut acc) = (0, 0);
+            loop {
+                let insn = filter[pc];
+                pc += 1;
+                match insn.code {
+                    0x20 => acc = load(insn.k),
+                    0x06 => return insn.k,
+                    0x15 | 0x35 | 0x45 => {
+                        let matched = match insn.code {
+                            0x15 => acc == insn.k,
+                            0x35 => acc >= insn.k,
+                            _ => acc & insn.k != 0,
+                        };
+                        pc += usize::from(if matched { insn.jt } else { insn.jf });
+                    }
+                    code => panic!("unexpected instruction {code:#x}"),
+                }
+            }
+        }
+
+        let filter = linux::seccomp_filter().unwrap();
+        let allow = 0x7fff_0000;
+        let eperm = 0x0005_0000 | libc::EPERM as u32;
+        let enosys = 0x0005_0000 | libc::ENOSYS as u32;
+        let thread = (libc::CLONE_VM | libc::CLONE_FS | libc::CLONE_THREAD) as u64;
+        assert_eq!(run(&filter, libc::SYS_clone, thread), allow);
+        assert_eq!(run(&filter, libc::SYS_clone, libc::SIGCHLD as u64), allow);
+        assert_eq!(
+            run(&filter, libc::SYS_clone, libc::CLONE_NEWUSER as u64),
+            eperm
+        );
+        assert_eq!(
+            run(&filter, libc::SYS_clone, thread | libc::CLONE_NEWNET as u64),
+            eperm
+        );
+        assert_eq!(run(&filter, libc::SYS_clone3, 0), enosys);
+        assert_eq!(run(&filter, libc::SYS_unshare, 0), eperm);
+        assert_eq!(run(&filter, libc::SYS_read, 0), allow);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_cgroup_name() {
+        let a = linux::cgroup_name("NHOST", "wasmcloud/http:server");
+        let b = linux::cgroup_name("NHOST", "wasmcloud_http-server");
+        assert!(a.starts_with("NHOST-wasmcloud_http_server-"));
+        assert!(b.starts_with("NHOST-wasmcloud_http-server-"));
+        assert_ne!(a, linux::cgroup_name("NHOST", "wasmcloud/http:server"));
+        assert_ne!(
+            linux::cgroup_name("NHOST", "wasmcloud/http"),
+            linux::cgroup_name("NHOST", "wasmcloud_http")
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_format_decimal() {
+        let mut buf = [0; 11];
+        assert_eq!(linux::format_decimal(0, &mut buf), c"0");
+        assert_eq!(linux::format_decimal(4_194_304, &mut buf), c"4194304");
+        assert_eq!(linux::format_decimal(u32::MAX, &mut buf), c"4294967295");
+    }
+}
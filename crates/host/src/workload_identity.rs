@@ -1,14 +1,39 @@
+use std::collections::{BTreeMap, HashMap};
+#[cfg(unix)]
+use std::pin::pin;
 use std::sync::Arc;
 
 #[cfg(target_family = "windows")]
 use anyhow::{bail, Result};
 #[cfg(unix)]
-use anyhow::{Context as _, Result};
+use anyhow::{ensure, Context as _, Result};
+use futures::stream;
+#[cfg(unix)]
+use futures::{Stream, StreamExt as _};
 use nkeys::KeyPair;
+#[cfg(unix)]
+use spire_api::{
+    selectors::Selector, DelegateAttestationRequest::Selectors, DelegatedIdentityClient,
+};
+use wasmcloud_core::secrets::SecretValue;
+#[cfg(unix)]
+use wasmcloud_core::secrets::{
+    WORKLOAD_X509_BUNDLE_SECRET, WORKLOAD_X509_SVID_KEY_SECRET, WORKLOAD_X509_SVID_SECRET,
+};
 
 // TODO(joonas): Figure out better naming here
 const AUTH_SERVICE_AUDIENCE_ENV: &str = "WASMCLOUD_WORKLOAD_IDENTITY_AUTH_SERVICE_AUDIENCE";
 
+// The key used to represent a wasmCloud-specific selector:
+// https://github.com/spiffe/spire-api-sdk/blob/3c6b1447f3d82210b91462d003f6c2774ffbe472/proto/spire/api/types/selector.proto#L6-L8
+//
+// Similar to existing types defined in the spire-api crate: https://github.com/maxlambrecht/rust-spiffe/blob/929a090f99d458dd67fa499b74afbeb2fc44b114/spire-api/src/selectors.rs#L4-L5
+#[cfg(unix)]
+const WASMCLOUD_SELECTOR_TYPE: &str = "wasmcloud";
+// Similar to the existing Kubernetes types: https://github.com/maxlambrecht/rust-spiffe/blob/929a090f99d458dd67fa499b74afbeb2fc44b114/spire-api/src/selectors.rs#L38-L39
+#[cfg(unix)]
+const WASMCLOUD_SELECTOR_LATTICE: &str = "lattice";
+
 /// WorkloadIdentityConfig is used by the experimental workload-identity feature
 #[derive(Clone, Default, Debug)]
 pub struct WorkloadIdentityConfig {
@@ -97,3 +122,357 @@ pub(crate) async fn setup_workload_identity_nats_connect_options(
 ) -> anyhow::Result<async_nats::ConnectOptions> {
     bail!("workload identity is not supported on Windows")
 }
+
+/// A workload running on the host, which SVIDs can be requested for on its behalf
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Workload<'a> {
+    /// A component, identified by its component ID
+    Component(&'a str),
+    /// A capability provider, identified by its provider ID
+    Provider(&'a str),
+}
+
+impl Workload<'_> {
+    /// The kind of the workload, as used in selectors and SPIFFE IDs
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Component(..) => "component",
+            Self::Provider(..) => "provider",
+        }
+    }
+
+    /// The identifier of the workload
+    #[must_use]
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Component(id) | Self::Provider(id) => id,
+        }
+    }
+
+    /// Derives the SPIFFE ID of this workload in `trust_domain`, which is of the form
+    /// `spiffe://{trust_domain}/wasmcloud/{lattice}/{kind}/{id}`
+    #[must_use]
+    pub fn spiffe_id(&self, trust_domain: &str, lattice: &str) -> String {
+        format!(
+            "spiffe://{trust_domain}/wasmcloud/{lattice}/{}/{}",
+            self.kind(),
+            self.id()
+        )
+    }
+
+    /// Selectors attesting this workload to the SPIRE Agent, derived from host labels, the lattice
+    /// and the workload itself
+    #[cfg(unix)]
+    fn selectors(&self, host_labels: &BTreeMap<String, String>, lattice: &str) -> Vec<Selector> {
+        // Host labels must not be able to claim the lattice or the identity of another workload,
+        // so label-derived selectors using one of the reserved keys are dropped
+        let mut selectors = parse_selectors_from_host_labels(host_labels);
+        selectors.retain(|selector| {
+            let Selector::Generic((_, value)) = selector else {
+                return true;
+            };
+            let key = value.split_once(':').map_or(value.as_str(), |(key, _)| key);
+            ![
+                WASMCLOUD_SELECTOR_LATTICE,
+                Workload::Component("").kind(),
+                Workload::Provider("").kind(),
+            ]
+            .contains(&key)
+        });
+        selectors.push(Selector::Generic((
+            WASMCLOUD_SELECTOR_TYPE.to_string(),
+            format!("{WASMCLOUD_SELECTOR_LATTICE}:{lattice}"),
+        )));
+        selectors.push(Selector::Generic((
+            WASMCLOUD_SELECTOR_TYPE.to_string(),
+            format!("{}:{}", self.kind(), self.id()),
+        )));
+        selectors
+    }
+}
+
+/// Fetches a JWT-SVID for `audience` on behalf of `workload` from the SPIRE Agent Delegated Identity API.
+///
+/// If `trust_domain` is set, only an SVID issued for the SPIFFE ID derived for the workload is returned,
+/// otherwise the first SVID issued for the workload is.
+#[cfg(unix)]
+pub(crate) async fn fetch_jwt_svid(
+    workload: Workload<'_>,
+    host_labels: &BTreeMap<String, String>,
+    lattice: &str,
+    trust_domain: Option<&str>,
+    audience: &str,
+) -> Result<Option<String>> {
+    let mut client = DelegatedIdentityClient::default()
+        .await
+        .context("unable to connect to workload identity service")?;
+    let svids = client
+        .fetch_jwt_svids(
+            &[audience],
+            Selectors(workload.selectors(host_labels, lattice)),
+        )
+        .await
+        .context("failed to fetch JWT-SVIDs")?;
+    let spiffe_id = trust_domain.map(|trust_domain| workload.spiffe_id(trust_domain, lattice));
+    Ok(svids
+        .iter()
+        .find(|svid| {
+            spiffe_id
+                .as_ref()
+                .is_none_or(|spiffe_id| svid.spiffe_id().to_string() == *spiffe_id)
+        })
+        .map(|svid| svid.token().to_string()))
+}
+
+#[cfg(target_family = "windows")]
+pub(crate) async fn fetch_jwt_svid(
+    _workload: Workload<'_>,
+    _host_labels: &BTreeMap<String, String>,
+    _lattice: &str,
+    _trust_domain: Option<&str>,
+    _audience: &str,
+) -> Result<Option<String>> {
+    bail!("workload identity is not supported on Windows")
+}
+
+/// Streams X.509-SVIDs of `workload` from the SPIRE Agent Delegated Identity API along with the
+/// trust bundle of its trust domain, as PEM-encoded secrets to be given to the workload. The stream
+/// yields the current SVID first and a new one every time it is rotated.
+///
+/// If `trust_domain` is set, each SVID must be issued for the SPIFFE ID derived for the workload.
+#[cfg(unix)]
+pub(crate) async fn stream_x509_svid_secrets(
+    workload: Workload<'_>,
+    host_labels: &BTreeMap<String, String>,
+    lattice: &str,
+    trust_domain: Option<&str>,
+) -> Result<impl Stream<Item = Result<HashMap<String, SecretValue>>> + Send + 'static> {
+    let mut client = DelegatedIdentityClient::default()
+        .await
+        .context("unable to connect to workload identity service")?;
+    let svids = client
+        .stream_x509_svids(Selectors(workload.selectors(host_labels, lattice)))
+        .await
+        .context("failed to stream X.509-SVIDs")?;
+    let expected = trust_domain.map(|trust_domain| workload.spiffe_id(trust_domain, lattice));
+    Ok(stream::unfold(
+        (Box::pin(svids), client, expected),
+        |(mut svids, mut client, expected)| async move {
+            let svid = svids.next().await?;
+            let secrets = async {
+                let svid = svid.context("failed to receive X.509-SVID")?;
+                if let Some(expected) = &expected {
+                    let actual = svid.spiffe_id().to_string();
+                    ensure!(
+                        actual == *expected,
+                        "X.509-SVID was issued for `{actual}`, expected `{expected}`"
+                    );
+                }
+                let mut secrets = HashMap::from([
+                    (
+                        WORKLOAD_X509_SVID_SECRET.to_string(),
+                        SecretValue::String(
+                            svid.cert_chain()
+                                .iter()
+                                .map(|cert| pem_encode("CERTIFICATE", cert.content()))
+                                .collect(),
+                        ),
+                    ),
+                    (
+                        WORKLOAD_X509_SVID_KEY_SECRET.to_string(),
+                        SecretValue::String(pem_encode(
+                            "PRIVATE KEY",
+                            svid.private_key().content(),
+                        )),
+                    ),
+                ]);
+                // Bundles are fetched along with every SVID, since rotated SVIDs may be signed
+                // by a rotated authority
+                let bundles = client
+                    .fetch_x509_bundles()
+                    .await
+                    .context("failed to fetch X.509 bundles")?;
+                if let Some(bundle) = bundles.get_bundle(svid.spiffe_id().trust_domain()) {
+                    secrets.insert(
+                        WORKLOAD_X509_BUNDLE_SECRET.to_string(),
+                        SecretValue::String(
+                            bundle
+                                .authorities()
+                                .iter()
+                                .map(|cert| pem_encode("CERTIFICATE", cert.content()))
+                                .collect(),
+                        ),
+                    );
+                }
+                anyhow::Ok(secrets)
+            }
+            .await;
+            Some((secrets, (svids, client, expected)))
+        },
+    ))
+}
+
+/// Fetches the current X.509-SVID of `workload`, see [`stream_x509_svid_secrets`]
+#[cfg(unix)]
+pub(crate) async fn fetch_x509_svid_secrets(
+    workload: Workload<'_>,
+    host_labels: &BTreeMap<String, String>,
+    lattice: &str,
+    trust_domain: Option<&str>,
+) -> Result<HashMap<String, SecretValue>> {
+    let svids = stream_x509_svid_secrets(workload, host_labels, lattice, trust_domain).await?;
+    pin!(svids)
+        .next()
+        .await
+        .context("X.509-SVID stream ended")?
+}
+
+#[cfg(target_family = "windows")]
+pub(crate) async fn fetch_x509_svid_secrets(
+    _workload: Workload<'_>,
+    _host_labels: &BTreeMap<String, String>,
+    _lattice: &str,
+    _trust_domain: Option<&str>,
+) -> Result<HashMap<String, SecretValue>> {
+    bail!("workload identity is not supported on Windows")
+}
+
+#[cfg(target_family = "windows")]
+pub(crate) async fn stream_x509_svid_secrets(
+    _workload: Workload<'_>,
+    _host_labels: &BTreeMap<String, String>,
+    _lattice: &str,
+    _trust_domain: Option<&str>,
+) -> Result<stream::Empty<Result<HashMap<String, SecretValue>>>> {
+    bail!("workload identity is not supported on Windows")
+}
+
+/// Encodes DER-encoded `der` as a PEM block with the given `label`
+#[cfg(unix)]
+fn pem_encode(label: &str, der: &[u8]) -> String {
+    use base64::Engine as _;
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(der);
+    let mut pem = format!("-----BEGIN {label}-----\n");
+    // Base64 output is ASCII, so splitting at any byte offset yields valid UTF-8
+    for line in encoded.as_bytes().chunks(64) {
+        pem.push_str(&String::from_utf8_lossy(line));
+        pem.push('\n');
+    }
+    pem.push_str(&format!("-----END {label}-----\n"));
+    pem
+}
+
+// TODO(joonas): Make this more generalized so we can support non-wasmcloud-specific
+// selectors as well.
+//
+// environment variable -> WASMCLOUD_LABEL_wasmcloud__ns=my-namespace-goes-here
+// becomes:
+// SPIRE Selector -> wasmcloud:ns:my-namespace-goes-here
+#[cfg(unix)]
+fn parse_selectors_from_host_labels(host_labels: &BTreeMap<String, String>) -> Vec<Selector> {
+    let mut selectors = vec![];
+
+    for (key, value) in host_labels.iter() {
+        // Ensure the label starts with `wasmcloud__` and doesn't end in `__`, i.e. just `wasmcloud__`
+        if key.starts_with("wasmcloud__") && !key.ends_with("__") {
+            let selector = key
+                // Replace all __ with :
+                .replace("__", ":")
+                // Remove the leading "wasmcloud"
+                .split_once(":")
+                // Map the remaining part of the label key together with the value `` to make it a selector
+                .map(|(_, selector)| format!("{}:{}", selector, value))
+                // This should never get triggered, but just in case.
+                .unwrap_or("unknown".to_string());
+
+            selectors.push(Selector::Generic((
+                WASMCLOUD_SELECTOR_TYPE.to_string(),
+                selector,
+            )));
+        }
+    }
+
+    selectors
+}
+
+#[cfg(unix)]
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env::consts::{ARCH, FAMILY, OS};
+
+    #[test]
+    fn test_parse_selectors_from_host_labels() {
+        let labels = BTreeMap::from([
+            ("hostcore.arch".into(), ARCH.into()),
+            ("hostcore.os".into(), OS.into()),
+            ("hostcore.osfamily".into(), FAMILY.into()),
+            ("wasmcloud__lattice".into(), "default".into()),
+        ]);
+
+        let selectors = parse_selectors_from_host_labels(&labels);
+
+        assert_eq!(selectors.len(), 1);
+
+        let (selector_type, selector_value) = match selectors.first() {
+            Some(Selector::Generic(pair)) => pair,
+            _ => &("wrong-value".into(), "wrong-value".into()),
+        };
+        assert_eq!(selector_type, WASMCLOUD_SELECTOR_TYPE);
+        assert_eq!(selector_value, "lattice:default");
+    }
+
+    #[test]
+    fn test_parse_selectors_from_host_labels_defaults_to_no_selectors() {
+        let no_labels = BTreeMap::new();
+        let selectors = parse_selectors_from_host_labels(&no_labels);
+        assert_eq!(selectors.len(), 0);
+    }
+
+    #[test]
+    fn test_workload_selectors_cannot_be_overridden() {
+        let labels = BTreeMap::from([
+            ("wasmcloud__component".into(), "other".into()),
+            ("wasmcloud__lattice".into(), "other".into()),
+            ("wasmcloud__ns".into(), "my-namespace".into()),
+            ("wasmcloud__provider__id".into(), "other".into()),
+        ]);
+        let selectors = Workload::Provider("http").selectors(&labels, "default");
+        let values = selectors
+            .iter()
+            .map(|selector| match selector {
+                Selector::Generic((_, value)) => value.as_str(),
+                _ => "wrong-value",
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            ["ns:my-namespace", "lattice:default", "provider:http"]
+        );
+    }
+
+    #[test]
+    fn test_workload_spiffe_id() {
+        assert_eq!(
+            Workload::Component("echo").spiffe_id("example.org", "default"),
+            "spiffe://example.org/wasmcloud/default/component/echo"
+        );
+        assert_eq!(
+            Workload::Provider("http-client").spiffe_id("example.org", "prod"),
+            "spiffe://example.org/wasmcloud/prod/provider/http-client"
+        );
+    }
+
+    #[test]
+    fn test_pem_encode() {
+        let pem = pem_encode("CERTIFICATE", &[0; 60]);
+        let mut lines = pem.lines();
+        assert_eq!(lines.next(), Some("-----BEGIN CERTIFICATE-----"));
+        assert_eq!(lines.next().map(str::len), Some(64));
+        assert_eq!(lines.next().map(str::len), Some(16));
+        assert_eq!(lines.next(), Some("-----END CERTIFICATE-----"));
+        assert_eq!(lines.next(), None);
+    }
+}
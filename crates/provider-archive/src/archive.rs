@@ -37,6 +37,7 @@ pub struct ProviderArchive {
     ver: Option<String>,
     token: Option<Token<CapabilityProvider>>,
     json_schema: Option<serde_json::Value>,
+    provider_config_schema: Option<serde_json::Value>,
     wit: Option<Vec<u8>>,
 }
 
@@ -52,6 +53,7 @@ impl ProviderArchive {
             ver,
             token: None,
             json_schema: None,
+            provider_config_schema: None,
             wit: None,
         }
     }
@@ -70,15 +72,25 @@ impl ProviderArchive {
         Ok(())
     }
 
-    /// Sets a JSON schema for this provider's link definition specification. This will be injected
-    /// into the claims written to a provider's PAR file, so you'll need to do this after instantiation
-    /// and prior to writing
+    /// Sets a JSON schema for this provider's link definition specification, which hosts validate
+    /// the configuration links supply to the provider against. This will be injected into the
+    /// claims written to a provider's PAR file, so you'll need to do this after instantiation and
+    /// prior to writing
     pub fn set_schema(&mut self, schema: serde_json::Value) -> Result<()> {
         self.json_schema = Some(schema);
 
         Ok(())
     }
 
+    /// Sets a JSON schema for the configuration this provider is started with, which hosts validate
+    /// the configuration against. This will be injected into the claims written to a provider's PAR
+    /// file, so you'll need to do this after instantiation and prior to writing
+    pub fn set_provider_config_schema(&mut self, schema: serde_json::Value) -> Result<()> {
+        self.provider_config_schema = Some(schema);
+
+        Ok(())
+    }
+
     /// Gets the list of architecture/OS targets within the archive
     #[must_use]
     pub fn targets(&self) -> Vec<String> {
@@ -112,6 +124,13 @@ impl ProviderArchive {
         self.json_schema.clone()
     }
 
+    /// Obtains the JSON schema of the provider start configuration if one was either set explicitly
+    /// on the structure or loaded from claims in the PAR
+    #[must_use]
+    pub fn provider_config_schema(&self) -> Option<serde_json::Value> {
+        self.provider_config_schema.clone()
+    }
+
     /// Returns the WIT embedded in this provider archive.
     #[must_use]
     pub fn wit_world(&self) -> Option<&[u8]> {
@@ -286,6 +305,7 @@ impl ProviderArchive {
             let rev = metadata.rev;
             let ver = metadata.ver.clone();
             let json_schema = metadata.config_schema.clone();
+            let provider_config_schema = metadata.provider_config_schema.clone();
 
             validate_hashes(&libraries, &wit_world, cl)?;
 
@@ -297,6 +317,7 @@ impl ProviderArchive {
                 ver,
                 token,
                 json_schema,
+                provider_config_schema,
                 wit: wit_world,
             })
         } else {
@@ -346,6 +367,9 @@ impl ProviderArchive {
         if let Some(schema) = self.json_schema.clone() {
             claims.metadata.as_mut().unwrap().config_schema = Some(schema);
         }
+        if let Some(schema) = self.provider_config_schema.clone() {
+            claims.metadata.as_mut().unwrap().provider_config_schema = Some(schema);
+        }
 
         let claims_jwt = claims.encode(issuer)?;
         self.token = Some(Token {
@@ -516,6 +540,7 @@ mod test {
         arch.add_library("x86_64-linux", b"bloobloo")?;
         arch.add_library("x86_64-macos", b"blarblar")?;
         arch.set_schema(json!({"property":"foo"}))?;
+        arch.set_provider_config_schema(json!({"property":"bar"}))?;
 
         let issuer = KeyPair::new_account();
         let subject = KeyPair::new_service();
@@ -564,6 +589,10 @@ mod test {
             .config_schema
             .unwrap();
         assert_eq!(json, json!({"property":"foo"}));
+        assert_eq!(
+            arch2.provider_config_schema(),
+            Some(json!({"property":"bar"}))
+        );
 
         let mut buf2 = Vec::new();
         let mut f2 = File::open(&firstpath).await?;
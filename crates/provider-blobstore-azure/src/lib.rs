@@ -5,22 +5,27 @@ use core::pin::Pin;
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{bail, Context as _, Result};
+use azure_core::request_options::{IfMatchCondition, Metadata};
+use azure_core::StatusCode;
+use azure_storage::shared_access_signature::service_sas::BlobSasPermissions;
 use azure_storage::CloudLocation;
 use azure_storage_blobs::prelude::*;
 use bytes::{Bytes, BytesMut};
 use futures::{Stream, StreamExt as _};
+use time::OffsetDateTime;
 use tokio::sync::{mpsc, RwLock};
 use tokio_stream::wrappers::ReceiverStream;
 use tracing::{error, instrument};
+use wasmcloud_provider_sdk::provider::{InvocationStreams, WrpcClient};
 use wasmcloud_provider_sdk::{
     get_connection, initialize_observability, load_host_data, propagate_trace_for_ctx,
     run_provider, serve_provider_exports, Context, HostData, LinkConfig, LinkDeleteInfo, Provider,
 };
 use wrpc_interface_blobstore::bindings::{
     exports::wrpc::blobstore::blobstore::Handler,
-    serve,
     wrpc::blobstore::types::{ContainerMetadata, ObjectId, ObjectMetadata},
 };
 
@@ -28,6 +33,23 @@ use config::StorageConfig;
 
 mod config;
 
+mod bindings {
+    wit_bindgen_wrpc::generate!({
+        world: "extensions",
+        with: {
+            "wasmcloud:blobstore/multipart@0.1.0-draft": generate,
+            "wasmcloud:blobstore/objects@0.1.0-draft": generate,
+            "wasmcloud:blobstore/presign@0.1.0-draft": generate,
+            "wasmcloud:blobstore/types@0.1.0-draft": generate,
+        },
+    });
+}
+use bindings::exports::wasmcloud::blobstore::{multipart, objects, presign};
+use bindings::wasmcloud::blobstore::types::{
+    CompletedPart, Condition, ObjectError, ObjectId as ExtObjectId, ObjectInfo, PresignedRequest,
+    WriteOptions,
+};
+
 /// Blobstore Azblob provider
 ///
 /// This struct will be the target of generated implementations (via wit-provider-bindgen)
@@ -89,6 +111,25 @@ impl Provider for BlobstoreAzblobProvider {
     }
 }
 
+/// Serve both `wrpc:blobstore` and `wasmcloud:blobstore` extension exports
+async fn serve(
+    wrpc: &WrpcClient,
+    provider: BlobstoreAzblobProvider,
+) -> anyhow::Result<InvocationStreams> {
+    let mut invocations = wrpc_interface_blobstore::bindings::serve(wrpc, provider.clone()).await?;
+    invocations.extend(bindings::serve(wrpc, provider).await?);
+    Ok(invocations)
+}
+
+/// Block ID used for a part of a multipart upload.
+///
+/// Multipart uploads are implemented using uncommitted blocks of a block blob, the upload ID
+/// is used as prefix of the block IDs to tell concurrent uploads apart. Part numbers are padded,
+/// since Azure requires all block IDs of a blob to have the same length.
+fn block_id(upload_id: &str, part_number: u32) -> String {
+    format!("{upload_id}{part_number:010}")
+}
+
 impl BlobstoreAzblobProvider {
     pub async fn run() -> anyhow::Result<()> {
         let HostData { config, .. } = load_host_data().context("failed to load host data")?;
@@ -602,3 +643,383 @@ impl Handler<Option<Context>> for BlobstoreAzblobProvider {
         .map_err(|err| format!("{err:#}")))
     }
 }
+
+impl BlobstoreAzblobProvider {
+    async fn presign(
+        &self,
+        cx: Option<Context>,
+        ExtObjectId { container, object }: ExtObjectId,
+        expires_in: u64,
+        put: bool,
+    ) -> anyhow::Result<PresignedRequest> {
+        propagate_trace_for_ctx!(cx);
+        let client = self
+            .get_config(cx.as_ref())
+            .await
+            .context("failed to retrieve azure blobstore client")?;
+        let client = client.container_client(container).blob_client(object);
+        let expiry = OffsetDateTime::now_utc() + Duration::from_secs(expires_in);
+        let permissions = if put {
+            BlobSasPermissions {
+                create: true,
+                write: true,
+                ..Default::default()
+            }
+        } else {
+            BlobSasPermissions {
+                read: true,
+                ..Default::default()
+            }
+        };
+        let sas = client
+            .shared_access_signature(permissions, expiry)
+            .await
+            .context("failed to create shared access signature")?;
+        let url = client
+            .generate_signed_blob_url(&sas)
+            .context("failed to generate signed blob URL")?;
+        let (method, headers) = if put {
+            ("PUT", vec![("x-ms-blob-type".into(), "BlockBlob".into())])
+        } else {
+            ("GET", vec![])
+        };
+        Ok(PresignedRequest {
+            url: url.to_string(),
+            method: method.into(),
+            headers,
+            expires_at: expiry
+                .unix_timestamp()
+                .try_into()
+                .context("failed to convert expiry to u64")?,
+        })
+    }
+}
+
+impl presign::Handler<Option<Context>> for BlobstoreAzblobProvider {
+    #[instrument(level = "trace", skip(self))]
+    async fn presign_get(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        expires_in: u64,
+    ) -> anyhow::Result<Result<PresignedRequest, String>> {
+        Ok(self
+            .presign(cx, id, expires_in, false)
+            .await
+            .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn presign_put(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        expires_in: u64,
+    ) -> anyhow::Result<Result<PresignedRequest, String>> {
+        Ok(self
+            .presign(cx, id, expires_in, true)
+            .await
+            .map_err(|err| format!("{err:#}")))
+    }
+}
+
+impl multipart::Handler<Option<Context>> for BlobstoreAzblobProvider {
+    #[instrument(level = "trace", skip(self))]
+    async fn create_upload(
+        &self,
+        cx: Option<Context>,
+        _id: ExtObjectId,
+    ) -> anyhow::Result<Result<String, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            // Ensure the link is configured, no state is kept by Azure until the first block is put
+            self.get_config(cx.as_ref())
+                .await
+                .context("failed to retrieve azure blobstore client")?;
+            anyhow::Ok(uuid::Uuid::new_v4().simple().to_string())
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self, data))]
+    async fn upload_part(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        upload_id: String,
+        part_number: u32,
+        data: Bytes,
+    ) -> anyhow::Result<Result<CompletedPart, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let client = self
+                .get_config(cx.as_ref())
+                .await
+                .context("failed to retrieve azure blobstore client")?;
+            let size = data.len().try_into().unwrap_or(u64::MAX);
+            let etag = block_id(&upload_id, part_number);
+            client
+                .container_client(id.container)
+                .blob_client(id.object)
+                .put_block(etag.clone(), data)
+                .await
+                .context("failed to put block")?;
+            anyhow::Ok(CompletedPart {
+                part_number,
+                etag,
+                size,
+            })
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn list_parts(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        upload_id: String,
+    ) -> anyhow::Result<Result<Vec<CompletedPart>, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let client = self
+                .get_config(cx.as_ref())
+                .await
+                .context("failed to retrieve azure blobstore client")?;
+            let blocks = client
+                .container_client(id.container)
+                .blob_client(id.object)
+                .get_block_list()
+                .block_list_type(BlockListType::Uncommitted)
+                .await
+                .context("failed to get block list")?;
+            let mut parts: Vec<_> = blocks
+                .block_with_size_list
+                .blocks
+                .into_iter()
+                .filter_map(|block| {
+                    let BlobBlockType::Uncommitted(block_id) = block.block_list_type else {
+                        return None;
+                    };
+                    let etag = String::from_utf8(block_id.bytes().to_vec()).ok()?;
+                    let part_number = etag.strip_prefix(&upload_id)?.parse().ok()?;
+                    Some(CompletedPart {
+                        part_number,
+                        etag,
+                        size: block.size_in_bytes,
+                    })
+                })
+                .collect();
+            parts.sort_by_key(|part| part.part_number);
+            anyhow::Ok(parts)
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn complete_upload(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        upload_id: String,
+        mut parts: Vec<CompletedPart>,
+    ) -> anyhow::Result<Result<(), String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let client = self
+                .get_config(cx.as_ref())
+                .await
+                .context("failed to retrieve azure blobstore client")?;
+            parts.sort_by_key(|part| part.part_number);
+            let blocks = parts
+                .into_iter()
+                .map(
+                    |CompletedPart {
+                         part_number, etag, ..
+                     }| {
+                        if etag != block_id(&upload_id, part_number) {
+                            bail!("part `{part_number}` does not belong to upload `{upload_id}`")
+                        }
+                        Ok(BlobBlockType::new_uncommitted(etag.into_bytes()))
+                    },
+                )
+                .collect::<anyhow::Result<_>>()?;
+            client
+                .container_client(id.container)
+                .blob_client(id.object)
+                .put_block_list(BlockList { blocks })
+                .await
+                .context("failed to put block list")?;
+            anyhow::Ok(())
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn abort_upload(
+        &self,
+        cx: Option<Context>,
+        _id: ExtObjectId,
+        _upload_id: String,
+    ) -> anyhow::Result<Result<(), String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            self.get_config(cx.as_ref())
+                .await
+                .context("failed to retrieve azure blobstore client")?;
+            // NOTE: Azure does not support explicitly discarding uncommitted blocks, they are
+            // garbage collected by the service after a week
+            anyhow::Ok(())
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+}
+
+/// Convert an error returned for a conditional request into an [`ObjectError`]
+fn object_error(err: azure_storage::Error, msg: &str) -> ObjectError {
+    match err.as_http_error().map(|err| err.status()) {
+        // `If-None-Match: *` conflicts with an existing blob are reported as `409 Conflict`
+        Some(StatusCode::PreconditionFailed | StatusCode::Conflict) => {
+            ObjectError::PreconditionFailed
+        }
+        _ => ObjectError::Other(format!(
+            "{:#}",
+            anyhow::Error::from(err).context(msg.to_string())
+        )),
+    }
+}
+
+impl objects::Handler<Option<Context>> for BlobstoreAzblobProvider {
+    #[instrument(level = "trace", skip(self))]
+    async fn get_object_info(
+        &self,
+        cx: Option<Context>,
+        ExtObjectId { container, object }: ExtObjectId,
+    ) -> anyhow::Result<Result<ObjectInfo, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let client = self
+                .get_config(cx.as_ref())
+                .await
+                .context("failed to retrieve azure blobstore client")?;
+            let Blob {
+                properties,
+                metadata,
+                ..
+            } = client
+                .container_client(container.clone())
+                .blob_client(object.clone())
+                .get_properties()
+                .await
+                .context("failed to get blob properties")?
+                .blob;
+            anyhow::Ok(ObjectInfo {
+                name: object,
+                container,
+                size: properties.content_length,
+                created_at: properties
+                    .creation_time
+                    .unix_timestamp()
+                    .try_into()
+                    .context("failed to convert created_at date to u64")?,
+                etag: properties.etag.to_string(),
+                content_type: Some(properties.content_type).filter(|v| !v.is_empty()),
+                metadata: metadata.unwrap_or_default().into_iter().collect(),
+            })
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self, data))]
+    async fn write_object(
+        &self,
+        cx: Option<Context>,
+        ExtObjectId { container, object }: ExtObjectId,
+        data: Bytes,
+        WriteOptions {
+            content_type,
+            metadata,
+            condition,
+        }: WriteOptions,
+    ) -> anyhow::Result<Result<String, ObjectError>> {
+        propagate_trace_for_ctx!(cx);
+        let client = match self.get_config(cx.as_ref()).await {
+            Ok(client) => client,
+            Err(err) => {
+                return Ok(Err(ObjectError::Other(format!(
+                    "{:#}",
+                    err.context("failed to retrieve azure blobstore client")
+                ))))
+            }
+        };
+        let mut req = client
+            .container_client(container)
+            .blob_client(object)
+            .put_block_blob(data);
+        if let Some(content_type) = content_type {
+            req = req.content_type(content_type);
+        }
+        if !metadata.is_empty() {
+            let mut md = Metadata::new();
+            for (k, v) in metadata {
+                md.insert(k, v);
+            }
+            req = req.metadata(md);
+        }
+        match condition {
+            Some(Condition::IfMatch(etag)) => req = req.if_match(IfMatchCondition::Match(etag)),
+            Some(Condition::IfNoneMatch) => {
+                req = req.if_match(IfMatchCondition::NotMatch("*".into()));
+            }
+            None => {}
+        }
+        Ok(req
+            .await
+            .map(|res| res.etag)
+            .map_err(|err| object_error(err, "failed to put blob")))
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn delete_object(
+        &self,
+        cx: Option<Context>,
+        ExtObjectId { container, object }: ExtObjectId,
+        condition: Option<Condition>,
+    ) -> anyhow::Result<Result<(), ObjectError>> {
+        propagate_trace_for_ctx!(cx);
+        let client = match self.get_config(cx.as_ref()).await {
+            Ok(client) => client,
+            Err(err) => {
+                return Ok(Err(ObjectError::Other(format!(
+                    "{:#}",
+                    err.context("failed to retrieve azure blobstore client")
+                ))))
+            }
+        };
+        let client = client.container_client(container).blob_client(object);
+        let req = match condition {
+            Some(Condition::IfMatch(etag)) => {
+                client.delete().if_match(IfMatchCondition::Match(etag))
+            }
+            // Deleting a blob, which does not exist, is a no-op
+            Some(Condition::IfNoneMatch) => {
+                return Ok(match client.exists().await {
+                    Ok(false) => Ok(()),
+                    Ok(true) => Err(ObjectError::PreconditionFailed),
+                    Err(err) => Err(object_error(err, "failed to check blob existence")),
+                });
+            }
+            None => client.delete(),
+        };
+        Ok(req
+            .await
+            .map(|_| ())
+            .map_err(|err| object_error(err, "failed to delete blob")))
+    }
+}
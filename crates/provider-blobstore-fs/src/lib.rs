@@ -8,30 +8,69 @@ use core::time::Duration;
 
 use std::collections::HashMap;
 use std::io::SeekFrom;
-use std::path::{Path, PathBuf};
+use std::net::SocketAddr;
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 use std::time::SystemTime;
 
-use anyhow::{anyhow, bail, Context as _};
+use anyhow::{anyhow, bail, ensure, Context as _};
 use bytes::Bytes;
 use futures::{Stream, StreamExt as _, TryStreamExt as _};
 use path_clean::PathClean;
+use sha2::{Digest as _, Sha256};
 use tokio::fs::{self, create_dir_all, File};
-use tokio::io::{self, AsyncReadExt as _, AsyncSeekExt as _};
-use tokio::sync::{mpsc, RwLock};
+use tokio::io::{self, AsyncReadExt as _, AsyncSeekExt as _, AsyncWriteExt as _};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tokio::task::JoinHandle;
 use tokio_stream::wrappers::{ReadDirStream, ReceiverStream};
 use tokio_util::io::{ReaderStream, StreamReader};
 use tracing::{debug, error, info, instrument, trace};
+use wasmcloud_provider_sdk::provider::{InvocationStreams, WrpcClient};
 use wasmcloud_provider_sdk::{
-    get_connection, initialize_observability, propagate_trace_for_ctx, run_provider,
-    serve_provider_exports, Context, LinkConfig, LinkDeleteInfo, Provider,
+    get_connection, initialize_observability, load_host_data, propagate_trace_for_ctx,
+    run_provider, serve_provider_exports, Context, LinkConfig, LinkDeleteInfo, Provider,
 };
 use wrpc_interface_blobstore::bindings::{
     exports::wrpc::blobstore::blobstore::Handler,
-    serve,
     wrpc::blobstore::types::{ContainerMetadata, ObjectId, ObjectMetadata},
 };
 
+use crate::presign::Presigner;
+
+mod objects;
+mod presign;
+mod watch;
+
+mod bindings {
+    wit_bindgen_wrpc::generate!({
+        world: "extensions",
+        additional_derives: [PartialEq, Eq],
+        with: {
+            "wasmcloud:blobstore/multipart@0.1.0-draft": generate,
+            "wasmcloud:blobstore/objects@0.1.0-draft": generate,
+            "wasmcloud:blobstore/presign@0.1.0-draft": generate,
+            "wasmcloud:blobstore/types@0.1.0-draft": generate,
+            "wasmcloud:blobstore/watcher@0.1.0-draft": generate,
+        },
+    });
+}
+use bindings::exports::wasmcloud::blobstore::{multipart, presign as presign_ext};
+use bindings::wasmcloud::blobstore::types::{
+    CompletedPart, ObjectId as ExtObjectId, PresignedRequest,
+};
+
+/// Directory within the root, in which in-progress multipart uploads are staged
+const MULTIPART_DIR: &str = ".multipart-uploads";
+/// File within an upload staging directory, which records the object the upload is for
+const MULTIPART_TARGET: &str = "target";
+/// Directory within the root, in which content types and user-defined metadata of objects are stored
+const METADATA_DIR: &str = ".object-metadata";
+/// Directory within the root, in which objects are staged before being moved into place
+const TEMP_DIR: &str = ".tmp";
+/// Directories within the root used by the provider itself, which are not containers
+const INTERNAL_DIRS: [&str; 3] = [MULTIPART_DIR, METADATA_DIR, TEMP_DIR];
+
 #[derive(Default, Debug, Clone)]
 struct FsProviderConfig {
     root: Arc<PathBuf>,
@@ -41,12 +80,56 @@ struct FsProviderConfig {
 #[derive(Default, Clone)]
 pub struct FsProvider {
     config: Arc<RwLock<HashMap<String, FsProviderConfig>>>,
+    /// Signer of pre-signed URLs, only set if the pre-signed request endpoint is enabled
+    presigner: Option<Arc<Presigner>>,
+    /// Object change watch tasks, keyed by (target component ID, link name)
+    watchers: Arc<RwLock<HashMap<(String, String), JoinHandle<()>>>>,
+    /// Lock serializing conditional `wasmcloud:blobstore/objects` operations
+    objects_lock: Arc<Mutex<()>>,
+}
+
+/// Determine the root directory from link configuration and ensure it exists
+async fn root_from_config(
+    config: &HashMap<String, String>,
+    component_id: &str,
+) -> anyhow::Result<PathBuf> {
+    // Determine the root path value
+    let root_val: PathBuf = match config.iter().find(|(key, _)| key.to_uppercase() == "ROOT") {
+        None => {
+            // If no root is specified, use the tempdir and create a specific directory for this component
+            let root = std::env::temp_dir();
+            // Resolve the subpath from the root to the component ID, carefully
+            match resolve_subpath(&root, component_id) {
+                Ok(path) => path,
+                Err(e) => {
+                    error!("Failed to resolve subpath to component directory: {e}");
+                    return Err(anyhow!(e).context("failed to resolve subpath to component dir"));
+                }
+            }
+        }
+        // If a root is manually specified, use that path exactly
+        Some((_, value)) => value.into(),
+    };
+
+    // Ensure the root path exists
+    if let Err(e) = create_dir_all(&root_val).await {
+        error!("Could not create component directory: {:?}", e);
+        return Err(anyhow!(e).context("failed to create component directory"));
+    }
+    Ok(root_val.clean())
 }
 
 pub async fn run() -> anyhow::Result<()> {
     FsProvider::run().await
 }
 
+/// Serve both `wrpc:blobstore` and `wasmcloud:blobstore` extension exports
+async fn serve(wrpc: &WrpcClient, provider: FsProvider) -> anyhow::Result<InvocationStreams> {
+    let mut invocations = wrpc_interface_blobstore::bindings::serve(wrpc, provider.clone()).await?;
+    invocations.extend(bindings::serve(wrpc, provider).await?);
+    Ok(invocations)
+}
+
 impl FsProvider {
     pub async fn run() -> anyhow::Result<()> {
         initialize_observability!(
@@ -54,7 +137,33 @@ impl FsProvider {
             std::env::var_os("PROVIDER_BLOBSTORE_FS_FLAMEGRAPH_PATH")
         );
 
-        let provider = Self::default();
+        let host_data = load_host_data().context("failed to load host data")?;
+        let mut provider = Self::default();
+        // The pre-signed request endpoint is only served if an address to listen on is configured
+        if let Some(addr) = host_data.config.get("PRESIGN_ADDRESS") {
+            let addr: SocketAddr = addr.parse().context("failed to parse `PRESIGN_ADDRESS`")?;
+            let listener = TcpListener::bind(addr).await.with_context(|| {
+                format!("failed to bind pre-signed request endpoint on `{addr}`")
+            })?;
+            let addr = listener
+                .local_addr()
+                .context("failed to lookup bound address")?;
+            let base_url = host_data
+                .config
+                .get("PRESIGN_BASE_URL")
+                .cloned()
+                .unwrap_or_else(|| format!("http://{addr}/"))
+                .parse()
+                .context("failed to parse `PRESIGN_BASE_URL`")?;
+            provider.presigner = Some(Arc::new(Presigner::new(base_url)?));
+            info!(%addr, "serving pre-signed requests");
+            let server = presign::serve(listener, provider.clone());
+            tokio::spawn(async move {
+                if let Err(err) = server.await {
+                    error!(?err, "pre-signed request endpoint failed");
+                }
+            });
+        }
         let shutdown = run_provider(provider.clone(), "blobstore-fs-provider")
             .await
             .context("failed to run provider")?;
@@ -98,6 +207,31 @@ fn resolve_subpath(root: &Path, path: impl AsRef<Path>) -> Result<PathBuf, std::
     Ok(joined)
 }
 
+/// Resolve the directory of `container` below `root`. Containers must be named and must not be
+/// one of the directories used by the provider itself
+fn resolve_container(root: &Path, container: impl AsRef<Path>) -> anyhow::Result<PathBuf> {
+    let container = container.as_ref();
+    match container.clean().components().next() {
+        Some(Component::Normal(name)) => ensure!(
+            !INTERNAL_DIRS.iter().any(|dir| name == *dir),
+            "container name `{}` is reserved",
+            container.display()
+        ),
+        _ => bail!("invalid container name `{}`", container.display()),
+    }
+    resolve_subpath(root, container).context("failed to resolve subpath")
+}
+
+/// Resolve the path of `object` within `container` below `root`
+fn resolve_object(
+    root: &Path,
+    container: impl AsRef<Path>,
+    object: impl AsRef<Path>,
+) -> anyhow::Result<PathBuf> {
+    let container = resolve_container(root, container)?;
+    resolve_subpath(&container, object).context("failed to resolve subpath")
+}
+
 impl FsProvider {
     async fn get_root(&self, context: Option<Context>) -> anyhow::Result<Arc<PathBuf>> {
         if let Some(ref source_id) = context.and_then(|Context { component, .. }| component) {
@@ -122,7 +256,7 @@ impl FsProvider {
             .get_root(context)
             .await
             .context("failed to get container root")?;
-        resolve_subpath(&root, container).context("failed to resolve subpath")
+        resolve_container(&root, container)
     }
 
     async fn get_object(
@@ -130,12 +264,79 @@ impl FsProvider {
         context: Option<Context>,
         ObjectId { container, object }: ObjectId,
     ) -> anyhow::Result<PathBuf> {
-        let container = self
-            .get_container(context, container)
+        let root = self
+            .get_root(context)
             .await
-            .context("failed to get container")?;
-        resolve_subpath(&container, object).context("failed to resolve subpath")
+            .context("failed to get container root")?;
+        resolve_object(&root, container, object)
     }
+
+    /// Resolve the staging directory of multipart upload `upload_id` and ensure it belongs to `id`
+    async fn get_upload(
+        &self,
+        context: Option<Context>,
+        ExtObjectId { container, object }: &ExtObjectId,
+        upload_id: &str,
+    ) -> anyhow::Result<PathBuf> {
+        let upload_id = uuid::Uuid::try_parse(upload_id).context("invalid upload ID")?;
+        let root = self
+            .get_root(context)
+            .await
+            .context("failed to get container root")?;
+        let dir = root
+            .join(MULTIPART_DIR)
+            .join(upload_id.simple().to_string());
+        let target = fs::read(dir.join(MULTIPART_TARGET))
+            .await
+            .with_context(|| format!("upload `{upload_id}` not found"))?;
+        if target != multipart_target(container, object) {
+            bail!("upload `{upload_id}` does not belong to object `{object}` in container `{container}`")
+        }
+        Ok(dir)
+    }
+}
+
+/// Contents of the target file of a multipart upload staging directory
+fn multipart_target(container: &str, object: &str) -> Vec<u8> {
+    format!("{container}\0{object}").into_bytes()
+}
+
+/// Parse a staged part file name of the form `{part_number:010}-{etag}`
+fn parse_part_name(name: &str) -> Option<(u32, &str)> {
+    let (n, etag) = name.split_once('-')?;
+    Some((n.parse().ok()?, etag))
+}
+
+/// List the parts staged in the upload directory, ordered by part number
+async fn staged_parts(dir: &Path) -> anyhow::Result<Vec<(CompletedPart, PathBuf)>> {
+    let mut entries = fs::read_dir(dir)
+        .await
+        .context("failed to read upload directory")?;
+    let mut parts = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .context("failed to read upload directory entry")?
+    {
+        let name = entry.file_name();
+        let Some((part_number, etag)) = name.to_str().and_then(parse_part_name) else {
+            continue;
+        };
+        let md = entry
+            .metadata()
+            .await
+            .context("failed to lookup part metadata")?;
+        parts.push((
+            CompletedPart {
+                part_number,
+                etag: etag.to_string(),
+                size: md.len(),
+            },
+            entry.path(),
+        ));
+    }
+    parts.sort_by_key(|(CompletedPart { part_number, .. }, _)| *part_number);
+    Ok(parts)
 }
 
 impl Handler<Option<Context>> for FsProvider {
@@ -327,14 +528,9 @@ impl Handler<Option<Context>> for FsProvider {
         Ok(async {
             propagate_trace_for_ctx!(cx);
             let root = self.get_root(cx).await.context("failed to get root")?;
-            let src_container = resolve_subpath(&root, src.container)
-                .context("failed to resolve source container path")?;
-            let src = resolve_subpath(&src_container, src.object)
+            let src = resolve_object(&root, src.container, src.object)
                 .context("failed to resolve source object path")?;
-
-            let dest_container = resolve_subpath(&root, dest.container)
-                .context("failed to resolve destination container path")?;
-            let dest = resolve_subpath(&dest_container, dest.object)
+            let dest = resolve_object(&root, dest.container, dest.object)
                 .context("failed to resolve destination object path")?;
             debug!("copy `{}` to `{}`", src.display(), dest.display());
             fs::copy(src, dest).await.context("failed to copy")?;
@@ -519,14 +715,9 @@ impl Handler<Option<Context>> for FsProvider {
         Ok(async {
             propagate_trace_for_ctx!(cx);
             let root = self.get_root(cx).await.context("failed to get root")?;
-            let src_container = resolve_subpath(&root, src.container)
-                .context("failed to resolve source container path")?;
-            let src = resolve_subpath(&src_container, src.object)
+            let src = resolve_object(&root, src.container, src.object)
                 .context("failed to resolve source object path")?;
-
-            let dest_container = resolve_subpath(&root, dest.container)
-                .context("failed to resolve destination container path")?;
-            let dest = resolve_subpath(&dest_container, dest.object)
+            let dest = resolve_object(&root, dest.container, dest.object)
                 .context("failed to resolve destination object path")?;
             debug!("copy `{}` to `{}`", src.display(), dest.display());
             fs::copy(&src, dest).await.context("failed to copy")?;
@@ -584,6 +775,258 @@ impl Handler<Option<Context>> for FsProvider {
     }
 }
 
+impl presign_ext::Handler<Option<Context>> for FsProvider {
+    #[instrument(level = "trace", skip(self))]
+    async fn presign_get(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        expires_in: u64,
+    ) -> anyhow::Result<Result<PresignedRequest, String>> {
+        Ok(self
+            .presign(cx, id, "GET", expires_in)
+            .await
+            .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn presign_put(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        expires_in: u64,
+    ) -> anyhow::Result<Result<PresignedRequest, String>> {
+        Ok(self
+            .presign(cx, id, "PUT", expires_in)
+            .await
+            .map_err(|err| format!("{err:#}")))
+    }
+}
+
+impl FsProvider {
+    async fn presign(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        method: &str,
+        expires_in: u64,
+    ) -> anyhow::Result<PresignedRequest> {
+        propagate_trace_for_ctx!(cx);
+        let presigner = self
+            .presigner
+            .as_ref()
+            .context("pre-signed requests are not enabled, set `PRESIGN_ADDRESS` to enable them")?;
+        let component = cx
+            .as_ref()
+            .and_then(|Context { component, .. }| component.clone())
+            .context("failed to lookup invocation source ID")?;
+        // Ensure the object path is valid before handing out a URL for it
+        self.get_object(
+            cx,
+            ObjectId {
+                container: id.container.clone(),
+                object: id.object.clone(),
+            },
+        )
+        .await?;
+        let (url, expires_at) = presigner.sign(
+            method,
+            &component,
+            &id.container,
+            &id.object,
+            Duration::from_secs(expires_in),
+        )?;
+        Ok(PresignedRequest {
+            url: url.into(),
+            method: method.into(),
+            headers: Vec::default(),
+            expires_at,
+        })
+    }
+}
+
+impl multipart::Handler<Option<Context>> for FsProvider {
+    #[instrument(level = "trace", skip(self))]
+    async fn create_upload(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+    ) -> anyhow::Result<Result<String, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let root = self
+                .get_root(cx)
+                .await
+                .context("failed to get container root")?;
+            // Validate the object path upfront, the same way as on completion, so that the upload
+            // cannot fail on completion due to an invalid object path
+            resolve_object(&root, &id.container, &id.object)?;
+            let upload_id = uuid::Uuid::new_v4().simple().to_string();
+            let dir = root.join(MULTIPART_DIR).join(&upload_id);
+            fs::create_dir_all(&dir)
+                .await
+                .context("failed to create upload directory")?;
+            fs::write(
+                dir.join(MULTIPART_TARGET),
+                multipart_target(&id.container, &id.object),
+            )
+            .await
+            .context("failed to write upload target")?;
+            debug!(dir = ?dir.display(), "created multipart upload");
+            Ok(upload_id)
+        }
+        .await
+        .map_err(|err: anyhow::Error| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self, data))]
+    async fn upload_part(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        upload_id: String,
+        part_number: u32,
+        data: Bytes,
+    ) -> anyhow::Result<Result<CompletedPart, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            ensure!(part_number > 0, "part numbers start at 1");
+            let dir = self.get_upload(cx, &id, &upload_id).await?;
+            let etag = hex::encode(Sha256::digest(&data));
+            // Write the part to a temporary file first, so that a failed upload does not
+            // clobber a previously uploaded part with the same number
+            let tmp = dir.join(format!("tmp-{}", uuid::Uuid::new_v4().simple()));
+            let mut file = File::create(&tmp)
+                .await
+                .context("failed to create part file")?;
+            file.write_all(&data)
+                .await
+                .context("failed to write part file")?;
+            file.sync_all().await.context("failed to sync part file")?;
+            for (part, path) in staged_parts(&dir).await? {
+                if part.part_number == part_number {
+                    fs::remove_file(path)
+                        .await
+                        .context("failed to remove replaced part")?;
+                }
+            }
+            fs::rename(&tmp, dir.join(format!("{part_number:010}-{etag}")))
+                .await
+                .context("failed to rename part file")?;
+            Ok(CompletedPart {
+                part_number,
+                etag,
+                size: data.len().try_into().unwrap_or(u64::MAX),
+            })
+        }
+        .await
+        .map_err(|err: anyhow::Error| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn list_parts(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        upload_id: String,
+    ) -> anyhow::Result<Result<Vec<CompletedPart>, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let dir = self.get_upload(cx, &id, &upload_id).await?;
+            let parts = staged_parts(&dir).await?;
+            Ok(parts.into_iter().map(|(part, _)| part).collect())
+        }
+        .await
+        .map_err(|err: anyhow::Error| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn complete_upload(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        upload_id: String,
+        parts: Vec<CompletedPart>,
+    ) -> anyhow::Result<Result<(), String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            ensure!(!parts.is_empty(), "at least one part is required");
+            ensure!(
+                parts
+                    .windows(2)
+                    .all(|w| w[0].part_number < w[1].part_number),
+                "parts must be in ascending order of part number"
+            );
+            let dir = self.get_upload(cx.clone(), &id, &upload_id).await?;
+            let staged: HashMap<_, _> = staged_parts(&dir)
+                .await?
+                .into_iter()
+                .map(|(part, path)| (part.part_number, (part.etag, path)))
+                .collect();
+            let assembled = dir.join("object");
+            let mut file = File::create(&assembled)
+                .await
+                .context("failed to create object file")?;
+            for CompletedPart {
+                part_number, etag, ..
+            } in &parts
+            {
+                let Some((staged_etag, path)) = staged.get(part_number) else {
+                    bail!("part `{part_number}` was not uploaded")
+                };
+                ensure!(staged_etag == etag, "part `{part_number}` ETag mismatch");
+                let mut part = File::open(path).await.context("failed to open part")?;
+                io::copy(&mut part, &mut file)
+                    .await
+                    .context("failed to copy part")?;
+            }
+            file.sync_all()
+                .await
+                .context("failed to sync object file")?;
+            let path = self
+                .get_object(
+                    cx,
+                    ObjectId {
+                        container: id.container,
+                        object: id.object,
+                    },
+                )
+                .await?;
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .await
+                    .context("failed to create parent directories")?;
+            }
+            fs::rename(&assembled, &path)
+                .await
+                .context("failed to move assembled object")?;
+            fs::remove_dir_all(&dir)
+                .await
+                .context("failed to remove upload directory")
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn abort_upload(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        upload_id: String,
+    ) -> anyhow::Result<Result<(), String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let dir = self.get_upload(cx, &id, &upload_id).await?;
+            fs::remove_dir_all(&dir)
+                .await
+                .context("failed to remove upload directory")
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+}
+
 impl Provider for FsProvider {
     /// The fs provider has one configuration parameter, the root of the file system
     async fn receive_link_config_as_target(
@@ -596,35 +1039,9 @@ impl Provider for FsProvider {
             info!("link definition configuration [{k}] set to [{v}]");
         }
 
-        // Determine the root path value
-        let root_val: PathBuf = match config.iter().find(|(key, _)| key.to_uppercase() == "ROOT") {
-            None => {
-                // If no root is specified, use the tempdir and create a specific directory for this component
-                let root = std::env::temp_dir();
-                // Resolve the subpath from the root to the component ID, carefully
-                match resolve_subpath(&root, source_id) {
-                    Ok(path) => path,
-                    Err(e) => {
-                        error!("Failed to resolve subpath to component directory: {e}");
-                        return Err(
-                            anyhow!(e).context("failed to resolve subpath to component dir")
-                        );
-                    }
-                }
-            }
-            // If a root is manually specified, use that path exactly
-            Some((_, value)) => value.into(),
-        };
-
-        // Ensure the root path exists
-        if let Err(e) = create_dir_all(&root_val).await {
-            error!("Could not create component directory: {:?}", e);
-            return Err(anyhow!(e).context("failed to create component directory"));
-        }
-
         // Build configuration for FS Provider to use later
         let config = FsProviderConfig {
-            root: Arc::new(root_val.clean()),
+            root: Arc::new(root_from_config(config, source_id).await?),
         };
 
         info!("Saved FsProviderConfig: {:#?}", config);
@@ -649,8 +1066,70 @@ impl Provider for FsProvider {
         Ok(())
     }
 
+    /// Deliver object change notifications to the linked component via `wasmcloud:blobstore/watcher`.
+    ///
+    /// The same `ROOT` configuration as for links targeting the provider is used, notifications
+    /// can be restricted to a comma-separated list of `CONTAINERS`.
+    #[instrument(level = "info", skip_all, fields(target_id))]
+    async fn receive_link_config_as_source(
+        &self,
+        LinkConfig {
+            target_id,
+            config,
+            link_name,
+            wit_metadata: (_, _, interfaces),
+            ..
+        }: LinkConfig<'_>,
+    ) -> anyhow::Result<()> {
+        if !interfaces.iter().any(|interface| interface == "watcher") {
+            return Ok(());
+        }
+        let root = root_from_config(config, target_id).await?;
+        let containers = config
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("CONTAINERS"))
+            .map(|(_, containers)| {
+                containers
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|container| !container.is_empty())
+                    .map(String::from)
+                    .collect()
+            });
+        let wrpc = get_connection()
+            .get_wrpc_client(target_id)
+            .await
+            .context("failed to construct wRPC client")?;
+        info!(root = ?root.display(), ?containers, "watching for object changes");
+        let task = tokio::spawn(watch::watch(root, containers, wrpc)?);
+        if let Some(task) = self
+            .watchers
+            .write()
+            .await
+            .insert((target_id.into(), link_name.into()), task)
+        {
+            task.abort();
+        }
+        Ok(())
+    }
+
+    #[instrument(level = "info", skip_all, fields(target_id = info.get_target_id()))]
+    async fn delete_link_as_source(&self, info: impl LinkDeleteInfo) -> anyhow::Result<()> {
+        let key = (
+            info.get_target_id().to_string(),
+            info.get_link_name().to_string(),
+        );
+        if let Some(task) = self.watchers.write().await.remove(&key) {
+            task.abort();
+        }
+        Ok(())
+    }
+
     async fn shutdown(&self) -> anyhow::Result<()> {
         self.config.write().await.drain();
+        for (_, task) in self.watchers.write().await.drain() {
+            task.abort();
+        }
         Ok(())
     }
 }
@@ -689,7 +1168,10 @@ mod tests {
                 root: Arc::new(root_path.clone()),
             },
         );
-        let provider = FsProvider { config };
+        let provider = FsProvider {
+            config,
+            ..Default::default()
+        };
 
         // Create a mock Context and ObjectId
         let context = Some(Context {
@@ -726,4 +1208,239 @@ mod tests {
         let contents = tokio::fs::read_to_string(file_path).await.unwrap();
         assert_eq!(contents, "Hello, world!");
     }
+
+    #[tokio::test]
+    async fn test_reserved_containers() {
+        let temp_dir = tempdir().unwrap();
+        let root_path = temp_dir.path().to_path_buf();
+
+        let config = Arc::new(RwLock::new(HashMap::new()));
+        config.write().await.insert(
+            "test_source".to_string(),
+            FsProviderConfig {
+                root: Arc::new(root_path.clone()),
+            },
+        );
+        let provider = FsProvider {
+            config,
+            presigner: Some(Arc::new(
+                Presigner::new("http://127.0.0.1:1234/".parse().unwrap()).unwrap(),
+            )),
+            ..Default::default()
+        };
+        let context = Some(Context {
+            component: Some("test_source".to_string()),
+            ..Default::default()
+        });
+        tokio::fs::create_dir_all(root_path.join("test_container"))
+            .await
+            .unwrap();
+        tokio::fs::write(root_path.join("test_container/file.txt"), "test")
+            .await
+            .unwrap();
+        let file = ObjectId {
+            container: "test_container".to_string(),
+            object: "file.txt".to_string(),
+        };
+
+        for name in [
+            MULTIPART_DIR,
+            "./.multipart-uploads/x",
+            "foo/../.multipart-uploads",
+            METADATA_DIR,
+            "./.object-metadata/test_container",
+            TEMP_DIR,
+            ".",
+            "",
+            "foo/..",
+        ] {
+            assert!(
+                provider
+                    .create_container(context.clone(), name.to_string())
+                    .await
+                    .unwrap()
+                    .is_err(),
+                "`{name}` should be rejected"
+            );
+            assert!(provider
+                .delete_container(context.clone(), name.to_string())
+                .await
+                .unwrap()
+                .is_err());
+            assert!(provider
+                .container_exists(context.clone(), name.to_string())
+                .await
+                .unwrap()
+                .is_err());
+
+            // Objects within reserved containers are not accessible either, including by
+            // referring to the reserved directories through the object path
+            for object in ["x", ".tmp/x", ".object-metadata/test_container/file.txt"] {
+                let reserved = ObjectId {
+                    container: name.to_string(),
+                    object: object.to_string(),
+                };
+                assert!(
+                    provider
+                        .copy_object(context.clone(), file.clone(), reserved.clone())
+                        .await
+                        .unwrap()
+                        .is_err(),
+                    "copy into `{name}/{object}` should be rejected"
+                );
+                assert!(provider
+                    .copy_object(context.clone(), reserved.clone(), file.clone())
+                    .await
+                    .unwrap()
+                    .is_err());
+                assert!(provider
+                    .move_object(context.clone(), reserved.clone(), file.clone())
+                    .await
+                    .unwrap()
+                    .is_err());
+                assert!(provider
+                    .move_object(context.clone(), file.clone(), reserved.clone())
+                    .await
+                    .unwrap()
+                    .is_err());
+                let reserved = ExtObjectId {
+                    container: reserved.container,
+                    object: reserved.object,
+                };
+                assert!(
+                    multipart::Handler::create_upload(&provider, context.clone(), reserved.clone())
+                        .await
+                        .unwrap()
+                        .is_err(),
+                    "upload to `{name}/{object}` should be rejected"
+                );
+                assert!(presign_ext::Handler::presign_get(
+                    &provider,
+                    context.clone(),
+                    reserved.clone(),
+                    60
+                )
+                .await
+                .unwrap()
+                .is_err());
+                assert!(presign_ext::Handler::presign_put(
+                    &provider,
+                    context.clone(),
+                    reserved,
+                    60
+                )
+                .await
+                .unwrap()
+                .is_err());
+            }
+        }
+        assert!(root_path.join("test_container/file.txt").exists());
+        assert!(!root_path.join(TEMP_DIR).exists());
+        assert!(!root_path.join(MULTIPART_DIR).exists());
+        assert!(!root_path.join(METADATA_DIR).join("test_container").exists());
+
+        provider
+            .create_container(context.clone(), ".multipart".to_string())
+            .await
+            .unwrap()
+            .expect("non-reserved container should be created");
+    }
+
+    #[tokio::test]
+    async fn test_multipart_upload() {
+        let temp_dir = tempdir().unwrap();
+        let root_path = temp_dir.path().to_path_buf();
+
+        let config = Arc::new(RwLock::new(HashMap::new()));
+        config.write().await.insert(
+            "test_source".to_string(),
+            FsProviderConfig {
+                root: Arc::new(root_path.clone()),
+            },
+        );
+        let provider = FsProvider {
+            config,
+            ..Default::default()
+        };
+        let context = Some(Context {
+            component: Some("test_source".to_string()),
+            ..Default::default()
+        });
+        let id = ExtObjectId {
+            container: "test_container".to_string(),
+            object: "multipart.txt".to_string(),
+        };
+
+        let upload_id = multipart::Handler::create_upload(&provider, context.clone(), id.clone())
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Upload parts out of order and replace one of them
+        for (part_number, data) in [(2, "world!"), (1, "Bye, "), (1, "Hello, ")] {
+            multipart::Handler::upload_part(
+                &provider,
+                context.clone(),
+                id.clone(),
+                upload_id.clone(),
+                part_number,
+                Bytes::from(data),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        }
+
+        let parts = multipart::Handler::list_parts(
+            &provider,
+            context.clone(),
+            id.clone(),
+            upload_id.clone(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            parts
+                .iter()
+                .map(
+                    |CompletedPart {
+                         part_number, size, ..
+                     }| (*part_number, *size)
+                )
+                .collect::<Vec<_>>(),
+            [(1, 7), (2, 6)]
+        );
+
+        // The upload cannot be accessed through a different object
+        assert!(multipart::Handler::list_parts(
+            &provider,
+            context.clone(),
+            ExtObjectId {
+                container: "test_container".to_string(),
+                object: "other.txt".to_string(),
+            },
+            upload_id.clone(),
+        )
+        .await
+        .unwrap()
+        .is_err());
+
+        multipart::Handler::complete_upload(
+            &provider,
+            context.clone(),
+            id.clone(),
+            upload_id.clone(),
+            parts,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        let contents = tokio::fs::read_to_string(root_path.join("test_container/multipart.txt"))
+            .await
+            .unwrap();
+        assert_eq!(contents, "Hello, world!");
+        assert!(!root_path.join(MULTIPART_DIR).join(&upload_id).exists());
+    }
 }
@@ -0,0 +1,498 @@
+//! Filesystem implementation of `wasmcloud:blobstore/objects`.
+//!
+//! Content types and user-defined metadata are stored in JSON files under [`METADATA_DIR`],
+//! mirroring the layout of the containers. ETags are derived from the modification time and size
+//! of the object file, like most HTTP file servers do.
+//!
+//! Conditional operations are serialized within the provider, so they are only atomic with respect
+//! to each other and not to writes performed via `wrpc:blobstore` or directly on the filesystem.
+
+use core::fmt;
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{anyhow, Context as _};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use tokio::fs::{self, File};
+use tokio::io::AsyncWriteExt as _;
+use tracing::{debug, instrument};
+use wasmcloud_provider_sdk::{propagate_trace_for_ctx, Context};
+
+use crate::bindings::exports::wasmcloud::blobstore::objects;
+use crate::bindings::wasmcloud::blobstore::types::{
+    Condition, ObjectError, ObjectId, ObjectInfo, WriteOptions,
+};
+use crate::{resolve_object, resolve_subpath, FsProvider, METADATA_DIR, TEMP_DIR};
+
+/// Error returned when the condition of an operation does not hold
+#[derive(Debug)]
+struct PreconditionFailed;
+
+impl fmt::Display for PreconditionFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("precondition failed")
+    }
+}
+
+impl std::error::Error for PreconditionFailed {}
+
+fn object_error(err: anyhow::Error) -> ObjectError {
+    if err.is::<PreconditionFailed>() {
+        ObjectError::PreconditionFailed
+    } else {
+        ObjectError::Other(format!("{err:#}"))
+    }
+}
+
+/// Metadata of an object, stored under [`METADATA_DIR`]
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct StoredMetadata {
+    /// ETag of the object version the metadata was written for
+    etag: String,
+    content_type: Option<String>,
+    metadata: Vec<(String, String)>,
+}
+
+/// Derive the ETag of an object file from its metadata
+fn file_etag(md: &std::fs::Metadata) -> anyhow::Result<String> {
+    let modified = md
+        .modified()
+        .context("failed to get file modification time")?
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .context("modification time before Unix epoch")?;
+    Ok(format!("{:x}-{:x}", modified.as_nanos(), md.len()))
+}
+
+/// Look up the ETag of the object file at `path`, [`None`] if it does not exist
+async fn current_etag(path: &Path) -> anyhow::Result<Option<String>> {
+    match fs::metadata(path).await {
+        Ok(md) => file_etag(&md).map(Some),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(anyhow!(err).context("failed to lookup file metadata")),
+    }
+}
+
+/// Whether `condition` holds for an object with current `etag`, [`None`] if it does not exist
+fn condition_holds(condition: Option<&Condition>, etag: Option<&str>) -> bool {
+    match (condition, etag) {
+        (None, _) => true,
+        (Some(Condition::IfMatch(expected)), Some(etag)) => expected == etag,
+        (Some(Condition::IfMatch(_)), None) => false,
+        (Some(Condition::IfNoneMatch), etag) => etag.is_none(),
+    }
+}
+
+/// Resolve the paths of the object file and of its metadata file.
+///
+/// The container is validated by [`resolve_object`], so reserved directories cannot be accessed.
+fn object_paths(root: &Path, container: &str, object: &str) -> anyhow::Result<(PathBuf, PathBuf)> {
+    let path = resolve_object(root, container, object).context("failed to resolve object path")?;
+    let md_path = resolve_subpath(&root.join(METADATA_DIR), container)
+        .and_then(|container| resolve_subpath(&container, object))
+        .context("failed to resolve metadata path")?;
+    let mut md_path = md_path.into_os_string();
+    md_path.push(".json");
+    Ok((path, md_path.into()))
+}
+
+/// Read the stored metadata of the object version with `etag`.
+///
+/// Metadata is ignored if the object has since been written without it.
+async fn read_metadata(path: &Path, etag: &str) -> anyhow::Result<StoredMetadata> {
+    let buf = match fs::read(path).await {
+        Ok(buf) => buf,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Ok(StoredMetadata::default())
+        }
+        Err(err) => return Err(anyhow!(err).context("failed to read object metadata")),
+    };
+    let md: StoredMetadata =
+        serde_json::from_slice(&buf).context("failed to parse object metadata")?;
+    if md.etag == etag {
+        Ok(md)
+    } else {
+        debug!(path = ?path.display(), "ignoring stale object metadata");
+        Ok(StoredMetadata::default())
+    }
+}
+
+/// Remove the file at `path`, if it exists
+async fn remove_file(path: &Path) -> anyhow::Result<()> {
+    match fs::remove_file(path).await {
+        Ok(()) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => {
+            Err(anyhow!(err).context(format!("failed to remove file at `{}`", path.display())))
+        }
+    }
+}
+
+impl FsProvider {
+    async fn write_object_file(
+        &self,
+        context: Option<Context>,
+        ObjectId { container, object }: ObjectId,
+        data: Bytes,
+        WriteOptions {
+            content_type,
+            metadata,
+            condition,
+        }: WriteOptions,
+    ) -> anyhow::Result<String> {
+        let root = self.get_root(context).await?;
+        let (path, md_path) = object_paths(&root, &container, &object)?;
+
+        // Stage the data outside of the container, so that the object is replaced atomically
+        let tmp_dir = root.join(TEMP_DIR);
+        fs::create_dir_all(&tmp_dir)
+            .await
+            .context("failed to create temporary directory")?;
+        let tmp = tmp_dir.join(uuid::Uuid::new_v4().simple().to_string());
+        let mut file = File::create(&tmp)
+            .await
+            .context("failed to create temporary file")?;
+        file.write_all(&data)
+            .await
+            .context("failed to write temporary file")?;
+        file.sync_all()
+            .await
+            .context("failed to sync temporary file")?;
+
+        let _lock = self.objects_lock.lock().await;
+        let etag = current_etag(&path).await?;
+        if !condition_holds(condition.as_ref(), etag.as_deref()) {
+            remove_file(&tmp).await?;
+            return Err(PreconditionFailed.into());
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .context("failed to create parent directories")?;
+        }
+        fs::rename(&tmp, &path)
+            .await
+            .context("failed to move object into place")?;
+        let md = fs::metadata(&path)
+            .await
+            .context("failed to lookup file metadata")?;
+        let etag = file_etag(&md)?;
+        if content_type.is_none() && metadata.is_empty() {
+            remove_file(&md_path).await?;
+        } else {
+            if let Some(parent) = md_path.parent() {
+                fs::create_dir_all(parent)
+                    .await
+                    .context("failed to create metadata directories")?;
+            }
+            let md = serde_json::to_vec(&StoredMetadata {
+                etag: etag.clone(),
+                content_type,
+                metadata,
+            })
+            .context("failed to encode object metadata")?;
+            fs::write(&md_path, md)
+                .await
+                .context("failed to write object metadata")?;
+        }
+        debug!(path = ?path.display(), etag, "wrote object");
+        Ok(etag)
+    }
+
+    async fn delete_object_file(
+        &self,
+        context: Option<Context>,
+        ObjectId { container, object }: ObjectId,
+        condition: Option<Condition>,
+    ) -> anyhow::Result<()> {
+        let root = self.get_root(context).await?;
+        let (path, md_path) = object_paths(&root, &container, &object)?;
+        let _lock = self.objects_lock.lock().await;
+        let etag = current_etag(&path).await?;
+        if !condition_holds(condition.as_ref(), etag.as_deref()) {
+            return Err(PreconditionFailed.into());
+        }
+        remove_file(&path).await?;
+        remove_file(&md_path).await
+    }
+}
+
+impl objects::Handler<Option<Context>> for FsProvider {
+    #[instrument(level = "trace", skip(self))]
+    async fn get_object_info(
+        &self,
+        cx: Option<Context>,
+        ObjectId { container, object }: ObjectId,
+    ) -> anyhow::Result<Result<ObjectInfo, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let root = self.get_root(cx).await?;
+            let (path, md_path) = object_paths(&root, &container, &object)?;
+            let md = fs::metadata(&path)
+                .await
+                .context("failed to lookup file metadata")?;
+            let etag = file_etag(&md)?;
+            let StoredMetadata {
+                content_type,
+                metadata,
+                ..
+            } = read_metadata(&md_path, &etag).await?;
+            // NOTE: Some platforms don't have support for creation time, so we default to the unix epoch
+            let created_at = md
+                .created()
+                .ok()
+                .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+                .map_or(0, |t| t.as_secs());
+            anyhow::Ok(ObjectInfo {
+                name: object,
+                container,
+                size: md.len(),
+                created_at,
+                etag,
+                content_type,
+                metadata,
+            })
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self, data))]
+    async fn write_object(
+        &self,
+        cx: Option<Context>,
+        id: ObjectId,
+        data: Bytes,
+        options: WriteOptions,
+    ) -> anyhow::Result<Result<String, ObjectError>> {
+        propagate_trace_for_ctx!(cx);
+        Ok(self
+            .write_object_file(cx, id, data, options)
+            .await
+            .map_err(object_error))
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn delete_object(
+        &self,
+        cx: Option<Context>,
+        id: ObjectId,
+        condition: Option<Condition>,
+    ) -> anyhow::Result<Result<(), ObjectError>> {
+        propagate_trace_for_ctx!(cx);
+        Ok(self
+            .delete_object_file(cx, id, condition)
+            .await
+            .map_err(object_error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use tempfile::tempdir;
+    use tokio::sync::RwLock;
+
+    use super::*;
+    use crate::FsProviderConfig;
+
+    #[test]
+    fn conditions() {
+        let if_match = Condition::IfMatch("a".into());
+        assert!(condition_holds(None, None));
+        assert!(condition_holds(None, Some("a")));
+        assert!(condition_holds(Some(&if_match), Some("a")));
+        assert!(!condition_holds(Some(&if_match), Some("b")));
+        assert!(!condition_holds(Some(&if_match), None));
+        assert!(condition_holds(Some(&Condition::IfNoneMatch), None));
+        assert!(!condition_holds(Some(&Condition::IfNoneMatch), Some("a")));
+    }
+
+    #[tokio::test]
+    async fn test_conditional_writes() {
+        let temp_dir = tempdir().unwrap();
+        let config = Arc::new(RwLock::new(HashMap::new()));
+        config.write().await.insert(
+            "test_source".to_string(),
+            FsProviderConfig {
+                root: Arc::new(temp_dir.path().to_path_buf()),
+            },
+        );
+        let provider = FsProvider {
+            config,
+            ..Default::default()
+        };
+        let context = Some(Context {
+            component: Some("test_source".to_string()),
+            ..Default::default()
+        });
+        let id = ObjectId {
+            container: "test_container".to_string(),
+            object: "dir/object.json".to_string(),
+        };
+        let write = |data: &'static str, condition| {
+            objects::Handler::write_object(
+                &provider,
+                context.clone(),
+                id.clone(),
+                Bytes::from(data),
+                WriteOptions {
+                    content_type: Some("application/json".into()),
+                    metadata: vec![("owner".into(), "test".into())],
+                    condition,
+                },
+            )
+        };
+
+        let etag = write("{}", Some(Condition::IfNoneMatch))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            write("[]", Some(Condition::IfNoneMatch)).await.unwrap(),
+            Err(ObjectError::PreconditionFailed)
+        );
+
+        let info = objects::Handler::get_object_info(&provider, context.clone(), id.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.etag, etag);
+        assert_eq!(info.size, 2);
+        assert_eq!(info.content_type.as_deref(), Some("application/json"));
+        assert_eq!(info.metadata, [("owner".into(), "test".into())]);
+
+        let new_etag = write("[1]", Some(Condition::IfMatch(etag.clone())))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_ne!(etag, new_etag);
+        assert_eq!(
+            write("[2]", Some(Condition::IfMatch(etag.clone())))
+                .await
+                .unwrap(),
+            Err(ObjectError::PreconditionFailed)
+        );
+        assert_eq!(
+            objects::Handler::delete_object(
+                &provider,
+                context.clone(),
+                id.clone(),
+                Some(Condition::IfMatch(etag))
+            )
+            .await
+            .unwrap(),
+            Err(ObjectError::PreconditionFailed)
+        );
+        objects::Handler::delete_object(
+            &provider,
+            context.clone(),
+            id.clone(),
+            Some(Condition::IfMatch(new_etag)),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(!temp_dir
+            .path()
+            .join("test_container/dir/object.json")
+            .exists());
+    }
+
+    #[tokio::test]
+    async fn test_reserved_containers() {
+        let temp_dir = tempdir().unwrap();
+        let config = Arc::new(RwLock::new(HashMap::new()));
+        config.write().await.insert(
+            "test_source".to_string(),
+            FsProviderConfig {
+                root: Arc::new(temp_dir.path().to_path_buf()),
+            },
+        );
+        let provider = FsProvider {
+            config,
+            ..Default::default()
+        };
+        let context = Some(Context {
+            component: Some("test_source".to_string()),
+            ..Default::default()
+        });
+
+        // Write an object with metadata, which must not be reachable via reserved containers
+        objects::Handler::write_object(
+            &provider,
+            context.clone(),
+            ObjectId {
+                container: "test_container".to_string(),
+                object: "file.txt".to_string(),
+            },
+            Bytes::from("test"),
+            WriteOptions {
+                content_type: Some("text/plain".into()),
+                metadata: vec![],
+                condition: None,
+            },
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        let md_path = temp_dir
+            .path()
+            .join(METADATA_DIR)
+            .join("test_container/file.txt.json");
+        assert!(md_path.exists());
+
+        for (container, object) in [
+            (METADATA_DIR, "test_container/file.txt.json"),
+            (TEMP_DIR, "x"),
+            (".", ".object-metadata/test_container/file.txt.json"),
+            ("", ".tmp/x"),
+            (
+                "test_container/..",
+                ".object-metadata/test_container/file.txt.json",
+            ),
+            (
+                "test_container",
+                "../.object-metadata/test_container/file.txt.json",
+            ),
+        ] {
+            let id = ObjectId {
+                container: container.to_string(),
+                object: object.to_string(),
+            };
+            assert!(matches!(
+                objects::Handler::write_object(
+                    &provider,
+                    context.clone(),
+                    id.clone(),
+                    Bytes::from("{}"),
+                    WriteOptions {
+                        content_type: None,
+                        metadata: vec![],
+                        condition: None,
+                    },
+                )
+                .await
+                .unwrap(),
+                Err(ObjectError::Other(_))
+            ));
+            assert!(
+                objects::Handler::get_object_info(&provider, context.clone(), id.clone())
+                    .await
+                    .unwrap()
+                    .is_err()
+            );
+            assert!(matches!(
+                objects::Handler::delete_object(&provider, context.clone(), id, None)
+                    .await
+                    .unwrap(),
+                Err(ObjectError::Other(_))
+            ));
+        }
+        assert!(md_path.exists());
+        assert!(!temp_dir.path().join(TEMP_DIR).join("x").exists());
+    }
+}
@@ -0,0 +1,322 @@
+//! Pre-signed URL support for the filesystem blobstore.
+//!
+//! The filesystem has no notion of pre-signed requests, so the provider serves a small HTTP
+//! endpoint itself. URLs minted by [`Presigner`] carry an HMAC-SHA256 signature over the method,
+//! the linked component, the object and the expiry, which the endpoint verifies before touching
+//! the filesystem. The signing key is generated when the provider starts, so URLs do not survive
+//! provider restarts.
+
+use std::time::{Duration, SystemTime};
+
+use anyhow::{bail, Context as _};
+use axum::body::Body;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse as _, Response};
+use axum::routing::get;
+use axum::Router;
+use futures::TryStreamExt as _;
+use ring::hmac;
+use ring::rand::SystemRandom;
+use serde::Deserialize;
+use tokio::fs::{self, File};
+use tokio::io;
+use tokio::net::TcpListener;
+use tokio_util::io::{ReaderStream, StreamReader};
+use tracing::{debug, error, warn};
+use url::Url;
+
+use crate::{resolve_object, FsProvider};
+
+/// Path of the object endpoint, relative to the base URL
+const OBJECT_PATH: &str = "object";
+
+/// Mints and verifies pre-signed URLs for the provider's HTTP endpoint
+pub(crate) struct Presigner {
+    key: hmac::Key,
+    base_url: Url,
+}
+
+/// Query parameters of a pre-signed request
+#[derive(Debug, Deserialize)]
+struct PresignedQuery {
+    component: String,
+    container: String,
+    object: String,
+    expires: u64,
+    signature: String,
+}
+
+fn signing_payload(
+    method: &str,
+    component: &str,
+    container: &str,
+    object: &str,
+    expires: u64,
+) -> String {
+    format!("{method}\n{component}\n{container}\n{object}\n{expires}")
+}
+
+fn now_secs() -> anyhow::Result<u64> {
+    Ok(SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .context("current time before Unix epoch")?
+        .as_secs())
+}
+
+impl Presigner {
+    /// Create a new [`Presigner`] with a random signing key, minting URLs relative to `base_url`
+    pub(crate) fn new(base_url: Url) -> anyhow::Result<Self> {
+        let key = hmac::Key::generate(hmac::HMAC_SHA256, &SystemRandom::new())
+            .map_err(|_| anyhow::anyhow!("failed to generate signing key"))?;
+        Ok(Self { key, base_url })
+    }
+
+    /// Create a signed URL for `method` on the object, valid for `expires_in`.
+    ///
+    /// Returns the URL and the expiry in seconds since the Unix epoch.
+    pub(crate) fn sign(
+        &self,
+        method: &str,
+        component: &str,
+        container: &str,
+        object: &str,
+        expires_in: Duration,
+    ) -> anyhow::Result<(Url, u64)> {
+        let expires = now_secs()?.saturating_add(expires_in.as_secs());
+        let signature = hmac::sign(
+            &self.key,
+            signing_payload(method, component, container, object, expires).as_bytes(),
+        );
+        let mut url = self
+            .base_url
+            .join(OBJECT_PATH)
+            .context("failed to construct object URL")?;
+        url.query_pairs_mut()
+            .append_pair("component", component)
+            .append_pair("container", container)
+            .append_pair("object", object)
+            .append_pair("expires", &expires.to_string())
+            .append_pair("signature", &hex::encode(signature.as_ref()));
+        Ok((url, expires))
+    }
+
+    /// Verify that the query was signed by this [`Presigner`] for `method` and has not expired
+    fn verify(&self, method: &str, query: &PresignedQuery) -> anyhow::Result<()> {
+        if query.expires < now_secs()? {
+            bail!("pre-signed request expired")
+        }
+        let signature = hex::decode(&query.signature).context("invalid signature encoding")?;
+        hmac::verify(
+            &self.key,
+            signing_payload(
+                method,
+                &query.component,
+                &query.container,
+                &query.object,
+                query.expires,
+            )
+            .as_bytes(),
+            &signature,
+        )
+        .map_err(|_| anyhow::anyhow!("invalid signature"))
+    }
+}
+
+/// Serve the pre-signed request endpoint on `listener`
+pub(crate) async fn serve(listener: TcpListener, provider: FsProvider) -> std::io::Result<()> {
+    let router = Router::new()
+        .route(&format!("/{OBJECT_PATH}"), get(get_object).put(put_object))
+        .with_state(provider);
+    axum::serve(listener, router).await
+}
+
+/// Verify the request and resolve the path of the object on disk
+async fn authorize(
+    provider: &FsProvider,
+    method: &str,
+    query: &PresignedQuery,
+) -> Result<std::path::PathBuf, (StatusCode, String)> {
+    let Some(presigner) = provider.presigner.as_ref() else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            "pre-signed requests are disabled".into(),
+        ));
+    };
+    if let Err(err) = presigner.verify(method, query) {
+        warn!(?err, "rejecting pre-signed request");
+        return Err((StatusCode::FORBIDDEN, format!("{err:#}")));
+    }
+    let root = provider
+        .config
+        .read()
+        .await
+        .get(&query.component)
+        .map(|config| config.root.clone());
+    let Some(root) = root else {
+        return Err((StatusCode::NOT_FOUND, "link not found".into()));
+    };
+    resolve_object(&root, &query.container, &query.object)
+        .map_err(|err| (StatusCode::FORBIDDEN, format!("{err:#}")))
+}
+
+async fn get_object(
+    State(provider): State<FsProvider>,
+    Query(query): Query<PresignedQuery>,
+) -> Response {
+    let path = match authorize(&provider, "GET", &query).await {
+        Ok(path) => path,
+        Err(err) => return err.into_response(),
+    };
+    debug!(path = ?path.display(), "serving pre-signed download");
+    match File::open(&path).await {
+        Ok(file) => Body::from_stream(ReaderStream::new(file)).into_response(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            StatusCode::NOT_FOUND.into_response()
+        }
+        Err(err) => {
+            error!(?err, path = ?path.display(), "failed to open object file");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+async fn put_object(
+    State(provider): State<FsProvider>,
+    Query(query): Query<PresignedQuery>,
+    body: Body,
+) -> Response {
+    let path = match authorize(&provider, "PUT", &query).await {
+        Ok(path) => path,
+        Err(err) => return err.into_response(),
+    };
+    debug!(path = ?path.display(), "serving pre-signed upload");
+    let res = async {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .context("failed to create parent directories")?;
+        }
+        let mut file = File::create(&path).await.context("failed to create file")?;
+        io::copy(
+            &mut StreamReader::new(body.into_data_stream().map_err(std::io::Error::other)),
+            &mut file,
+        )
+        .await
+        .context("failed to write file")
+    }
+    .await;
+    match res {
+        Ok(n) => {
+            debug!(n, path = ?path.display(), "finished pre-signed upload");
+            StatusCode::OK.into_response()
+        }
+        Err(err) => {
+            error!(?err, path = ?path.display(), "failed to handle pre-signed upload");
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query_of(url: &Url) -> PresignedQuery {
+        let pairs: std::collections::HashMap<_, _> = url.query_pairs().collect();
+        PresignedQuery {
+            component: pairs["component"].to_string(),
+            container: pairs["container"].to_string(),
+            object: pairs["object"].to_string(),
+            expires: pairs["expires"].parse().unwrap(),
+            signature: pairs["signature"].to_string(),
+        }
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let presigner = Presigner::new("http://127.0.0.1:1234/".parse().unwrap()).unwrap();
+        let (url, expires) = presigner
+            .sign(
+                "GET",
+                "component",
+                "container",
+                "dir/object name",
+                Duration::from_secs(60),
+            )
+            .unwrap();
+        assert_eq!(url.path(), "/object");
+        let query = query_of(&url);
+        assert_eq!(query.object, "dir/object name");
+        assert_eq!(query.expires, expires);
+        presigner.verify("GET", &query).expect("signature is valid");
+
+        // The signature is bound to the method and object
+        assert!(presigner.verify("PUT", &query).is_err());
+        let query = PresignedQuery {
+            object: "other".into(),
+            ..query_of(&url)
+        };
+        assert!(presigner.verify("GET", &query).is_err());
+    }
+
+    #[tokio::test]
+    async fn reject_reserved_containers() {
+        use std::sync::Arc;
+
+        let root = tempfile::tempdir().unwrap();
+        let presigner =
+            Arc::new(Presigner::new("http://127.0.0.1:1234/".parse().unwrap()).unwrap());
+        let provider = FsProvider {
+            presigner: Some(Arc::clone(&presigner)),
+            ..Default::default()
+        };
+        provider.config.write().await.insert(
+            "component".into(),
+            crate::FsProviderConfig {
+                root: Arc::new(root.path().to_path_buf()),
+            },
+        );
+        for (container, object) in [(".tmp", "x"), (".", ".object-metadata/x"), ("", ".tmp/x")] {
+            let (url, _) = presigner
+                .sign(
+                    "PUT",
+                    "component",
+                    container,
+                    object,
+                    Duration::from_secs(60),
+                )
+                .unwrap();
+            let (status, _) = authorize(&provider, "PUT", &query_of(&url))
+                .await
+                .expect_err("reserved container should be rejected");
+            assert_eq!(status, StatusCode::FORBIDDEN);
+        }
+        let (url, _) = presigner
+            .sign(
+                "PUT",
+                "component",
+                "container",
+                "x",
+                Duration::from_secs(60),
+            )
+            .unwrap();
+        assert_eq!(
+            authorize(&provider, "PUT", &query_of(&url)).await.unwrap(),
+            root.path().join("container/x")
+        );
+    }
+
+    #[test]
+    fn reject_expired() {
+        let presigner = Presigner::new("http://127.0.0.1:1234/".parse().unwrap()).unwrap();
+        let (url, _) = presigner
+            .sign("GET", "component", "container", "object", Duration::ZERO)
+            .unwrap();
+        let query = PresignedQuery {
+            expires: 0,
+            ..query_of(&url)
+        };
+        assert!(presigner.verify("GET", &query).is_err());
+    }
+}
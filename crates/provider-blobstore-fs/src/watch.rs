@@ -0,0 +1,190 @@
+//! Object change notifications for the filesystem blobstore.
+//!
+//! Changes under the root are observed using [`notify`] (inotify on Linux) and delivered to the
+//! component linked to the provider as a target via `wasmcloud:blobstore/watcher`.
+//! The first path component below the root is the container, the rest is the object name.
+
+use core::future::Future;
+
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::Context as _;
+use notify::event::{AccessKind, AccessMode, ModifyKind, RemoveKind, RenameMode};
+use notify::{Event, EventKind, RecursiveMode, Watcher as _};
+use tokio::sync::mpsc;
+use tracing::{debug, error, instrument, warn};
+use wasmcloud_provider_sdk::provider::WrpcClient;
+use wasmcloud_provider_sdk::wasmcloud_tracing::context::TraceContextInjector;
+
+use crate::bindings::wasmcloud::blobstore::types::ObjectId;
+use crate::bindings::wasmcloud::blobstore::watcher;
+use crate::INTERNAL_DIRS;
+
+/// A change to an object
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Change {
+    Create(ObjectId),
+    Delete(ObjectId),
+}
+
+/// Resolve the object located at `path` within `root`
+fn object_id(root: &Path, path: &Path) -> Option<ObjectId> {
+    let mut components = path.strip_prefix(root).ok()?.components();
+    let Some(Component::Normal(container)) = components.next() else {
+        return None;
+    };
+    let container = container.to_str()?;
+    if INTERNAL_DIRS.contains(&container) {
+        return None;
+    }
+    let object = components
+        .map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if object.is_empty() {
+        return None;
+    }
+    Some(ObjectId {
+        container: container.into(),
+        object: object.join("/"),
+    })
+}
+
+/// Determine object changes from a filesystem event.
+///
+/// Objects are considered created once they are closed after writing or moved into place.
+pub(crate) fn changes(root: &Path, event: Event) -> Vec<Change> {
+    let change: fn(ObjectId) -> Change = match event.kind {
+        EventKind::Access(AccessKind::Close(AccessMode::Write))
+        | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => Change::Create,
+        EventKind::Remove(RemoveKind::File | RemoveKind::Any)
+        | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => Change::Delete,
+        _ => return Vec::default(),
+    };
+    event
+        .paths
+        .iter()
+        .filter_map(|path| object_id(root, path))
+        .map(change)
+        .collect()
+}
+
+/// Invoke `wasmcloud:blobstore/watcher` on the component for a change
+#[instrument(level = "debug", skip(wrpc))]
+async fn notify_component(wrpc: &WrpcClient, change: &Change) {
+    let mut cx = async_nats::HeaderMap::new();
+    for (k, v) in TraceContextInjector::default_with_span().iter() {
+        cx.insert(k.as_str(), v.as_str());
+    }
+    let res = match change {
+        Change::Create(id) => watcher::on_create(wrpc, Some(cx), id).await,
+        Change::Delete(id) => watcher::on_delete(wrpc, Some(cx), id).await,
+    };
+    if let Err(err) = res {
+        error!(?err, "failed to notify component of object change");
+    }
+}
+
+/// Start watching `root` for changes to objects in `containers` (or all containers if [`None`]),
+/// returning a future delivering notifications to the component
+pub(crate) fn watch(
+    root: PathBuf,
+    containers: Option<HashSet<String>>,
+    wrpc: WrpcClient,
+) -> anyhow::Result<impl Future<Output = ()>> {
+    let (tx, mut rx) = mpsc::channel(1024);
+    let mut watcher = notify::recommended_watcher(move |res| {
+        // The receiver is only dropped once the watch is stopped
+        let _ = tx.blocking_send(res);
+    })
+    .context("failed to create filesystem watcher")?;
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .with_context(|| format!("failed to watch `{}`", root.display()))?;
+    Ok(async move {
+        // The watcher stops once dropped, so it is owned by the task
+        let _watcher = watcher;
+        while let Some(res) = rx.recv().await {
+            let event = match res {
+                Ok(event) => event,
+                Err(err) => {
+                    warn!(?err, "filesystem watcher error");
+                    continue;
+                }
+            };
+            for change in changes(&root, event) {
+                let (Change::Create(ObjectId { container, .. })
+                | Change::Delete(ObjectId { container, .. })) = &change;
+                if containers
+                    .as_ref()
+                    .is_some_and(|containers| !containers.contains(container))
+                {
+                    continue;
+                }
+                debug!(?change, "object changed");
+                notify_component(&wrpc, &change).await;
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(container: &str, object: &str) -> ObjectId {
+        ObjectId {
+            container: container.into(),
+            object: object.into(),
+        }
+    }
+
+    #[test]
+    fn object_ids() {
+        let root = Path::new("/data");
+        assert_eq!(
+            object_id(root, Path::new("/data/container/dir/object.txt")),
+            Some(id("container", "dir/object.txt"))
+        );
+        assert_eq!(object_id(root, Path::new("/data/container")), None);
+        assert_eq!(object_id(root, Path::new("/other/container/object")), None);
+        assert_eq!(
+            object_id(root, Path::new("/data/.multipart-uploads/id/target")),
+            None
+        );
+        assert_eq!(
+            object_id(
+                root,
+                Path::new("/data/.object-metadata/container/object.json")
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn event_changes() {
+        let root = Path::new("/data");
+        let event = |kind| Event::new(kind).add_path("/data/container/object".into());
+        assert_eq!(
+            changes(
+                root,
+                event(EventKind::Access(AccessKind::Close(AccessMode::Write)))
+            ),
+            [Change::Create(id("container", "object"))]
+        );
+        assert_eq!(
+            changes(root, event(EventKind::Remove(RemoveKind::File))),
+            [Change::Delete(id("container", "object"))]
+        );
+        assert_eq!(
+            changes(
+                root,
+                event(EventKind::Modify(ModifyKind::Name(RenameMode::Both)))
+            ),
+            []
+        );
+    }
+}
@@ -298,6 +298,10 @@ impl bindings::exports::wrpc::blobstore::blobstore::Handler<Option<Context>>
                 Box::pin(async move {
                     while let Some(object) = objects.next().await {
                         let object = object.map_err(|e| format!("{e:#}"))?;
+                        // Skip objects staging in-progress multipart uploads
+                        if crate::extensions::is_multipart_staging(&object.name) {
+                            continue;
+                        }
                         tx.send(vec![object.name])
                             .await
                             .map_err(|e| format!("{e:#}"))?;
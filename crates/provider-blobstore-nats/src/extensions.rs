@@ -0,0 +1,423 @@
+//! NATS implementation for the `wasmcloud:blobstore` extensions to `wrpc:blobstore/blobstore@0.2.0`.
+//!
+//! NATS object stores have no native support for multipart uploads, so parts are staged as
+//! regular objects under [`MULTIPART_PREFIX`] in the target container and assembled into the
+//! target object when the upload is completed.
+//!
+//! Content types are stored in the `Content-Type` header of the object and user-defined metadata
+//! in the object metadata. The object digest is used as the ETag. Object stores cannot be written
+//! conditionally, so conditional operations are not supported.
+
+use anyhow::{bail, ensure, Context as _};
+use async_nats::jetstream::object_store::ObjectStore;
+use bytes::Bytes;
+use futures::{StreamExt as _, TryStreamExt as _};
+use std::collections::HashMap;
+use tokio_util::io::{ReaderStream, StreamReader};
+use tracing::{debug, instrument};
+use wasmcloud_provider_sdk::{propagate_trace_for_ctx, Context};
+
+use crate::bindings::exports::wasmcloud::blobstore::{multipart, objects, presign};
+use crate::bindings::wasmcloud::blobstore::types::{
+    CompletedPart, Condition, ObjectError, ObjectId, ObjectInfo, PresignedRequest, WriteOptions,
+};
+use crate::NatsBlobstoreProvider;
+
+/// Prefix of objects staging in-progress multipart uploads
+const MULTIPART_PREFIX: &str = ".multipart";
+
+/// Whether the object with `name` stages an in-progress multipart upload
+pub(crate) fn is_multipart_staging(name: &str) -> bool {
+    name.strip_prefix(MULTIPART_PREFIX)
+        .is_some_and(|name| name.starts_with('/'))
+}
+
+/// Header of objects storing the content type
+const CONTENT_TYPE_HEADER: &str = "Content-Type";
+
+/// Name of the marker object of an upload, which records the name of the target object
+fn upload_marker(upload_id: &str) -> String {
+    format!("{MULTIPART_PREFIX}/{upload_id}")
+}
+
+/// Name of the object staging part `part_number` of an upload
+fn upload_part(upload_id: &str, part_number: u32) -> String {
+    format!("{MULTIPART_PREFIX}/{upload_id}/{part_number:010}")
+}
+
+impl NatsBlobstoreProvider {
+    /// Get the container of the object
+    async fn get_container(
+        &self,
+        context: Option<Context>,
+        container: &str,
+    ) -> anyhow::Result<ObjectStore> {
+        let blobstore = self
+            .get_blobstore(context)
+            .await
+            .context("failed to get NATS Blobstore connection")?;
+        blobstore
+            .jetstream
+            .get_object_store(container)
+            .await
+            .context("failed to get container")
+    }
+
+    /// Get the container of the object and ensure that the upload belongs to the object
+    async fn get_upload(
+        &self,
+        context: Option<Context>,
+        id: &ObjectId,
+        upload_id: &str,
+    ) -> anyhow::Result<ObjectStore> {
+        let blobstore = self
+            .get_blobstore(context)
+            .await
+            .context("failed to get NATS Blobstore connection")?;
+        let container = blobstore
+            .jetstream
+            .get_object_store(&id.container)
+            .await
+            .context("failed to get container")?;
+        let mut marker = container
+            .get(upload_marker(upload_id))
+            .await
+            .with_context(|| format!("upload `{upload_id}` not found"))?;
+        let mut object = Vec::new();
+        tokio::io::copy(&mut marker, &mut object)
+            .await
+            .context("failed to read upload marker")?;
+        ensure!(
+            object == id.object.as_bytes(),
+            "upload `{upload_id}` does not belong to object `{}`",
+            id.object
+        );
+        Ok(container)
+    }
+}
+
+/// List the parts of an upload staged in the container, ordered by part number
+async fn staged_parts(
+    container: &ObjectStore,
+    upload_id: &str,
+) -> anyhow::Result<Vec<CompletedPart>> {
+    let prefix = format!("{}/", upload_marker(upload_id));
+    let mut parts: Vec<_> = container
+        .list()
+        .await
+        .context("failed to list container objects")?
+        .map_err(anyhow::Error::from)
+        .try_filter_map(|info| {
+            let part = info
+                .name
+                .strip_prefix(&prefix)
+                .and_then(|n| n.parse().ok())
+                .filter(|_| !info.deleted)
+                .map(|part_number| CompletedPart {
+                    part_number,
+                    etag: info.digest.clone().unwrap_or_default(),
+                    size: info.size.try_into().unwrap_or(u64::MAX),
+                });
+            async move { Ok(part) }
+        })
+        .try_collect()
+        .await?;
+    parts.sort_by_key(|CompletedPart { part_number, .. }| *part_number);
+    Ok(parts)
+}
+
+/// Delete the marker and all parts of an upload
+async fn delete_upload(container: &ObjectStore, upload_id: &str) -> anyhow::Result<()> {
+    for CompletedPart { part_number, .. } in staged_parts(container, upload_id).await? {
+        container
+            .delete(upload_part(upload_id, part_number))
+            .await
+            .with_context(|| format!("failed to delete part `{part_number}`"))?;
+    }
+    container
+        .delete(upload_marker(upload_id))
+        .await
+        .context("failed to delete upload marker")
+}
+
+impl presign::Handler<Option<Context>> for NatsBlobstoreProvider {
+    #[instrument(level = "debug", skip(self))]
+    async fn presign_get(
+        &self,
+        _context: Option<Context>,
+        _id: ObjectId,
+        _expires_in: u64,
+    ) -> anyhow::Result<Result<PresignedRequest, String>> {
+        Ok(Err(
+            "pre-signed requests are not supported by NATS object stores".into(),
+        ))
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn presign_put(
+        &self,
+        _context: Option<Context>,
+        _id: ObjectId,
+        _expires_in: u64,
+    ) -> anyhow::Result<Result<PresignedRequest, String>> {
+        Ok(Err(
+            "pre-signed requests are not supported by NATS object stores".into(),
+        ))
+    }
+}
+
+impl multipart::Handler<Option<Context>> for NatsBlobstoreProvider {
+    #[instrument(level = "debug", skip(self))]
+    async fn create_upload(
+        &self,
+        context: Option<Context>,
+        id: ObjectId,
+    ) -> anyhow::Result<Result<String, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(context);
+            let blobstore = self
+                .get_blobstore(context)
+                .await
+                .context("failed to get NATS Blobstore connection")?;
+            let container = blobstore
+                .jetstream
+                .get_object_store(&id.container)
+                .await
+                .context("failed to get container")?;
+            let upload_id = uuid::Uuid::new_v4().simple().to_string();
+            container
+                .put(
+                    upload_marker(&upload_id).as_str(),
+                    &mut id.object.as_bytes(),
+                )
+                .await
+                .context("failed to write upload marker")?;
+            debug!(upload_id, "created multipart upload");
+            Ok(upload_id)
+        }
+        .await
+        .map_err(|err: anyhow::Error| format!("{err:#}")))
+    }
+
+    #[instrument(level = "debug", skip(self, data))]
+    async fn upload_part(
+        &self,
+        context: Option<Context>,
+        id: ObjectId,
+        upload_id: String,
+        part_number: u32,
+        data: Bytes,
+    ) -> anyhow::Result<Result<CompletedPart, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(context);
+            ensure!(part_number > 0, "part numbers start at 1");
+            let container = self.get_upload(context, &id, &upload_id).await?;
+            let info = container
+                .put(
+                    upload_part(&upload_id, part_number).as_str(),
+                    &mut data.as_ref(),
+                )
+                .await
+                .context("failed to write part")?;
+            Ok(CompletedPart {
+                part_number,
+                etag: info.digest.unwrap_or_default(),
+                size: info.size.try_into().unwrap_or(u64::MAX),
+            })
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn list_parts(
+        &self,
+        context: Option<Context>,
+        id: ObjectId,
+        upload_id: String,
+    ) -> anyhow::Result<Result<Vec<CompletedPart>, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(context);
+            let container = self.get_upload(context, &id, &upload_id).await?;
+            staged_parts(&container, &upload_id).await
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn complete_upload(
+        &self,
+        context: Option<Context>,
+        id: ObjectId,
+        upload_id: String,
+        parts: Vec<CompletedPart>,
+    ) -> anyhow::Result<Result<(), String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(context);
+            ensure!(!parts.is_empty(), "at least one part is required");
+            ensure!(
+                parts
+                    .windows(2)
+                    .all(|w| w[0].part_number < w[1].part_number),
+                "parts must be in ascending order of part number"
+            );
+            let container = self.get_upload(context, &id, &upload_id).await?;
+            let staged: HashMap<_, _> = staged_parts(&container, &upload_id)
+                .await?
+                .into_iter()
+                .map(
+                    |CompletedPart {
+                         part_number, etag, ..
+                     }| (part_number, etag),
+                )
+                .collect();
+            let mut objects = Vec::with_capacity(parts.len());
+            for CompletedPart {
+                part_number, etag, ..
+            } in &parts
+            {
+                let Some(staged_etag) = staged.get(part_number) else {
+                    bail!("part `{part_number}` was not uploaded")
+                };
+                ensure!(staged_etag == etag, "part `{part_number}` ETag mismatch");
+                let object = container
+                    .get(upload_part(&upload_id, *part_number))
+                    .await
+                    .with_context(|| format!("failed to get part `{part_number}`"))?;
+                objects.push(object);
+            }
+            let mut data =
+                StreamReader::new(futures::stream::iter(objects).flat_map(ReaderStream::new));
+            container
+                .put(id.object.as_str(), &mut data)
+                .await
+                .context("failed to write assembled object")?;
+            delete_upload(&container, &upload_id).await
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn abort_upload(
+        &self,
+        context: Option<Context>,
+        id: ObjectId,
+        upload_id: String,
+    ) -> anyhow::Result<Result<(), String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(context);
+            let container = self.get_upload(context, &id, &upload_id).await?;
+            delete_upload(&container, &upload_id).await
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+}
+
+impl objects::Handler<Option<Context>> for NatsBlobstoreProvider {
+    #[instrument(level = "debug", skip(self))]
+    async fn get_object_info(
+        &self,
+        context: Option<Context>,
+        ObjectId { container, object }: ObjectId,
+    ) -> anyhow::Result<Result<ObjectInfo, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(context);
+            let info = self
+                .get_container(context, &container)
+                .await?
+                .info(&object)
+                .await
+                .context("failed to get object info")?;
+            let content_type = info
+                .headers
+                .as_ref()
+                .and_then(|headers| headers.get(CONTENT_TYPE_HEADER))
+                .map(ToString::to_string);
+            Ok(ObjectInfo {
+                name: object,
+                container,
+                size: info.size.try_into().unwrap_or(u64::MAX),
+                // NATS doesn't store the object creation time, so the modification time is used
+                created_at: info
+                    .modified
+                    .and_then(|t| t.unix_timestamp().try_into().ok())
+                    .unwrap_or_default(),
+                etag: info.digest.unwrap_or_default(),
+                content_type,
+                metadata: info.metadata.into_iter().collect(),
+            })
+        }
+        .await
+        .map_err(|err: anyhow::Error| format!("{err:#}")))
+    }
+
+    #[instrument(level = "debug", skip(self, data))]
+    async fn write_object(
+        &self,
+        context: Option<Context>,
+        ObjectId { container, object }: ObjectId,
+        data: Bytes,
+        WriteOptions {
+            content_type,
+            metadata,
+            condition,
+        }: WriteOptions,
+    ) -> anyhow::Result<Result<String, ObjectError>> {
+        if condition.is_some() {
+            return Ok(Err(ObjectError::Other(
+                "conditional writes are not supported by NATS object stores".into(),
+            )));
+        }
+        Ok(async {
+            propagate_trace_for_ctx!(context);
+            let container = self.get_container(context, &container).await?;
+            let headers = content_type.map(|content_type| {
+                let mut headers = async_nats::HeaderMap::new();
+                headers.insert(CONTENT_TYPE_HEADER, content_type.as_str());
+                headers
+            });
+            let info = container
+                .put(
+                    async_nats::jetstream::object_store::ObjectMetadata {
+                        name: object,
+                        metadata: metadata.into_iter().collect(),
+                        headers,
+                        ..Default::default()
+                    },
+                    &mut data.as_ref(),
+                )
+                .await
+                .context("failed to write object")?;
+            Ok(info.digest.unwrap_or_default())
+        }
+        .await
+        .map_err(|err: anyhow::Error| ObjectError::Other(format!("{err:#}"))))
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    async fn delete_object(
+        &self,
+        context: Option<Context>,
+        ObjectId { container, object }: ObjectId,
+        condition: Option<Condition>,
+    ) -> anyhow::Result<Result<(), ObjectError>> {
+        if condition.is_some() {
+            return Ok(Err(ObjectError::Other(
+                "conditional deletes are not supported by NATS object stores".into(),
+            )));
+        }
+        Ok(async {
+            propagate_trace_for_ctx!(context);
+            self.get_container(context, &container)
+                .await?
+                .delete(&object)
+                .await
+                .context("failed to delete object")
+        }
+        .await
+        .map_err(|err| ObjectError::Other(format!("{err:#}"))))
+    }
+}
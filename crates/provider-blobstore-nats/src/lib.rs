@@ -3,6 +3,7 @@ use crate::config::{NatsConnectionConfig, StorageConfig};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
 
 /// [`NatsBlobstore`] holds the handle to opened NATS Object Stores, and their container (bucket) storage configuration.
 #[derive(Clone)]
@@ -11,6 +12,9 @@ pub(crate) struct NatsBlobstore {
     pub(crate) storage_config: StorageConfig,
 }
 
+/// Tasks delivering object change notifications, keyed by (component_id, link_name)
+type WatchTaskMap = HashMap<(String, String), JoinHandle<()>>;
+
 /// [`NatsBlobstoreProvider`] holds the default NATS connection configuration and individual consumer
 /// components' established NATS JetStream connections.
 #[derive(Default, Clone)]
@@ -18,9 +22,26 @@ pub struct NatsBlobstoreProvider {
     /// Map of component_id -> link_name -> NATS Object Store JetStream Context (supports multiple links per component)
     consumer_components: Arc<RwLock<HashMap<String, HashMap<String, NatsBlobstore>>>>,
     default_config: NatsConnectionConfig,
+    /// Tasks delivering object change notifications to components linked as targets
+    watchers: Arc<RwLock<WatchTaskMap>>,
 }
 
 mod blobstore;
 /// Provider modules
 mod config;
+mod extensions;
 mod provider;
+mod watch;
+
+mod bindings {
+    wit_bindgen_wrpc::generate!({
+        world: "extensions",
+        with: {
+            "wasmcloud:blobstore/multipart@0.1.0-draft": generate,
+            "wasmcloud:blobstore/objects@0.1.0-draft": generate,
+            "wasmcloud:blobstore/presign@0.1.0-draft": generate,
+            "wasmcloud:blobstore/types@0.1.0-draft": generate,
+            "wasmcloud:blobstore/watcher@0.1.0-draft": generate,
+        },
+    });
+}
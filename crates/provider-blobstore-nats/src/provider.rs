@@ -16,6 +16,7 @@ use futures::Stream;
 use tokio::fs;
 use tracing::{debug, error, info, instrument, warn};
 use wascap::prelude::KeyPair;
+use wasmcloud_provider_sdk::provider::{InvocationStreams, WrpcClient};
 use wasmcloud_provider_sdk::{
     get_connection, initialize_observability, load_host_data, run_provider, serve_provider_exports,
     Context, HostData, LinkConfig, LinkDeleteInfo, Provider, ProviderConfigUpdate,
@@ -26,6 +27,16 @@ use crate::{NatsBlobstore, NatsBlobstoreProvider};
 // Import the wrpc interface bindings
 use wrpc_interface_blobstore::bindings;
 
+/// Serve both `wrpc:blobstore` and `wasmcloud:blobstore` extension exports
+async fn serve(
+    wrpc: &WrpcClient,
+    provider: NatsBlobstoreProvider,
+) -> anyhow::Result<InvocationStreams> {
+    let mut invocations = bindings::serve(wrpc, provider.clone()).await?;
+    invocations.extend(crate::bindings::serve(wrpc, provider).await?);
+    Ok(invocations)
+}
+
 /// Implement the [`NatsBlobstoreProvider`] and [`Provider`] traits
 impl NatsBlobstoreProvider {
     pub async fn run() -> anyhow::Result<()> {
@@ -47,7 +58,7 @@ impl NatsBlobstoreProvider {
                 .await?,
             provider,
             shutdown,
-            bindings::serve,
+            serve,
         )
         .await
         .context("failed to serve provider exports")
@@ -302,6 +313,73 @@ impl Provider for NatsBlobstoreProvider {
         Ok(())
     }
 
+    /// Deliver object change notifications for a comma-separated list of `CONTAINERS` to the linked
+    /// component via `wasmcloud:blobstore/watcher`
+    #[instrument(level = "debug", skip_all, fields(target_id))]
+    async fn receive_link_config_as_source(
+        &self,
+        link_config: LinkConfig<'_>,
+    ) -> anyhow::Result<()> {
+        let LinkConfig {
+            target_id,
+            link_name,
+            wit_metadata: (_, _, interfaces),
+            ..
+        } = link_config;
+        if !interfaces.iter().any(|interface| interface == "watcher") {
+            return Ok(());
+        }
+
+        let containers: Vec<String> = link_config
+            .config
+            .get("CONTAINERS")
+            .context("`CONTAINERS` to watch must be specified")?
+            .split(',')
+            .map(str::trim)
+            .filter(|container| !container.is_empty())
+            .map(String::from)
+            .collect();
+        // The link config always contains `CONTAINERS`, so the connection configuration is always
+        // merged over the default one
+        let config =
+            NatsConnectionConfig::from_link_config(link_config.config, link_config.secrets)
+                .map(|ncc| self.default_config.merge(&ncc))
+                .context("failed to build NATS connection configuration")?;
+        let jetstream = self
+            .connect(config)
+            .await
+            .context("failed to connect to NATS")?;
+        let wrpc = get_connection()
+            .get_wrpc_client(target_id)
+            .await
+            .context("failed to construct wRPC client")?;
+        info!(?containers, "watching for object changes");
+        let task = tokio::spawn(crate::watch::watch(&jetstream, containers, wrpc).await?);
+        if let Some(task) = self
+            .watchers
+            .write()
+            .await
+            .insert((target_id.into(), link_name.into()), task)
+        {
+            task.abort();
+        }
+        Ok(())
+    }
+
+    /// Stop delivering object change notifications to the component
+    #[instrument(level = "info", skip_all, fields(target_id = info.get_target_id(), link_name = info.get_link_name()))]
+    async fn delete_link_as_source(&self, info: impl LinkDeleteInfo) -> anyhow::Result<()> {
+        let key = (
+            info.get_target_id().to_string(),
+            info.get_link_name().to_string(),
+        );
+        if let Some(task) = self.watchers.write().await.remove(&key) {
+            task.abort();
+            debug!("stopped watching for object changes");
+        }
+        Ok(())
+    }
+
     /// Provider should perform any operations needed for configuration updates, including cleaning up
     /// invalidated link resources.
     #[instrument(level = "debug", skip_all, fields(link_name))]
@@ -353,6 +431,11 @@ impl Provider for NatsBlobstoreProvider {
         let mut consumers = self.consumer_components.write().await;
         consumers.clear();
 
+        // stop all watches
+        for (_, task) in self.watchers.write().await.drain() {
+            task.abort();
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,85 @@
+//! NATS implementation of object change notifications, delivered to components via
+//! `wasmcloud:blobstore/watcher@0.1.0-draft`.
+//!
+//! Changes are observed using the NATS object store watch API, which yields the updated object
+//! metadata for every object written or deleted in a container (bucket).
+
+use core::future::Future;
+
+use anyhow::Context as _;
+use futures::StreamExt as _;
+use tracing::{debug, error, instrument, warn};
+use wasmcloud_provider_sdk::provider::WrpcClient;
+use wasmcloud_provider_sdk::wasmcloud_tracing::context::TraceContextInjector;
+
+use crate::bindings::wasmcloud::blobstore::types::ObjectId;
+use crate::bindings::wasmcloud::blobstore::watcher;
+use crate::extensions::is_multipart_staging;
+
+/// Invoke `wasmcloud:blobstore/watcher` on the component for an object change
+#[instrument(level = "debug", skip(wrpc))]
+async fn notify_component(wrpc: &WrpcClient, id: ObjectId, deleted: bool) {
+    let mut cx = async_nats::HeaderMap::new();
+    for (k, v) in TraceContextInjector::default_with_span().iter() {
+        cx.insert(k.as_str(), v.as_str());
+    }
+    let res = if deleted {
+        watcher::on_delete(wrpc, Some(cx), &id).await
+    } else {
+        watcher::on_create(wrpc, Some(cx), &id).await
+    };
+    if let Err(err) = res {
+        error!(?err, "failed to notify component of object change");
+    }
+}
+
+/// Start watching `containers` for object changes, returning a future delivering notifications
+/// to the component
+pub(crate) async fn watch(
+    jetstream: &async_nats::jetstream::context::Context,
+    containers: Vec<String>,
+    wrpc: WrpcClient,
+) -> anyhow::Result<impl Future<Output = ()>> {
+    let mut watches = Vec::with_capacity(containers.len());
+    for container in containers {
+        let store = jetstream
+            .get_object_store(&container)
+            .await
+            .with_context(|| format!("failed to get container `{container}`"))?;
+        let watch = store
+            .watch()
+            .await
+            .with_context(|| format!("failed to watch container `{container}`"))?;
+        watches.push(watch.map(move |res| (container.clone(), res)));
+    }
+    let mut changes = futures::stream::select_all(watches);
+    Ok(async move {
+        while let Some((container, res)) = changes.next().await {
+            let info = match res {
+                Ok(info) => info,
+                Err(err) => {
+                    warn!(?err, container, "object store watch error");
+                    continue;
+                }
+            };
+            if is_multipart_staging(&info.name) {
+                continue;
+            }
+            debug!(
+                container,
+                object = info.name,
+                info.deleted,
+                "object changed"
+            );
+            notify_component(
+                &wrpc,
+                ObjectId {
+                    container,
+                    object: info.name,
+                },
+                info.deleted,
+            )
+            .await;
+        }
+    })
+}
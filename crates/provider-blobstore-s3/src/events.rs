@@ -0,0 +1,306 @@
+//! Object change notifications for S3.
+//!
+//! S3 publishes [event notifications] for a bucket to an SQS queue, which is configured on the
+//! bucket. The provider long-polls the queue and delivers the events to components linked to the
+//! provider as targets via `wasmcloud:blobstore/watcher`.
+//!
+//! [event notifications]: https://docs.aws.amazon.com/AmazonS3/latest/userguide/EventNotifications.html
+
+use core::future::Future;
+use core::time::Duration;
+
+use anyhow::Context as _;
+use aws_sdk_sqs::types::DeleteMessageBatchRequestEntry;
+use serde::Deserialize;
+use tracing::{debug, error, instrument, warn};
+
+use crate::{http_client, StorageClient, StorageConfig};
+
+/// Maximum time to wait for messages in a single receive call, in seconds
+const RECEIVE_WAIT_TIME_SECONDS: i32 = 20;
+/// Maximum amount of messages received in a single receive call
+const RECEIVE_MAX_MESSAGES: i32 = 10;
+/// Time to wait before receiving again after a failed receive call
+const RECEIVE_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// A change to an object, reported by S3
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ObjectEvent {
+    /// An object was created or overwritten
+    Created { bucket: String, key: String },
+    /// An object was deleted
+    Removed { bucket: String, key: String },
+}
+
+impl ObjectEvent {
+    /// Name of the bucket containing the object
+    pub fn bucket(&self) -> &str {
+        let (Self::Created { bucket, .. } | Self::Removed { bucket, .. }) = self;
+        bucket
+    }
+}
+
+/// S3 event notification message, as delivered to SQS
+#[derive(Debug, Deserialize)]
+struct Notification {
+    /// Test events, sent when notifications are configured, do not contain records
+    #[serde(rename = "Records", default)]
+    records: Vec<Record>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Record {
+    event_name: String,
+    s3: RecordEntity,
+}
+
+#[derive(Debug, Deserialize)]
+struct RecordEntity {
+    bucket: RecordBucket,
+    object: RecordObject,
+}
+
+#[derive(Debug, Deserialize)]
+struct RecordBucket {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RecordObject {
+    /// URL-encoded object key
+    key: String,
+}
+
+/// Parse object events from an S3 event notification message body
+fn parse_notification(body: &str) -> anyhow::Result<Vec<ObjectEvent>> {
+    let Notification { records } =
+        serde_json::from_str(body).context("failed to parse event notification")?;
+    let mut events = Vec::with_capacity(records.len());
+    for Record {
+        event_name,
+        s3:
+            RecordEntity {
+                bucket: RecordBucket { name: bucket },
+                object: RecordObject { key },
+            },
+    } in records
+    {
+        // Keys are URL-encoded, with spaces encoded as `+`
+        let key = percent_encoding::percent_decode_str(&key.replace('+', " "))
+            .decode_utf8()
+            .context("invalid object key encoding")?
+            .into_owned();
+        if event_name.starts_with("ObjectCreated:") {
+            events.push(ObjectEvent::Created { bucket, key });
+        } else if event_name.starts_with("ObjectRemoved:") {
+            events.push(ObjectEvent::Removed { bucket, key });
+        } else {
+            debug!(event_name, "ignoring event");
+        }
+    }
+    Ok(events)
+}
+
+/// A message received from the SQS queue, carrying S3 object events
+#[derive(Clone, Debug)]
+pub struct EventMessage {
+    /// Object events contained in the message, empty if the message is malformed
+    pub events: Vec<ObjectEvent>,
+    receipt_handle: Option<String>,
+}
+
+/// SQS queue receiving S3 event notifications
+#[derive(Clone)]
+pub struct EventQueue {
+    sqs_client: aws_sdk_sqs::Client,
+    queue_url: String,
+}
+
+impl EventQueue {
+    pub async fn new(config: &StorageConfig, queue_url: impl Into<String>) -> Self {
+        let sqs_client = aws_sdk_sqs::Client::from_conf(
+            aws_sdk_sqs::Config::from(&config.load_sdk_config().await)
+                .to_builder()
+                .http_client(http_client())
+                .build(),
+        );
+        Self {
+            sqs_client,
+            queue_url: queue_url.into(),
+        }
+    }
+
+    /// Wait for the next batch of messages carrying object events.
+    ///
+    /// Messages remain in the queue until they are [deleted](Self::delete), so that messages whose
+    /// events could not be delivered are received again once their visibility timeout expires.
+    /// Malformed messages are returned without events.
+    #[instrument(level = "debug", skip(self), fields(queue_url = self.queue_url))]
+    pub async fn receive(&self) -> anyhow::Result<Vec<EventMessage>> {
+        let messages = self
+            .sqs_client
+            .receive_message()
+            .queue_url(&self.queue_url)
+            .wait_time_seconds(RECEIVE_WAIT_TIME_SECONDS)
+            .max_number_of_messages(RECEIVE_MAX_MESSAGES)
+            .send()
+            .await
+            .context("failed to receive messages")?
+            .messages
+            .unwrap_or_default();
+        Ok(messages
+            .into_iter()
+            .map(|message| {
+                let events =
+                    parse_notification(message.body().unwrap_or_default()).unwrap_or_else(|err| {
+                        warn!(?err, message_id = message.message_id, "skipping message");
+                        Vec::default()
+                    });
+                EventMessage {
+                    events,
+                    receipt_handle: message.receipt_handle,
+                }
+            })
+            .collect())
+    }
+
+    /// Delete messages from the queue, once their events have been delivered
+    #[instrument(level = "debug", skip_all, fields(queue_url = self.queue_url))]
+    pub async fn delete(
+        &self,
+        messages: impl IntoIterator<Item = EventMessage>,
+    ) -> anyhow::Result<()> {
+        let entries = messages
+            .into_iter()
+            .filter_map(|message| message.receipt_handle)
+            .enumerate()
+            .map(|(i, receipt_handle)| {
+                DeleteMessageBatchRequestEntry::builder()
+                    .id(i.to_string())
+                    .receipt_handle(receipt_handle)
+                    .build()
+                    .context("failed to build message deletion entry")
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let res = self
+            .sqs_client
+            .delete_message_batch()
+            .queue_url(&self.queue_url)
+            .set_entries(Some(entries))
+            .send()
+            .await
+            .context("failed to delete messages")?;
+        for failed in res.failed {
+            warn!(
+                id = failed.id,
+                code = failed.code,
+                "failed to delete message"
+            );
+        }
+        Ok(())
+    }
+
+    /// Receive object events and deliver them using `deliver`, until the returned future is
+    /// dropped.
+    ///
+    /// Buckets are reported under their alias in `client`, if any. If `containers` is set, only
+    /// events for the listed buckets or aliases are delivered. Messages are deleted once all of
+    /// their events were delivered.
+    pub async fn watch<F, Fut>(
+        &self,
+        client: &StorageClient,
+        containers: Option<&[String]>,
+        mut deliver: F,
+    ) where
+        F: FnMut(ObjectEvent) -> Fut,
+        Fut: Future<Output = anyhow::Result<()>>,
+    {
+        loop {
+            let messages = match self.receive().await {
+                Ok(messages) => messages,
+                Err(err) => {
+                    error!(?err, "failed to receive object events");
+                    tokio::time::sleep(RECEIVE_RETRY_DELAY).await;
+                    continue;
+                }
+            };
+            // Only delete messages, all events of which were delivered, so that the others
+            // are received again after their visibility timeout
+            let mut delivered = Vec::with_capacity(messages.len());
+            'messages: for message in messages {
+                for event in &message.events {
+                    let bucket = event.bucket();
+                    let alias = client.alias(bucket);
+                    if containers.is_some_and(|containers| {
+                        !containers
+                            .iter()
+                            .any(|container| container == bucket || container == alias)
+                    }) {
+                        continue;
+                    }
+                    let event = match event.clone() {
+                        ObjectEvent::Created { key, .. } => ObjectEvent::Created {
+                            bucket: alias.to_string(),
+                            key,
+                        },
+                        ObjectEvent::Removed { key, .. } => ObjectEvent::Removed {
+                            bucket: alias.to_string(),
+                            key,
+                        },
+                    };
+                    if let Err(err) = deliver(event).await {
+                        error!(?err, "failed to deliver object event");
+                        continue 'messages;
+                    }
+                }
+                delivered.push(message);
+            }
+            if let Err(err) = self.delete(delivered).await {
+                error!(?err, "failed to delete delivered object events");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_events() {
+        let events = parse_notification(
+            r#"{"Records":[
+                {"eventName":"ObjectCreated:Put","s3":{"bucket":{"name":"bucket"},"object":{"key":"dir/my+file%3F.txt","size":3}}},
+                {"eventName":"ObjectRemoved:Delete","s3":{"bucket":{"name":"bucket"},"object":{"key":"other"}}},
+                {"eventName":"ObjectRestore:Post","s3":{"bucket":{"name":"bucket"},"object":{"key":"archived"}}}
+            ]}"#,
+        )
+        .expect("failed to parse notification");
+        assert_eq!(
+            events,
+            [
+                ObjectEvent::Created {
+                    bucket: "bucket".into(),
+                    key: "dir/my file?.txt".into()
+                },
+                ObjectEvent::Removed {
+                    bucket: "bucket".into(),
+                    key: "other".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_test_event() {
+        let events = parse_notification(
+            r#"{"Service":"Amazon S3","Event":"s3:TestEvent","Time":"2025-01-01T00:00:00.000Z","Bucket":"bucket"}"#,
+        )
+        .expect("failed to parse test event");
+        assert!(events.is_empty());
+    }
+}
@@ -14,21 +14,26 @@ use core::str::FromStr;
 use std::collections::HashMap;
 use std::env;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 use anyhow::{anyhow, bail, Context as _, Result};
 use aws_config::default_provider::credentials::DefaultCredentialsChain;
 use aws_config::default_provider::region::DefaultRegionChain;
 use aws_config::retry::RetryConfig;
 use aws_config::sts::AssumeRoleProvider;
+use aws_config::SdkConfig;
+use aws_sdk_s3::config::SharedHttpClient;
 use aws_sdk_s3::config::{Region, SharedCredentialsProvider};
-use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
+use aws_sdk_s3::error::{DisplayErrorContext, ProvideErrorMetadata, SdkError};
 use aws_sdk_s3::operation::create_bucket::{CreateBucketError, CreateBucketOutput};
 use aws_sdk_s3::operation::get_object::GetObjectOutput;
 use aws_sdk_s3::operation::head_bucket::HeadBucketError;
 use aws_sdk_s3::operation::head_object::{HeadObjectError, HeadObjectOutput};
 use aws_sdk_s3::operation::list_objects_v2::ListObjectsV2Output;
+use aws_sdk_s3::presigning::PresigningConfig;
 use aws_sdk_s3::types::{
-    BucketLocationConstraint, CreateBucketConfiguration, Delete, Object, ObjectIdentifier,
+    BucketLocationConstraint, CompletedMultipartUpload, CreateBucketConfiguration, Delete, Object,
+    ObjectIdentifier, Part,
 };
 use aws_smithy_runtime::client::http::hyper_014::HyperClientBuilder;
 use base64::Engine as _;
@@ -37,23 +42,50 @@ use futures::{stream, Stream, StreamExt as _};
 use serde::Deserialize;
 use tokio::io::AsyncReadExt as _;
 use tokio::sync::{mpsc, RwLock};
+use tokio::task::JoinHandle;
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::io::ReaderStream;
 use tracing::{debug, error, instrument, warn};
 use wasmcloud_provider_sdk::core::secrets::SecretValue;
 use wasmcloud_provider_sdk::core::tls;
+use wasmcloud_provider_sdk::provider::{InvocationStreams, WrpcClient};
+use wasmcloud_provider_sdk::wasmcloud_tracing::context::TraceContextInjector;
 use wasmcloud_provider_sdk::{
     get_connection, initialize_observability, propagate_trace_for_ctx, run_provider,
     serve_provider_exports, Context, LinkConfig, LinkDeleteInfo, Provider,
 };
 use wrpc_interface_blobstore::bindings::{
     exports::wrpc::blobstore::blobstore::Handler,
-    serve,
     wrpc::blobstore::types::{ContainerMetadata, ObjectId, ObjectMetadata},
 };
 
+mod bindings {
+    wit_bindgen_wrpc::generate!({
+        world: "extensions",
+        with: {
+            "wasmcloud:blobstore/multipart@0.1.0-draft": generate,
+            "wasmcloud:blobstore/objects@0.1.0-draft": generate,
+            "wasmcloud:blobstore/presign@0.1.0-draft": generate,
+            "wasmcloud:blobstore/types@0.1.0-draft": generate,
+            "wasmcloud:blobstore/watcher@0.1.0-draft": generate,
+        },
+    });
+}
+use bindings::exports::wasmcloud::blobstore::{multipart, objects, presign};
+use bindings::wasmcloud::blobstore::types::{
+    CompletedPart, Condition, ObjectError, ObjectId as ExtObjectId, ObjectInfo, PresignedRequest,
+    WriteOptions,
+};
+use bindings::wasmcloud::blobstore::watcher;
+
+pub use events::{EventMessage, EventQueue, ObjectEvent};
+
+mod events;
+
 const ALIAS_PREFIX: &str = "alias_";
 const DEFAULT_STS_SESSION: &str = "blobstore_s3_provider";
+/// Maximum validity of a pre-signed request supported by S3 (SigV4)
+const MAX_PRESIGN_EXPIRY: Duration = Duration::from_secs(7 * 24 * 60 * 60);
 
 /// Configuration for connecting to S3-compatible storage
 ///
@@ -166,9 +198,29 @@ pub struct StorageClient {
     bucket_region: Option<BucketLocationConstraint>,
 }
 
-impl StorageClient {
-    pub async fn new(
-        StorageConfig {
+/// HTTP client used by AWS SDK clients
+fn http_client() -> SharedHttpClient {
+    HyperClientBuilder::new().build(
+        hyper_rustls::HttpsConnectorBuilder::new()
+            .with_tls_config(
+                // use `tls::DEFAULT_CLIENT_CONFIG` directly once `rustls` versions
+                // are in sync
+                rustls::ClientConfig::builder()
+                    .with_root_certificates(rustls::RootCertStore {
+                        roots: tls::DEFAULT_ROOTS.roots.clone(),
+                    })
+                    .with_no_client_auth(),
+            )
+            .https_or_http()
+            .enable_all_versions()
+            .build(),
+    )
+}
+
+impl StorageConfig {
+    /// Load AWS SDK configuration (region, credentials, retries and endpoint)
+    pub async fn load_sdk_config(&self) -> SdkConfig {
+        let StorageConfig {
             access_key_id,
             secret_access_key,
             session_token,
@@ -176,13 +228,10 @@ impl StorageClient {
             max_attempts,
             sts_config,
             endpoint,
-            mut aliases,
-            bucket_region,
-        }: StorageConfig,
-        config_values: &HashMap<String, String>,
-    ) -> Self {
+            ..
+        } = self;
         let region = match region {
-            Some(region) => Some(Region::new(region)),
+            Some(region) => Some(Region::new(region.clone())),
             _ => DefaultRegionChain::builder().build().region().await,
         };
 
@@ -192,7 +241,7 @@ impl StorageClient {
                 SharedCredentialsProvider::new(aws_sdk_s3::config::Credentials::new(
                     access_key_id,
                     secret_access_key,
-                    session_token,
+                    session_token.clone(),
                     None,
                     "static",
                 ))
@@ -211,10 +260,13 @@ impl StorageClient {
             external_id,
         }) = sts_config
         {
-            let mut role = AssumeRoleProvider::builder(role)
-                .session_name(session.unwrap_or_else(|| DEFAULT_STS_SESSION.to_string()));
+            let mut role = AssumeRoleProvider::builder(role).session_name(
+                session
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_STS_SESSION.to_string()),
+            );
             if let Some(region) = region {
-                role = role.region(Region::new(region));
+                role = role.region(Region::new(region.clone()));
             }
             if let Some(external_id) = external_id {
                 role = role.external_id(external_id);
@@ -224,7 +276,7 @@ impl StorageClient {
 
         let mut retry_config = RetryConfig::standard();
         if let Some(max_attempts) = max_attempts {
-            retry_config = retry_config.with_max_attempts(max_attempts);
+            retry_config = retry_config.with_max_attempts(*max_attempts);
         }
         let mut loader = aws_config::defaults(aws_config::BehaviorVersion::v2025_01_17())
             .region(region)
@@ -233,31 +285,27 @@ impl StorageClient {
         if let Some(endpoint) = endpoint {
             loader = loader.endpoint_url(endpoint);
         };
+        loader.load().await
+    }
+}
+
+impl StorageClient {
+    pub async fn new(config: StorageConfig, config_values: &HashMap<String, String>) -> Self {
+        let sdk_config = config.load_sdk_config().await;
+        let StorageConfig {
+            mut aliases,
+            bucket_region,
+            ..
+        } = config;
         let s3_client = aws_sdk_s3::Client::from_conf(
-            aws_sdk_s3::Config::from(&loader.load().await)
+            aws_sdk_s3::Config::from(&sdk_config)
                 .to_builder()
                 // Since minio requires force path style,
                 // turn it on since it's disabled by default
                 // due to deprecation by AWS.
                 // https://github.com/awslabs/aws-sdk-rust/issues/390
                 .force_path_style(true)
-                .http_client(
-                    HyperClientBuilder::new().build(
-                        hyper_rustls::HttpsConnectorBuilder::new()
-                            .with_tls_config(
-                                // use `tls::DEFAULT_CLIENT_CONFIG` directly once `rustls` versions
-                                // are in sync
-                                rustls::ClientConfig::builder()
-                                    .with_root_certificates(rustls::RootCertStore {
-                                        roots: tls::DEFAULT_ROOTS.roots.clone(),
-                                    })
-                                    .with_no_client_auth(),
-                            )
-                            .https_or_http()
-                            .enable_all_versions()
-                            .build(),
-                    ),
-                )
+                .http_client(http_client())
                 .build(),
         );
 
@@ -297,6 +345,19 @@ impl StorageClient {
         }
     }
 
+    /// perform reverse alias lookup on bucket name, returning the alias, under which the bucket
+    /// is known to components, if there is one
+    ///
+    /// If several aliases refer to the same bucket, the lexicographically smallest one is returned
+    pub fn alias<'n, 's: 'n>(&'s self, bucket: &'n str) -> &'n str {
+        self.aliases
+            .iter()
+            .filter(|(_, name)| *name == bucket)
+            .map(|(alias, _)| alias.as_str())
+            .min()
+            .unwrap_or(bucket)
+    }
+
     /// Check whether a container exists
     #[instrument(level = "debug", skip(self))]
     pub async fn container_exists(&self, bucket: &str) -> anyhow::Result<bool> {
@@ -541,6 +602,313 @@ impl StorageClient {
     }
 }
 
+/// Convert an error returned for a conditional request into an [`ObjectError`]
+fn object_error<E: ProvideErrorMetadata + std::error::Error + 'static, R: std::fmt::Debug>(
+    err: SdkError<E, R>,
+    msg: &str,
+) -> ObjectError {
+    match err.code() {
+        Some("PreconditionFailed" | "ConditionalRequestConflict") => {
+            ObjectError::PreconditionFailed
+        }
+        _ => ObjectError::Other(format!("{msg}: {}", DisplayErrorContext(&err))),
+    }
+}
+
+impl StorageClient {
+    /// Create a pre-signed request for the object, `put` selects an upload rather than a download
+    #[instrument(level = "debug", skip(self))]
+    pub async fn presign(
+        &self,
+        bucket: &str,
+        key: &str,
+        expires_in: Duration,
+        put: bool,
+    ) -> anyhow::Result<PresignedRequest> {
+        let expires_in = expires_in.min(MAX_PRESIGN_EXPIRY);
+        let config = PresigningConfig::expires_in(expires_in)
+            .context("failed to build presigning configuration")?;
+        let expires_at = (config.start_time() + expires_in)
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .context("expiry before Unix epoch")?
+            .as_secs();
+        let req = if put {
+            self.s3_client
+                .put_object()
+                .bucket(bucket)
+                .key(key)
+                .presigned(config)
+                .await
+                .context("failed to presign `put_object` request")?
+        } else {
+            self.s3_client
+                .get_object()
+                .bucket(bucket)
+                .key(key)
+                .presigned(config)
+                .await
+                .context("failed to presign `get_object` request")?
+        };
+        Ok(PresignedRequest {
+            url: req.uri().to_string(),
+            method: req.method().to_string(),
+            headers: req
+                .headers()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            expires_at,
+        })
+    }
+
+    /// Start a multipart upload, returning the upload ID
+    #[instrument(level = "debug", skip(self))]
+    pub async fn create_multipart_upload(&self, bucket: &str, key: &str) -> anyhow::Result<String> {
+        let out = self
+            .s3_client
+            .create_multipart_upload()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .context("failed to create multipart upload")?;
+        out.upload_id()
+            .map(String::from)
+            .context("upload ID missing in response")
+    }
+
+    #[instrument(level = "debug", skip(self, data))]
+    pub async fn upload_part(
+        &self,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+        part_number: u32,
+        data: Bytes,
+    ) -> anyhow::Result<CompletedPart> {
+        let size = data.len().try_into().unwrap_or(u64::MAX);
+        let out = self
+            .s3_client
+            .upload_part()
+            .bucket(bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .part_number(part_number.try_into().context("part number out of range")?)
+            .body(data.into())
+            .send()
+            .await
+            .context("failed to upload part")?;
+        let etag = out
+            .e_tag()
+            .map(String::from)
+            .context("ETag missing in response")?;
+        Ok(CompletedPart {
+            part_number,
+            etag,
+            size,
+        })
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    pub async fn list_parts(
+        &self,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+    ) -> anyhow::Result<Vec<CompletedPart>> {
+        let mut parts = Vec::new();
+        let mut marker = None;
+        loop {
+            let out = self
+                .s3_client
+                .list_parts()
+                .bucket(bucket)
+                .key(key)
+                .upload_id(upload_id)
+                .set_part_number_marker(marker)
+                .send()
+                .await
+                .context("failed to list parts")?;
+            parts.extend(out.parts().iter().filter_map(
+                |Part {
+                     part_number,
+                     e_tag,
+                     size,
+                     ..
+                 }| {
+                    Some(CompletedPart {
+                        part_number: (*part_number)?.try_into().ok()?,
+                        etag: e_tag.clone()?,
+                        size: size
+                            .and_then(|size| size.try_into().ok())
+                            .unwrap_or_default(),
+                    })
+                },
+            ));
+            if out.is_truncated() != Some(true) {
+                return Ok(parts);
+            }
+            marker = out.next_part_number_marker().map(String::from);
+        }
+    }
+
+    #[instrument(level = "debug", skip(self, parts))]
+    pub async fn complete_multipart_upload(
+        &self,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+        mut parts: Vec<CompletedPart>,
+    ) -> anyhow::Result<()> {
+        parts.sort_by_key(|part| part.part_number);
+        let parts = parts
+            .into_iter()
+            .map(
+                |CompletedPart {
+                     part_number, etag, ..
+                 }| {
+                    anyhow::Ok(
+                        aws_sdk_s3::types::CompletedPart::builder()
+                            .part_number(
+                                part_number.try_into().context("part number out of range")?,
+                            )
+                            .e_tag(etag)
+                            .build(),
+                    )
+                },
+            )
+            .collect::<anyhow::Result<_>>()?;
+        self.s3_client
+            .complete_multipart_upload()
+            .bucket(bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(parts))
+                    .build(),
+            )
+            .send()
+            .await
+            .context("failed to complete multipart upload")?;
+        Ok(())
+    }
+
+    #[instrument(level = "debug", skip(self))]
+    pub async fn abort_multipart_upload(
+        &self,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+    ) -> anyhow::Result<()> {
+        self.s3_client
+            .abort_multipart_upload()
+            .bucket(bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .send()
+            .await
+            .context("failed to abort multipart upload")?;
+        Ok(())
+    }
+
+    /// Retrieve metadata about the object, including its ETag, content type and user-defined
+    /// metadata
+    #[instrument(level = "debug", skip(self))]
+    pub async fn object_info(&self, bucket: &str, key: &str) -> anyhow::Result<ObjectInfo> {
+        let HeadObjectOutput {
+            content_length,
+            last_modified,
+            e_tag,
+            content_type,
+            metadata,
+            ..
+        } = self
+            .s3_client
+            .head_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .with_context(|| format!("failed to get metadata of object [{bucket}/{key}]"))?;
+        Ok(ObjectInfo {
+            name: key.into(),
+            container: bucket.into(),
+            size: content_length
+                .and_then(|v| v.try_into().ok())
+                .unwrap_or_default(),
+            // NOTE: S3 does not report the creation time, so the last modification time is used
+            created_at: last_modified
+                .and_then(|t| t.secs().try_into().ok())
+                .unwrap_or_default(),
+            etag: e_tag.unwrap_or_default(),
+            content_type,
+            metadata: metadata.unwrap_or_default().into_iter().collect(),
+        })
+    }
+
+    /// Write the object with the given options, returning the ETag of the written version
+    #[instrument(level = "debug", skip(self, data))]
+    pub async fn put_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        data: Bytes,
+        WriteOptions {
+            content_type,
+            metadata,
+            condition,
+        }: WriteOptions,
+    ) -> Result<String, ObjectError> {
+        let req = self
+            .s3_client
+            .put_object()
+            .bucket(bucket)
+            .key(key)
+            .body(data.into())
+            .set_content_type(content_type)
+            .set_metadata(Some(metadata.into_iter().collect()));
+        let req = match condition {
+            Some(Condition::IfMatch(etag)) => req.if_match(etag),
+            Some(Condition::IfNoneMatch) => req.if_none_match("*"),
+            None => req,
+        };
+        let out = req
+            .send()
+            .await
+            .map_err(|err| object_error(err, "failed to put object"))?;
+        out.e_tag
+            .ok_or_else(|| ObjectError::Other("ETag missing in response".into()))
+    }
+
+    /// Delete the object, if `condition` holds
+    #[instrument(level = "debug", skip(self))]
+    pub async fn delete_object_if(
+        &self,
+        bucket: &str,
+        key: &str,
+        condition: Option<Condition>,
+    ) -> Result<(), ObjectError> {
+        let req = self.s3_client.delete_object().bucket(bucket).key(key);
+        let req = match condition {
+            Some(Condition::IfMatch(etag)) => req.if_match(etag),
+            // S3 does not support `If-None-Match` on deletes, but deleting an object, which does
+            // not exist, is a no-op anyway
+            Some(Condition::IfNoneMatch) => {
+                return match self.has_object(bucket, key).await {
+                    Ok(false) => Ok(()),
+                    Ok(true) => Err(ObjectError::PreconditionFailed),
+                    Err(err) => Err(ObjectError::Other(format!("{err:#}"))),
+                };
+            }
+            None => req,
+        };
+        req.send()
+            .await
+            .map_err(|err| object_error(err, "failed to delete object"))?;
+        Ok(())
+    }
+}
+
 /// Blobstore S3 provider
 ///
 /// This struct will be the target of generated implementations (via wit-provider-bindgen)
@@ -549,12 +917,24 @@ impl StorageClient {
 pub struct BlobstoreS3Provider {
     /// Per-component storage for NATS connection clients
     actors: Arc<RwLock<HashMap<String, StorageClient>>>,
+    /// Per-link tasks delivering object change notifications to components linked as targets
+    watchers: Arc<RwLock<HashMap<(String, String), JoinHandle<()>>>>,
 }
 
 pub async fn run() -> anyhow::Result<()> {
     BlobstoreS3Provider::run().await
 }
 
+/// Serve both `wrpc:blobstore` and `wasmcloud:blobstore` extension exports
+async fn serve(
+    wrpc: &WrpcClient,
+    provider: BlobstoreS3Provider,
+) -> anyhow::Result<InvocationStreams> {
+    let mut invocations = wrpc_interface_blobstore::bindings::serve(wrpc, provider.clone()).await?;
+    invocations.extend(bindings::serve(wrpc, provider).await?);
+    Ok(invocations)
+}
+
 impl BlobstoreS3Provider {
     pub async fn run() -> anyhow::Result<()> {
         initialize_observability!(
@@ -903,6 +1283,243 @@ impl Handler<Option<Context>> for BlobstoreS3Provider {
     }
 }
 
+impl presign::Handler<Option<Context>> for BlobstoreS3Provider {
+    #[instrument(level = "trace", skip(self))]
+    async fn presign_get(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        expires_in: u64,
+    ) -> anyhow::Result<Result<PresignedRequest, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let client = self.client(cx).await?;
+            client
+                .presign(
+                    client.unalias(&id.container),
+                    &id.object,
+                    Duration::from_secs(expires_in),
+                    false,
+                )
+                .await
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn presign_put(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        expires_in: u64,
+    ) -> anyhow::Result<Result<PresignedRequest, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let client = self.client(cx).await?;
+            client
+                .presign(
+                    client.unalias(&id.container),
+                    &id.object,
+                    Duration::from_secs(expires_in),
+                    true,
+                )
+                .await
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+}
+
+impl multipart::Handler<Option<Context>> for BlobstoreS3Provider {
+    #[instrument(level = "trace", skip(self))]
+    async fn create_upload(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+    ) -> anyhow::Result<Result<String, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let client = self.client(cx).await?;
+            client
+                .create_multipart_upload(client.unalias(&id.container), &id.object)
+                .await
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self, data))]
+    async fn upload_part(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        upload_id: String,
+        part_number: u32,
+        data: Bytes,
+    ) -> anyhow::Result<Result<CompletedPart, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let client = self.client(cx).await?;
+            client
+                .upload_part(
+                    client.unalias(&id.container),
+                    &id.object,
+                    &upload_id,
+                    part_number,
+                    data,
+                )
+                .await
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn list_parts(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        upload_id: String,
+    ) -> anyhow::Result<Result<Vec<CompletedPart>, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let client = self.client(cx).await?;
+            client
+                .list_parts(client.unalias(&id.container), &id.object, &upload_id)
+                .await
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn complete_upload(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        upload_id: String,
+        parts: Vec<CompletedPart>,
+    ) -> anyhow::Result<Result<(), String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let client = self.client(cx).await?;
+            client
+                .complete_multipart_upload(
+                    client.unalias(&id.container),
+                    &id.object,
+                    &upload_id,
+                    parts,
+                )
+                .await
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn abort_upload(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        upload_id: String,
+    ) -> anyhow::Result<Result<(), String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let client = self.client(cx).await?;
+            client
+                .abort_multipart_upload(client.unalias(&id.container), &id.object, &upload_id)
+                .await
+        }
+        .await
+        .map_err(|err| format!("{err:#}")))
+    }
+}
+
+impl objects::Handler<Option<Context>> for BlobstoreS3Provider {
+    #[instrument(level = "trace", skip(self))]
+    async fn get_object_info(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+    ) -> anyhow::Result<Result<ObjectInfo, String>> {
+        Ok(async {
+            propagate_trace_for_ctx!(cx);
+            let client = self.client(cx).await?;
+            let info = client
+                .object_info(client.unalias(&id.container), &id.object)
+                .await?;
+            Ok(ObjectInfo {
+                container: id.container,
+                ..info
+            })
+        }
+        .await
+        .map_err(|err: anyhow::Error| format!("{err:#}")))
+    }
+
+    #[instrument(level = "trace", skip(self, data))]
+    async fn write_object(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        data: Bytes,
+        options: WriteOptions,
+    ) -> anyhow::Result<Result<String, ObjectError>> {
+        propagate_trace_for_ctx!(cx);
+        let client = match self.client(cx).await {
+            Ok(client) => client,
+            Err(err) => return Ok(Err(ObjectError::Other(format!("{err:#}")))),
+        };
+        Ok(client
+            .put_object(client.unalias(&id.container), &id.object, data, options)
+            .await)
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn delete_object(
+        &self,
+        cx: Option<Context>,
+        id: ExtObjectId,
+        condition: Option<Condition>,
+    ) -> anyhow::Result<Result<(), ObjectError>> {
+        propagate_trace_for_ctx!(cx);
+        let client = match self.client(cx).await {
+            Ok(client) => client,
+            Err(err) => return Ok(Err(ObjectError::Other(format!("{err:#}")))),
+        };
+        Ok(client
+            .delete_object_if(client.unalias(&id.container), &id.object, condition)
+            .await)
+    }
+}
+
+/// Invoke `wasmcloud:blobstore/watcher` on the component for an object event
+#[instrument(level = "debug", skip(wrpc))]
+async fn notify_component(wrpc: &WrpcClient, event: ObjectEvent) -> anyhow::Result<()> {
+    let mut cx = async_nats::HeaderMap::new();
+    for (k, v) in TraceContextInjector::default_with_span().iter() {
+        cx.insert(k.as_str(), v.as_str());
+    }
+    let res = match event {
+        ObjectEvent::Created { bucket, key } => {
+            let id = ExtObjectId {
+                container: bucket,
+                object: key,
+            };
+            watcher::on_create(wrpc, Some(cx), &id).await
+        }
+        ObjectEvent::Removed { bucket, key } => {
+            let id = ExtObjectId {
+                container: bucket,
+                object: key,
+            };
+            watcher::on_delete(wrpc, Some(cx), &id).await
+        }
+    };
+    res.context("failed to notify component of object change")
+}
+
 /// Handle provider control commands
 /// `put_link` (new component link command), `del_link` (remove link command), and shutdown
 impl Provider for BlobstoreS3Provider {
@@ -939,11 +1556,79 @@ impl Provider for BlobstoreS3Provider {
         Ok(())
     }
 
+    /// Deliver object change notifications received on the `EVENTS_QUEUE_URL` SQS queue to the
+    /// component via `wasmcloud:blobstore/watcher`.
+    ///
+    /// Notifications can be restricted to a comma-separated list of `CONTAINERS`.
+    async fn receive_link_config_as_source(
+        &self,
+        link_config: LinkConfig<'_>,
+    ) -> anyhow::Result<()> {
+        let (_, _, interfaces) = link_config.wit_metadata;
+        if !interfaces.iter().any(|interface| interface == "watcher") {
+            return Ok(());
+        }
+        let queue_url = link_config.config.get("EVENTS_QUEUE_URL").context(
+            "`EVENTS_QUEUE_URL` must be specified to receive object change notifications",
+        )?;
+        let containers: Option<Vec<String>> =
+            link_config.config.get("CONTAINERS").map(|containers| {
+                containers
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|container| !container.is_empty())
+                    .map(String::from)
+                    .collect()
+            });
+        let config = StorageConfig::from_link_config(&link_config)
+            .await
+            .context("failed to build storage config")?;
+        let queue = EventQueue::new(&config, queue_url).await;
+        let client = StorageClient::new(config, link_config.config).await;
+        let wrpc = get_connection()
+            .get_wrpc_client(link_config.target_id)
+            .await
+            .context("failed to construct wRPC client")?;
+        let task = tokio::spawn(async move {
+            queue
+                .watch(&client, containers.as_deref(), |event| {
+                    notify_component(&wrpc, event)
+                })
+                .await
+        });
+        if let Some(task) = self.watchers.write().await.insert(
+            (
+                link_config.target_id.to_string(),
+                link_config.link_name.to_string(),
+            ),
+            task,
+        ) {
+            task.abort();
+        }
+        Ok(())
+    }
+
+    /// Stop delivering object change notifications to the component
+    #[instrument(level = "info", skip_all, fields(target_id = info.get_target_id()))]
+    async fn delete_link_as_source(&self, info: impl LinkDeleteInfo) -> anyhow::Result<()> {
+        let key = (
+            info.get_target_id().to_string(),
+            info.get_link_name().to_string(),
+        );
+        if let Some(task) = self.watchers.write().await.remove(&key) {
+            task.abort();
+        }
+        Ok(())
+    }
+
     /// Handle shutdown request by closing all connections
     async fn shutdown(&self) -> anyhow::Result<()> {
         let mut aw = self.actors.write().await;
         // empty the component link data and stop all servers
         aw.drain();
+        for (_, task) in self.watchers.write().await.drain() {
+            task.abort();
+        }
         Ok(())
     }
 }
@@ -968,5 +1653,9 @@ mod test {
         assert_eq!(client.unalias(&format!("{ALIAS_PREFIX}foo")), "bar");
         // undefined alias
         assert_eq!(client.unalias(&format!("{ALIAS_PREFIX}baz")), "baz");
+
+        // reverse lookup
+        assert_eq!(client.alias("bar"), "foo");
+        assert_eq!(client.alias("boo"), "boo");
     }
 }
@@ -22,6 +22,9 @@
 //! cargo test test_create_container -- --nocapture
 //! ```
 //!
+//! `test_watch_events` additionally requires SQS, which is started along with S3 in LocalStack,
+//! if `AWS_ENDPOINT` is not set.
+//!
 //! To see warnings, make sure add & enable `tracing_subscriber` in the appropriate test(s):
 //!
 //! ```rust
@@ -30,9 +33,13 @@
 
 use std::collections::HashMap;
 use std::env;
+use std::time::Duration;
 
 use anyhow::{Context as _, Result};
-use wasmcloud_provider_blobstore_s3::{StorageClient, StorageConfig};
+use aws_sdk_s3::types::{Event, NotificationConfiguration, QueueConfiguration};
+use aws_sdk_sqs::types::QueueAttributeName;
+use tokio::sync::oneshot;
+use wasmcloud_provider_blobstore_s3::{EventQueue, ObjectEvent, StorageClient, StorageConfig};
 use wasmcloud_test_util::testcontainers::{AsyncRunner as _, ContainerAsync, ImageExt, LocalStack};
 
 struct TestEnv {
@@ -46,7 +53,7 @@ impl TestEnv {
             (ep, None)
         } else {
             let node = LocalStack::default()
-                .with_env_var("SERVICES", "s3")
+                .with_env_var("SERVICES", "s3,sqs")
                 .start()
                 .await
                 .context("should have started localstack")?;
@@ -68,7 +75,11 @@ impl TestEnv {
     }
 
     pub async fn configure_test_client(&self) -> StorageClient {
-        let conf = StorageConfig {
+        StorageClient::new(self.storage_config(), &HashMap::new()).await
+    }
+
+    pub fn storage_config(&self) -> StorageConfig {
+        StorageConfig {
             endpoint: Some(self.endpoint.clone()),
             access_key_id: Self::env_var_or_default("AWS_ACCESS_KEY_ID", Some("test".to_string())),
             secret_access_key: Self::env_var_or_default(
@@ -81,9 +92,7 @@ impl TestEnv {
             session_token: None,
             sts_config: None,
             bucket_region: Self::env_var_or_default("BUCKET_REGION", None),
-        };
-
-        StorageClient::new(conf, &HashMap::new()).await
+        }
     }
 
     fn env_var_or_default(key: &str, default: Option<String>) -> Option<String> {
@@ -116,3 +125,103 @@ async fn test_create_container() {
         "Container should exist"
     );
 }
+
+/// Tests
+/// - object events are delivered from the bucket notification queue
+/// - buckets are reported under their alias
+#[tokio::test]
+async fn test_watch_events() {
+    let env = TestEnv::new()
+        .await
+        .expect("should have setup the test environment");
+
+    let config = env.storage_config();
+    let sdk_config = config.load_sdk_config().await;
+
+    let num = rand::random::<u64>();
+    let bucket = format!("test-events-{num}");
+    let s3 = StorageClient::new(
+        env.storage_config(),
+        &HashMap::from([("alias_events".into(), bucket.clone())]),
+    )
+    .await;
+    s3.create_container(&bucket).await.unwrap();
+
+    let sqs = aws_sdk_sqs::Client::new(&sdk_config);
+    let queue_url = sqs
+        .create_queue()
+        .queue_name(format!("test-events-{num}"))
+        .send()
+        .await
+        .expect("should have created queue")
+        .queue_url
+        .expect("queue URL should be set");
+    let queue_arn = sqs
+        .get_queue_attributes()
+        .queue_url(&queue_url)
+        .attribute_names(QueueAttributeName::QueueArn)
+        .send()
+        .await
+        .expect("should have gotten queue attributes")
+        .attributes
+        .and_then(|mut attributes| attributes.remove(&QueueAttributeName::QueueArn))
+        .expect("queue ARN should be set");
+
+    let s3_client = aws_sdk_s3::Client::from_conf(
+        aws_sdk_s3::Config::from(&sdk_config)
+            .to_builder()
+            .force_path_style(true)
+            .build(),
+    );
+    s3_client
+        .put_bucket_notification_configuration()
+        .bucket(&bucket)
+        .notification_configuration(
+            NotificationConfiguration::builder()
+                .queue_configurations(
+                    QueueConfiguration::builder()
+                        .queue_arn(queue_arn)
+                        .events(Event::S3ObjectCreated)
+                        .build()
+                        .unwrap(),
+                )
+                .build(),
+        )
+        .send()
+        .await
+        .expect("should have configured bucket notifications");
+    s3_client
+        .put_object()
+        .bucket(s3.unalias("events"))
+        .key("file.txt")
+        .body(b"test".to_vec().into())
+        .send()
+        .await
+        .expect("should have put object");
+
+    let queue = EventQueue::new(&config, queue_url).await;
+    let (tx, rx) = oneshot::channel();
+    let mut tx = Some(tx);
+    let containers = ["events".to_string()];
+    let watch = queue.watch(&s3, Some(&containers[..]), |event| {
+        if let Some(tx) = tx.take() {
+            _ = tx.send(event);
+        }
+        async { Ok(()) }
+    });
+    let event = tokio::time::timeout(Duration::from_secs(60), async {
+        tokio::select! {
+            () = watch => unreachable!("watching events should not stop"),
+            event = rx => event.expect("event should have been sent"),
+        }
+    })
+    .await
+    .expect("event should have been delivered");
+    assert_eq!(
+        event,
+        ObjectEvent::Created {
+            bucket: "events".into(),
+            key: "file.txt".into(),
+        }
+    );
+}
@@ -24,6 +24,10 @@ use tokio::time::sleep;
 use tokio::{select, spawn};
 use tracing::{debug, error, instrument, trace, warn, Instrument as _};
 
+use wasmcloud_provider_sdk::core::secrets::{
+    SecretValue, WORKLOAD_X509_BUNDLE_SECRET, WORKLOAD_X509_SVID_KEY_SECRET,
+    WORKLOAD_X509_SVID_SECRET,
+};
 use wasmcloud_provider_sdk::core::tls;
 use wasmcloud_provider_sdk::{
     get_connection, initialize_observability, load_host_data, propagate_trace_for_ctx,
@@ -44,6 +48,9 @@ static ZERO_INSTANT: LazyLock<Instant> = LazyLock::new(Instant::now);
 #[derive(Clone)]
 pub struct HttpClientProvider {
     tls: tokio_rustls::TlsConnector,
+    roots: Arc<rustls::RootCertStore>,
+    /// TLS connector presenting the X.509-SVID of the provider, if the host delivered one
+    identity: Arc<RwLock<Option<tokio_rustls::TlsConnector>>>,
     conns: ConnPool<wrpc_interface_http::HttpBody>,
     #[allow(unused)]
     tasks: Arc<JoinSet<()>>,
@@ -268,6 +275,7 @@ impl<T> ConnPool<T> {
     #[cfg(any(target_arch = "riscv64", target_arch = "s390x"))]
     pub async fn connect_https(
         &self,
+        _tls: &tokio_rustls::TlsConnector,
         _authority: &str,
     ) -> Result<Cacheable<PooledConn<http1::SendRequest<T>>>, types::ErrorCode> {
         Err(types::ErrorCode::InternalError(Some(
@@ -349,6 +357,7 @@ pub async fn run() -> anyhow::Result<()> {
     );
     let host_data = load_host_data()?;
     let provider = HttpClientProvider::new(&host_data.config, DEFAULT_IDLE_TIMEOUT).await?;
+    provider.update_identity(&host_data.secrets).await?;
     let shutdown = run_provider(provider.clone(), "http-client-provider")
         .await
         .context("failed to run provider")?;
@@ -394,8 +403,11 @@ impl HttpClientProvider {
         idle_timeout: Duration,
     ) -> anyhow::Result<Self> {
         // Short circuit to the default connector if no configuration is provided
-        let tls = if config.is_empty() {
-            tls::DEFAULT_RUSTLS_CONNECTOR.clone()
+        let (tls, roots) = if config.is_empty() {
+            (
+                tls::DEFAULT_RUSTLS_CONNECTOR.clone(),
+                Arc::clone(&tls::DEFAULT_ROOTS),
+            )
         } else {
             let mut ca = rustls::RootCertStore::empty();
 
@@ -431,11 +443,15 @@ impl HttpClientProvider {
                     ignored, "added additional root certificates from file"
                 );
             }
-            tokio_rustls::TlsConnector::from(Arc::new(
-                rustls::ClientConfig::builder()
-                    .with_root_certificates(ca)
-                    .with_no_client_auth(),
-            ))
+            let ca = Arc::new(ca);
+            (
+                tokio_rustls::TlsConnector::from(Arc::new(
+                    rustls::ClientConfig::builder()
+                        .with_root_certificates(Arc::clone(&ca))
+                        .with_no_client_auth(),
+                )),
+                ca,
+            )
         };
         let conns = ConnPool::default();
         let mut tasks = JoinSet::new();
@@ -450,10 +466,66 @@ impl HttpClientProvider {
         });
         Ok(Self {
             tls,
+            roots,
+            identity: Arc::default(),
             conns,
             tasks: Arc::new(tasks),
         })
     }
+
+    /// Authenticates outgoing HTTPS requests using the X.509-SVID of the provider contained in
+    /// `secrets`, if any. Pooled connections authenticated using a previous X.509-SVID are dropped.
+    async fn update_identity(&self, secrets: &HashMap<String, SecretValue>) -> anyhow::Result<()> {
+        let Some(tls) = identity_tls_connector(&self.roots, secrets)? else {
+            return Ok(());
+        };
+        debug!("using X.509-SVID of the provider");
+        *self.identity.write().await = Some(tls);
+        self.conns.https.write().await.clear();
+        Ok(())
+    }
+}
+
+/// Builds a TLS connector authenticating with the X.509-SVID contained in `secrets`, trusting
+/// the X.509 bundle of its trust domain in addition to `roots`.
+///
+/// Returns `None` if `secrets` do not contain an X.509-SVID
+fn identity_tls_connector(
+    roots: &rustls::RootCertStore,
+    secrets: &HashMap<String, SecretValue>,
+) -> anyhow::Result<Option<tokio_rustls::TlsConnector>> {
+    let (Some(svid), Some(key)) = (
+        secrets
+            .get(WORKLOAD_X509_SVID_SECRET)
+            .and_then(SecretValue::as_string),
+        secrets
+            .get(WORKLOAD_X509_SVID_KEY_SECRET)
+            .and_then(SecretValue::as_string),
+    ) else {
+        return Ok(None);
+    };
+    let certs = rustls_pemfile::certs(&mut svid.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .context("failed to parse X.509-SVID certificate chain")?;
+    let key = rustls_pemfile::private_key(&mut key.as_bytes())
+        .context("failed to parse X.509-SVID private key")?
+        .context("X.509-SVID private key missing")?;
+    let mut ca = roots.clone();
+    if let Some(bundle) = secrets
+        .get(WORKLOAD_X509_BUNDLE_SECRET)
+        .and_then(SecretValue::as_string)
+    {
+        let bundle = rustls_pemfile::certs(&mut bundle.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .context("failed to parse X.509 bundle")?;
+        let (added, ignored) = ca.add_parsable_certificates(bundle);
+        debug!(added, ignored, "added X.509 bundle root certificates");
+    }
+    let config = rustls::ClientConfig::builder()
+        .with_root_certificates(ca)
+        .with_client_auth_cert(certs, key)
+        .context("invalid X.509-SVID")?;
+    Ok(Some(tokio_rustls::TlsConnector::from(Arc::new(config))))
 }
 
 fn dns_error(rcode: String, info_code: u16) -> types::ErrorCode {
@@ -496,6 +568,13 @@ impl ServeOutgoingHandlerHttp<Option<Context>> for HttpClientProvider {
         wasmcloud_provider_sdk::wasmcloud_tracing::http::HeaderInjector(request.headers_mut())
             .inject_context();
 
+        let tls = self
+            .identity
+            .read()
+            .await
+            .clone()
+            .unwrap_or_else(|| self.tls.clone());
+
         // Adapted from:
         // https://github.com/bytecodealliance/wasmtime/blob/d943d57e78950da21dd430e0847f3b8fd0ade073/crates/wasi-http/src/types.rs#L333-L475
 
@@ -556,7 +635,7 @@ impl ServeOutgoingHandlerHttp<Option<Context>> for HttpClientProvider {
                 let mut sender = if use_tls {
                     tokio::time::timeout(
                         connect_timeout,
-                        self.conns.connect_https(&self.tls, &authority),
+                        self.conns.connect_https(&tls, &authority),
                     )
                     .await
                 } else {
@@ -637,7 +716,16 @@ impl ServeOutgoingHandlerHttp<Option<Context>> for HttpClientProvider {
 }
 
 /// Handle provider control commands
-impl Provider for HttpClientProvider {}
+impl Provider for HttpClientProvider {
+    /// Uses the rotated X.509-SVID of the provider to authenticate outgoing HTTPS requests
+    #[instrument(level = "debug", skip_all)]
+    async fn on_secrets_update(
+        &self,
+        secrets: &HashMap<String, SecretValue>,
+    ) -> anyhow::Result<()> {
+        self.update_identity(secrets).await
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -171,6 +171,12 @@ pub(crate) async fn invoke_component(
     {
         cx.insert(k.as_str(), v.as_str());
     }
+    if let Some(timeout) = timeout {
+        cx.insert(
+            wasmcloud_core::WRPC_TIMEOUT_HEADER,
+            timeout.as_millis().to_string().as_str(),
+        );
+    }
 
     trace!(?req, component_id = target, "httpserver calling component");
     let fut = wrpc.invoke_handle_http(Some(cx), req);
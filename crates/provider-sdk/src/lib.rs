@@ -273,6 +273,22 @@ pub trait Provider<E = anyhow::Error>: Sync {
         async { Ok(()) }
     }
 
+    /// Process a secrets update for the provider
+    ///
+    /// The host sends the secrets of the provider again when they change after startup, e.g. when
+    /// the X.509-SVID of the provider is rotated. Only the updated secrets are contained in `secrets`.
+    ///
+    /// # Arguments
+    ///
+    /// * `secrets` - The updated secrets
+    fn on_secrets_update(
+        &self,
+        secrets: &HashMap<String, SecretValue>,
+    ) -> impl Future<Output = Result<(), E>> + Send {
+        let _ = secrets;
+        async { Ok(()) }
+    }
+
     /// Receive and handle a link that has been established on the lattice where this provider is the source.
     ///
     /// Implement this when your provider needs to call other components.
@@ -25,8 +25,8 @@ use wasmcloud_core::nats::convert_header_map_to_hashmap;
 use wasmcloud_core::rpc::{health_subject, link_del_subject, link_put_subject, shutdown_subject};
 use wasmcloud_core::secrets::SecretValue;
 use wasmcloud_core::{
-    provider_config_update_subject, HealthCheckRequest, HealthCheckResponse, HostData,
-    InterfaceLinkDefinition, LatticeTarget,
+    provider_config_update_subject, provider_secrets_update_subject, HealthCheckRequest,
+    HealthCheckResponse, HostData, InterfaceLinkDefinition, LatticeTarget,
 };
 
 #[cfg(feature = "otel")]
@@ -370,12 +370,46 @@ async fn subscribe_config_update(
     Ok(config_update_rx)
 }
 
+/// Subscribe to secrets updates that are passed by the host.
+///
+/// The host sends updated secrets of the provider sealed with the provider xkey, which are
+/// decrypted once they are handled.
+async fn subscribe_secrets_update(
+    nats: Arc<async_nats::Client>,
+    mut quit: broadcast::Receiver<()>,
+    lattice: &str,
+    provider_key: &str,
+) -> ProviderInitResult<mpsc::Receiver<(Bytes, oneshot::Sender<()>)>> {
+    let (secrets_update_tx, secrets_update_rx) = mpsc::channel(1);
+    let mut sub = nats
+        .subscribe(provider_secrets_update_subject(lattice, provider_key).to_subject())
+        .await?;
+    spawn({
+        async move {
+            process_until_quit!(sub, quit, msg, {
+                let (tx, rx) = oneshot::channel();
+                if let Err(err) = secrets_update_tx.send((msg.payload, tx)).await {
+                    error!(%err, "failed to send secrets update");
+                    continue;
+                }
+                if let Err(err) = rx.await.as_ref() {
+                    error!(%err, "failed to receive secrets update response");
+                }
+            });
+        }
+        .instrument(tracing::debug_span!("subscribe_secrets_update"))
+    });
+
+    Ok(secrets_update_rx)
+}
+
 pub struct ProviderCommandReceivers {
     health: mpsc::Receiver<(HealthCheckRequest, oneshot::Sender<HealthCheckResponse>)>,
     shutdown: mpsc::Receiver<oneshot::Sender<()>>,
     link_put: mpsc::Receiver<(InterfaceLinkDefinition, oneshot::Sender<()>)>,
     link_del: mpsc::Receiver<(InterfaceLinkDefinition, oneshot::Sender<()>)>,
     config_update: mpsc::Receiver<(HashMap<String, String>, oneshot::Sender<()>)>,
+    secrets_update: mpsc::Receiver<(Bytes, oneshot::Sender<()>)>,
 }
 
 impl ProviderCommandReceivers {
@@ -387,7 +421,7 @@ impl ProviderCommandReceivers {
         provider_link_put_id: &str,
         host_id: &str,
     ) -> ProviderInitResult<Self> {
-        let (health, shutdown, link_put, link_del, config_update) = try_join!(
+        let (health, shutdown, link_put, link_del, config_update, secrets_update) = try_join!(
             subscribe_health(
                 Arc::clone(&nats),
                 quit_tx.subscribe(),
@@ -419,6 +453,12 @@ impl ProviderCommandReceivers {
                 lattice,
                 provider_key
             ),
+            subscribe_secrets_update(
+                Arc::clone(&nats),
+                quit_tx.subscribe(),
+                lattice,
+                provider_key
+            ),
         )?;
         Ok(Self {
             health,
@@ -426,6 +466,7 @@ impl ProviderCommandReceivers {
             link_put,
             link_del,
             config_update,
+            secrets_update,
         })
     }
 }
@@ -673,6 +714,7 @@ pub async fn handle_provider_commands(
         mut link_put,
         mut link_del,
         mut config_update,
+        mut secrets_update,
     }: ProviderCommandReceivers,
 ) {
     loop {
@@ -797,6 +839,36 @@ pub async fn handle_provider_commands(
                     return
                 };
             }
+            req = secrets_update.recv() => {
+                if let Some((secrets, tx)) = req {
+                    match decrypt_link_secret(
+                        Some(&secrets),
+                        &connection.provider_xkey,
+                        &connection.host_xkey,
+                    ) {
+                        Ok(secrets) => {
+                            // Notify the provider that some secrets have been updated
+                            if let Err(e) = provider.on_secrets_update(&secrets).await {
+                                error!(error = %e, "failed to pass through secrets update for provider");
+                            }
+                        }
+                        Err(e) => error!(error = %e, "received invalid secrets update"),
+                    }
+
+                    if tx.send(()).is_err() {
+                        error!("failed to send secrets update response");
+                    }
+                } else {
+                    error!("failed to handle secrets update, shutdown");
+                    if let Err(e) = provider.shutdown().await {
+                        error!(error = %e, "failed to shutdown provider");
+                    }
+                    if quit_tx.send(()).is_err() {
+                        error!("failed to send quit");
+                    };
+                    return
+                };
+            }
         }
     }
 }
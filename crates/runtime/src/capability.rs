@@ -127,8 +127,8 @@ impl std::fmt::Display for wasmtime_bindings::wasi::logging0_1_0_draft::logging:
 pub use unversioned_logging_bindings::wasi::logging as unversioned_logging;
 pub use wasmtime_bindings::wasi::{blobstore, keyvalue, logging0_1_0_draft as logging};
 pub use wasmtime_bindings::wasmcloud::{
-    bus1_0_0, bus2_0_1 as bus, bus2_0_1, identity, messaging0_2_0, messaging0_3_0 as messaging,
-    messaging0_3_0, secrets,
+    blobstore as wasmcloud_blobstore, bus1_0_0, bus2_0_1 as bus, bus2_0_1, identity,
+    messaging0_2_0, messaging0_3_0 as messaging, messaging0_3_0, secrets,
 };
 pub use wasmtime_bindings::Interfaces;
 pub use wasmtime_wasi_http::bindings::http;
@@ -25,6 +25,8 @@ use crate::capability::blobstore::types::{
     ContainerMetadata, Error, ObjectId, ObjectMetadata, ObjectName,
 };
 use crate::capability::blobstore::{blobstore, container, types};
+use crate::capability::wasmcloud_blobstore::{multipart, objects, presign, types as ext_types};
+use crate::capability::wrpc::wasmcloud::blobstore as wrpc_ext;
 use crate::capability::wrpc::wrpc::blobstore::blobstore as blobstore_0_1_0;
 use crate::io::BufferedIncomingStream;
 
@@ -59,7 +61,7 @@ async fn invoke_with_fallback<
                 );
                 f_0_1_0().await
             }
-            InvocationErrorKind::Trap => Err(err),
+            InvocationErrorKind::Trap | InvocationErrorKind::Timeout => Err(err),
         },
     }
 }
@@ -1066,3 +1068,303 @@ where
 }
 
 impl<H> container::Host for Ctx<H> where H: Handler {}
+
+fn ext_object_id(
+    ext_types::ObjectId { container, object }: ext_types::ObjectId,
+) -> wrpc_ext::types::ObjectId {
+    wrpc_ext::types::ObjectId { container, object }
+}
+
+impl From<wrpc_ext::types::PresignedRequest> for ext_types::PresignedRequest {
+    fn from(
+        wrpc_ext::types::PresignedRequest {
+            url,
+            method,
+            headers,
+            expires_at,
+        }: wrpc_ext::types::PresignedRequest,
+    ) -> Self {
+        Self {
+            url,
+            method,
+            headers,
+            expires_at,
+        }
+    }
+}
+
+impl From<wrpc_ext::types::CompletedPart> for ext_types::CompletedPart {
+    fn from(
+        wrpc_ext::types::CompletedPart {
+            part_number,
+            etag,
+            size,
+        }: wrpc_ext::types::CompletedPart,
+    ) -> Self {
+        Self {
+            part_number,
+            etag,
+            size,
+        }
+    }
+}
+
+impl From<ext_types::CompletedPart> for wrpc_ext::types::CompletedPart {
+    fn from(
+        ext_types::CompletedPart {
+            part_number,
+            etag,
+            size,
+        }: ext_types::CompletedPart,
+    ) -> Self {
+        Self {
+            part_number,
+            etag,
+            size,
+        }
+    }
+}
+
+impl From<wrpc_ext::types::ObjectInfo> for ext_types::ObjectInfo {
+    fn from(
+        wrpc_ext::types::ObjectInfo {
+            name,
+            container,
+            size,
+            created_at,
+            etag,
+            content_type,
+            metadata,
+        }: wrpc_ext::types::ObjectInfo,
+    ) -> Self {
+        Self {
+            name,
+            container,
+            size,
+            created_at,
+            etag,
+            content_type,
+            metadata,
+        }
+    }
+}
+
+impl From<ext_types::Condition> for wrpc_ext::types::Condition {
+    fn from(condition: ext_types::Condition) -> Self {
+        match condition {
+            ext_types::Condition::IfMatch(etag) => Self::IfMatch(etag),
+            ext_types::Condition::IfNoneMatch => Self::IfNoneMatch,
+        }
+    }
+}
+
+impl From<ext_types::WriteOptions> for wrpc_ext::types::WriteOptions {
+    fn from(
+        ext_types::WriteOptions {
+            content_type,
+            metadata,
+            condition,
+        }: ext_types::WriteOptions,
+    ) -> Self {
+        Self {
+            content_type,
+            metadata,
+            condition: condition.map(Into::into),
+        }
+    }
+}
+
+impl From<wrpc_ext::types::ObjectError> for ext_types::ObjectError {
+    fn from(err: wrpc_ext::types::ObjectError) -> Self {
+        match err {
+            wrpc_ext::types::ObjectError::PreconditionFailed => Self::PreconditionFailed,
+            wrpc_ext::types::ObjectError::Other(err) => Self::Other(err),
+        }
+    }
+}
+
+impl<H: Handler> ext_types::Host for Ctx<H> {}
+
+impl<H> presign::Host for Ctx<H>
+where
+    H: Handler,
+{
+    #[instrument(skip(self))]
+    async fn presign_get(
+        &mut self,
+        id: ext_types::ObjectId,
+        expires_in: u64,
+    ) -> anyhow::Result<Result<ext_types::PresignedRequest>> {
+        self.attach_parent_context();
+        let res = wrpc_ext::presign::presign_get(
+            &self.handler,
+            Some(ReplacedInstanceTarget::BlobstorePresign),
+            &ext_object_id(id),
+            expires_in,
+        )
+        .await?;
+        Ok(res.map(Into::into))
+    }
+
+    #[instrument(skip(self))]
+    async fn presign_put(
+        &mut self,
+        id: ext_types::ObjectId,
+        expires_in: u64,
+    ) -> anyhow::Result<Result<ext_types::PresignedRequest>> {
+        self.attach_parent_context();
+        let res = wrpc_ext::presign::presign_put(
+            &self.handler,
+            Some(ReplacedInstanceTarget::BlobstorePresign),
+            &ext_object_id(id),
+            expires_in,
+        )
+        .await?;
+        Ok(res.map(Into::into))
+    }
+}
+
+impl<H> multipart::Host for Ctx<H>
+where
+    H: Handler,
+{
+    #[instrument(skip(self))]
+    async fn create_upload(&mut self, id: ext_types::ObjectId) -> anyhow::Result<Result<String>> {
+        self.attach_parent_context();
+        wrpc_ext::multipart::create_upload(
+            &self.handler,
+            Some(ReplacedInstanceTarget::BlobstoreMultipart),
+            &ext_object_id(id),
+        )
+        .await
+    }
+
+    #[instrument(skip(self, data))]
+    async fn upload_part(
+        &mut self,
+        id: ext_types::ObjectId,
+        upload_id: String,
+        part_number: u32,
+        data: Vec<u8>,
+    ) -> anyhow::Result<Result<ext_types::CompletedPart>> {
+        self.attach_parent_context();
+        let res = wrpc_ext::multipart::upload_part(
+            &self.handler,
+            Some(ReplacedInstanceTarget::BlobstoreMultipart),
+            &ext_object_id(id),
+            &upload_id,
+            part_number,
+            &Bytes::from(data),
+        )
+        .await?;
+        Ok(res.map(Into::into))
+    }
+
+    #[instrument(skip(self))]
+    async fn list_parts(
+        &mut self,
+        id: ext_types::ObjectId,
+        upload_id: String,
+    ) -> anyhow::Result<Result<Vec<ext_types::CompletedPart>>> {
+        self.attach_parent_context();
+        let res = wrpc_ext::multipart::list_parts(
+            &self.handler,
+            Some(ReplacedInstanceTarget::BlobstoreMultipart),
+            &ext_object_id(id),
+            &upload_id,
+        )
+        .await?;
+        Ok(res.map(|parts| parts.into_iter().map(Into::into).collect()))
+    }
+
+    #[instrument(skip(self))]
+    async fn complete_upload(
+        &mut self,
+        id: ext_types::ObjectId,
+        upload_id: String,
+        parts: Vec<ext_types::CompletedPart>,
+    ) -> anyhow::Result<Result<()>> {
+        self.attach_parent_context();
+        let parts: Vec<_> = parts.into_iter().map(Into::into).collect();
+        wrpc_ext::multipart::complete_upload(
+            &self.handler,
+            Some(ReplacedInstanceTarget::BlobstoreMultipart),
+            &ext_object_id(id),
+            &upload_id,
+            &parts,
+        )
+        .await
+    }
+
+    #[instrument(skip(self))]
+    async fn abort_upload(
+        &mut self,
+        id: ext_types::ObjectId,
+        upload_id: String,
+    ) -> anyhow::Result<Result<()>> {
+        self.attach_parent_context();
+        wrpc_ext::multipart::abort_upload(
+            &self.handler,
+            Some(ReplacedInstanceTarget::BlobstoreMultipart),
+            &ext_object_id(id),
+            &upload_id,
+        )
+        .await
+    }
+}
+
+impl<H> objects::Host for Ctx<H>
+where
+    H: Handler,
+{
+    #[instrument(skip(self))]
+    async fn get_object_info(
+        &mut self,
+        id: ext_types::ObjectId,
+    ) -> anyhow::Result<Result<ext_types::ObjectInfo>> {
+        self.attach_parent_context();
+        let res = wrpc_ext::objects::get_object_info(
+            &self.handler,
+            Some(ReplacedInstanceTarget::BlobstoreObjects),
+            &ext_object_id(id),
+        )
+        .await?;
+        Ok(res.map(Into::into))
+    }
+
+    #[instrument(skip(self, data))]
+    async fn write_object(
+        &mut self,
+        id: ext_types::ObjectId,
+        data: Vec<u8>,
+        options: ext_types::WriteOptions,
+    ) -> anyhow::Result<Result<String, ext_types::ObjectError>> {
+        self.attach_parent_context();
+        let res = wrpc_ext::objects::write_object(
+            &self.handler,
+            Some(ReplacedInstanceTarget::BlobstoreObjects),
+            &ext_object_id(id),
+            &Bytes::from(data),
+            &options.into(),
+        )
+        .await?;
+        Ok(res.map_err(Into::into))
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_object(
+        &mut self,
+        id: ext_types::ObjectId,
+        condition: Option<ext_types::Condition>,
+    ) -> anyhow::Result<Result<(), ext_types::ObjectError>> {
+        self.attach_parent_context();
+        let res = wrpc_ext::objects::delete_object(
+            &self.handler,
+            Some(ReplacedInstanceTarget::BlobstoreObjects),
+            &ext_object_id(id),
+            condition.map(Into::into),
+        )
+        .await?;
+        Ok(res.map_err(Into::into))
+    }
+}
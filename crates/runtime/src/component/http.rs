@@ -1,5 +1,8 @@
 use core::ops::Deref;
 
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
 use anyhow::{bail, Context as _};
 use futures::stream::StreamExt as _;
 use tokio::sync::oneshot;
@@ -16,7 +19,11 @@ use wrpc_interface_http::ServeIncomingHandlerWasmtime;
 
 use crate::capability::http::types;
 
-use super::{new_store, Ctx, Handler, Instance, ReplacedInstanceTarget, WrpcServeEvent};
+use super::pool::PooledInstance;
+use super::{
+    Ctx, Handler, Instance, InstanceReuse, InvocationDeadline, ReplacedInstanceTarget,
+    WrpcServeEvent,
+};
 
 pub mod incoming_http_bindings {
     wasmtime::component::bindgen!({
@@ -117,7 +124,7 @@ where
 impl<H, C> ServeIncomingHandlerWasmtime<C> for Instance<H, C>
 where
     H: Handler,
-    C: Send + Deref<Target = tracing::Span>,
+    C: Send + Deref<Target = tracing::Span> + InvocationDeadline,
 {
     #[instrument(level = "debug", skip_all)]
     async fn handle(
@@ -136,15 +143,19 @@ where
         let scheme = wrpc_interface_http::bindings::wrpc::http::types::Scheme::from(scheme).into();
 
         let (tx, rx) = oneshot::channel();
-        let mut store = new_store(&self.engine, self.handler.clone(), self.max_execution_time);
-        let pre = incoming_http_bindings::IncomingHttpPre::new(self.pre.clone())
-            .context("failed to pre-instantiate `wasi:http/incoming-handler`")?;
         trace!("instantiating `wasi:http/incoming-handler`");
-        let bindings = pre
-            .instantiate_async(&mut store)
+        let PooledInstance {
+            mut store,
+            instance,
+            pool_hit,
+        } = self
+            .acquire(cx.deadline())
             .instrument(debug_span!("instantiate_async"))
             .await
             .context("failed to instantiate `wasi:http/incoming-handler`")?;
+        let bindings = incoming_http_bindings::IncomingHttp::new(&mut store, &instance)
+            .context("failed to load `wasi:http/incoming-handler` exports")?;
+        let fuel_consumed = store.data().fuel_consumed.clone();
         let data = store.data_mut();
 
         // The below is adapted from `WasiHttpView::new_incoming_request`, which is unusable for
@@ -167,7 +178,7 @@ where
         // Set the current invocation parent context for injection on outgoing wRPC requests
         let call_incoming_handle = info_span!("call_http_incoming_handle");
         store.data_mut().parent_context = Some(call_incoming_handle.context());
-        let handle = spawn(
+        let mut handle = spawn(
             async move {
                 debug!("invoking `wasi:http/incoming-handler.handle`");
                 if let Err(err) = bindings
@@ -179,7 +190,7 @@ where
                     warn!(?err, "failed to call `wasi:http/incoming-handler.handle`");
                     bail!(err.context("failed to call `wasi:http/incoming-handler.handle`"));
                 }
-                Ok(())
+                Ok(store)
             }
             .in_current_span(),
         );
@@ -199,7 +210,7 @@ where
                 }
                 Err(_) => {
                     debug!("`wasi:http/incoming-handler.handle` response sender dropped");
-                    handle
+                    (&mut handle)
                         .instrument(debug_span!("await_response"))
                         .await
                         .context("failed to join handle task")??;
@@ -210,11 +221,23 @@ where
         .in_current_span()
         .await;
         let success = res.as_ref().is_ok_and(Result::is_ok);
+        if success && self.pool.reuse() == InstanceReuse::Stateless {
+            // The store can only be reused once the guest has returned from the handler, which
+            // may happen after the response was received
+            let pool = Arc::clone(&self.pool);
+            spawn(async move {
+                if let Ok(Ok(store)) = handle.await {
+                    pool.release(store, instance);
+                }
+            });
+        }
         if let Err(err) = self
             .events
             .try_send(WrpcServeEvent::HttpIncomingHandlerHandleReturned {
                 context: cx,
                 success,
+                fuel_consumed: fuel_consumed.map(|fuel| fuel.load(Ordering::Relaxed)),
+                pool_hit,
             })
         {
             warn!(
@@ -1,4 +1,5 @@
-use super::{new_store, Ctx, Handler, Instance, ReplacedInstanceTarget};
+use super::pool::PooledInstance;
+use super::{Ctx, Handler, Instance, ReplacedInstanceTarget};
 
 use crate::capability::keyvalue::{atomics, batch, store};
 use crate::capability::wrpc;
@@ -295,14 +296,17 @@ where
         key: String,
         value: bytes::Bytes,
     ) -> anyhow::Result<(), anyhow::Error> {
-        let mut store = new_store(&self.engine, self.handler.clone(), self.max_execution_time);
-        let pre = keyvalue_watcher_bindings::WatcherPre::new(self.pre.clone())
-            .context("failed to pre-instantiate `wasi:keyvalue/watcher`")?;
         trace!("instantiating `wasi:keyvalue/watcher`");
-        let bindings = pre
-            .instantiate_async(&mut store)
+        let PooledInstance {
+            mut store,
+            instance,
+            ..
+        } = self
+            .acquire(None)
             .await
             .context("failed to instantiate `wasi:keyvalue/watcher.on_set`")?;
+        let bindings = keyvalue_watcher_bindings::Watcher::new(&mut store, &instance)
+            .context("failed to load `wasi:keyvalue/watcher` exports")?;
         let bucket_repr: u32 = bucket.parse().context("failed to parse bucket as u32")?;
         let new_bucket = Resource::new_own(bucket_repr);
         debug!("invoking `wasi:keyvalue/watcher.on_set`");
@@ -311,6 +315,7 @@ where
             .call_on_set(&mut store, new_bucket, &key, &value)
             .await
             .context("failed to call `wasi:keyvalue/watcher.on_set`")?;
+        self.pool.release(store, instance);
         Ok(())
     }
 
@@ -321,14 +326,17 @@ where
         bucket: String,
         key: String,
     ) -> anyhow::Result<(), anyhow::Error> {
-        let mut store = new_store(&self.engine, self.handler.clone(), self.max_execution_time);
-        let pre = keyvalue_watcher_bindings::WatcherPre::new(self.pre.clone())
-            .context("failed to pre-instantiate `wasi:keyvalue/watcher`")?;
         trace!("instantiating `wasi:keyvalue/watcher`");
-        let bindings = pre
-            .instantiate_async(&mut store)
+        let PooledInstance {
+            mut store,
+            instance,
+            ..
+        } = self
+            .acquire(None)
             .await
             .context("failed to instantiate `wasi:keyvalue/watcher.on_delete`")?;
+        let bindings = keyvalue_watcher_bindings::Watcher::new(&mut store, &instance)
+            .context("failed to load `wasi:keyvalue/watcher` exports")?;
         let bucket_repr: u32 = bucket.parse().context("failed to parse bucket as u32")?;
         let new_bucket = Resource::new_own(bucket_repr);
         debug!("invoking `wasi:keyvalue/watcher.on_delete`");
@@ -337,6 +345,7 @@ where
             .call_on_delete(&mut store, new_bucket, &key)
             .await
             .context("failed to call `wasi:keyvalue/watcher.on_delete`")?;
+        self.pool.release(store, instance);
         Ok(())
     }
 }
@@ -1,11 +1,14 @@
 use core::ops::Deref;
 
+use std::sync::atomic::Ordering;
+
 use anyhow::Context as _;
 use tracing::{instrument, warn, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt as _;
 
 use crate::capability::wrpc;
-use crate::component::{new_store, Handler, Instance, WrpcServeEvent};
+use crate::component::pool::PooledInstance;
+use crate::component::{Handler, Instance, InvocationDeadline, WrpcServeEvent};
 
 pub mod v0_2;
 pub mod v0_3;
@@ -13,7 +16,7 @@ pub mod v0_3;
 impl<H, C> wrpc::exports::wasmcloud::messaging0_2_0::handler::Handler<C> for Instance<H, C>
 where
     H: Handler,
-    C: Send + Deref<Target = Span>,
+    C: Send + Deref<Target = Span> + InvocationDeadline,
 {
     #[instrument(level = "debug", skip_all)]
     async fn handle_message(
@@ -23,30 +26,38 @@ where
     ) -> anyhow::Result<Result<(), String>> {
         // Set the parent of the current context to the span passed in
         Span::current().set_parent(cx.deref().context());
-        let mut store = new_store(&self.engine, self.handler.clone(), self.max_execution_time);
+        let PooledInstance {
+            mut store,
+            instance,
+            pool_hit,
+        } = self
+            .acquire(cx.deadline())
+            .await
+            .context("failed to instantiate `wasmcloud:messaging/handler`")?;
 
-        // If wasmcloud:messaging@0.3.0 is enabled and we can instantiate the 0.3.0 bindings,
+        // If wasmcloud:messaging@0.3.0 is enabled and the component exports the 0.3.0 handler,
         // handle the message using 0.3.0. Otherwise, use the 0.2.0 bindings.
-        let res = if self.experimental_features.wasmcloud_messaging_v3 {
-            if let Ok(pre) = v0_3::bindings::MessagingHandlerPre::new(self.pre.clone()) {
-                v0_3::handle_message(pre, &mut store, msg).await
-            } else {
-                let pre = v0_2::bindings::MessagingHandlerOhTwoPre::new(self.pre.clone())
-                    .context("failed to pre-instantiate `wasmcloud:messaging/handler`")?;
-                v0_2::handle_message(pre, &mut store, msg).await
-            }
+        let res = if self.experimental_features.wasmcloud_messaging_v3
+            && v0_3::bindings::MessagingHandlerPre::new(self.pre.clone()).is_ok()
+        {
+            v0_3::handle_message(&mut store, &instance, msg).await
         } else {
-            let pre = v0_2::bindings::MessagingHandlerOhTwoPre::new(self.pre.clone())
-                .context("failed to pre-instantiate `wasmcloud:messaging/handler`")?;
-            v0_2::handle_message(pre, &mut store, msg).await
+            v0_2::handle_message(&mut store, &instance, msg).await
         };
 
         let success = res.is_ok();
+        let fuel_consumed = store
+            .data()
+            .fuel_consumed
+            .as_ref()
+            .map(|fuel| fuel.load(Ordering::Relaxed));
         if let Err(err) =
             self.events
                 .try_send(WrpcServeEvent::MessagingHandlerHandleMessageReturned {
                     context: cx,
                     success,
+                    fuel_consumed,
+                    pool_hit,
                 })
         {
             warn!(
@@ -54,6 +65,9 @@ where
                 success, "failed to send `wasmcloud:messaging/handler.handle-message` return event"
             );
         }
+        if success {
+            self.pool.release(store, instance);
+        }
         res
     }
 }
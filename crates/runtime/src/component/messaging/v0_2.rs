@@ -62,8 +62,8 @@ where
 
 #[instrument(level = "debug", skip_all)]
 pub(crate) async fn handle_message<H>(
-    pre: bindings::MessagingHandlerOhTwoPre<Ctx<H>>,
     mut store: &mut Store<Ctx<H>>,
+    instance: &wasmtime::component::Instance,
     msg: wrpc::wasmcloud::messaging0_2_0::types::BrokerMessage,
 ) -> anyhow::Result<Result<(), String>>
 where
@@ -71,7 +71,8 @@ where
 {
     let call_handle_message = info_span!("call_handle_message");
     store.data_mut().parent_context = Some(call_handle_message.context());
-    let bindings = pre.instantiate_async(&mut store).await?;
+    let bindings = bindings::MessagingHandlerOhTwo::new(&mut store, instance)
+        .context("failed to load `wasmcloud:messaging/handler@0.2.0` exports")?;
     bindings
         .wasmcloud_messaging0_2_0_handler()
         .call_handle_message(
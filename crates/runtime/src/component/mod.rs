@@ -5,7 +5,9 @@ use core::pin::Pin;
 use core::time::Duration;
 
 use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Instant;
 
 use anyhow::{ensure, Context as _};
 use futures::{Stream, TryStreamExt as _};
@@ -19,6 +21,7 @@ use wasi_preview1_component_adapter_provider::{
     WASI_SNAPSHOT_PREVIEW1_ADAPTER_NAME, WASI_SNAPSHOT_PREVIEW1_REACTOR_ADAPTER,
 };
 use wasmtime::component::{types, Linker, ResourceTable, ResourceTableError, ResourceType};
+use wasmtime::{CallHook, StoreLimits, StoreLimitsBuilder, UpdateDeadline};
 use wasmtime_wasi::{IoView, WasiCtx, WasiCtxBuilder, WasiView};
 use wasmtime_wasi_http::WasiHttpCtx;
 use wrpc_runtime_wasmtime::{
@@ -30,6 +33,8 @@ use crate::capability::{self, wrpc};
 use crate::experimental::Features;
 use crate::Runtime;
 
+use pool::InstancePool;
+
 pub use bus::{Bus, Error};
 pub use bus1_0_0::Bus as Bus1_0_0;
 pub use config::Config;
@@ -40,6 +45,7 @@ pub use messaging::v0_3::{
     Client as MessagingClient0_3, GuestMessage as MessagingGuestMessage0_3,
     HostMessage as MessagingHostMessage0_3, Messaging as Messaging0_3,
 };
+pub use pool::InstanceReuse;
 pub use secrets::Secrets;
 
 pub(crate) mod blobstore;
@@ -51,6 +57,7 @@ mod identity;
 mod keyvalue;
 mod logging;
 pub(crate) mod messaging;
+mod pool;
 mod secrets;
 
 /// Instance target, which is replaced in wRPC
@@ -62,6 +69,12 @@ pub enum ReplacedInstanceTarget {
     BlobstoreBlobstore,
     /// `wasi:blobstore/container` instance replacement
     BlobstoreContainer,
+    /// `wasmcloud:blobstore/multipart` instance replacement
+    BlobstoreMultipart,
+    /// `wasmcloud:blobstore/objects` instance replacement
+    BlobstoreObjects,
+    /// `wasmcloud:blobstore/presign` instance replacement
+    BlobstorePresign,
     /// `wasi:keyvalue/atomic` instance replacement
     KeyvalueAtomics,
     /// `wasi:keyvalue/store` instance replacement
@@ -99,6 +112,33 @@ pub enum InvocationErrorKind {
 
     /// An error kind, which will result in a trap in the component
     Trap,
+
+    /// This occurs when the invocation did not complete before its deadline, for example as would
+    /// happen when the callee exceeded its execution deadline and was trapped, or the caller timed out
+    Timeout,
+}
+
+/// Error returned by invocations, which were trapped after exceeding their execution deadline
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DeadlineExceeded;
+
+impl fmt::Display for DeadlineExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invocation exceeded its execution deadline")
+    }
+}
+
+impl std::error::Error for DeadlineExceeded {}
+
+/// Implementations of this trait are invocation contexts, which may carry a deadline derived
+/// from the timeout of the caller
+pub trait InvocationDeadline {
+    /// Returns the instant by which the invocation must complete, if the caller specified one.
+    /// The invocation is trapped once it exceeds the earlier of this deadline and the maximum
+    /// execution time of the component.
+    fn deadline(&self) -> Option<Instant> {
+        None
+    }
 }
 
 /// Implementations of this trait are able to introspect an error returned by wRPC invocations
@@ -178,6 +218,39 @@ pub fn claims_token(wasm: impl AsRef<[u8]>) -> anyhow::Result<Option<jwt::Token<
     Ok(Some(claims))
 }
 
+/// Name of the custom section, in which a component can embed a JSON schema describing the
+/// configuration it expects
+pub const CONFIG_SCHEMA_SECTION: &str = "wasmcloud:config-schema";
+
+/// Extracts the JSON schema of the configuration expected by a WebAssembly binary, if present
+///
+/// # Arguments
+///
+/// * `wasm` - Bytes that constitute a valid WebAssembly binary
+///
+/// # Errors
+///
+/// Fails if either parsing fails, or the [`CONFIG_SCHEMA_SECTION`] custom section does not contain
+/// valid JSON
+pub fn config_schema(wasm: impl AsRef<[u8]>) -> anyhow::Result<Option<serde_json::Value>> {
+    use wasmparser::Payload::{ComponentSection, CustomSection, End, ModuleSection};
+
+    let mut depth = 0;
+    for payload in wasmparser::Parser::new(0).parse_all(wasm.as_ref()) {
+        match payload.context("failed to parse WebAssembly binary")? {
+            ModuleSection { .. } | ComponentSection { .. } => depth += 1,
+            End { .. } => depth -= 1,
+            CustomSection(c) if c.name() == CONFIG_SCHEMA_SECTION && depth == 0 => {
+                let schema = serde_json::from_slice(c.data())
+                    .with_context(|| format!("`{CONFIG_SCHEMA_SECTION}` is not valid JSON"))?;
+                return Ok(Some(schema));
+            }
+            _ => {}
+        }
+    }
+    Ok(None)
+}
+
 /// Pre-compiled component [Component], which is cheapily-[Cloneable](Clone)
 #[derive(Clone)]
 pub struct Component<H>
@@ -186,9 +259,15 @@ where
 {
     engine: wasmtime::Engine,
     claims: Option<jwt::Claims<jwt::Component>>,
+    config_schema: Option<Arc<serde_json::Value>>,
     instance_pre: wasmtime::component::InstancePre<Ctx<H>>,
     host_resources: Arc<HashMap<Box<str>, HashMap<Box<str>, (ResourceType, ResourceType)>>>,
     max_execution_time: Duration,
+    epoch_tick_interval: Duration,
+    max_linear_memory: Option<u64>,
+    max_table_elements: Option<u32>,
+    fuel: Option<u64>,
+    pool: Arc<InstancePool<H>>,
     experimental_features: Features,
 }
 
@@ -199,16 +278,25 @@ where
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Component")
             .field("claims", &self.claims)
+            .field("config_schema", &self.config_schema)
             .field("runtime", &"wasmtime")
             .field("max_execution_time", &self.max_execution_time)
+            .field("max_linear_memory", &self.max_linear_memory)
+            .field("max_table_elements", &self.max_table_elements)
+            .field("fuel", &self.fuel)
+            .field("min_idle_instances", &self.pool.min_idle())
+            .field("instance_reuse", &self.pool.reuse())
             .finish_non_exhaustive()
     }
 }
 
-fn new_store<H: Handler>(
+fn new_store<H: Send + Sync + 'static>(
     engine: &wasmtime::Engine,
     handler: H,
     max_execution_time: Duration,
+    deadline: Option<Instant>,
+    fuel: Option<u64>,
+    limits: StoreLimits,
 ) -> wasmtime::Store<Ctx<H>> {
     let table = ResourceTable::new();
     let wasi = WasiCtxBuilder::new()
@@ -226,9 +314,41 @@ fn new_store<H: Handler>(
             shared_resources: SharedResourceTable::default(),
             timeout: max_execution_time,
             parent_context: None,
+            fuel_consumed: fuel.map(|_| Arc::default()),
+            deadline: Deadline::new(max_execution_time, deadline),
+            limits,
         },
     );
-    store.set_epoch_deadline(max_execution_time.as_secs());
+    store.limiter(|ctx| &mut ctx.limits);
+    // Check the deadline on every epoch tick, since the invocation being polled may have an
+    // earlier deadline than the store
+    store.set_epoch_deadline(1);
+    store.epoch_deadline_callback(|store| {
+        if store.data().deadline.is_exceeded() || InvocationBudget::is_exceeded() {
+            Err(DeadlineExceeded.into())
+        } else {
+            Ok(UpdateDeadline::Continue(1))
+        }
+    });
+    if let Some(fuel) = fuel {
+        if let Err(err) = store.set_fuel(fuel) {
+            warn!(?err, "failed to set store fuel");
+        }
+        // Keep the consumed fuel up to date on every boundary crossing, so that it can be read
+        // even if the store is dropped mid-invocation (e.g. on trap or fuel exhaustion)
+        store.call_hook(move |store, hook| {
+            if matches!(hook, CallHook::CallingHost | CallHook::ReturningFromWasm) {
+                if let (Some(consumed), Ok(remaining)) =
+                    (store.data().fuel_consumed.as_ref(), store.get_fuel())
+                {
+                    let fuel_consumed = fuel.saturating_sub(remaining);
+                    consumed.store(fuel_consumed, Ordering::Relaxed);
+                    InvocationBudget::record_fuel_consumed(fuel_consumed);
+                }
+            }
+            Ok(())
+        });
+    }
     store
 }
 
@@ -241,6 +361,11 @@ pub enum WrpcServeEvent<C> {
         context: C,
         /// Whether the invocation was successfully handled
         success: bool,
+        /// Fuel consumed by the invocation, if fuel metering is enabled
+        fuel_consumed: Option<u64>,
+        /// Whether the invocation was handled by a pre-instantiated instance taken from the
+        /// instance pool, if pooling is enabled for the component and the export
+        pool_hit: Option<bool>,
     },
     /// `wasmcloud:messaging/handler.handle-message` return event
     MessagingHandlerHandleMessageReturned {
@@ -248,6 +373,11 @@ pub enum WrpcServeEvent<C> {
         context: C,
         /// Whether the invocation was successfully handled
         success: bool,
+        /// Fuel consumed by the invocation, if fuel metering is enabled
+        fuel_consumed: Option<u64>,
+        /// Whether the invocation was handled by a pre-instantiated instance taken from the
+        /// instance pool, if pooling is enabled for the component and the export
+        pool_hit: Option<bool>,
     },
     /// dynamic export return event
     DynamicExportReturned {
@@ -255,6 +385,11 @@ pub enum WrpcServeEvent<C> {
         context: C,
         /// Whether the invocation was successfully handled
         success: bool,
+        /// Fuel consumed by the invocation, if fuel metering is enabled
+        fuel_consumed: Option<u64>,
+        /// Whether the invocation was handled by a pre-instantiated instance taken from the
+        /// instance pool, if pooling is enabled for the component and the export
+        pool_hit: Option<bool>,
     },
 }
 
@@ -308,6 +443,7 @@ where
         let engine = rt.engine.clone();
         let claims_token = claims_token(wasm)?;
         let claims = claims_token.map(|c| c.claims);
+        let config_schema = config_schema(wasm)?.map(Arc::new);
         let component = wasmtime::component::Component::new(&engine, wasm)
             .context("failed to compile component")?;
 
@@ -370,6 +506,18 @@ where
                 .context("failed to link `wasmcloud:identity/store`")?;
         }
 
+        // Only link wasmcloud:blobstore if the blobstore extensions feature is enabled
+        if rt.experimental_features.blobstore_extensions {
+            capability::wasmcloud_blobstore::types::add_to_linker(&mut linker, |ctx| ctx)
+                .context("failed to link `wasmcloud:blobstore/types`")?;
+            capability::wasmcloud_blobstore::presign::add_to_linker(&mut linker, |ctx| ctx)
+                .context("failed to link `wasmcloud:blobstore/presign`")?;
+            capability::wasmcloud_blobstore::multipart::add_to_linker(&mut linker, |ctx| ctx)
+                .context("failed to link `wasmcloud:blobstore/multipart`")?;
+            capability::wasmcloud_blobstore::objects::add_to_linker(&mut linker, |ctx| ctx)
+                .context("failed to link `wasmcloud:blobstore/objects`")?;
+        }
+
         // Only link wrpc:rpc if the RPC feature is enabled
         if rt.experimental_features.rpc_interface {
             rpc::add_to_linker(&mut linker).context("failed to link `wrpc:rpc`")?;
@@ -531,21 +679,109 @@ where
         Ok(Self {
             engine,
             claims,
+            config_schema,
             instance_pre,
             host_resources,
             max_execution_time: rt.max_execution_time,
+            epoch_tick_interval: rt.epoch_tick_interval,
+            max_linear_memory: None,
+            max_table_elements: None,
+            fuel: rt.fuel_metering.then_some(u64::MAX),
+            pool: Arc::default(),
             experimental_features: rt.experimental_features,
         })
     }
 
     /// Sets maximum execution time for functionality exported by this component.
-    /// Values below 1 second will be interpreted as 1 second.
+    /// Values below the epoch tick interval of the [Runtime] will be interpreted as a single tick.
     #[instrument(level = "trace", skip_all)]
     pub fn set_max_execution_time(&mut self, max_execution_time: Duration) -> &mut Self {
-        self.max_execution_time = max_execution_time.max(Duration::from_secs(1));
+        self.max_execution_time = max_execution_time.max(self.epoch_tick_interval);
+        self
+    }
+
+    /// Sets maximum amount of linear memory, in bytes, that a single linear memory of an instance
+    /// of this component can grow to. This can only restrict the limit configured on the [Runtime].
+    #[instrument(level = "trace", skip_all)]
+    pub fn set_max_linear_memory(&mut self, max_linear_memory: Option<u64>) -> &mut Self {
+        self.max_linear_memory = max_linear_memory;
         self
     }
 
+    /// Sets maximum number of elements in a single table of an instance of this component.
+    /// This can only restrict the limit configured on the [Runtime].
+    #[instrument(level = "trace", skip_all)]
+    pub fn set_max_table_elements(&mut self, max_table_elements: Option<u32>) -> &mut Self {
+        self.max_table_elements = max_table_elements;
+        self
+    }
+
+    /// Sets maximum amount of fuel a single invocation of functionality exported by this component
+    /// may consume before trapping. `None` leaves invocations unbounded, but still metered.
+    /// This has no effect unless fuel metering is enabled on the [Runtime].
+    #[instrument(level = "trace", skip_all)]
+    pub fn set_max_fuel(&mut self, max_fuel: Option<u64>) -> &mut Self {
+        if self.fuel.is_some() {
+            self.fuel = Some(max_fuel.unwrap_or(u64::MAX));
+        }
+        self
+    }
+
+    /// Sets the minimum number of pre-instantiated, idle instances kept ready to handle
+    /// invocations of this component. Instances are created in the background by [Self::prewarm]
+    /// and replenished as they are taken by invocations.
+    ///
+    /// Pooling applies to `wasi:http/incoming-handler`, `wasmcloud:messaging/handler` and
+    /// `wasi:keyvalue/watcher` exports.
+    #[instrument(level = "trace", skip_all)]
+    pub fn set_min_idle_instances(&mut self, min_idle_instances: usize) -> &mut Self {
+        self.pool = Arc::new(InstancePool::new(min_idle_instances, self.pool.reuse()));
+        self
+    }
+
+    /// Sets the [`InstanceReuse`] policy of this component, which defaults to
+    /// [`InstanceReuse::Never`]
+    #[instrument(level = "trace", skip_all)]
+    pub fn set_instance_reuse(&mut self, reuse: InstanceReuse) -> &mut Self {
+        self.pool = Arc::new(InstancePool::new(self.pool.min_idle(), reuse));
+        self
+    }
+
+    /// Pre-instantiates the minimum number of idle instances of this component in the background,
+    /// using `handler` to satisfy imports of the pre-instantiated instances.
+    ///
+    /// This must be called from within a Tokio runtime and has no effect, unless a minimum number
+    /// of idle instances was set using [Self::set_min_idle_instances].
+    #[instrument(level = "trace", skip_all)]
+    pub fn prewarm(&self, handler: &H) {
+        self.pool.warm(
+            &self.engine,
+            &self.instance_pre,
+            handler,
+            self.max_execution_time,
+            self.fuel,
+            self.store_limits(),
+        );
+    }
+
+    /// Returns the [`StoreLimits`] enforced on every store of this component
+    fn store_limits(&self) -> StoreLimits {
+        let mut limits = StoreLimitsBuilder::new();
+        if let Some(max) = self.max_linear_memory {
+            limits = limits.memory_size(usize::try_from(max).unwrap_or(usize::MAX));
+        }
+        if let Some(max) = self.max_table_elements {
+            limits = limits.table_elements(usize::try_from(max).unwrap_or(usize::MAX));
+        }
+        limits.build()
+    }
+
+    /// Whether fuel consumption of invocations of this component is metered
+    #[must_use]
+    pub fn fuel_metering(&self) -> bool {
+        self.fuel.is_some()
+    }
+
     /// Reads the WebAssembly binary asynchronously and calls [Component::new].
     ///
     /// # Errors
@@ -578,6 +814,13 @@ where
         self.claims.as_ref()
     }
 
+    /// JSON schema of the configuration expected by this [Component], embedded in the
+    /// [`CONFIG_SCHEMA_SECTION`] custom section
+    #[instrument(level = "trace")]
+    pub fn config_schema(&self) -> Option<&serde_json::Value> {
+        self.config_schema.as_deref()
+    }
+
     /// Instantiates the component given a handler and event channel
     pub fn instantiate<C>(
         &self,
@@ -589,6 +832,9 @@ where
             pre: self.instance_pre.clone(),
             handler,
             max_execution_time: self.max_execution_time,
+            fuel: self.fuel,
+            limits: self.store_limits(),
+            pool: Arc::clone(&self.pool),
             events,
             experimental_features: self.experimental_features,
         }
@@ -609,9 +855,11 @@ where
     ) -> anyhow::Result<Vec<InvocationStream>>
     where
         S: wrpc_transport::Serve,
-        S::Context: Deref<Target = tracing::Span>,
+        S::Context: Deref<Target = tracing::Span> + InvocationDeadline,
     {
         let max_execution_time = self.max_execution_time;
+        let fuel = self.fuel;
+        let limits = self.store_limits();
         let mut invocations = vec![];
         let instance = self.instantiate(handler.clone(), events.clone());
         for (name, ty) in self
@@ -663,13 +911,20 @@ where
                     let engine = self.engine.clone();
                     let handler = handler.clone();
                     let pre = self.instance_pre.clone();
+                    let limits = limits.clone();
                     debug!(?name, "serving root function");
                     let func = srv
                         .serve_function(
                             move || {
                                 let span = info_span!("call_instance_function");
-                                let mut store =
-                                    new_store(&engine, handler.clone(), max_execution_time);
+                                let mut store = new_store(
+                                    &engine,
+                                    handler.clone(),
+                                    max_execution_time,
+                                    None,
+                                    fuel,
+                                    limits.clone(),
+                                );
                                 store.data_mut().parent_context = Some(span.context());
                                 store
                             },
@@ -685,15 +940,23 @@ where
                     invocations.push(Box::pin(func.map_ok(move |(cx, res)| {
                         let events = events.clone();
                         let span = cx.deref().clone();
+                        let budget = Arc::new(InvocationBudget::new(
+                            max_execution_time,
+                            cx.deadline(),
+                            fuel,
+                        ));
                         Box::pin(
                             async move {
-                                let res =
-                                    res.instrument(info_span!("handle_instance_function")).await;
+                                let res = budget
+                                    .scope(res.instrument(info_span!("handle_instance_function")))
+                                    .await;
                                 let success = res.is_ok();
                                 if let Err(err) =
                                     events.try_send(WrpcServeEvent::DynamicExportReturned {
                                         context: cx,
                                         success,
+                                        fuel_consumed: budget.fuel_consumed(),
+                                        pool_hit: None,
                                     })
                                 {
                                     warn!(
@@ -724,6 +987,7 @@ where
                                 let engine = self.engine.clone();
                                 let handler = handler.clone();
                                 let pre = self.instance_pre.clone();
+                                let limits = limits.clone();
                                 debug!(?instance_name, ?name, "serving instance function");
                                 let func = srv
                                     .serve_function(
@@ -733,6 +997,9 @@ where
                                                 &engine,
                                                 handler.clone(),
                                                 max_execution_time,
+                                                None,
+                                                fuel,
+                                                limits.clone(),
                                             );
                                             store.data_mut().parent_context = Some(span.context());
                                             store
@@ -749,14 +1016,21 @@ where
                                 invocations.push(Box::pin(func.map_ok(move |(cx, res)| {
                                     let events = events.clone();
                                     let span = cx.deref().clone();
+                                    let budget = Arc::new(InvocationBudget::new(
+                                        max_execution_time,
+                                        cx.deadline(),
+                                        fuel,
+                                    ));
                                     Box::pin(
                                         async move {
-                                            let res = res.await;
+                                            let res = budget.scope(res).await;
                                             let success = res.is_ok();
                                             if let Err(err) = events.try_send(
                                                 WrpcServeEvent::DynamicExportReturned {
                                                     context: cx,
                                                     success,
+                                                    fuel_consumed: budget.fuel_consumed(),
+                                                    pool_hit: None,
                                                 },
                                             ) {
                                                 warn!(
@@ -826,6 +1100,9 @@ where
     pre: wasmtime::component::InstancePre<Ctx<H>>,
     handler: H,
     max_execution_time: Duration,
+    fuel: Option<u64>,
+    limits: StoreLimits,
+    pool: Arc<InstancePool<H>>,
     events: mpsc::Sender<WrpcServeEvent<C>>,
     experimental_features: Features,
 }
@@ -840,6 +1117,9 @@ where
             pre: self.pre.clone(),
             handler: self.handler.clone(),
             max_execution_time: self.max_execution_time,
+            fuel: self.fuel,
+            limits: self.limits.clone(),
+            pool: Arc::clone(&self.pool),
             events: self.events.clone(),
             experimental_features: self.experimental_features,
         }
@@ -851,10 +1131,7 @@ type TableResult<T> = Result<T, ResourceTableError>;
 /// Wasmtime Context for a component instance, with access to
 /// WASI context, HTTP context, and WRPC Invocation context.
 /// This is a low-level API and has to be paired with `Component::new_with_linker`.
-pub struct Ctx<H>
-where
-    H: Handler,
-{
+pub struct Ctx<H> {
     handler: H,
     wasi: WasiCtx,
     http: WasiHttpCtx,
@@ -862,6 +1139,9 @@ where
     shared_resources: SharedResourceTable,
     timeout: Duration,
     parent_context: Option<opentelemetry::Context>,
+    fuel_consumed: Option<Arc<AtomicU64>>,
+    deadline: Deadline,
+    limits: StoreLimits,
 }
 
 impl<H: Handler> IoView for Ctx<H> {
@@ -892,7 +1172,11 @@ impl<H: Handler> WrpcView for Ctx<H> {
     }
 
     fn timeout(&self) -> Option<Duration> {
-        Some(self.timeout)
+        Some(
+            self.timeout
+                .min(self.deadline.remaining())
+                .min(InvocationBudget::remaining()),
+        )
     }
 }
 
@@ -909,3 +1193,147 @@ impl<H: Handler> Ctx<H> {
         }
     }
 }
+
+/// Execution deadline of an invocation
+#[derive(Clone, Copy, Debug)]
+struct Deadline(Option<Instant>);
+
+impl Deadline {
+    fn new(max_execution_time: Duration, deadline: Option<Instant>) -> Self {
+        let max = Instant::now().checked_add(max_execution_time);
+        Self(match (deadline, max) {
+            (Some(deadline), Some(max)) => Some(deadline.min(max)),
+            (deadline, max) => deadline.or(max),
+        })
+    }
+
+    fn remaining(&self) -> Duration {
+        self.0.map_or(Duration::MAX, |deadline| {
+            deadline.saturating_duration_since(Instant::now())
+        })
+    }
+
+    fn is_exceeded(&self) -> bool {
+        self.0.is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+tokio::task_local! {
+    /// Budget of the invocation being polled on the current task, if any
+    static INVOCATION_BUDGET: Arc<InvocationBudget>;
+}
+
+/// Execution budget of an invocation served by
+/// [`wrpc_runtime_wasmtime::ServeExt::serve_function`].
+///
+/// The stores of such invocations are created without access to the invocation context, so the
+/// budget is carried by the invocation future instead and looked up by the hooks of the store
+/// while the invocation is polled.
+#[derive(Debug)]
+struct InvocationBudget {
+    deadline: Deadline,
+    fuel_consumed: Option<AtomicU64>,
+}
+
+impl InvocationBudget {
+    fn new(max_execution_time: Duration, deadline: Option<Instant>, fuel: Option<u64>) -> Self {
+        Self {
+            deadline: Deadline::new(max_execution_time, deadline),
+            fuel_consumed: fuel.map(|_| AtomicU64::default()),
+        }
+    }
+
+    /// Polls `fut` with this budget as the budget of the current invocation
+    async fn scope<F: Future>(self: &Arc<Self>, fut: F) -> F::Output {
+        INVOCATION_BUDGET.scope(Arc::clone(self), fut).await
+    }
+
+    /// Fuel consumed by the invocation, if fuel metering is enabled
+    fn fuel_consumed(&self) -> Option<u64> {
+        self.fuel_consumed
+            .as_ref()
+            .map(|fuel| fuel.load(Ordering::Relaxed))
+    }
+
+    /// Records fuel consumed by the current invocation, if any
+    fn record_fuel_consumed(fuel_consumed: u64) {
+        let _ = INVOCATION_BUDGET.try_with(|budget| {
+            if let Some(consumed) = budget.fuel_consumed.as_ref() {
+                consumed.store(fuel_consumed, Ordering::Relaxed);
+            }
+        });
+    }
+
+    /// Time remaining until the deadline of the current invocation, if any
+    fn remaining() -> Duration {
+        INVOCATION_BUDGET
+            .try_with(|budget| budget.deadline.remaining())
+            .unwrap_or(Duration::MAX)
+    }
+
+    /// Whether the current invocation, if any, exceeded its deadline
+    fn is_exceeded() -> bool {
+        INVOCATION_BUDGET
+            .try_with(|budget| budget.deadline.is_exceeded())
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_concurrent_invocation_budgets() {
+        assert!(!InvocationBudget::is_exceeded());
+        assert_eq!(InvocationBudget::remaining(), Duration::MAX);
+        InvocationBudget::record_fuel_consumed(1);
+
+        let expired = Arc::new(InvocationBudget::new(
+            Duration::from_secs(60),
+            Some(Instant::now()),
+            Some(100),
+        ));
+        let open = Arc::new(InvocationBudget::new(
+            Duration::from_secs(60),
+            None,
+            Some(100),
+        ));
+        let unmetered = Arc::new(InvocationBudget::new(Duration::from_secs(60), None, None));
+
+        let invoke = |budget: Arc<InvocationBudget>, fuel: u64| {
+            tokio::spawn(async move {
+                budget
+                    .scope(async move {
+                        let mut exceeded = Vec::new();
+                        for i in 1..=fuel {
+                            InvocationBudget::record_fuel_consumed(i);
+                            exceeded.push(InvocationBudget::is_exceeded());
+                            tokio::task::yield_now().await;
+                        }
+                        (exceeded, InvocationBudget::remaining())
+                    })
+                    .await
+            })
+        };
+        let (expired_res, open_res, unmetered_res) = tokio::join!(
+            invoke(Arc::clone(&expired), 10),
+            invoke(Arc::clone(&open), 20),
+            invoke(Arc::clone(&unmetered), 30),
+        );
+
+        let (exceeded, remaining) = expired_res.expect("invocation should not panic");
+        assert!(exceeded.iter().all(|exceeded| *exceeded));
+        assert_eq!(remaining, Duration::ZERO);
+        assert_eq!(expired.fuel_consumed(), Some(10));
+
+        let (exceeded, remaining) = open_res.expect("invocation should not panic");
+        assert!(exceeded.iter().all(|exceeded| !*exceeded));
+        assert!(remaining > Duration::from_secs(50));
+        assert_eq!(open.fuel_consumed(), Some(20));
+
+        let (exceeded, _) = unmetered_res.expect("invocation should not panic");
+        assert!(exceeded.iter().all(|exceeded| !*exceeded));
+        assert_eq!(unmetered.fuel_consumed(), None);
+    }
+}
@@ -0,0 +1,385 @@
+use core::str::FromStr;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::time::Duration;
+
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use anyhow::{bail, Context as _};
+use tracing::{debug, instrument, warn};
+use wasmtime::StoreLimits;
+
+use super::{new_store, Ctx, Deadline, Handler, Instance};
+
+/// Policy determining whether component instances are reused across invocations
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum InstanceReuse {
+    /// Every invocation is handled by a fresh instance, which is discarded afterwards
+    #[default]
+    Never,
+    /// Instances which successfully handled an invocation are returned to the pool and reused by
+    /// subsequent invocations. Only suitable for components, which do not keep state between
+    /// invocations, since linear memory, globals and resources are preserved
+    Stateless,
+}
+
+impl FromStr for InstanceReuse {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "never" => Ok(Self::Never),
+            "stateless" => Ok(Self::Stateless),
+            _ => bail!("unknown instance reuse policy `{s}`, expected `never` or `stateless`"),
+        }
+    }
+}
+
+/// Store with an instantiated component, ready to handle an invocation
+pub(crate) struct PooledInstance<H> {
+    pub(crate) store: wasmtime::Store<Ctx<H>>,
+    pub(crate) instance: wasmtime::component::Instance,
+    /// Whether the instance was taken from the pool, if pooling is enabled
+    pub(crate) pool_hit: Option<bool>,
+}
+
+/// Pool of pre-instantiated component instances, shared by all [Instance]s of a component
+pub(crate) struct InstancePool<H> {
+    min_idle: usize,
+    reuse: InstanceReuse,
+    idle: Mutex<Vec<(wasmtime::Store<Ctx<H>>, wasmtime::component::Instance)>>,
+    warming: AtomicUsize,
+}
+
+impl<H> Default for InstancePool<H> {
+    fn default() -> Self {
+        Self::new(0, InstanceReuse::default())
+    }
+}
+
+impl<H> InstancePool<H> {
+    pub(crate) fn new(min_idle: usize, reuse: InstanceReuse) -> Self {
+        Self {
+            min_idle,
+            reuse,
+            idle: Mutex::default(),
+            warming: AtomicUsize::default(),
+        }
+    }
+
+    pub(crate) fn min_idle(&self) -> usize {
+        self.min_idle
+    }
+
+    pub(crate) fn reuse(&self) -> InstanceReuse {
+        self.reuse
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.min_idle > 0 || self.reuse != InstanceReuse::Never
+    }
+
+    /// Maximum number of idle instances kept in the pool
+    fn capacity(&self) -> usize {
+        if self.reuse == InstanceReuse::Never {
+            self.min_idle
+        } else {
+            self.min_idle.max(1)
+        }
+    }
+
+    fn idle_len(&self) -> usize {
+        self.idle.lock().map_or(0, |idle| idle.len())
+    }
+
+    fn take(&self) -> Option<(wasmtime::Store<Ctx<H>>, wasmtime::component::Instance)> {
+        self.idle.lock().ok()?.pop()
+    }
+
+    fn put(&self, store: wasmtime::Store<Ctx<H>>, instance: wasmtime::component::Instance) {
+        let Ok(mut idle) = self.idle.lock() else {
+            return;
+        };
+        if idle.len() < self.capacity() {
+            idle.push((store, instance));
+        }
+    }
+}
+
+impl<H> InstancePool<H>
+where
+    H: Clone + Send + Sync + 'static,
+{
+    /// Returns an instance, which successfully handled an invocation, to the pool if the reuse
+    /// policy of the component allows for it
+    pub(crate) fn release(
+        &self,
+        store: wasmtime::Store<Ctx<H>>,
+        instance: wasmtime::component::Instance,
+    ) {
+        if self.reuse == InstanceReuse::Stateless {
+            self.put(store, instance);
+        }
+    }
+
+    /// Instantiates the component in the background until the pool holds `min_idle` instances
+    #[instrument(level = "trace", skip_all)]
+    pub(crate) fn warm(
+        self: &Arc<Self>,
+        engine: &wasmtime::Engine,
+        pre: &wasmtime::component::InstancePre<Ctx<H>>,
+        handler: &H,
+        max_execution_time: Duration,
+        fuel: Option<u64>,
+        limits: StoreLimits,
+    ) {
+        let missing = self
+            .min_idle
+            .saturating_sub(self.idle_len() + self.warming.load(Ordering::Relaxed));
+        if missing == 0 {
+            return;
+        }
+        self.warming.fetch_add(missing, Ordering::Relaxed);
+        let pool = Arc::clone(self);
+        let engine = engine.clone();
+        let pre = pre.clone();
+        let handler = handler.clone();
+        tokio::spawn(async move {
+            for n in 0..missing {
+                let mut store = new_store(
+                    &engine,
+                    handler.clone(),
+                    max_execution_time,
+                    None,
+                    fuel,
+                    limits.clone(),
+                );
+                match pre.instantiate_async(&mut store).await {
+                    Ok(instance) => {
+                        pool.warming.fetch_sub(1, Ordering::Relaxed);
+                        pool.put(store, instance);
+                    }
+                    Err(err) => {
+                        warn!(?err, "failed to pre-instantiate component");
+                        pool.warming.fetch_sub(missing - n, Ordering::Relaxed);
+                        return;
+                    }
+                }
+            }
+            debug!(missing, "pre-instantiated component instances");
+        });
+    }
+
+    /// Returns a store with an instantiated component for an invocation handled by `handler`,
+    /// taking it from the pool if one is available
+    #[allow(clippy::too_many_arguments)]
+    async fn acquire(
+        self: &Arc<Self>,
+        engine: &wasmtime::Engine,
+        pre: &wasmtime::component::InstancePre<Ctx<H>>,
+        handler: &H,
+        max_execution_time: Duration,
+        deadline: Option<Instant>,
+        fuel: Option<u64>,
+        limits: StoreLimits,
+    ) -> anyhow::Result<PooledInstance<H>> {
+        let pooled = self.is_enabled().then(|| {
+            let pooled = self.take();
+            self.warm(
+                engine,
+                pre,
+                handler,
+                max_execution_time,
+                fuel,
+                limits.clone(),
+            );
+            pooled
+        });
+        let pool_hit = pooled.as_ref().map(Option::is_some);
+        if let Some(Some((mut store, instance))) = pooled {
+            reset_store(&mut store, handler, max_execution_time, deadline, fuel);
+            return Ok(PooledInstance {
+                store,
+                instance,
+                pool_hit,
+            });
+        }
+        let mut store = new_store(
+            engine,
+            handler.clone(),
+            max_execution_time,
+            deadline,
+            fuel,
+            limits,
+        );
+        let instance = pre
+            .instantiate_async(&mut store)
+            .await
+            .context("failed to instantiate component")?;
+        Ok(PooledInstance {
+            store,
+            instance,
+            pool_hit,
+        })
+    }
+}
+
+/// Prepares a store, which may have been created ahead of time for a different invocation, for
+/// handling an invocation by `handler`
+fn reset_store<H: Clone>(
+    store: &mut wasmtime::Store<Ctx<H>>,
+    handler: &H,
+    max_execution_time: Duration,
+    deadline: Option<Instant>,
+    fuel: Option<u64>,
+) {
+    let data = store.data_mut();
+    data.handler = handler.clone();
+    data.deadline = Deadline::new(max_execution_time, deadline);
+    data.parent_context = None;
+    if let Some(fuel) = fuel {
+        data.fuel_consumed = Some(Arc::default());
+        if let Err(err) = store.set_fuel(fuel) {
+            warn!(?err, "failed to set store fuel");
+        }
+    }
+    store.set_epoch_deadline(1);
+}
+
+impl<H, C> Instance<H, C>
+where
+    H: Handler,
+{
+    /// Returns a store with an instantiated component, taking it from the instance pool if one
+    /// is available
+    pub(crate) async fn acquire(
+        &self,
+        deadline: Option<Instant>,
+    ) -> anyhow::Result<PooledInstance<H>> {
+        self.pool
+            .acquire(
+                &self.engine,
+                &self.pre,
+                &self.handler,
+                self.max_execution_time,
+                deadline,
+                self.fuel,
+                self.limits.clone(),
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Binary encoding of an empty component
+    const EMPTY_COMPONENT: &[u8] = b"\0asm\x0d\0\x01\0";
+
+    fn pre(engine: &wasmtime::Engine) -> wasmtime::component::InstancePre<Ctx<u32>> {
+        let component = wasmtime::component::Component::new(engine, EMPTY_COMPONENT)
+            .expect("failed to compile component");
+        wasmtime::component::Linker::new(engine)
+            .instantiate_pre(&component)
+            .expect("failed to pre-instantiate component")
+    }
+
+    fn engine() -> wasmtime::Engine {
+        let mut config = wasmtime::Config::new();
+        config.async_support(true);
+        wasmtime::Engine::new(&config).expect("failed to create engine")
+    }
+
+    async fn acquire(
+        pool: &Arc<InstancePool<u32>>,
+        engine: &wasmtime::Engine,
+        pre: &wasmtime::component::InstancePre<Ctx<u32>>,
+        handler: u32,
+    ) -> PooledInstance<u32> {
+        pool.acquire(
+            engine,
+            pre,
+            &handler,
+            Duration::from_secs(10),
+            None,
+            None,
+            StoreLimits::default(),
+        )
+        .await
+        .expect("failed to acquire instance")
+    }
+
+    async fn wait_idle(pool: &InstancePool<u32>, n: usize) {
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while pool.idle_len() < n {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("pool was not warmed in time");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_acquire_pool_hit() {
+        let engine = engine();
+        let pre = pre(&engine);
+
+        let pool = Arc::new(InstancePool::new(0, InstanceReuse::Never));
+        assert_eq!(acquire(&pool, &engine, &pre, 1).await.pool_hit, None);
+
+        let pool = Arc::new(InstancePool::new(1, InstanceReuse::Never));
+        assert_eq!(acquire(&pool, &engine, &pre, 1).await.pool_hit, Some(false));
+        wait_idle(&pool, 1).await;
+        assert_eq!(acquire(&pool, &engine, &pre, 1).await.pool_hit, Some(true));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_release() {
+        let engine = engine();
+        let pre = pre(&engine);
+
+        let pool = Arc::new(InstancePool::new(0, InstanceReuse::Stateless));
+        let PooledInstance {
+            store,
+            instance,
+            pool_hit,
+        } = acquire(&pool, &engine, &pre, 1).await;
+        assert_eq!(pool_hit, Some(false));
+        pool.release(store, instance);
+        assert_eq!(pool.idle_len(), 1);
+        assert_eq!(acquire(&pool, &engine, &pre, 1).await.pool_hit, Some(true));
+        assert_eq!(pool.idle_len(), 0);
+
+        let pool = Arc::new(InstancePool::new(0, InstanceReuse::Never));
+        let PooledInstance {
+            store, instance, ..
+        } = acquire(&pool, &engine, &pre, 1).await;
+        pool.release(store, instance);
+        assert_eq!(pool.idle_len(), 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_acquire_handler() {
+        let engine = engine();
+        let pre = pre(&engine);
+
+        // The pool is warmed using the handler of the invocation which triggered it
+        let pool = Arc::new(InstancePool::new(1, InstanceReuse::Stateless));
+        let pooled = acquire(&pool, &engine, &pre, 1).await;
+        assert_eq!(pooled.store.data().handler, 1);
+        wait_idle(&pool, 1).await;
+
+        let PooledInstance {
+            store, pool_hit, ..
+        } = acquire(&pool, &engine, &pre, 2).await;
+        assert_eq!(pool_hit, Some(true));
+        assert_eq!(store.data().handler, 2);
+        // Taking an instance from the pool warms it again, now using the handler of this invocation
+        wait_idle(&pool, 1).await;
+
+        let pooled = acquire(&pool, &engine, &pre, 3).await;
+        assert_eq!(pooled.pool_hit, Some(true));
+        assert_eq!(pooled.store.data().handler, 3);
+    }
+}
@@ -2,7 +2,8 @@ use tracing::warn;
 
 /// Feature flags to enable experimental functionality in the runtime. Flags are disabled
 /// by default and must be explicitly enabled.
-#[derive(Copy, Clone, Debug, Default)]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Features {
     /// Enable the wasmcloud:messaging@v3 interface support in the runtime
     pub wasmcloud_messaging_v3: bool,
@@ -10,6 +11,8 @@ pub struct Features {
     pub workload_identity_interface: bool,
     /// Enable the wrpc:rpc interface support in the runtime
     pub rpc_interface: bool,
+    /// Enable the wasmcloud:blobstore extension interfaces support in the runtime
+    pub blobstore_extensions: bool,
 }
 
 impl Features {
@@ -39,6 +42,13 @@ impl Features {
         self.rpc_interface = true;
         self
     }
+
+    /// Enable `wasmcloud:blobstore` extension interfaces support in the runtime
+    #[must_use]
+    pub fn enable_blobstore_extensions(mut self) -> Self {
+        self.blobstore_extensions = true;
+        self
+    }
 }
 
 /// This enables unioning feature flags together
@@ -51,6 +61,7 @@ impl std::ops::BitOr for Features {
             workload_identity_interface: self.workload_identity_interface
                 || rhs.workload_identity_interface,
             rpc_interface: self.rpc_interface || rhs.rpc_interface,
+            blobstore_extensions: self.blobstore_extensions || rhs.blobstore_extensions,
         }
     }
 }
@@ -75,6 +86,9 @@ impl From<&str> for Features {
                 Self::new().enable_workload_identity_interface()
             }
             "rpc-interface" | "rpc_interface" => Self::new().enable_rpc_interface(),
+            "blobstore-extensions" | "blobstore_extensions" => {
+                Self::new().enable_blobstore_extensions()
+            }
             _ => {
                 warn!(%s, "unknown feature flag");
                 Self::new()
@@ -15,9 +15,15 @@ pub const MAX_LINEAR_MEMORY: u64 = 256 * 1024 * 1024;
 pub const MAX_COMPONENT_SIZE: u64 = 50 * 1024 * 1024;
 /// Default max number of components
 pub const MAX_COMPONENTS: u32 = 10_000;
+/// Max number of elements in a single table of a component
+pub const MAX_TABLE_ELEMENTS: u32 = 15_000;
 
 /// Default number of max core instances per component
 pub const DEFAULT_MAX_CORE_INSTANCES_PER_COMPONENT: u32 = 30;
+/// Default interval at which the engine epoch is incremented
+pub const DEFAULT_EPOCH_TICK_INTERVAL: Duration = Duration::from_secs(1);
+/// Minimum interval at which the engine epoch can be incremented
+pub const MIN_EPOCH_TICK_INTERVAL: Duration = Duration::from_millis(1);
 
 /// [`RuntimeBuilder`] used to configure and build a [Runtime]
 #[derive(Clone, Default)]
@@ -29,8 +35,10 @@ pub struct RuntimeBuilder {
     max_component_size: u64,
     max_linear_memory: u64,
     max_execution_time: Duration,
+    epoch_tick_interval: Duration,
     component_config: ComponentConfig,
     force_pooling_allocator: bool,
+    fuel_metering: bool,
     experimental_features: Features,
 }
 
@@ -52,8 +60,10 @@ impl RuntimeBuilder {
             max_linear_memory: MAX_LINEAR_MEMORY,
             max_core_instances_per_component: DEFAULT_MAX_CORE_INSTANCES_PER_COMPONENT,
             max_execution_time: Duration::from_secs(10 * 60),
+            epoch_tick_interval: DEFAULT_EPOCH_TICK_INTERVAL,
             component_config: ComponentConfig::default(),
             force_pooling_allocator: false,
+            fuel_metering: false,
             experimental_features: Features::default(),
         }
     }
@@ -104,12 +114,24 @@ impl RuntimeBuilder {
     }
 
     /// Sets the maximum execution time of a component. Defaults to 10 minutes.
-    /// This operates on second precision and value of 1 second is the minimum.
-    /// Any value below 1 second will be interpreted as 1 second limit.
+    /// This operates on the precision of the epoch tick interval (see [`Self::epoch_tick_interval`])
+    /// and any value below the tick interval will be interpreted as a single tick.
     #[must_use]
     pub fn max_execution_time(self, max_execution_time: Duration) -> Self {
         Self {
-            max_execution_time: max_execution_time.max(Duration::from_secs(1)),
+            max_execution_time,
+            ..self
+        }
+    }
+
+    /// Sets the interval at which the engine epoch is incremented, which is the resolution at
+    /// which execution deadlines are enforced. Defaults to 1 second.
+    /// Shorter intervals allow for tighter deadlines at the cost of more frequent deadline checks.
+    /// Any value below 1 millisecond will be interpreted as 1 millisecond.
+    #[must_use]
+    pub fn epoch_tick_interval(self, epoch_tick_interval: Duration) -> Self {
+        Self {
+            epoch_tick_interval: epoch_tick_interval.max(MIN_EPOCH_TICK_INTERVAL),
             ..self
         }
     }
@@ -123,6 +145,17 @@ impl RuntimeBuilder {
         }
     }
 
+    /// Enables fuel metering, which counts the work performed by each component invocation and
+    /// allows bounding it with a per-component fuel budget. Metering adds a small overhead to
+    /// execution and is disabled by default.
+    #[must_use]
+    pub fn fuel_metering(self) -> Self {
+        Self {
+            fuel_metering: true,
+            ..self
+        }
+    }
+
     /// Set the experimental features to enable in the runtime
     #[must_use]
     pub fn experimental_features(self, experimental_features: Features) -> Self {
@@ -147,7 +180,7 @@ impl RuntimeBuilder {
         // configure all these values via something smarter that can look at total memory available
         let memories_per_component = 1;
         let tables_per_component = 1;
-        let table_elements = 15000;
+        let table_elements = MAX_TABLE_ELEMENTS;
 
         #[allow(clippy::cast_possible_truncation)]
         pooling_config
@@ -178,6 +211,7 @@ impl RuntimeBuilder {
             .table_keep_resident(10 * 1024);
         self.engine_config
             .allocation_strategy(InstanceAllocationStrategy::Pooling(pooling_config));
+        self.engine_config.consume_fuel(self.fuel_metering);
         let engine = match wasmtime::Engine::new(&self.engine_config)
             .context("failed to construct engine")
         {
@@ -192,10 +226,11 @@ impl RuntimeBuilder {
                 wasmtime::Engine::new(&self.engine_config).context("failed to construct engine")?
             }
         };
+        let epoch_tick_interval = self.epoch_tick_interval;
         let epoch = {
             let engine = engine.weak();
             thread::spawn(move || loop {
-                thread::sleep(Duration::from_secs(1));
+                thread::sleep(epoch_tick_interval);
                 let Some(engine) = engine.upgrade() else {
                     return Ok(());
                 };
@@ -206,7 +241,9 @@ impl RuntimeBuilder {
             Runtime {
                 engine,
                 component_config: self.component_config,
-                max_execution_time: self.max_execution_time,
+                max_execution_time: self.max_execution_time.max(epoch_tick_interval),
+                epoch_tick_interval,
+                fuel_metering: self.fuel_metering,
                 experimental_features: self.experimental_features,
             },
             epoch,
@@ -228,6 +265,8 @@ pub struct Runtime {
     pub(crate) engine: wasmtime::Engine,
     pub(crate) component_config: ComponentConfig,
     pub(crate) max_execution_time: Duration,
+    pub(crate) epoch_tick_interval: Duration,
+    pub(crate) fuel_metering: bool,
     pub(crate) experimental_features: Features,
 }
 
@@ -237,6 +276,8 @@ impl Debug for Runtime {
             .field("component_config", &self.component_config)
             .field("runtime", &"wasmtime")
             .field("max_execution_time", &"max_execution_time")
+            .field("epoch_tick_interval", &self.epoch_tick_interval)
+            .field("fuel_metering", &self.fuel_metering)
             .finish_non_exhaustive()
     }
 }
@@ -279,6 +320,12 @@ impl Runtime {
             | "wrpc:rpc/error@0.1.0"
             | "wrpc:rpc/invoker@0.1.0"
             | "wrpc:rpc/transport@0.1.0" => self.experimental_features.rpc_interface,
+            "wasmcloud:blobstore/multipart@0.1.0-draft"
+            | "wasmcloud:blobstore/objects@0.1.0-draft"
+            | "wasmcloud:blobstore/presign@0.1.0-draft"
+            | "wasmcloud:blobstore/types@0.1.0-draft" => {
+                self.experimental_features.blobstore_extensions
+            }
             _ => false,
         }
     }
@@ -94,25 +94,60 @@ impl WasmCloudTestHost {
         secrets_topic_prefix: Option<String>,
         experimental_features: Option<Features>,
     ) -> Result<Self> {
-        let nats_url = Url::try_from(nats_url.as_ref()).context("failed to parse NATS URL")?;
-        let lattice_name = lattice_name.as_ref();
-        let cluster_key = Arc::new(cluster_key.unwrap_or(KeyPair::new_cluster()));
-        let host_key = Arc::new(host_key.unwrap_or(KeyPair::new_server()));
         let experimental_features = experimental_features.unwrap_or_else(|| {
             Features::new()
                 .enable_builtin_http_server()
                 .enable_builtin_messaging_nats()
                 .enable_wasmcloud_messaging_v3()
         });
+        Self::start_custom_with_config(
+            nats_url,
+            lattice_name,
+            cluster_key,
+            host_key,
+            policy_service_config,
+            secrets_topic_prefix,
+            HostConfig {
+                provider_shutdown_delay: Some(Duration::from_millis(300)),
+                allow_file_load: true,
+                experimental_features,
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
+    /// Start a test wasmCloud [`Host`] like [`Self::start_custom`], with `host_config` as the
+    /// configuration of the host. The NATS URL, lattice and host key of `host_config` are overridden.
+    ///
+    /// # Arguments
+    ///
+    /// * `nats_url` - URL of the NATS instance to which we should connect (ex. "nats://localhost:4222")
+    /// * `lattice_name` - Name of the wasmCloud lattice to which we should connect (ex. "default")
+    /// * `cluster_key` - An optional `nkeys::KeyPair` to use for the lattice. If not specified, one is generated.
+    /// * `host_key` - An optional `nkeys::KeyPair` to use for the host. If not specified, one is generated.
+    /// * `policy_service_config` - Configuration for a [Policy Service](https://wasmcloud.com/docs/deployment/security/policy-service) to use with the host
+    /// * `secrets_backend_topic` - Topic for the host to use for secrets requests
+    /// * `host_config` - Configuration of the host
+    pub async fn start_custom_with_config(
+        nats_url: impl AsRef<str>,
+        lattice_name: impl AsRef<str>,
+        cluster_key: Option<KeyPair>,
+        host_key: Option<KeyPair>,
+        policy_service_config: Option<PolicyService>,
+        secrets_topic_prefix: Option<String>,
+        host_config: HostConfig,
+    ) -> Result<Self> {
+        let nats_url = Url::try_from(nats_url.as_ref()).context("failed to parse NATS URL")?;
+        let lattice_name = lattice_name.as_ref();
+        let cluster_key = Arc::new(cluster_key.unwrap_or(KeyPair::new_cluster()));
+        let host_key = Arc::new(host_key.unwrap_or(KeyPair::new_server()));
 
         let host_config = HostConfig {
             rpc_nats_url: nats_url.clone(),
             lattice: lattice_name.into(),
             host_key: Arc::clone(&host_key),
-            provider_shutdown_delay: Some(Duration::from_millis(300)),
-            allow_file_load: true,
-            experimental_features,
-            ..Default::default()
+            ..host_config
         };
 
         let nats_client = connect_nats(nats_url.as_str(), None, None, false, None, None)
@@ -92,9 +92,14 @@ pub struct CapabilityProvider {
     pub ver: Option<String>,
     /// The file hashes that correspond to the architecture-OS target triples for this provider.
     pub target_hashes: HashMap<String, String>,
-    /// If the provider chooses, it can supply a JSON schma that describes its expected link configuration
+    /// If the provider chooses, it can supply a JSON schema that describes its expected link
+    /// configuration. Hosts refuse links supplying configuration to the provider not matching it
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub config_schema: Option<serde_json::Value>,
+    /// If the provider chooses, it can supply a JSON schema that describes the configuration it is
+    /// started with. Hosts refuse to start the provider with configuration not matching it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider_config_schema: Option<serde_json::Value>,
 }
 
 /// The claims metadata corresponding to an account
@@ -401,6 +406,7 @@ impl Claims<CapabilityProvider> {
                 target_hashes: hashes,
                 vendor,
                 config_schema: None,
+                provider_config_schema: None,
             }),
             expires,
             id: nuid::next().to_string(),
@@ -860,6 +866,7 @@ impl CapabilityProvider {
             rev,
             ver,
             config_schema: None,
+            provider_config_schema: None,
         }
     }
 }
@@ -1183,7 +1190,8 @@ mod test {
                 rev: Some(1),
                 ver: Some("v0.0.1".to_string()),
                 target_hashes: hashes,
-                config_schema: Some(schema),
+                config_schema: Some(schema.clone()),
+                provider_config_schema: Some(schema),
             })
             .build();
 
@@ -1202,6 +1210,16 @@ mod test {
                 .unwrap()["properties"]["port"]["minimum"],
             4000
         );
+        assert_eq!(
+            decoded
+                .metadata
+                .as_ref()
+                .unwrap()
+                .provider_config_schema
+                .as_ref()
+                .unwrap()["properties"]["port"]["maximum"],
+            10000
+        );
     }
 
     #[test]
@@ -25,13 +25,13 @@ use wash::lib::cli::stop::StopCommand;
 use wash::lib::cli::update::UpdateCommand;
 use wash::lib::cli::{CommandOutput, OutputKind};
 use wash::lib::config::WASH_DIRECTORIES;
-use wash::lib::drain::Drain as DrainSelection;
 use wash::lib::generate::emoji;
 use wash::lib::plugin::subcommand::{DirMapping, SubcommandRunner};
 use wash::lib::start::get_wash_versions_newer_than;
 
 use wash::cli::app::{self, AppCliCommand};
 use wash::cli::build::{self, BuildCommand};
+use wash::cli::cache::{self, CacheCliCommand};
 use wash::cli::call::{self, CallCli};
 use wash::cli::cmd::config::{self, ConfigCliCommand};
 use wash::cli::cmd::dev::{self, DevCommand};
@@ -43,7 +43,7 @@ use wash::cli::completions::{self, CompletionOpts};
 use wash::cli::config::{NATS_SERVER_VERSION, WADM_VERSION, WASMCLOUD_HOST_VERSION};
 use wash::cli::ctx::{self, CtxCommand};
 use wash::cli::down::{self, DownCommand};
-use wash::cli::drain;
+use wash::cli::drain::{self, DrainCommand};
 use wash::cli::generate::{self, NewCliCommand};
 use wash::cli::keys::{self, KeysCliCommand};
 use wash::cli::par::{self, ParCliCommand};
@@ -188,9 +188,10 @@ ________________________________________________________________________________
         HelpTopic {
             name: "Configure:",
             commands: vec![
+                ("cache", "List and prune the local OCI artifact cache"),
                 ("completions", "Generate shell completions for wash"),
                 ("ctx", "Manage wasmCloud host configuration contexts"),
-                ("drain", "Manage contents of local wasmCloud caches"),
+                ("drain", "Manage contents of local wasmCloud caches or drain a host"),
                 ("keys", "Generate and manage signing keys"),
                 ("claims", "Generate and manage JWTs for wasmCloud components and capability providers"),
                 ("plugin", "Manage wash plugins"),
@@ -296,6 +297,9 @@ enum CliCommand {
     /// Build (and sign) a wasmCloud component or capability provider
     #[clap(name = "build")]
     Build(BuildCommand),
+    /// List and prune the local OCI artifact cache
+    #[clap(name = "cache", subcommand)]
+    Cache(CacheCliCommand),
     /// Invoke a simple function on a component running in a wasmCloud host
     #[clap(name = "call")]
     Call(CallCli),
@@ -320,9 +324,9 @@ enum CliCommand {
     /// Tear down a local wasmCloud environment (launched with wash up)
     #[clap(name = "down")]
     Down(DownCommand),
-    /// Manage contents of local wasmCloud caches
+    /// Manage contents of local wasmCloud caches or drain a host
     #[clap(name = "drain", subcommand)]
-    Drain(DrainSelection),
+    Drain(DrainCommand),
     /// Get information about different running wasmCloud resources
     #[clap(name = "get", subcommand)]
     Get(GetCommand),
@@ -529,12 +533,13 @@ async fn main() {
     let res: anyhow::Result<CommandOutput> = match cli_command {
         CliCommand::App(app_cli) => app::handle_command(app_cli, output_kind).await,
         CliCommand::Build(build_cli) => build::handle_command(build_cli).await,
+        CliCommand::Cache(cache_cli) => cache::handle_command(cache_cli).await,
         CliCommand::Call(call_cli) => call::handle_command(call_cli.command()).await,
         CliCommand::Capture(capture_cli) => {
             if !cli.experimental {
                 experimental_error_message("capture")
             } else if let Some(CaptureSubcommand::Replay(cmd)) = capture_cli.replay {
-                wash::lib::cli::capture::handle_replay_command(cmd).await
+                wash::lib::cli::capture::handle_replay_command(cmd, output_kind).await
             } else {
                 wash::lib::cli::capture::handle_command(capture_cli).await
             }
@@ -549,7 +554,7 @@ async fn main() {
         CliCommand::Ctx(ctx_cli) => ctx::handle_command(ctx_cli).await,
         CliCommand::Dev(dev_cli) => dev::handle_command(dev_cli, output_kind).await,
         CliCommand::Down(down_cli) => down::handle_command(down_cli, output_kind).await,
-        CliCommand::Drain(drain_cli) => drain::handle_command(drain_cli),
+        CliCommand::Drain(drain_cli) => drain::handle_command(drain_cli).await,
         CliCommand::Get(get_cli) => common::get_cmd::handle_command(get_cli, output_kind).await,
         CliCommand::Inspect(inspect_cli) => {
             wash::lib::cli::inspect::handle_command(inspect_cli, output_kind).await
@@ -569,7 +574,7 @@ async fn main() {
             if !cli.experimental {
                 experimental_error_message("spy")
             } else {
-                wash::lib::cli::spy::handle_command(spy_cli).await
+                wash::lib::cli::spy::handle_command(spy_cli, output_kind).await
             }
         }
         CliCommand::Scale(scale_cli) => {
@@ -0,0 +1,160 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+use clap::Subcommand;
+use serde_json::json;
+use wasmcloud_core::{ArtifactCache, CachedArtifact};
+
+use crate::lib::cli::CommandOutput;
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum CacheCliCommand {
+    /// List artifacts in the OCI artifact cache, least recently used first
+    #[clap(name = "list")]
+    List {
+        /// Directory of the OCI artifact cache. Defaults to the cache used by wasmCloud hosts
+        #[clap(long = "cache-dir", env = "WASMCLOUD_OCI_CACHE_DIR")]
+        cache_dir: Option<PathBuf>,
+    },
+    /// Evict least recently used artifacts from the OCI artifact cache
+    #[clap(name = "prune")]
+    Prune {
+        /// Directory of the OCI artifact cache. Defaults to the cache used by wasmCloud hosts
+        #[clap(long = "cache-dir", env = "WASMCLOUD_OCI_CACHE_DIR")]
+        cache_dir: Option<PathBuf>,
+        /// Evict artifacts until the cache is no larger than this many bytes
+        #[clap(long = "max-size", conflicts_with = "all")]
+        max_size: Option<u64>,
+        /// Evict all artifacts
+        #[clap(long = "all")]
+        all: bool,
+        /// OCI references or digests of artifacts to keep, can be specified multiple times
+        #[clap(long = "keep")]
+        keep: Vec<String>,
+    },
+}
+
+pub async fn handle_command(command: CacheCliCommand) -> Result<CommandOutput> {
+    match command {
+        CacheCliCommand::List { cache_dir } => list(cache(cache_dir)).await,
+        CacheCliCommand::Prune {
+            cache_dir,
+            max_size,
+            all,
+            keep,
+        } => {
+            let max_size = match (max_size, all) {
+                (_, true) => 0,
+                (Some(max_size), false) => max_size,
+                (None, false) => bail!("one of `--max-size` or `--all` must be specified"),
+            };
+            prune(cache(cache_dir), max_size, keep.into_iter().collect()).await
+        }
+    }
+}
+
+fn cache(cache_dir: Option<PathBuf>) -> ArtifactCache {
+    ArtifactCache::new(cache_dir.unwrap_or_else(ArtifactCache::default_dir), None)
+}
+
+fn describe(artifact: &CachedArtifact) -> String {
+    let references = artifact
+        .references
+        .iter()
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{}\t{}\t{references}", artifact.digest, artifact.size)
+}
+
+async fn list(cache: ArtifactCache) -> Result<CommandOutput> {
+    let artifacts = cache.list().await?;
+    let total_size: u64 = artifacts.iter().map(|a| a.size).sum();
+
+    let mut map = HashMap::new();
+    map.insert("artifacts".to_string(), json!(artifacts));
+    map.insert("total_size".to_string(), json!(total_size));
+    Ok(CommandOutput::new(
+        format!(
+            "====== Artifacts cached in {} ({total_size} bytes) ======\n{}",
+            cache.root().display(),
+            artifacts
+                .iter()
+                .map(describe)
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+        map,
+    ))
+}
+
+async fn prune(
+    cache: ArtifactCache,
+    max_size: u64,
+    keep: HashSet<String>,
+) -> Result<CommandOutput> {
+    let res = cache.prune(max_size, &keep).await?;
+
+    let mut map = HashMap::new();
+    map.insert("evicted".to_string(), json!(res.evicted));
+    map.insert("remaining_size".to_string(), json!(res.remaining_size));
+    Ok(CommandOutput::new(
+        format!(
+            "Evicted {} artifacts from {}, {} bytes remaining\n{}",
+            res.evicted.len(),
+            cache.root().display(),
+            res.remaining_size,
+            res.evicted
+                .iter()
+                .map(describe)
+                .collect::<Vec<_>>()
+                .join("\n")
+        ),
+        map,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Debug, Parser)]
+    struct Cmd {
+        #[clap(subcommand)]
+        cache: CacheCliCommand,
+    }
+
+    #[test]
+    fn test_prune_args() {
+        let cmd: Cmd = Parser::try_parse_from([
+            "cache",
+            "prune",
+            "--max-size",
+            "1024",
+            "--keep",
+            "ghcr.io/foo:0.1.0",
+            "--keep",
+            "ghcr.io/bar:0.1.0",
+        ])
+        .unwrap();
+        match cmd.cache {
+            CacheCliCommand::Prune {
+                max_size,
+                all,
+                keep,
+                ..
+            } => {
+                assert_eq!(max_size, Some(1024));
+                assert!(!all);
+                assert_eq!(keep, vec!["ghcr.io/foo:0.1.0", "ghcr.io/bar:0.1.0"]);
+            }
+            _ => panic!("cache constructed incorrect command"),
+        }
+        assert!(
+            Cmd::try_parse_from(["cache", "prune", "--max-size", "1024", "--all"]).is_err(),
+            "--max-size and --all should conflict"
+        );
+    }
+}
@@ -12,6 +12,7 @@ use crate::secrets::is_secret;
 pub async fn invoke(
     opts: CliConnectionOpts,
     name: &str,
+    revision: Option<u64>,
     output_kind: OutputKind,
 ) -> anyhow::Result<CommandOutput> {
     let sp: Spinner = Spinner::new(&output_kind)?;
@@ -25,10 +26,12 @@ pub async fn invoke(
     let wco: WashConnectionOptions = opts.try_into()?;
     let ctl_client = wco.into_ctl_client(None).await?;
 
-    let config_response = ctl_client
-        .get_config(name)
-        .await
-        .map_err(suggest_run_host_error)?;
+    let config_response = if let Some(revision) = revision {
+        ctl_client.get_config_revision(name, revision).await
+    } else {
+        ctl_client.get_config(name).await
+    }
+    .map_err(suggest_run_host_error)?;
 
     sp.finish_and_clear();
 
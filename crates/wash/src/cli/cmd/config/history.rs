@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use anyhow::bail;
+use serde_json::json;
+use term_table::row::Row;
+use term_table::table_cell::{Alignment, TableCell};
+use term_table::Table;
+use wasmcloud_control_interface::ConfigRevision;
+
+use crate::appearance::spinner::Spinner;
+use crate::errors::suggest_run_host_error;
+use crate::lib::cli::{CliConnectionOpts, CommandOutput, OutputKind};
+use crate::lib::config::WashConnectionOptions;
+
+/// Invoke `wash config history`
+pub async fn invoke(
+    opts: CliConnectionOpts,
+    name: &str,
+    output_kind: OutputKind,
+) -> anyhow::Result<CommandOutput> {
+    let sp: Spinner = Spinner::new(&output_kind)?;
+    sp.update_spinner_message("Getting configuration history...".to_string());
+
+    let wco: WashConnectionOptions = opts.try_into()?;
+    let ctl_client = wco.into_ctl_client(None).await?;
+
+    let history_response = ctl_client
+        .get_config_history(name)
+        .await
+        .map_err(suggest_run_host_error)?;
+
+    sp.finish_and_clear();
+
+    if !history_response.succeeded() {
+        bail!(
+            "Error getting configuration history: {}",
+            history_response.message()
+        );
+    }
+    let revisions = history_response.into_data().unwrap_or_default();
+    if revisions.is_empty() {
+        bail!("No configuration history found for name: {name}");
+    }
+
+    Ok(CommandOutput::new(
+        history_table(&revisions),
+        HashMap::from([
+            ("name".to_string(), json!(name)),
+            ("revisions".to_string(), json!(revisions)),
+        ]),
+    ))
+}
+
+fn history_table(revisions: &[ConfigRevision]) -> String {
+    let mut table = Table::new();
+    crate::util::configure_table_style(&mut table, 3);
+
+    table.add_row(Row::new(vec![
+        TableCell::new_with_alignment("Revision", 1, Alignment::Left),
+        TableCell::new_with_alignment("Created", 1, Alignment::Left),
+        TableCell::new_with_alignment("Operation", 1, Alignment::Left),
+    ]));
+
+    for revision in revisions {
+        table.add_row(Row::new(vec![
+            TableCell::new_with_alignment(revision.revision(), 1, Alignment::Left),
+            TableCell::new_with_alignment(
+                revision.created_at().unwrap_or("N/A"),
+                1,
+                Alignment::Left,
+            ),
+            TableCell::new_with_alignment(
+                if revision.deleted() { "delete" } else { "put" },
+                1,
+                Alignment::Left,
+            ),
+        ]));
+    }
+
+    table.render()
+}
@@ -8,7 +8,9 @@ use crate::secrets::ensure_not_secret;
 
 pub(crate) mod delete;
 pub(crate) mod get;
+pub(crate) mod history;
 pub(crate) mod put;
+pub(crate) mod rollback;
 
 #[derive(Debug, Clone, Subcommand)]
 #[allow(clippy::enum_variant_names)]
@@ -33,6 +35,9 @@ pub enum ConfigCliCommand {
         /// The name of the configuration to get
         #[clap(name = "name")]
         name: String,
+        /// Get the configuration as it was at this revision, as listed by `wash config history`
+        #[clap(long = "revision")]
+        revision: Option<u64>,
     },
     /// Delete a named configuration
     #[clap(name = "del", alias = "delete")]
@@ -43,6 +48,27 @@ pub enum ConfigCliCommand {
         #[clap(name = "name")]
         name: String,
     },
+    /// List the revisions of a named configuration
+    #[clap(name = "history")]
+    HistoryCommand {
+        #[clap(flatten)]
+        opts: CliConnectionOpts,
+        /// The name of the configuration to list the revisions of
+        #[clap(name = "name")]
+        name: String,
+    },
+    /// Restore a named configuration to a previous revision
+    #[clap(name = "rollback")]
+    RollbackCommand {
+        #[clap(flatten)]
+        opts: CliConnectionOpts,
+        /// The name of the configuration to roll back
+        #[clap(name = "name")]
+        name: String,
+        /// The revision to restore, as listed by `wash config history`
+        #[clap(name = "revision")]
+        revision: u64,
+    },
 }
 
 /// Handle any `wash config` prefixed (sub)command
@@ -65,13 +91,29 @@ pub async fn handle_command(
             )
             .await
         }
-        ConfigCliCommand::GetCommand { opts, name } => {
+        ConfigCliCommand::GetCommand {
+            opts,
+            name,
+            revision,
+        } => {
             ensure_not_secret(&name)?;
-            cmd::config::get::invoke(opts, &name, output_kind).await
+            cmd::config::get::invoke(opts, &name, revision, output_kind).await
         }
         ConfigCliCommand::DelCommand { opts, name } => {
             ensure_not_secret(&name)?;
             cmd::config::delete::invoke(opts, &name, output_kind).await
         }
+        ConfigCliCommand::HistoryCommand { opts, name } => {
+            ensure_not_secret(&name)?;
+            cmd::config::history::invoke(opts, &name, output_kind).await
+        }
+        ConfigCliCommand::RollbackCommand {
+            opts,
+            name,
+            revision,
+        } => {
+            ensure_not_secret(&name)?;
+            cmd::config::rollback::invoke(opts, &name, revision, output_kind).await
+        }
     }
 }
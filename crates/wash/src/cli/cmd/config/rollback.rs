@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+use serde_json::json;
+
+use crate::appearance::spinner::Spinner;
+use crate::errors::suggest_run_host_error;
+use crate::lib::cli::{CliConnectionOpts, CommandOutput, OutputKind};
+use crate::lib::config::WashConnectionOptions;
+
+/// Invoke `wash config rollback`
+pub async fn invoke(
+    opts: CliConnectionOpts,
+    name: &str,
+    revision: u64,
+    output_kind: OutputKind,
+) -> anyhow::Result<CommandOutput> {
+    let sp: Spinner = Spinner::new(&output_kind)?;
+    sp.update_spinner_message(format!(
+        "Rolling back configuration to revision {revision}..."
+    ));
+
+    let wco: WashConnectionOptions = opts.try_into()?;
+    let ctl_client = wco.into_ctl_client(None).await?;
+
+    let rollback_response = ctl_client
+        .rollback_config(name, revision)
+        .await
+        .map_err(suggest_run_host_error)?;
+
+    sp.finish_and_clear();
+
+    let new_revision = rollback_response.data().map(|revision| revision.revision());
+    let message = match new_revision {
+        Some(new_revision) if rollback_response.succeeded() => format!(
+            "Configuration '{name}' rolled back to revision {revision} as revision {new_revision}."
+        ),
+        _ => rollback_response.message().to_string(),
+    };
+    let json_out = HashMap::from_iter([
+        ("success".to_string(), json!(rollback_response.succeeded())),
+        ("message".to_string(), json!(message)),
+        ("revision".to_string(), json!(new_revision)),
+    ]);
+
+    Ok(CommandOutput::new(message, json_out))
+}
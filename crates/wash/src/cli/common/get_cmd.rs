@@ -7,15 +7,18 @@ use std::{collections::HashMap, io::Write, time::Duration};
 use tokio::time::sleep;
 use crate::lib::cli::claims::get_claims;
 use crate::lib::cli::get::{
-    get_host_inventories, get_hosts, GetCommand, GetHostInventoriesCommand, GetLinksCommand,
+    find_provider_logs_host, get_host_inventories, get_hosts, get_provider_logs, GetCommand,
+    GetHostInventoriesCommand, GetLinksCommand, GetProviderLogsCommand,
 };
 use crate::lib::cli::link::{LinkCommand, LinkQueryCommand};
 use crate::lib::cli::{CommandOutput, OutputKind};
+use crate::lib::config::WashConnectionOptions;
 
 use crate::appearance::spinner::Spinner;
 use crate::cmd::link::invoke as invoke_link_cmd;
 use crate::ctl::{
-    get_claims_output, get_host_inventories_output, get_hosts_output, host_inventories_table,
+    get_claims_output, get_host_inventories_output, get_hosts_output, get_provider_logs_output,
+    host_inventories_table,
 };
 
 pub async fn handle_command(command: GetCommand, output_kind: OutputKind) -> Result<CommandOutput> {
@@ -44,6 +47,14 @@ pub async fn handle_command(command: GetCommand, output_kind: OutputKind) -> Res
             }
             get_inventory_handler(cmd, sp).await?
         }
+        GetCommand::ProviderLogs(cmd) => {
+            let sp: Spinner = Spinner::new(&output_kind)?;
+            sp.update_spinner_message(format!(
+                " Retrieving logs for provider {} ...",
+                cmd.provider_id
+            ));
+            get_provider_logs_handler(cmd, sp).await?
+        }
     };
 
     Ok(out)
@@ -65,6 +76,47 @@ async fn get_inventory_handler(
     }
 }
 
+async fn get_provider_logs_handler(
+    cmd: GetProviderLogsCommand,
+    sp: Spinner,
+) -> Result<CommandOutput> {
+    let wco: WashConnectionOptions = cmd.opts.clone().try_into()?;
+    let client = wco.into_ctl_client(None).await?;
+    let host_id = find_provider_logs_host(&cmd, &client).await?;
+    let logs = get_provider_logs(&client, &host_id, &cmd.provider_id, None, cmd.lines).await?;
+    if !cmd.follow {
+        return Ok(get_provider_logs_output(logs));
+    }
+
+    sp.finish_and_clear();
+    let mut stdout = std::io::stdout();
+    let mut ctrlc = std::pin::pin!(tokio::signal::ctrl_c());
+    let mut logs = logs;
+    let mut since = None;
+    loop {
+        for line in logs.lines() {
+            writeln!(stdout, "{}", line.line())
+                .map_err(|e| anyhow::anyhow!("Failed to write logs to stdout: {}", e))?;
+            since = Some(line.sequence());
+        }
+        stdout
+            .flush()
+            .map_err(|e| anyhow::anyhow!("Failed to flush stdout: {}", e))?;
+
+        tokio::select! {
+            () = sleep(Duration::from_secs(1)) => {}
+            res = &mut ctrlc => {
+                res?;
+                return Ok(CommandOutput::new(
+                    "Completed following provider logs".to_string(),
+                    HashMap::new(),
+                ));
+            }
+        }
+        logs = get_provider_logs(&client, &host_id, &cmd.provider_id, since, None).await?;
+    }
+}
+
 async fn watch_inventory(cmd: GetHostInventoriesCommand, sp: Spinner) -> Result<()> {
     let mut stdout = std::io::stdout();
     let invs = get_host_inventories(cmd.clone()).await?;
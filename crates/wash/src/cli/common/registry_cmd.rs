@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
 
 use anyhow::{bail, Context, Result};
 use docker_credential::{get_credential, DockerCredential};
@@ -12,8 +13,8 @@ use crate::lib::cli::registry::{RegistryPullCommand, RegistryPushCommand};
 use crate::lib::cli::{input_vec_to_hashmap, CommandOutput, OutputKind};
 use crate::lib::parser::{load_config, ProjectConfig};
 use crate::lib::registry::{
-    identify_artifact, pull_oci_artifact, push_oci_artifact, ArtifactType, OciPullOptions,
-    OciPushOptions,
+    identify_artifact, pull_oci_artifact, push_oci_artifact, write_oci_artifact_layout,
+    ArtifactType, OciPullOptions, OciPushOptions,
 };
 use wasmcloud_control_interface::RegistryCredential;
 
@@ -97,6 +98,27 @@ pub async fn registry_push(
         &cmd.registry.unwrap_or_default(),
         project_config.as_ref(),
     )?;
+    let annotations = cmd.annotations.and_then(|annotations| {
+        Some(
+            input_vec_to_hashmap(annotations)
+                .ok()?
+                .into_iter()
+                .collect(),
+        )
+    });
+
+    if let Some(layout) = cmd.to_layout {
+        return registry_push_layout(
+            layout,
+            &image,
+            cmd.artifact,
+            cmd.config,
+            cmd.allow_latest,
+            annotations,
+        )
+        .await;
+    }
+
     let artifact_url = image.whole();
     if artifact_url.starts_with("localhost:") && !cmd.opts.insecure {
         warn!(" Unless an SSL certificate has been installed, pushing to localhost without the --insecure option will fail");
@@ -112,15 +134,6 @@ pub async fn registry_push(
         _ => resolve_registry_credentials(image.registry()).await,
     }?;
 
-    let annotations = cmd.annotations.and_then(|annotations| {
-        Some(
-            input_vec_to_hashmap(annotations)
-                .ok()?
-                .into_iter()
-                .collect(),
-        )
-    });
-
     let (maybe_tag, digest) = push_oci_artifact(
         artifact_url.clone(),
         cmd.artifact,
@@ -153,6 +166,44 @@ pub async fn registry_push(
     Ok(CommandOutput::new(text, map))
 }
 
+async fn registry_push_layout(
+    layout: PathBuf,
+    image: &Reference,
+    artifact: String,
+    config: Option<PathBuf>,
+    allow_latest: bool,
+    annotations: Option<BTreeMap<String, String>>,
+) -> Result<CommandOutput> {
+    let tag = image
+        .tag()
+        .context("an artifact tag is required to write to an OCI layout")?;
+    let digest = write_oci_artifact_layout(
+        &layout,
+        tag,
+        &artifact,
+        OciPushOptions {
+            config,
+            allow_latest,
+            annotations,
+            ..Default::default()
+        },
+    )
+    .await?;
+
+    let layout_ref = format!("oci-layout://{}:{tag}", layout.display());
+    let map = HashMap::from_iter([
+        ("url".to_string(), json!(layout_ref)),
+        ("tag".to_string(), json!(tag)),
+        ("digest".to_string(), json!(digest)),
+    ]);
+    Ok(CommandOutput::new(
+        format!(
+            "{SHOWER_EMOJI} Successfully wrote {artifact} to {layout_ref}\n{tag}: digest: {digest}"
+        ),
+        map,
+    ))
+}
+
 fn resolve_artifact_ref(
     url: &str,
     registry: &str,
@@ -333,6 +384,22 @@ mod tests {
             _ => panic!("`wash push` constructed incorrect command"),
         };
 
+        let push_layout: Cmd = Parser::try_parse_from([
+            "wash",
+            "push",
+            echo_push_basic,
+            &format!("{TESTDIR}/echopush.wasm"),
+            "--to-layout",
+            "/tmp/bundle.tar",
+        ])
+        .unwrap();
+        match push_layout.sub {
+            RegistryCommand::Push(RegistryPushCommand { to_layout, .. }) => {
+                assert_eq!(to_layout, Some(std::path::PathBuf::from("/tmp/bundle.tar")));
+            }
+            _ => panic!("`wash push` constructed incorrect command"),
+        };
+
         // Push logging.par.gz and pull from local registry
         let logging_push_all_flags = &format!("{LOCAL_REGISTRY}/logging:allflags");
         let push_all_flags: Cmd = Parser::try_parse_from([
@@ -117,6 +117,9 @@ mod test {
             HOST_ID,
             "--link-name",
             "default",
+            "--min-available-memory",
+            "1024",
+            "--anti-affinity",
             "--skip-wait",
             "ghcr.io/provider:v1",
             "providerv1",
@@ -132,6 +135,8 @@ mod test {
                 auction_timeout_ms,
                 config,
                 skip_wait,
+                min_available_memory,
+                anti_affinity,
             })) => {
                 assert_eq!(&opts.ctl_host.unwrap(), CTL_HOST);
                 assert_eq!(&opts.ctl_port.unwrap(), CTL_PORT);
@@ -145,6 +150,8 @@ mod test {
                 assert_eq!(provider_id, "providerv1".to_string());
                 assert!(config.is_empty());
                 assert!(skip_wait);
+                assert_eq!(min_available_memory, Some(1024));
+                assert!(anti_affinity);
             }
             cmd => panic!("ctl start provider constructed incorrect command {cmd:?}"),
         }
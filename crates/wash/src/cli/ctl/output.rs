@@ -7,7 +7,7 @@ use term_table::{
     Table,
 };
 use crate::lib::{cli::CommandOutput, plugin::subcommand::Metadata};
-use wasmcloud_control_interface::{Host, HostInventory, Link};
+use wasmcloud_control_interface::{Host, HostInventory, Link, ProviderLogLine, ProviderLogs};
 
 use crate::util::format_optional;
 
@@ -29,6 +29,21 @@ use crate::util::format_optional;
     CommandOutput::new(claims_table(claims), map)
 }
 
+#[must_use] pub fn get_provider_logs_output(logs: ProviderLogs) -> CommandOutput {
+    let mut text = logs
+        .lines()
+        .iter()
+        .map(ProviderLogLine::line)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if logs.truncated() {
+        text.insert_str(0, "(earlier lines are no longer retained by the host)\n");
+    }
+    let mut map = HashMap::new();
+    map.insert("logs".to_string(), json!(logs));
+    CommandOutput::new(text, map)
+}
+
 #[must_use] pub fn links_table(mut list: Vec<Link>) -> String {
     // Sort the list based on the `source_id` field in ascending order
     list.sort_by(|a, b| a.source_id().cmp(b.source_id()));
@@ -1,12 +1,31 @@
 use std::collections::HashMap;
 
+use crate::lib::cli::drain::{drain_host, DrainHostCommand};
 use crate::lib::cli::CommandOutput;
 use crate::lib::config::{host_pid_file, wadm_pid_file};
 use crate::lib::drain::Drain;
 use anyhow::Result;
+use clap::Subcommand;
 use serde_json::json;
 
-pub fn handle_command(cmd: Drain) -> Result<CommandOutput, anyhow::Error> {
+#[derive(Debug, Clone, Subcommand)]
+pub enum DrainCommand {
+    #[clap(flatten)]
+    Cache(Drain),
+    /// Cordon a host, so that it no longer responds to auctions, and stop its components and
+    /// providers once in-flight invocations have finished
+    #[clap(name = "host")]
+    Host(DrainHostCommand),
+}
+
+pub async fn handle_command(cmd: DrainCommand) -> Result<CommandOutput, anyhow::Error> {
+    match cmd {
+        DrainCommand::Cache(cmd) => drain_cache(cmd),
+        DrainCommand::Host(cmd) => drain_host(cmd).await,
+    }
+}
+
+fn drain_cache(cmd: Drain) -> Result<CommandOutput, anyhow::Error> {
     if matches!(cmd, Drain::All | Drain::Downloads) {
         let wasmcloud_pid_path = host_pid_file().unwrap();
         let wadm_pid_path = wadm_pid_file().unwrap();
@@ -55,4 +74,53 @@ mod test {
             _ => panic!("drain constructed incorrect command"),
         }
     }
+
+    #[derive(Parser)]
+    struct DrainCmd {
+        #[clap(subcommand)]
+        drain: DrainCommand,
+    }
+
+    #[test]
+    fn test_drain_host() {
+        const HOST_ID: &str = "NCE7YHGI42RWEKBRDJZWXBEJJCFNE5YTNHLBCJFOY2NT2WD4JIYNHN5H";
+        let cache: DrainCmd = Parser::try_parse_from(["drain", "oci"]).unwrap();
+        assert!(matches!(cache.drain, DrainCommand::Cache(Drain::Oci)));
+
+        let host: DrainCmd = Parser::try_parse_from([
+            "drain",
+            "host",
+            HOST_ID,
+            "--drain-timeout",
+            "1000",
+            "--stop-host",
+        ])
+        .unwrap();
+        match host.drain {
+            DrainCommand::Host(DrainHostCommand {
+                host_id,
+                drain_timeout,
+                stop_host,
+                cordon_only,
+                uncordon,
+                ..
+            }) => {
+                assert_eq!(host_id, HOST_ID);
+                assert_eq!(drain_timeout, Some(1000));
+                assert!(stop_host);
+                assert!(!cordon_only);
+                assert!(!uncordon);
+            }
+            _ => panic!("drain constructed incorrect command"),
+        }
+
+        assert!(DrainCmd::try_parse_from([
+            "drain",
+            "host",
+            HOST_ID,
+            "--cordon-only",
+            "--stop-host"
+        ])
+        .is_err());
+    }
 }
@@ -1,6 +1,7 @@
 pub mod app;
 pub mod appearance;
 pub mod build;
+pub mod cache;
 pub mod call;
 pub mod cmd;
 pub mod common;
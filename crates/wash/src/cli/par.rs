@@ -42,7 +42,8 @@ pub struct CreateCommand {
     #[clap(long = "version")]
     version: Option<String>,
 
-    /// Optional path to a JSON schema describing the link definition specification for this provider.
+    /// Optional path to a JSON schema describing the link configuration of this provider. See
+    /// `--provider-config-schema` for the configuration the provider is started with.
     #[clap(
         short = 'j',
         long = "schema",
@@ -51,6 +52,15 @@ pub struct CreateCommand {
     )]
     schema: Option<PathBuf>,
 
+    /// Optional path to a JSON schema describing the configuration this provider is started with.
+    /// Hosts refuse to start the provider with configuration not matching it.
+    #[clap(
+        long = "provider-config-schema",
+        env = "WASH_PROVIDER_CONFIG_SCHEMA",
+        hide_env_values = true
+    )]
+    provider_config_schema: Option<PathBuf>,
+
     /// Location of key files for signing. Defaults to $`WASH_KEYS` ($HOME/.wash/keys)
     #[clap(
         short = 'd',
@@ -222,6 +232,7 @@ impl From<CreateCommand> for par::ParCreateArgs {
             revision: cmd.revision,
             version: cmd.version,
             schema: cmd.schema,
+            provider_config_schema: cmd.provider_config_schema,
             name: cmd.name,
             arch: cmd.arch,
         }
@@ -416,6 +427,8 @@ mod test {
             "--compress",
             "--wit-directory",
             "./wit",
+            "--provider-config-schema",
+            "./config.schema.json",
         ])
         .unwrap();
         match create_long.par {
@@ -424,6 +437,7 @@ mod test {
                 revision,
                 version,
                 schema,
+                provider_config_schema,
                 directory,
                 issuer,
                 subject,
@@ -446,6 +460,10 @@ mod test {
                 assert_eq!(revision.unwrap(), 1);
                 assert_eq!(version.unwrap(), "1.11.111");
                 assert_eq!(schema, None);
+                assert_eq!(
+                    provider_config_schema.unwrap(),
+                    PathBuf::from("./config.schema.json")
+                );
                 assert!(disable_keygen);
                 assert!(compress);
                 assert_eq!(wit_dir.unwrap(), PathBuf::from("./wit"));
@@ -485,6 +503,7 @@ mod test {
                 revision,
                 version,
                 schema,
+                provider_config_schema,
                 directory,
                 issuer,
                 subject,
@@ -507,6 +526,7 @@ mod test {
                 assert_eq!(revision.unwrap(), 1);
                 assert_eq!(version.unwrap(), "1.11.111");
                 assert_eq!(schema, None);
+                assert_eq!(provider_config_schema, None);
                 assert!(!disable_keygen);
                 assert!(!compress);
                 assert_eq!(wit_dir.unwrap(), PathBuf::from("./wit"));
@@ -86,7 +86,7 @@ pub async fn handle_command(
             cmd::config::put::invoke(opts, &secret_configdata_key(&name), values, output_kind).await
         }
         SecretsCliCommand::GetCommand { opts, name } => {
-            cmd::config::get::invoke(opts, &secret_configdata_key(&name), output_kind).await
+            cmd::config::get::invoke(opts, &secret_configdata_key(&name), None, output_kind).await
         }
         SecretsCliCommand::DelCommand { opts, name } => {
             cmd::config::delete::invoke(opts, &secret_configdata_key(&name), output_kind).await
@@ -71,6 +71,7 @@ pub(crate) async fn build_provider(
             revision: Some(common_config.revision),
             version: Some(common_config.version.to_string()),
             schema: None,
+            provider_config_schema: None,
             name: common_config.name.to_string(),
             arch: detect_arch(),
         },
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::io::Cursor;
 use std::path::{Path, PathBuf};
@@ -5,6 +6,7 @@ use std::path::{Path, PathBuf};
 use anyhow::Result;
 use async_compression::tokio::{bufread::GzipDecoder, write::GzipEncoder};
 use async_nats::HeaderMap;
+use bytes::{Bytes, BytesMut};
 use futures::TryStreamExt;
 use serde::{Deserialize, Serialize};
 use tokio::{
@@ -46,6 +48,50 @@ impl TryFrom<async_nats::jetstream::Message> for SerializableMessage {
     }
 }
 
+/// A wRPC invocation reconstructed from captured messages
+#[derive(Debug, Clone)]
+pub struct CapturedInvocation {
+    /// Lattice the invocation was sent on
+    pub lattice: String,
+    /// ID of the component or provider the invocation was sent to
+    pub target_id: String,
+    /// ID of the invoking component, if it was set by the host
+    pub source_id: Option<String>,
+    /// Invoked instance, e.g. `wasi:http/incoming-handler@0.2.0`
+    pub instance: String,
+    /// Invoked function within the instance
+    pub function: String,
+    /// Headers sent along with the invocation
+    pub headers: Option<HeaderMap>,
+    /// Encoded parameters sent along with the invocation, including the parameters sent in
+    /// follow-up messages, if they did not fit into the invocation message
+    pub params: Bytes,
+    /// Encoded results returned by the target, if they were captured
+    pub results: Option<Bytes>,
+    /// Time the invocation was published at
+    pub published: time::OffsetDateTime,
+}
+
+/// Parse a wRPC invocation subject of the form `<lattice>.<target>.wrpc.0.0.1.<instance>.<function>`
+/// into the lattice, target ID, instance and function
+#[must_use]
+pub fn parse_invocation_subject(subject: &str) -> Option<(&str, &str, &str, &str)> {
+    let (lattice, subject) = subject.split_once('.')?;
+    let (target, subject) = subject.split_once('.')?;
+    // Instances may contain dots in their version, but functions never do
+    let (instance, function) = subject
+        .strip_prefix(wrpc_transport_nats::PROTOCOL)?
+        .strip_prefix('.')?
+        .rsplit_once('.')?;
+    if [lattice, target, instance, function]
+        .iter()
+        .any(|part| part.is_empty())
+    {
+        return None;
+    }
+    Some((lattice, target, instance, function))
+}
+
 /// A read capture is a parsed tarball that contains all of the messages and inventory for a given
 /// capture.
 ///
@@ -105,6 +151,84 @@ impl ReadCapture {
         }
         Ok(capture)
     }
+
+    /// Reconstruct the wRPC invocations contained in the capture, in the order they were published.
+    ///
+    /// Results are matched to invocations using the reply subject of the invocation message.
+    /// Parameters, which did not fit into the invocation message, are matched using the reply
+    /// subject of the handshake message sent by the target in response to the invocation.
+    #[must_use]
+    pub fn invocations(&self) -> Vec<CapturedInvocation> {
+        let mut streams: HashMap<&str, ChunkedStream> = HashMap::new();
+        let mut handshakes: HashMap<&str, &str> = HashMap::new();
+        for msg in &self.messages {
+            if msg.subject.ends_with(".results") || msg.subject.ends_with(".params") {
+                streams
+                    .entry(msg.subject.as_str())
+                    .or_default()
+                    .push(&msg.payload);
+            } else if parse_invocation_subject(&msg.subject).is_none() {
+                if let Some(reply) = &msg.reply {
+                    handshakes.insert(msg.subject.as_str(), reply.as_str());
+                }
+            }
+        }
+        self.messages
+            .iter()
+            .filter_map(|msg| {
+                let (lattice, target_id, instance, function) =
+                    parse_invocation_subject(&msg.subject)?;
+                let reply = msg.reply.as_deref();
+                let results = reply.and_then(|reply| {
+                    streams
+                        .get(format!("{reply}.results").as_str())
+                        .map(|stream| stream.buf.clone().freeze())
+                });
+                let mut params = BytesMut::from(msg.payload.as_ref());
+                if let Some(stream) = reply
+                    .and_then(|reply| handshakes.get(reply))
+                    .and_then(|target| streams.get(format!("{target}.params").as_str()))
+                {
+                    params.extend_from_slice(&stream.buf);
+                }
+                Some(CapturedInvocation {
+                    lattice: lattice.to_string(),
+                    target_id: target_id.to_string(),
+                    source_id: msg
+                        .headers
+                        .as_ref()
+                        .and_then(|headers| headers.get("source-id"))
+                        .map(ToString::to_string),
+                    instance: instance.to_string(),
+                    function: function.to_string(),
+                    headers: msg.headers.clone(),
+                    params: params.freeze(),
+                    results,
+                    published: msg.published,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Chunks of a captured wRPC byte stream, which ends with an empty chunk
+#[derive(Default)]
+struct ChunkedStream {
+    buf: BytesMut,
+    ended: bool,
+}
+
+impl ChunkedStream {
+    fn push(&mut self, chunk: &[u8]) {
+        if self.ended {
+            return;
+        }
+        if chunk.is_empty() {
+            self.ended = true;
+        } else {
+            self.buf.extend_from_slice(chunk);
+        }
+    }
 }
 
 pub struct WriteCapture {
@@ -169,6 +293,90 @@ impl WriteCapture {
 mod test {
     use super::*;
 
+    #[test]
+    fn test_parse_invocation_subject() {
+        assert_eq!(
+            parse_invocation_subject(
+                "default.http_component.wrpc.0.0.1.wasi:http/incoming-handler@0.2.0.handle"
+            ),
+            Some((
+                "default",
+                "http_component",
+                "wasi:http/incoming-handler@0.2.0",
+                "handle"
+            ))
+        );
+        assert_eq!(
+            parse_invocation_subject("default.component.wrpc.0.0.1.wasi:keyvalue/store.get"),
+            Some(("default", "component", "wasi:keyvalue/store", "get"))
+        );
+        assert_eq!(
+            parse_invocation_subject("_INBOX.abc.def.results"),
+            None,
+            "Should not parse result subjects"
+        );
+        assert_eq!(
+            parse_invocation_subject("default.component.wrpc.0.0.1.handle"),
+            None,
+            "Should require an instance"
+        );
+    }
+
+    #[test]
+    fn test_invocations() {
+        let message =
+            |subject: &str, reply: Option<&str>, payload: &'static str| SerializableMessage {
+                subject: subject.to_string(),
+                reply: reply.map(String::from),
+                payload: bytes::Bytes::from(payload),
+                description: None,
+                length: payload.len(),
+                published: time::OffsetDateTime::now_utc(),
+                headers: None,
+            };
+        let capture = ReadCapture {
+            inventory: Vec::new(),
+            messages: vec![
+                message(
+                    "default.component.wrpc.0.0.1.test:pkg/iface@0.1.0.first",
+                    Some("_INBOX.a.b"),
+                    "params",
+                ),
+                message("_INBOX.a.b", Some("_INBOX.c.d"), ""),
+                message("_INBOX.c.d.params", None, "-and"),
+                message("_INBOX.c.d.params", None, "-more"),
+                message("_INBOX.c.d.params", None, ""),
+                message("_INBOX.c.d.params", None, "-after-end"),
+                message("_INBOX.a.b.results", None, "res"),
+                message("_INBOX.a.b.results", None, "ults"),
+                message("_INBOX.a.b.results", None, ""),
+                message(
+                    "default.component.wrpc.0.0.1.test:pkg/iface@0.1.0.second",
+                    Some("_INBOX.e.f"),
+                    "",
+                ),
+                message("_INBOX.a.b.results", None, "-ignored"),
+            ],
+        };
+        let invocations = capture.invocations();
+        assert_eq!(invocations.len(), 2, "Should only contain invocations");
+        assert_eq!(invocations[0].function, "first");
+        assert_eq!(
+            invocations[0].params, "params-and-more",
+            "Should concatenate parameter chunks until the end of the stream"
+        );
+        assert_eq!(
+            invocations[0].results.as_deref(),
+            Some(b"results".as_slice()),
+            "Should concatenate result chunks"
+        );
+        assert_eq!(invocations[1].instance, "test:pkg/iface@0.1.0");
+        assert_eq!(
+            invocations[1].results, None,
+            "Should not have results if they were not captured"
+        );
+    }
+
     #[tokio::test]
     async fn test_roundtrip() {
         let tempdir = tempfile::tempdir().unwrap();
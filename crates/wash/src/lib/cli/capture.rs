@@ -1,20 +1,23 @@
+use std::collections::HashMap;
 use std::{path::PathBuf, time::Duration};
 
-use anyhow::Result;
+use anyhow::{bail, Context as _, Result};
 use async_nats::jetstream::{
     consumer::{pull::Config as ConsumerConfig, AckPolicy, DeliverPolicy},
     stream::Config,
 };
+use bytes::Bytes;
 use clap::{Parser, Subcommand};
 use futures::TryStreamExt;
+use serde_json::json;
 use tokio::io::{stdin, stdout, AsyncReadExt, AsyncWriteExt};
 use tokio::time::Instant;
-use tracing::debug;
+use wrpc_transport::Invoke as _;
 
-use super::{CliConnectionOpts, CommandOutput};
-use crate::lib::capture::{ReadCapture, WriteCapture};
+use super::{CliConnectionOpts, CommandOutput, OutputKind};
+use crate::lib::capture::{CapturedInvocation, ReadCapture, WriteCapture};
 use crate::lib::config::WashConnectionOptions;
-use crate::lib::spier::{ObservedInvocation, ObservedMessage};
+use crate::lib::spier::{FunctionTypes, ObservedMessage};
 
 pub const CAPTURE_STREAM_NAME: &str = "wash-capture";
 
@@ -62,67 +65,90 @@ pub struct CaptureReplayCommand {
     #[clap(name = "interactive", long = "interactive")]
     pub interactive: bool,
 
+    /// Re-send the captured invocations to the lattice and compare the responses with the recorded
+    /// ones, failing if any of them differ
+    #[clap(name = "invoke", long = "invoke", conflicts_with = "interactive")]
+    pub invoke: bool,
+
+    /// Send invocations captured for a component to a different component instead, in the form
+    /// `<CAPTURED_ID>=<NEW_ID>`. May be specified multiple times
+    #[clap(
+        name = "retarget",
+        long = "retarget",
+        requires = "invoke",
+        value_parser = parse_retarget
+    )]
+    pub retarget: Vec<(String, String)>,
+
+    /// A file path or OCI reference of a component or provider archive, whose WIT is used to
+    /// decode the captured parameters and results
+    #[clap(name = "wit", long = "wit")]
+    pub wit: Option<String>,
+
+    #[clap(flatten)]
+    pub opts: CliConnectionOpts,
+
     /// The file path to the capture file to read from
     #[clap(name = "capturefile")]
     pub capture_file_path: PathBuf,
 }
 
-pub async fn handle_replay_command(cmd: CaptureReplayCommand) -> Result<CommandOutput> {
-    let capture = ReadCapture::load(cmd.capture_file_path).await?;
-
-    let filtered = capture.messages.into_iter().filter_map(|msg| {
-        // lattice.component.wrpc.0.0.1.operation.function
-        let subject_parts = msg.subject.split('.').collect::<Vec<_>>();
-        let component_id = subject_parts.get(1);
-        let operation = subject_parts.get(6);
-        let function = subject_parts.get(7);
-
-        if component_id.is_none() || operation.is_none() || function.is_none() {
-            debug!("Received invocation with invalid subject: {}", msg.subject);
-            return None;
+fn parse_retarget(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((from, to)) if !from.is_empty() && !to.is_empty() => {
+            Ok((from.to_string(), to.to_string()))
         }
-        let target = component_id.unwrap();
-        let operation = format!("{}.{}", operation.unwrap(), function.unwrap());
-
-        let source = msg
-            .headers
-            .and_then(|headers| {
-                headers
-                    .get("source-id")
-                    .map(std::string::ToString::to_string)
-            })
-            .unwrap_or_default();
-
-        let from = match cmd.source_id {
-            Some(ref id) if id == source.as_str() => id.to_string(),
-            Some(_) => {
-                return None;
-            }
-            None => source,
-        };
+        _ => Err(format!(
+            "invalid retarget `{s}`, expected `<CAPTURED_ID>=<NEW_ID>`"
+        )),
+    }
+}
 
-        let to = match cmd.target_id {
-            Some(ref id) if id == target => id.to_string(),
-            Some(_) => {
-                return None;
-            }
-            None => (*target).to_string(),
-        };
-
-        Some((
-            ObservedInvocation {
-                timestamp: chrono::Local::now(),
-                from,
-                to,
-                operation,
-                message: ObservedMessage::parse(msg.payload.to_vec()),
-            },
-            msg.published,
-        ))
+pub async fn handle_replay_command(
+    cmd: CaptureReplayCommand,
+    output_kind: OutputKind,
+) -> Result<CommandOutput> {
+    let capture = ReadCapture::load(&cmd.capture_file_path).await?;
+    let types = match &cmd.wit {
+        Some(wit) => Some(FunctionTypes::from_image_ref(wit).await?),
+        None => None,
+    };
+
+    let (source_id, target_id) = (cmd.source_id.clone(), cmd.target_id.clone());
+    let invocations = capture.invocations().into_iter().filter(move |inv| {
+        source_id
+            .as_ref()
+            .is_none_or(|id| inv.source_id.as_ref() == Some(id))
+            && target_id.as_ref().is_none_or(|id| inv.target_id == *id)
     });
+    if cmd.invoke {
+        return replay_invocations(cmd, types.as_ref(), invocations).await;
+    }
 
     let mut out = stdout();
-    for (msg, published) in filtered {
+    for inv in invocations {
+        let operation = format!("{}.{}", inv.instance, inv.function);
+        let message =
+            ObservedMessage::decode_params(types.as_ref(), &operation, inv.params.to_vec());
+        let response = inv.results.map(|results| {
+            ObservedMessage::decode_results(types.as_ref(), &operation, results.to_vec())
+        });
+        if output_kind == OutputKind::Json {
+            println!(
+                "{}",
+                json!({
+                    "published": inv
+                        .published
+                        .format(&time::format_description::well_known::Rfc3339)?,
+                    "from": inv.source_id,
+                    "to": inv.target_id,
+                    "operation": operation,
+                    "message": message.to_json(),
+                    "response": response.as_ref().map(ObservedMessage::to_json),
+                })
+            );
+            continue;
+        }
         println!(
             r"
 [{}]
@@ -130,8 +156,15 @@ From: {}  To: {}
 
 Operation: {}
 Message: {}",
-            published, msg.from, msg.to, msg.operation, msg.message
+            inv.published,
+            inv.source_id.unwrap_or_default(),
+            inv.target_id,
+            operation,
+            message
         );
+        if let Some(response) = response {
+            println!("Response: {response}");
+        }
         if cmd.interactive {
             out.write_all(b"Press Enter to continue...").await.unwrap();
             out.flush().await.unwrap();
@@ -141,6 +174,105 @@ Message: {}",
     Ok(CommandOutput::default())
 }
 
+/// Send a captured invocation to the target and return the encoded results
+async fn invoke(
+    client: &wrpc_transport_nats::Client,
+    timeout: Duration,
+    inv: CapturedInvocation,
+) -> Result<Bytes> {
+    tokio::time::timeout(timeout, async {
+        let (mut params, mut results) = client
+            .invoke(
+                inv.headers,
+                &inv.instance,
+                &inv.function,
+                inv.params,
+                Vec::<Box<[Option<usize>]>>::default(),
+            )
+            .await
+            .context("failed to invoke target")?;
+        params
+            .shutdown()
+            .await
+            .context("failed to finish sending parameters")?;
+        let mut buf = Vec::new();
+        results
+            .read_to_end(&mut buf)
+            .await
+            .context("failed to receive results")?;
+        Ok(Bytes::from(buf))
+    })
+    .await
+    .context("invocation timed out")?
+}
+
+/// Re-send captured invocations to the lattice and compare the responses with recorded ones
+async fn replay_invocations(
+    cmd: CaptureReplayCommand,
+    types: Option<&FunctionTypes>,
+    invocations: impl IntoIterator<Item = CapturedInvocation>,
+) -> Result<CommandOutput> {
+    let wco: WashConnectionOptions = cmd.opts.try_into()?;
+    let lattice = wco.lattice.clone().unwrap_or_else(|| "default".to_string());
+    let timeout = Duration::from_millis(wco.timeout_ms);
+    let nats_client = wco.into_nats_client().await?;
+    let retarget: HashMap<_, _> = cmd.retarget.into_iter().collect();
+
+    let (mut total, mut matched, mut mismatched, mut unrecorded, mut failed) = (0, 0, 0, 0, 0);
+    for inv in invocations {
+        total += 1;
+        let target_id = retarget
+            .get(&inv.target_id)
+            .unwrap_or(&inv.target_id)
+            .clone();
+        let operation = format!("{}.{}", inv.instance, inv.function);
+        let recorded = inv.results.clone();
+        let client = wrpc_transport_nats::Client::new(
+            nats_client.clone(),
+            format!("{lattice}.{target_id}"),
+            None,
+        )
+        .await
+        .context("failed to construct wRPC client")?;
+        let res = invoke(&client, timeout, inv).await;
+        match (res, recorded) {
+            (Err(err), _) => {
+                failed += 1;
+                println!("FAILED    {target_id} {operation}: {err:#}");
+            }
+            (Ok(_), None) => {
+                unrecorded += 1;
+                println!("UNKNOWN   {target_id} {operation}: no response was recorded");
+            }
+            (Ok(results), Some(recorded)) if results == recorded => {
+                matched += 1;
+                println!("MATCH     {target_id} {operation}");
+            }
+            (Ok(results), Some(recorded)) => {
+                mismatched += 1;
+                println!(
+                    "MISMATCH  {target_id} {operation}\n  Recorded: {}\n  Replayed: {}",
+                    ObservedMessage::decode_results(types, &operation, recorded.to_vec()),
+                    ObservedMessage::decode_results(types, &operation, results.to_vec())
+                );
+            }
+        }
+    }
+
+    if mismatched > 0 || failed > 0 {
+        bail!("{mismatched} of {total} replayed invocations returned different responses and {failed} failed");
+    }
+    Ok(CommandOutput::new(
+        format!("Replayed {total} invocations, {matched} matched the recorded responses and {unrecorded} had no recorded response"),
+        [
+            ("total".to_string(), json!(total)),
+            ("matched".to_string(), json!(matched)),
+            ("unrecorded".to_string(), json!(unrecorded)),
+        ]
+        .into(),
+    ))
+}
+
 /// Handles the spy command, printing all output to stdout until the command is interrupted
 pub async fn handle_command(cmd: CaptureCommand) -> Result<CommandOutput> {
     let wco: WashConnectionOptions = cmd.opts.try_into()?;
@@ -184,13 +316,23 @@ pub async fn enable(
             format!("Capture is already enabled for lattice {lattice_id}"),
         ));
     }
+    let inbox_prefix = wasmcloud_core::rpc_inbox_prefix(lattice_id);
     ctx.create_stream(Config {
         name: stream_name(lattice_id),
         storage: async_nats::jetstream::stream::StorageType::File,
         max_age: window_size,
         // This needs to be set or it breaks invocations
         no_ack: true,
-        subjects: vec![format!("wasmbus.rpc.{}.>", lattice_id)],
+        subjects: vec![
+            format!("wasmbus.rpc.{}.>", lattice_id),
+            // wRPC invocations
+            format!("{lattice_id}.*.{}.>", wrpc_transport_nats::PROTOCOL),
+            // wRPC invocation handshakes, parameters and results, which are sent to the inboxes of
+            // the invoker and of the target
+            format!("{inbox_prefix}.*.*"),
+            format!("{inbox_prefix}.*.*.params"),
+            format!("{inbox_prefix}.*.*.results"),
+        ],
         ..Default::default()
     })
     .await
@@ -0,0 +1,79 @@
+use anyhow::{bail, Result};
+use clap::Parser;
+use std::collections::HashMap;
+
+use crate::lib::{
+    cli::{CliConnectionOpts, CommandOutput},
+    common::{boxed_err_to_anyhow, find_host_id},
+    config::WashConnectionOptions,
+};
+
+#[derive(Debug, Clone, Parser)]
+pub struct DrainHostCommand {
+    #[clap(flatten)]
+    pub opts: CliConnectionOpts,
+
+    /// Id of host to drain. If a non-ID is provided, the host will be selected based on matching the
+    /// prefix of the ID or the friendly name and will return an error if more than one host
+    /// matches.
+    #[clap(name = "host-id")]
+    pub host_id: String,
+
+    /// The timeout in ms to give in-flight invocations on the host to finish before its components
+    /// and providers are stopped. By default, the host waits until all invocations have finished
+    #[clap(long = "drain-timeout")]
+    pub drain_timeout: Option<u64>,
+
+    /// Stop the host once all of its components and providers have been stopped
+    #[clap(long = "stop-host")]
+    pub stop_host: bool,
+
+    /// Only cordon the host, so that it no longer responds to auctions, but keeps running its
+    /// components and providers
+    #[clap(
+        long = "cordon-only",
+        conflicts_with_all = ["drain_timeout", "stop_host", "uncordon"]
+    )]
+    pub cordon_only: bool,
+
+    /// Uncordon a cordoned or drained host, so that it responds to auctions again
+    #[clap(long = "uncordon", conflicts_with_all = ["drain_timeout", "stop_host"])]
+    pub uncordon: bool,
+}
+
+pub async fn drain_host(cmd: DrainHostCommand) -> Result<CommandOutput> {
+    let wco: WashConnectionOptions = cmd.opts.try_into()?;
+    let client = wco.into_ctl_client(None).await?;
+
+    let host_id = find_host_id(&cmd.host_id, &client).await?.0;
+    let (ack, text) = if cmd.uncordon {
+        (
+            client.uncordon_host(&host_id).await,
+            format!("Host {host_id} uncordoned"),
+        )
+    } else if cmd.cordon_only {
+        (
+            client.cordon_host(&host_id).await,
+            format!("Host {host_id} cordoned"),
+        )
+    } else {
+        (
+            client
+                .drain_host(&host_id, cmd.drain_timeout, cmd.stop_host)
+                .await,
+            format!("Host {host_id} acknowledged drain request"),
+        )
+    };
+    let ack = ack.map_err(boxed_err_to_anyhow)?;
+    if !ack.succeeded() {
+        bail!("Operation failed: {}", ack.message());
+    }
+
+    Ok(CommandOutput::new(
+        text.clone(),
+        HashMap::from([
+            ("result".into(), text.into()),
+            ("host_id".into(), host_id.into_string().into()),
+        ]),
+    ))
+}
@@ -1,15 +1,16 @@
 use std::str::FromStr;
 
 use crate::lib::{
-    common::{boxed_err_to_anyhow, get_all_inventories},
+    common::{boxed_err_to_anyhow, find_host_id, get_all_inventories},
     config::WashConnectionOptions,
     id::ServerId,
 };
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use clap::Parser;
-use wasmcloud_control_interface::{Host, HostInventory};
+use wasmcloud_control_interface::{Client as CtlClient, Host, HostInventory, ProviderLogs};
 
-use super::CliConnectionOpts;
+use super::stop::find_host_with_provider;
+use super::{validate_component_id, CliConnectionOpts};
 
 #[derive(Debug, Clone, Parser)]
 pub struct GetClaimsCommand {
@@ -43,6 +44,30 @@ pub struct GetHostsCommand {
     pub opts: CliConnectionOpts,
 }
 
+#[derive(Debug, Clone, Parser)]
+pub struct GetProviderLogsCommand {
+    #[clap(flatten)]
+    pub opts: CliConnectionOpts,
+
+    /// Id of the host the provider is running on. If a non-ID is provided, the host will be selected
+    /// based on matching the prefix of the ID or the friendly name. If no host ID is passed, the host
+    /// running the provider will be selected
+    #[clap(long = "host-id")]
+    pub host_id: Option<String>,
+
+    /// Provider Id (e.g. the public key for the provider)
+    #[clap(name = "provider-id", value_parser = validate_component_id)]
+    pub provider_id: String,
+
+    /// Maximum number of most recent lines to retrieve
+    #[clap(long = "lines", short = 'n')]
+    pub lines: Option<usize>,
+
+    /// Keep retrieving new lines as the provider writes them, until interrupted
+    #[clap(long = "follow", short = 'f')]
+    pub follow: bool,
+}
+
 #[derive(Debug, Clone, Parser)]
 pub enum GetCommand {
     /// Retrieve all known links in the lattice
@@ -60,6 +85,10 @@ pub enum GetCommand {
     /// Retrieve inventory a given host on in the lattice
     #[clap(name = "inventory", alias = "inventories")]
     HostInventories(GetHostInventoriesCommand),
+
+    /// Retrieve the most recent output of a provider
+    #[clap(name = "logs")]
+    ProviderLogs(GetProviderLogsCommand),
 }
 
 /// Retrieve host inventory
@@ -108,6 +137,38 @@ pub async fn get_hosts(cmd: GetHostsCommand) -> Result<Vec<Host>> {
         .context("Was able to connect to NATS, but failed to get hosts.")
 }
 
+/// Resolve the ID of the host running the provider targeted by a provider logs command
+pub async fn find_provider_logs_host(
+    cmd: &GetProviderLogsCommand,
+    client: &CtlClient,
+) -> Result<ServerId> {
+    let host_id = if let Some(host_id) = cmd.host_id.as_deref() {
+        find_host_id(host_id, client).await?.0
+    } else {
+        find_host_with_provider(&cmd.provider_id, client).await?
+    };
+    Ok(host_id)
+}
+
+/// Retrieve the output of a provider written after the line with sequence number `since`
+pub async fn get_provider_logs(
+    client: &CtlClient,
+    host_id: &ServerId,
+    provider_id: &str,
+    since: Option<u64>,
+    max_lines: Option<usize>,
+) -> Result<ProviderLogs> {
+    let res = client
+        .get_provider_logs(host_id, provider_id, since, max_lines)
+        .await
+        .map_err(boxed_err_to_anyhow)
+        .context("Was able to connect to NATS, but failed to get provider logs.")?;
+    if !res.succeeded() {
+        bail!("Operation failed: {}", res.message());
+    }
+    Ok(res.into_data().unwrap_or_default())
+}
+
 pub fn parse_watch_interval(arg: &str) -> Result<std::time::Duration, String> {
     if let Ok(duration) = humantime::Duration::from_str(arg) {
         return Ok(duration.into());
@@ -328,6 +328,9 @@ pub(crate) async fn render_provider_claims(
     if let Some(schema) = artifact.schema() {
         map.insert("schema".to_string(), json!(schema));
     }
+    if let Some(schema) = artifact.provider_config_schema() {
+        map.insert("provider_config_schema".to_string(), json!(schema));
+    }
 
     let text_table = {
         let mut table = Table::new();
@@ -388,6 +391,20 @@ pub(crate) async fn render_provider_claims(
             )]));
         }
 
+        if artifact.provider_config_schema().is_some() {
+            table.add_row(Row::new(vec![TableCell::new_with_alignment(
+                "\nProvider Configuration Schema",
+                2,
+                Alignment::Center,
+            )]));
+
+            table.add_row(Row::new(vec![TableCell::new_with_alignment(
+                "\nUse the JSON output option to extract the schema",
+                2,
+                Alignment::Left,
+            )]));
+        }
+
         table.render()
     };
 
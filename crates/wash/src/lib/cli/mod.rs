@@ -39,6 +39,7 @@ use crate::lib::{
 pub mod capture;
 pub mod claims;
 pub mod dev;
+pub mod drain;
 pub mod get;
 pub mod inspect;
 pub mod label;
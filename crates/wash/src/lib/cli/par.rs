@@ -13,6 +13,7 @@ pub struct ParCreateArgs {
     pub revision: Option<i32>,
     pub version: Option<String>,
     pub schema: Option<PathBuf>,
+    pub provider_config_schema: Option<PathBuf>,
     pub name: String,
     pub arch: String,
 }
@@ -23,6 +24,7 @@ pub fn create_provider_archive(
         revision,
         version,
         schema,
+        provider_config_schema,
         name,
         arch,
     }: ParCreateArgs,
@@ -47,6 +49,16 @@ pub fn create_provider_archive(
         .with_context(|| format!("Error parsing JSON schema from file '{schema:?}'"))?;
     }
 
+    if let Some(ref schema) = provider_config_schema {
+        let bytes = std::fs::read(schema)?;
+        par.set_provider_config_schema(
+            serde_json::from_slice::<serde_json::Value>(&bytes)
+                .with_context(|| "Unable to parse JSON from file contents".to_string())?,
+        )
+        .map_err(convert_error)
+        .with_context(|| format!("Error parsing JSON schema from file '{schema:?}'"))?;
+    }
+
     Ok(par)
 }
 
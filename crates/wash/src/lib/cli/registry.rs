@@ -103,4 +103,9 @@ pub struct RegistryPushCommand {
     /// Push the artifact monolithically instead of chunked
     #[clap(long = "monolithic-push", env = "WASH_MONOLITHIC_PUSH")]
     pub monolithic_push: bool,
+
+    /// Write the artifact to a local OCI image layout at this path instead of pushing it to a
+    /// registry, tagged with the tag of the URL. Paths ending in `.tar` are written as a tarball
+    #[clap(long = "to-layout")]
+    pub to_layout: Option<PathBuf>,
 }
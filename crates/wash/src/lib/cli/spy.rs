@@ -2,7 +2,7 @@ use anyhow::Result;
 use clap::Parser;
 use futures::StreamExt;
 
-use super::{validate_component_id, CliConnectionOpts, CommandOutput};
+use super::{validate_component_id, CliConnectionOpts, CommandOutput, OutputKind};
 use crate::lib::{config::WashConnectionOptions, spier::Spier};
 
 #[derive(Debug, Parser, Clone)]
@@ -16,15 +16,27 @@ pub struct SpyCommand {
     pub opts: CliConnectionOpts,
 }
 
-/// Handles the spy command, printing all output to stdout until the command is interrupted
-pub async fn handle_command(cmd: SpyCommand) -> Result<CommandOutput> {
+/// Handles the spy command, printing all output to stdout until the command is interrupted.
+///
+/// With JSON output, every observed message is printed as a JSON object on a single line
+pub async fn handle_command(cmd: SpyCommand, output_kind: OutputKind) -> Result<CommandOutput> {
     let wco: WashConnectionOptions = cmd.opts.try_into()?;
     let ctl_client = wco.clone().into_ctl_client(None).await?;
     let nats_client = wco.into_nats_client().await?;
 
     let mut spier = Spier::new(&cmd.component_id, &ctl_client, &nats_client).await?;
 
+    if output_kind == OutputKind::Json {
+        while let Some(msg) = spier.next().await {
+            println!("{}", msg.to_json());
+        }
+        return Ok(CommandOutput::default());
+    }
+
     println!("Spying on component {}\n", spier.component_id());
+    if !spier.decodes_invocations() {
+        println!("Unable to resolve the WIT of the component, messages will not be decoded\n");
+    }
 
     while let Some(msg) = spier.next().await {
         println!(
@@ -32,9 +44,9 @@ pub async fn handle_command(cmd: SpyCommand) -> Result<CommandOutput> {
 [{}]
 From: {:<25} To: {:<25}
 
-Operation: {}
+Operation: {} ({})
 Message: {}",
-            msg.timestamp, msg.from, msg.to, msg.operation, msg.message
+            msg.timestamp, msg.from, msg.to, msg.operation, msg.kind, msg.message
         );
     }
 
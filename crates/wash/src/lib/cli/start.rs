@@ -3,6 +3,7 @@ use std::collections::{BTreeMap, HashMap};
 use anyhow::{bail, Context, Result};
 use clap::Parser;
 use tokio::time::Duration;
+use wasmcloud_control_interface::{ComponentAuctionRequest, ProviderAuctionRequest};
 
 use crate::lib::cli::{input_vec_to_hashmap, CliConnectionOpts, CommandOutput};
 use crate::lib::common::{boxed_err_to_anyhow, find_host_id};
@@ -61,6 +62,16 @@ pub struct StartComponentCommand {
     #[clap(short = 'c', long = "constraint", name = "constraints")]
     pub constraints: Option<Vec<String>>,
 
+    /// Minimum amount of memory, in bytes, that must be available on a host for it to bid in the
+    /// component auction. If host-id is supplied, this is ignored
+    #[clap(long = "min-available-memory")]
+    pub min_available_memory: Option<u64>,
+
+    /// Exclude hosts already running a component with the same reference from the component
+    /// auction. If host-id is supplied, this is ignored
+    #[clap(long = "anti-affinity")]
+    pub anti_affinity: bool,
+
     /// Timeout to await an auction response, defaults to 2000 milliseconds
     #[clap(long = "auction-timeout-ms", default_value_t = default_timeout_ms())]
     pub auction_timeout_ms: u64,
@@ -127,12 +138,18 @@ pub async fn handle_start_component(cmd: StartComponentCommand) -> Result<Comman
     let host = if let Some(host) = cmd.host_id {
         find_host_id(&host, &client).await?.0
     } else {
+        let mut request = ComponentAuctionRequest::builder()
+            .component_ref(component_ref.clone())
+            .component_id(cmd.component_id.clone())
+            .constraints(BTreeMap::from_iter(input_vec_to_hashmap(
+                cmd.constraints.unwrap_or_default(),
+            )?))
+            .anti_affinity(cmd.anti_affinity);
+        if let Some(min_available_memory) = cmd.min_available_memory {
+            request = request.min_available_memory(min_available_memory);
+        }
         let suitable_hosts = client
-            .perform_component_auction(
-                &component_ref,
-                &cmd.component_id,
-                BTreeMap::from_iter(input_vec_to_hashmap(cmd.constraints.unwrap_or_default())?),
-            )
+            .perform_component_auction_request(request.build().map_err(boxed_err_to_anyhow)?)
             .await
             .map_err(boxed_err_to_anyhow)
             .with_context(|| {
@@ -218,6 +235,16 @@ pub struct StartProviderCommand {
     #[clap(short = 'c', long = "constraint", name = "constraints")]
     pub constraints: Option<Vec<String>>,
 
+    /// Minimum amount of memory, in bytes, that must be available on a host for it to bid in the
+    /// provider auction. If host-id is supplied, this is ignored
+    #[clap(long = "min-available-memory")]
+    pub min_available_memory: Option<u64>,
+
+    /// Exclude hosts already running a provider with the same reference from the provider auction.
+    /// If host-id is supplied, this is ignored
+    #[clap(long = "anti-affinity")]
+    pub anti_affinity: bool,
+
     /// Timeout to await an auction response, defaults to 2000 milliseconds
     #[clap(long = "auction-timeout-ms", default_value_t = default_timeout_ms())]
     pub auction_timeout_ms: u64,
@@ -250,12 +277,18 @@ pub async fn handle_start_provider(cmd: StartProviderCommand) -> Result<CommandO
     let host = if let Some(host) = cmd.host_id {
         find_host_id(&host, &client).await?.0
     } else {
+        let mut request = ProviderAuctionRequest::builder()
+            .provider_ref(provider_ref.clone())
+            .provider_id(cmd.link_name.clone())
+            .constraints(BTreeMap::from_iter(input_vec_to_hashmap(
+                cmd.constraints.unwrap_or_default(),
+            )?))
+            .anti_affinity(cmd.anti_affinity);
+        if let Some(min_available_memory) = cmd.min_available_memory {
+            request = request.min_available_memory(min_available_memory);
+        }
         let suitable_hosts = client
-            .perform_provider_auction(
-                &provider_ref,
-                &cmd.link_name,
-                BTreeMap::from_iter(input_vec_to_hashmap(cmd.constraints.unwrap_or_default())?),
-            )
+            .perform_provider_auction_request(request.build().map_err(boxed_err_to_anyhow)?)
             .await
             .map_err(boxed_err_to_anyhow)
             .with_context(|| {
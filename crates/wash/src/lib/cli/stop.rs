@@ -269,7 +269,7 @@ pub async fn stop_host(cmd: StopHostCommand) -> Result<CommandOutput> {
     ))
 }
 
-async fn find_host_with_provider(
+pub(crate) async fn find_host_with_provider(
     provider_id: &str,
     ctl_client: &wasmcloud_control_interface::Client,
 ) -> Result<ServerId, FindIdError> {
@@ -17,6 +17,7 @@ use provider_archive::ProviderArchive;
 use sha2::Digest;
 use tokio::fs::File;
 use tokio::io::AsyncReadExt;
+use wasmcloud_core::oci_layout::{write_oci_layout, LayoutBlob};
 use wasmcloud_core::tls;
 
 const PROVIDER_ARCHIVE_MEDIA_TYPE: &str = "application/vnd.wasmcloud.provider.archive.layer.v1+par";
@@ -193,33 +194,7 @@ pub async fn push_oci_artifact(
         bail!("Pushing artifacts with tag 'latest' is prohibited");
     };
 
-    let mut artifact_buf = vec![];
-    let mut f = File::open(&artifact)
-        .await
-        .with_context(|| format!("failed to open artifact [{}]", artifact.as_ref().display()))?;
-    f.read_to_end(&mut artifact_buf).await?;
-
-    let (config, layer, is_wasm) = match parse_and_validate_artifact(&artifact_buf).await? {
-        SupportedArtifacts::Wasm(conf, layer) => (conf, layer, true),
-        SupportedArtifacts::Par(mut conf, layer) => {
-            let mut config_buf = vec![];
-            match options.config {
-                Some(config_file) => {
-                    let mut f = File::open(&config_file).await.with_context(|| {
-                        format!("failed to open config file [{}]", config_file.display())
-                    })?;
-                    f.read_to_end(&mut config_buf).await?;
-                }
-                None => {
-                    // If no config provided, send blank config
-                    config_buf = b"{}".to_vec();
-                }
-            };
-            conf.data = config_buf;
-            (conf, layer, false)
-        }
-    };
-
+    let (config, layer, is_wasm) = load_artifact(artifact, options.config).await?;
     let layers = vec![layer];
 
     let client = Client::new(ClientConfig {
@@ -262,6 +237,74 @@ pub async fn push_oci_artifact(
     Ok((image.tag().map(ToString::to_string), digest))
 }
 
+/// Write an artifact to a local OCI image layout under the given tag, returning the manifest
+/// digest.
+///
+/// If `layout` ends in `.tar` the layout is written as a tarball, otherwise it is written to (or
+/// merged into) a layout directory. The resulting layout can be loaded by hosts with
+/// `oci-layout://<layout>:<tag>` references.
+pub async fn write_oci_artifact_layout(
+    layout: impl AsRef<Path>,
+    tag: &str,
+    artifact: impl AsRef<Path>,
+    options: OciPushOptions,
+) -> Result<String> {
+    if tag == "latest" && !options.allow_latest {
+        bail!("Pushing artifacts with tag 'latest' is prohibited");
+    };
+
+    let (config, layer, is_wasm) = load_artifact(artifact, options.config).await?;
+    write_oci_layout(
+        layout,
+        tag,
+        is_wasm.then_some(WASM_MANIFEST_MEDIA_TYPE),
+        LayoutBlob {
+            media_type: config.media_type,
+            data: config.data,
+        },
+        vec![LayoutBlob {
+            media_type: layer.media_type,
+            data: layer.data,
+        }],
+        options.annotations.unwrap_or_default(),
+    )
+    .await
+}
+
+/// Read the artifact at the given path and parse it into a config and layer, returning whether
+/// the artifact is a component
+async fn load_artifact(
+    artifact: impl AsRef<Path>,
+    config: Option<PathBuf>,
+) -> Result<(Config, ImageLayer, bool)> {
+    let mut artifact_buf = vec![];
+    let mut f = File::open(&artifact)
+        .await
+        .with_context(|| format!("failed to open artifact [{}]", artifact.as_ref().display()))?;
+    f.read_to_end(&mut artifact_buf).await?;
+
+    match parse_and_validate_artifact(&artifact_buf).await? {
+        SupportedArtifacts::Wasm(conf, layer) => Ok((conf, layer, true)),
+        SupportedArtifacts::Par(mut conf, layer) => {
+            let mut config_buf = vec![];
+            match config {
+                Some(config_file) => {
+                    let mut f = File::open(&config_file).await.with_context(|| {
+                        format!("failed to open config file [{}]", config_file.display())
+                    })?;
+                    f.read_to_end(&mut config_buf).await?;
+                }
+                None => {
+                    // If no config provided, send blank config
+                    config_buf = b"{}".to_vec();
+                }
+            };
+            conf.data = config_buf;
+            Ok((conf, layer, false))
+        }
+    }
+}
+
 /// Helper function to determine artifact type and parse it into a config and layer ready for use in
 /// pushing to OCI
 pub async fn parse_and_validate_artifact(artifact: &[u8]) -> Result<SupportedArtifacts> {
@@ -0,0 +1,410 @@
+//! Decoding of wRPC-encoded invocation parameters and results into JSON values using the WIT
+//! types of the invoked functions
+
+use std::collections::HashMap;
+
+use anyhow::{bail, ensure, Context as _, Result};
+use provider_archive::ProviderArchive;
+use serde_json::{json, Map, Value};
+use wit_parser::{Function, Handle, Resolve, Results, Type, TypeDefKind};
+
+use crate::lib::registry::{get_oci_artifact, OciPullOptions};
+
+/// The functions of all interfaces known to a WIT [`Resolve`], which can be used to decode
+/// wRPC-encoded parameters and results of invocations of these functions
+pub struct FunctionTypes {
+    resolve: Resolve,
+    /// Functions keyed by the `<instance>.<function>` operation used in wRPC subjects
+    functions: HashMap<String, Function>,
+}
+
+impl FunctionTypes {
+    /// Index the functions of all named interfaces in `resolve`
+    #[must_use]
+    pub fn new(resolve: Resolve) -> Self {
+        let mut functions = HashMap::new();
+        for (id, interface) in &resolve.interfaces {
+            let Some(instance) = resolve.id_of(id) else {
+                continue;
+            };
+            for (name, func) in &interface.functions {
+                functions.insert(format!("{instance}.{name}"), func.clone());
+            }
+        }
+        Self { resolve, functions }
+    }
+
+    /// Load the WIT of a Wasm component, an encoded WIT package or a provider archive
+    pub async fn from_artifact(artifact: &[u8]) -> Result<Self> {
+        let decoded = if wasmparser::Parser::is_component(artifact) {
+            wit_component::decode(artifact).context("failed to decode component WIT")?
+        } else {
+            let par = ProviderArchive::try_load(artifact)
+                .await
+                .map_err(|e| anyhow::anyhow!("failed to load provider archive: {e}"))?;
+            let wit = par
+                .wit_world()
+                .context("provider archive does not contain a WIT world")?;
+            wit_component::decode(wit).context("failed to decode provider WIT")?
+        };
+        Ok(Self::new(decoded.resolve().clone()))
+    }
+
+    /// Load the WIT of the component or provider at `image_ref`, which may be a local file or an
+    /// OCI reference
+    pub async fn from_image_ref(image_ref: &str) -> Result<Self> {
+        let image_ref = image_ref.strip_prefix("file://").unwrap_or(image_ref);
+        let artifact = get_oci_artifact(
+            image_ref.to_string(),
+            None,
+            OciPullOptions {
+                allow_latest: true,
+                ..Default::default()
+            },
+        )
+        .await
+        .with_context(|| format!("failed to fetch `{image_ref}`"))?;
+        Self::from_artifact(&artifact).await
+    }
+
+    /// Returns `true` if the types of `operation` are known
+    #[must_use]
+    pub fn contains(&self, operation: &str) -> bool {
+        self.functions.contains_key(operation)
+    }
+
+    /// Decode the parameters of an invocation of `operation` into a JSON object keyed by
+    /// parameter name
+    pub fn decode_params(&self, operation: &str, mut payload: &[u8]) -> Result<Value> {
+        let func = self.function(operation)?;
+        let mut params = Map::with_capacity(func.params.len());
+        for (name, ty) in &func.params {
+            let v = self
+                .decode(ty, &mut payload)
+                .with_context(|| format!("failed to decode parameter `{name}`"))?;
+            params.insert(name.clone(), v);
+        }
+        ensure!(payload.is_empty(), "trailing bytes after parameters");
+        Ok(Value::Object(params))
+    }
+
+    /// Decode the results of an invocation of `operation`. A single unnamed result is decoded
+    /// as-is, named results into a JSON object keyed by result name
+    pub fn decode_results(&self, operation: &str, mut payload: &[u8]) -> Result<Value> {
+        let results = match &self.function(operation)?.results {
+            Results::Anon(ty) => self
+                .decode(ty, &mut payload)
+                .context("failed to decode result")?,
+            Results::Named(named) => {
+                let mut results = Map::with_capacity(named.len());
+                for (name, ty) in named {
+                    let v = self
+                        .decode(ty, &mut payload)
+                        .with_context(|| format!("failed to decode result `{name}`"))?;
+                    results.insert(name.clone(), v);
+                }
+                Value::Object(results)
+            }
+        };
+        ensure!(payload.is_empty(), "trailing bytes after results");
+        Ok(results)
+    }
+
+    fn function(&self, operation: &str) -> Result<&Function> {
+        self.functions
+            .get(operation)
+            .with_context(|| format!("unknown operation `{operation}`"))
+    }
+
+    fn decode(&self, ty: &Type, buf: &mut &[u8]) -> Result<Value> {
+        let v = match ty {
+            Type::Bool => match read_u8(buf)? {
+                0 => json!(false),
+                1 => json!(true),
+                b => bail!("invalid bool value `{b}`"),
+            },
+            Type::U8 => json!(read_u8(buf)?),
+            Type::S8 => json!(read_u8(buf)? as i8),
+            Type::U16 => json!(read_uleb(buf, 16)?),
+            Type::U32 => json!(read_uleb(buf, 32)?),
+            Type::U64 => json!(read_uleb(buf, 64)?),
+            Type::S16 => json!(read_sleb(buf, 16)?),
+            Type::S32 => json!(read_sleb(buf, 32)?),
+            Type::S64 => json!(read_sleb(buf, 64)?),
+            Type::F32 => json!(f32::from_le_bytes(read_array(buf)?)),
+            Type::F64 => json!(f64::from_le_bytes(read_array(buf)?)),
+            Type::Char => {
+                let n = match buf.first() {
+                    Some(b) if b & 0x80 == 0 => 1,
+                    Some(b) if b & 0xe0 == 0xc0 => 2,
+                    Some(b) if b & 0xf0 == 0xe0 => 3,
+                    _ => 4,
+                };
+                json!(std::str::from_utf8(read_bytes(buf, n)?).context("invalid char")?)
+            }
+            Type::String => json!(read_string(buf)?),
+            Type::Id(id) => return self.decode_kind(&self.resolve.types[*id].kind, buf),
+        };
+        Ok(v)
+    }
+
+    fn decode_kind(&self, kind: &TypeDefKind, buf: &mut &[u8]) -> Result<Value> {
+        let v = match kind {
+            TypeDefKind::Type(ty) => return self.decode(ty, buf),
+            TypeDefKind::Record(record) => {
+                let mut fields = Map::with_capacity(record.fields.len());
+                for field in &record.fields {
+                    fields.insert(field.name.clone(), self.decode(&field.ty, buf)?);
+                }
+                Value::Object(fields)
+            }
+            TypeDefKind::Tuple(tuple) => Value::Array(
+                tuple
+                    .types
+                    .iter()
+                    .map(|ty| self.decode(ty, buf))
+                    .collect::<Result<_>>()?,
+            ),
+            TypeDefKind::List(ty) => {
+                let n = read_len(buf)?;
+                Value::Array(
+                    (0..n)
+                        .map(|_| self.decode(ty, buf))
+                        .collect::<Result<_>>()?,
+                )
+            }
+            TypeDefKind::Option(ty) => match read_u8(buf)? {
+                0 => Value::Null,
+                1 => self.decode(ty, buf)?,
+                b => bail!("invalid option discriminant `{b}`"),
+            },
+            TypeDefKind::Result(result) => {
+                let (case, ty) = match read_u8(buf)? {
+                    0 => ("ok", result.ok),
+                    1 => ("err", result.err),
+                    b => bail!("invalid result discriminant `{b}`"),
+                };
+                let v = ty
+                    .map(|ty| self.decode(&ty, buf))
+                    .transpose()?
+                    .unwrap_or(Value::Null);
+                json!({ case: v })
+            }
+            TypeDefKind::Enum(enum_) => {
+                let disc = read_uleb(buf, 32)?;
+                let case = usize::try_from(disc)
+                    .ok()
+                    .and_then(|i| enum_.cases.get(i))
+                    .with_context(|| format!("invalid enum discriminant `{disc}`"))?;
+                json!(case.name)
+            }
+            TypeDefKind::Variant(variant) => {
+                let disc = read_uleb(buf, 32)?;
+                let case = usize::try_from(disc)
+                    .ok()
+                    .and_then(|i| variant.cases.get(i))
+                    .with_context(|| format!("invalid variant discriminant `{disc}`"))?;
+                match &case.ty {
+                    Some(ty) => json!({ case.name.clone(): self.decode(ty, buf)? }),
+                    None => json!(case.name),
+                }
+            }
+            TypeDefKind::Flags(flags) => {
+                let bits = read_bytes(buf, flags.flags.len().div_ceil(8))?;
+                Value::Array(
+                    flags
+                        .flags
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| bits[i / 8] & (1 << (i % 8)) != 0)
+                        .map(|(_, flag)| json!(flag.name))
+                        .collect(),
+                )
+            }
+            // Resource handles are opaque byte strings
+            TypeDefKind::Handle(Handle::Own(..) | Handle::Borrow(..)) => {
+                let n = read_len(buf)?;
+                json!(hex::encode(read_bytes(buf, n)?))
+            }
+            TypeDefKind::Future(..) | TypeDefKind::Stream(..) => {
+                bail!("decoding async values is not supported")
+            }
+            kind => bail!("unsupported type `{}`", kind.as_str()),
+        };
+        Ok(v)
+    }
+}
+
+fn read_bytes<'a>(buf: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+    ensure!(buf.len() >= n, "unexpected end of payload");
+    let (bytes, rest) = buf.split_at(n);
+    *buf = rest;
+    Ok(bytes)
+}
+
+fn read_array<const N: usize>(buf: &mut &[u8]) -> Result<[u8; N]> {
+    Ok(read_bytes(buf, N)?.try_into()?)
+}
+
+fn read_u8(buf: &mut &[u8]) -> Result<u8> {
+    Ok(read_bytes(buf, 1)?[0])
+}
+
+/// Read an unsigned LEB128-encoded integer of at most `bits` bits
+fn read_uleb(buf: &mut &[u8], bits: u32) -> Result<u64> {
+    let mut v = 0u64;
+    let mut shift = 0;
+    loop {
+        ensure!(shift < bits, "LEB128 integer too large");
+        let b = read_u8(buf)?;
+        v |= u64::from(b & 0x7f) << shift;
+        shift += 7;
+        if b & 0x80 == 0 {
+            return Ok(v);
+        }
+    }
+}
+
+/// Read a signed LEB128-encoded integer of at most `bits` bits
+fn read_sleb(buf: &mut &[u8], bits: u32) -> Result<i64> {
+    let mut v = 0i64;
+    let mut shift = 0;
+    loop {
+        ensure!(shift < bits, "LEB128 integer too large");
+        let b = read_u8(buf)?;
+        v |= i64::from(b & 0x7f) << shift;
+        shift += 7;
+        if b & 0x80 == 0 {
+            if shift < 64 && b & 0x40 != 0 {
+                v |= -1 << shift;
+            }
+            return Ok(v);
+        }
+    }
+}
+
+fn read_len(buf: &mut &[u8]) -> Result<usize> {
+    Ok(usize::try_from(read_uleb(buf, 32)?)?)
+}
+
+fn read_string(buf: &mut &[u8]) -> Result<String> {
+    let n = read_len(buf)?;
+    let s = std::str::from_utf8(read_bytes(buf, n)?).context("invalid string")?;
+    Ok(s.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const WIT: &str = r#"
+package test:spy@0.1.0;
+
+interface store {
+    enum kind { file, directory }
+
+    flags access { read, write, exec }
+
+    record entry {
+        name: string,
+        size: u64,
+        kind: kind,
+        access: access,
+    }
+
+    variant error {
+        not-found,
+        other(string),
+    }
+
+    resource bucket {
+        get: func(key: string) -> result<option<list<u8>>, error>;
+    }
+
+    list-entries: func(prefix: option<string>, limit: s32) -> result<list<entry>, error>;
+    open: func(name: string) -> bucket;
+    stat: func(c: char) -> (a: f32, b: tuple<bool, s8>);
+}
+"#;
+
+    fn types() -> FunctionTypes {
+        let mut resolve = Resolve::new();
+        resolve
+            .push_str("test.wit", WIT)
+            .expect("failed to parse WIT");
+        FunctionTypes::new(resolve)
+    }
+
+    #[test]
+    fn test_decode_params() {
+        let types = types();
+        assert!(types.contains("test:spy/store@0.1.0.list-entries"));
+        assert!(types.contains("test:spy/store@0.1.0.[method]bucket.get"));
+        assert!(!types.contains("test:spy/store@0.1.0.unknown"));
+
+        assert_eq!(
+            types
+                .decode_params(
+                    "test:spy/store@0.1.0.list-entries",
+                    // some("dir"), -65
+                    &[1, 3, b'd', b'i', b'r', 0xbf, 0x7f]
+                )
+                .expect("failed to decode params"),
+            json!({ "prefix": "dir", "limit": -65 })
+        );
+        assert_eq!(
+            types
+                .decode_params(
+                    "test:spy/store@0.1.0.[method]bucket.get",
+                    &[2, 0xab, 0xcd, 1, b'k']
+                )
+                .expect("failed to decode params"),
+            json!({ "self": "abcd", "key": "k" })
+        );
+        assert!(types
+            .decode_params("test:spy/store@0.1.0.list-entries", &[1, 3, b'd'])
+            .is_err());
+        assert!(types
+            .decode_params("test:spy/store@0.1.0.list-entries", &[0, 1, 0])
+            .is_err());
+    }
+
+    #[test]
+    fn test_decode_results() {
+        let types = types();
+        assert_eq!(
+            types
+                .decode_results(
+                    "test:spy/store@0.1.0.list-entries",
+                    // ok([{ name: "a", size: 300, kind: directory, access: read | exec }])
+                    &[0, 1, 1, b'a', 0xac, 0x02, 1, 0b101]
+                )
+                .expect("failed to decode results"),
+            json!({ "ok": [{ "name": "a", "size": 300, "kind": "directory", "access": ["read", "exec"] }] })
+        );
+        assert_eq!(
+            types
+                .decode_results("test:spy/store@0.1.0.list-entries", &[1, 1, 2, b'n', b'o'])
+                .expect("failed to decode results"),
+            json!({ "err": { "other": "no" } })
+        );
+        assert_eq!(
+            types
+                .decode_results("test:spy/store@0.1.0.[method]bucket.get", &[0, 1, 2, 1, 2])
+                .expect("failed to decode results"),
+            json!({ "ok": [1, 2] })
+        );
+        assert_eq!(
+            types
+                .decode_results("test:spy/store@0.1.0.stat", &[0, 0, 0xc0, 0x3f, 1, 0xff])
+                .expect("failed to decode results"),
+            json!({ "a": 1.5, "b": [true, -1] })
+        );
+        assert_eq!(
+            types
+                .decode_params("test:spy/store@0.1.0.stat", "é".as_bytes())
+                .expect("failed to decode params"),
+            json!({ "c": "é" })
+        );
+    }
+}
@@ -0,0 +1,484 @@
+use std::collections::HashMap;
+use std::task::Poll;
+
+use anyhow::{Context as _, Result};
+use chrono::{DateTime, Local};
+use futures::{Stream, StreamExt};
+use serde::Serialize;
+use serde_json::json;
+use tracing::{debug, warn};
+
+use crate::lib::common::get_all_inventories;
+
+mod decode;
+
+pub use decode::FunctionTypes;
+
+/// A struct that represents an invocation that was observed by the spier.
+#[derive(Debug)]
+pub struct ObservedInvocation {
+    /// The timestamp when this was received
+    pub timestamp: DateTime<Local>,
+    /// The name or id of the entity that sent this invocation
+    pub from: String,
+    /// The name or id of the entity that received this invocation
+    pub to: String,
+    /// The operation that was invoked
+    pub operation: String,
+    /// Whether this is the invocation itself or the response to it
+    pub kind: ObservedKind,
+    /// The inner message that was received. We will attempt to decode the parameters or results
+    /// using the WIT of the invoked function and fall back to the raw bytes if we are unable to do so
+    pub message: ObservedMessage,
+}
+
+impl ObservedInvocation {
+    /// Renders this invocation as a JSON object, suitable for line-delimited output
+    #[must_use]
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "timestamp": self.timestamp.to_rfc3339(),
+            "from": self.from,
+            "to": self.to,
+            "operation": self.operation,
+            "kind": self.kind,
+            "message": self.message.to_json(),
+        })
+    }
+}
+
+/// The kind of message observed for an invocation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ObservedKind {
+    /// An invocation sent to the target, carrying the parameters
+    Invocation,
+    /// A response sent back by the target, carrying the results
+    Response,
+}
+
+impl std::fmt::Display for ObservedKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invocation => write!(f, "invocation"),
+            Self::Response => write!(f, "response"),
+        }
+    }
+}
+
+/// A inner message that we've seen in an invocation message. This will either be a raw bytes or a
+/// parsed value if it was a format we recognized.
+///
+/// Please note that this struct is meant for debugging, so its `Display` implementation does some
+/// heavier lifting like constructing strings from the raw bytes.
+#[derive(Debug)]
+pub enum ObservedMessage {
+    Raw(Vec<u8>),
+    Parsed(String),
+    Decoded(serde_json::Value),
+}
+
+impl std::fmt::Display for ObservedMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Raw(bytes) => write!(f, "{}", String::from_utf8_lossy(bytes)),
+            Self::Parsed(v) => {
+                write!(f, "{v}")
+            }
+            Self::Decoded(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl ObservedMessage {
+    #[must_use]
+    pub fn parse(data: Vec<u8>) -> Self {
+        Self::Parsed(String::from_utf8_lossy(&data).to_string())
+    }
+
+    /// Decodes the parameters of an invocation of `operation` using `types`, falling back to
+    /// [`ObservedMessage::parse`] if the types are unknown or decoding fails
+    #[must_use]
+    pub fn decode_params(types: Option<&FunctionTypes>, operation: &str, data: Vec<u8>) -> Self {
+        match types.map(|types| types.decode_params(operation, &data)) {
+            Some(Ok(v)) => Self::Decoded(v),
+            Some(Err(err)) => {
+                debug!(?err, operation, "failed to decode parameters");
+                Self::parse(data)
+            }
+            None => Self::parse(data),
+        }
+    }
+
+    /// Decodes the results of an invocation of `operation` using `types`, falling back to
+    /// [`ObservedMessage::parse`] if the types are unknown or decoding fails
+    #[must_use]
+    pub fn decode_results(types: Option<&FunctionTypes>, operation: &str, data: Vec<u8>) -> Self {
+        match types.map(|types| types.decode_results(operation, &data)) {
+            Some(Ok(v)) => Self::Decoded(v),
+            Some(Err(err)) => {
+                debug!(?err, operation, "failed to decode results");
+                Self::parse(data)
+            }
+            None => Self::parse(data),
+        }
+    }
+
+    /// Renders this message as a JSON value. Messages that could not be decoded are rendered as
+    /// strings
+    #[must_use]
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Raw(bytes) => String::from_utf8_lossy(bytes).into(),
+            Self::Parsed(v) => v.as_str().into(),
+            Self::Decoded(v) => v.clone(),
+        }
+    }
+}
+
+/// An invocation that is waiting for its results to be received
+struct PendingResponse {
+    from: String,
+    to: String,
+    operation: String,
+    results: Vec<u8>,
+}
+
+/// A struct that can spy on the RPC messages sent to and from an component, consumable as a stream
+pub struct Spier {
+    stream: futures::stream::SelectAll<async_nats::Subscriber>,
+    component_id: String,
+    friendly_name: Option<String>,
+    types: Option<FunctionTypes>,
+    /// Invocations awaiting results, keyed by the subject the results are sent to
+    pending: HashMap<String, PendingResponse>,
+}
+
+impl Spier {
+    /// Creates a new Spier instance for the given component. Will return an error if the component cannot
+    /// be found or if there are connection issues
+    pub async fn new(
+        component_id: &str,
+        ctl_client: &wasmcloud_control_interface::Client,
+        nats_client: &async_nats::Client,
+    ) -> Result<Self> {
+        let linked_component = get_linked_components(component_id, ctl_client).await?;
+
+        let lattice = ctl_client.lattice();
+        let rpc_topic = format!("{lattice}.{component_id}.wrpc.>");
+        let component_stream = nats_client.subscribe(rpc_topic).await?;
+
+        let mut subs = futures::future::join_all(linked_component.iter().map(|prov| {
+            let topic = format!("{lattice}.{}.wrpc.>", &prov.id);
+            nats_client.subscribe(topic)
+        }))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()?;
+        subs.push(component_stream);
+        subs.push(nats_client.subscribe(results_subject(lattice)).await?);
+
+        let stream = futures::stream::select_all(subs);
+
+        let types = match get_function_types(component_id, ctl_client).await {
+            Ok(types) => Some(types),
+            Err(err) => {
+                warn!(
+                    ?err,
+                    "unable to resolve WIT of component, invocations will not be decoded"
+                );
+                None
+            }
+        };
+
+        Ok(Self {
+            stream,
+            component_id: component_id.to_string(),
+            friendly_name: None,
+            types,
+            pending: HashMap::new(),
+        })
+    }
+
+    /// Returns `true` if the WIT of the component was resolved and invocations can be decoded
+    pub fn decodes_invocations(&self) -> bool {
+        self.types.is_some()
+    }
+
+    /// Returns the component name, or id if no name is set, that this spier is spying on
+    pub fn component_id(&self) -> &str {
+        self.friendly_name
+            .as_deref()
+            .unwrap_or_else(|| self.component_id.as_ref())
+    }
+}
+
+impl Stream for Spier {
+    type Item = ObservedInvocation;
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        loop {
+            let msg = match self.stream.poll_next_unpin(cx) {
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Ready(Some(msg)) => msg,
+                Poll::Pending => return Poll::Pending,
+            };
+            if let Some(invocation) = self.observe(msg) {
+                return Poll::Ready(Some(invocation));
+            }
+        }
+    }
+}
+
+impl Spier {
+    /// Handles a received invocation or result message, returning the observed invocation, once
+    /// it is complete
+    fn observe(&mut self, msg: async_nats::Message) -> Option<ObservedInvocation> {
+        if msg.subject.ends_with(".results") {
+            // Results of invocations we did not observe are skipped
+            let pending = self.pending.get_mut(msg.subject.as_str())?;
+            // Results may be split across multiple messages, an empty message marks the end
+            if !msg.payload.is_empty() {
+                pending.results.extend_from_slice(&msg.payload);
+                return None;
+            }
+            let PendingResponse {
+                from,
+                to,
+                operation,
+                results,
+            } = self
+                .pending
+                .remove(msg.subject.as_str())
+                .expect("pending response missing");
+            let message = ObservedMessage::decode_results(self.types.as_ref(), &operation, results);
+            return Some(ObservedInvocation {
+                timestamp: Local::now(),
+                from,
+                to,
+                operation,
+                kind: ObservedKind::Response,
+                message,
+            });
+        }
+
+        // <lattice>.<component>.wrpc.0.0.1.<operation>@<versionX.Y.Z>.<function>
+        let mut subject_parts = msg.subject.split('.');
+        subject_parts.next(); // Skip the lattice
+        let component_id = subject_parts.next();
+        // Skip "wrpc.0.0.1", collect the rest
+        let operation = subject_parts.skip(4).collect::<Vec<_>>();
+
+        // The length assertion is to ensure that at least the `operation.function` is present since the
+        // version is technically optional.
+        if component_id.is_none() || operation.len() < 2 {
+            debug!("Received invocation with invalid subject: {}", msg.subject);
+            return None;
+        }
+        let component_id = component_id.unwrap();
+
+        let (from, to) = if component_id == self.component_id {
+            // Attempt to get the source from the message header
+            let from = msg
+                .headers
+                .as_ref()
+                .and_then(|headers| headers.get("source-id").map(ToString::to_string))
+                .unwrap_or_else(|| "linked component".to_string());
+            (from, (*component_id).to_string())
+        } else {
+            (self.component_id.to_string(), (*component_id).to_string())
+        };
+        let operation = operation.join(".");
+
+        if let Some(reply) = &msg.reply {
+            self.pending.insert(
+                format!("{reply}.results"),
+                PendingResponse {
+                    from: to.clone(),
+                    to: from.clone(),
+                    operation: operation.clone(),
+                    results: Vec::new(),
+                },
+            );
+        }
+
+        // NOTE(thomastaylor312): Ideally we'd consume `msg.payload` above with a
+        // `Cursor` and `from_reader` and then manually reconstruct the acking using the
+        // message context, but I didn't want to waste time optimizing yet
+        let message =
+            ObservedMessage::decode_params(self.types.as_ref(), &operation, msg.payload.to_vec());
+        Some(ObservedInvocation {
+            timestamp: Local::now(),
+            from,
+            to,
+            operation,
+            kind: ObservedKind::Invocation,
+            message,
+        })
+    }
+}
+
+/// Subject of the wRPC results sent to invokers within `lattice`.
+///
+/// Invokers receive results on `<inbox>.<invocation>.results`, where `<inbox>` is the inbox of the
+/// invoker's NATS client below [`wasmcloud_core::rpc_inbox_prefix`]
+fn results_subject(lattice: &str) -> String {
+    format!("{}.*.*.results", wasmcloud_core::rpc_inbox_prefix(lattice))
+}
+
+/// Resolves the WIT of the component or provider with the given ID from its image
+async fn get_function_types(
+    component_id: &str,
+    ctl_client: &wasmcloud_control_interface::Client,
+) -> Result<FunctionTypes> {
+    let image_ref = get_all_inventories(ctl_client)
+        .await?
+        .iter()
+        .find_map(|inventory| {
+            if let Some(component) = inventory
+                .components()
+                .iter()
+                .find(|component| component.id() == component_id)
+            {
+                return Some(component.image_ref().to_string());
+            }
+            inventory
+                .providers()
+                .iter()
+                .find(|provider| provider.id() == component_id)
+                .and_then(|provider| provider.image_ref().map(ToString::to_string))
+        })
+        .with_context(|| format!("unable to find image of `{component_id}`"))?;
+    FunctionTypes::from_image_ref(&image_ref).await
+}
+
+#[derive(Debug)]
+struct ProviderDetails {
+    id: String,
+}
+
+/// Fetches all components linked to the given component
+async fn get_linked_components(
+    component_id: &str,
+    ctl_client: &wasmcloud_control_interface::Client,
+) -> Result<Vec<ProviderDetails>> {
+    let details = ctl_client
+        .get_links()
+        .await
+        .map_err(|e| anyhow::anyhow!("Unable to get links: {e:?}"))
+        .map(wasmcloud_control_interface::CtlResponse::into_data)?
+        .map(|linkdefs| {
+            linkdefs
+                .into_iter()
+                .filter_map(|link| {
+                    if link.source_id() == component_id {
+                        Some(ProviderDetails {
+                            id: link.target().to_string(),
+                        })
+                    } else if link.target() == component_id {
+                        Some(ProviderDetails {
+                            id: link.source_id().to_string(),
+                        })
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    Ok(details)
+}
+
+#[cfg(test)]
+mod test {
+    use wit_parser::Resolve;
+
+    use super::*;
+
+    fn message(subject: &str, reply: Option<&str>, payload: &'static [u8]) -> async_nats::Message {
+        async_nats::Message {
+            subject: subject.into(),
+            reply: reply.map(Into::into),
+            payload: payload.into(),
+            headers: None,
+            status: None,
+            description: None,
+            length: payload.len(),
+        }
+    }
+
+    /// Checks whether `subject` matches the NATS subject `pattern`, which may only contain `*`
+    /// wildcards
+    fn matches(pattern: &str, subject: &str) -> bool {
+        let mut subject = subject.split('.');
+        pattern
+            .split('.')
+            .all(|token| match (token, subject.next()) {
+                ("*", Some(_)) => true,
+                (token, Some(part)) => token == part,
+                (_, None) => false,
+            })
+            && subject.next().is_none()
+    }
+
+    #[test]
+    fn test_decode_results_subject() {
+        let mut resolve = Resolve::new();
+        resolve
+            .push_str(
+                "test.wit",
+                r#"
+package test:spy@0.1.0;
+
+interface counter {
+    increment: func(by: u32) -> result<u64, string>;
+}
+"#,
+            )
+            .expect("failed to parse WIT");
+        let mut spier = Spier {
+            stream: futures::stream::SelectAll::new(),
+            component_id: "component".to_string(),
+            friendly_name: None,
+            types: Some(FunctionTypes::new(resolve)),
+            pending: HashMap::new(),
+        };
+
+        // Subjects as used by `wrpc-transport-nats` with the default NATS inbox prefix
+        let inbox = "_INBOX.u5zZVbKvAHnKqRYFFMp3Ha.Pq5tr5kbZ7fq3LAb8jQwI1";
+        let results = format!("{inbox}.results");
+        assert!(matches(&results_subject("default"), &results));
+        assert!(!matches(
+            &results_subject("default"),
+            &format!("{inbox}.params")
+        ));
+
+        let invocation = spier
+            .observe(message(
+                "default.component.wrpc.0.0.1.test:spy/counter@0.1.0.increment",
+                Some(inbox),
+                &[5],
+            ))
+            .expect("invocation should have been observed");
+        assert_eq!(invocation.kind, ObservedKind::Invocation);
+        assert_eq!(invocation.to, "component");
+        assert_eq!(invocation.message.to_json(), json!({ "by": 5 }));
+
+        // ok(300), split across messages
+        assert!(spier.observe(message(&results, None, &[0])).is_none());
+        assert!(spier
+            .observe(message(&results, None, &[0xac, 0x02]))
+            .is_none());
+        let response = spier
+            .observe(message(&results, None, &[]))
+            .expect("response should have been observed");
+        assert_eq!(response.kind, ObservedKind::Response);
+        assert_eq!(response.from, "component");
+        assert_eq!(response.operation, "test:spy/counter@0.1.0.increment");
+        assert_eq!(response.message.to_json(), json!({ "ok": 300 }));
+        assert!(spier.pending.is_empty());
+    }
+}
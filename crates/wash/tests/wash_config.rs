@@ -34,6 +34,7 @@ async fn test_config_put_and_get() -> anyhow::Result<()> {
                     ..Default::default()
                 },
                 name: "foobar".to_string(),
+                revision: None,
             },
             OutputKind::Json,
         )
@@ -47,6 +48,94 @@ async fn test_config_put_and_get() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_config_history_and_rollback() -> anyhow::Result<()> {
+    let wash_instance = TestWashInstance::create().await?;
+    let opts = CliConnectionOpts {
+        ctl_port: Some(wash_instance.nats_port.to_string()),
+        ..Default::default()
+    };
+
+    // Put two revisions of the config
+    for value in ["first", "second"] {
+        wash::cli::cmd::config::handle_command(
+            ConfigCliCommand::PutCommand {
+                opts: opts.clone(),
+                name: "versioned".to_string(),
+                config_values: vec![format!("key={value}")],
+            },
+            OutputKind::Json,
+        )
+        .await?;
+    }
+
+    let history = wash::cli::cmd::config::handle_command(
+        ConfigCliCommand::HistoryCommand {
+            opts: opts.clone(),
+            name: "versioned".to_string(),
+        },
+        OutputKind::Json,
+    )
+    .await?
+    .map;
+    let revisions = history
+        .get("revisions")
+        .and_then(serde_json::Value::as_array)
+        .expect("history should contain revisions");
+    assert_eq!(revisions.len(), 2);
+    let first_revision = revisions[0]
+        .get("revision")
+        .and_then(serde_json::Value::as_u64)
+        .expect("revision should be a number");
+
+    // The first revision is still retrievable
+    let first_config = wash::cli::cmd::config::handle_command(
+        ConfigCliCommand::GetCommand {
+            opts: opts.clone(),
+            name: "versioned".to_string(),
+            revision: Some(first_revision),
+        },
+        OutputKind::Json,
+    )
+    .await?
+    .map;
+    assert_eq!(first_config.get("key").unwrap(), "first");
+
+    // Roll back to the first revision, which is stored as a new revision
+    let rollback = wash::cli::cmd::config::handle_command(
+        ConfigCliCommand::RollbackCommand {
+            opts: opts.clone(),
+            name: "versioned".to_string(),
+            revision: first_revision,
+        },
+        OutputKind::Json,
+    )
+    .await?
+    .map;
+    assert_eq!(rollback.get("success").unwrap(), true);
+    assert!(
+        rollback
+            .get("revision")
+            .and_then(serde_json::Value::as_u64)
+            .expect("rollback should return the new revision")
+            > first_revision
+    );
+
+    let config = wash::cli::cmd::config::handle_command(
+        ConfigCliCommand::GetCommand {
+            opts,
+            name: "versioned".to_string(),
+            revision: None,
+        },
+        OutputKind::Json,
+    )
+    .await?
+    .map;
+    assert_eq!(config.get("key").unwrap(), "first");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_config_secret_name_error() -> anyhow::Result<()> {
     // Attempt to create a config with a secret name
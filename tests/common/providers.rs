@@ -18,12 +18,31 @@ pub struct Provider {
 
 impl Provider {
     pub async fn new(name: &str, bin: &str) -> anyhow::Result<Self> {
+        Self::with_schemas(name, bin, None, None).await
+    }
+
+    /// Builds a PAR declaring the JSON schemas of the link configuration and the configuration the
+    /// provider is started with
+    pub async fn with_schemas(
+        name: &str,
+        bin: &str,
+        schema: Option<serde_json::Value>,
+        provider_config_schema: Option<serde_json::Value>,
+    ) -> anyhow::Result<Self> {
         let mut par = ProviderArchive::new(name, "test", None, None);
         let bin = fs::read(bin)
             .await
             .with_context(|| format!("failed to read binary at `{bin}`"))?;
         par.add_library(&format!("{ARCH}-{OS}"), &bin)
             .map_err(|e| anyhow!(e).context("failed to add  binary to PAR"))?;
+        if let Some(schema) = schema {
+            par.set_schema(schema)
+                .map_err(|e| anyhow!(e).context("failed to set schema"))?;
+        }
+        if let Some(schema) = provider_config_schema {
+            par.set_provider_config_schema(schema)
+                .map_err(|e| anyhow!(e).context("failed to set provider config schema"))?;
+        }
         let subject = KeyPair::new_service();
         let tmp = NamedTempFile::new().context("failed to create temporary file")?;
         par.write(tmp.path(), &ISSUER, &subject, false)
@@ -179,12 +179,27 @@ pub async fn register_spiffe_workload(
     workload_selector: &str,
     spire_server_socket: &Path,
 ) -> anyhow::Result<()> {
-    let output = Command::new(
-        env::var("TEST_SPIRE_SERVER_BIN")
-            .as_deref()
-            .unwrap_or("spire-server"),
+    register_spiffe_workload_with_x509_svid_ttl(
+        parent_id,
+        spiffe_id,
+        workload_selector,
+        None,
+        spire_server_socket,
     )
-    .args([
+    .await
+}
+
+// Same as `register_spiffe_workload`, but issues X.509-SVIDs with the given TTL to the
+// workload, e.g. to have them rotated quickly
+pub async fn register_spiffe_workload_with_x509_svid_ttl(
+    parent_id: &str,
+    spiffe_id: &str,
+    workload_selector: &str,
+    x509_svid_ttl: Option<Duration>,
+    spire_server_socket: &Path,
+) -> anyhow::Result<()> {
+    let x509_svid_ttl = x509_svid_ttl.map(|ttl| ttl.as_secs().to_string());
+    let mut args = vec![
         "entry",
         "create",
         "-parentID",
@@ -193,9 +208,18 @@ pub async fn register_spiffe_workload(
         spiffe_id,
         "-selector",
         workload_selector,
-        "-socketPath",
-        &spire_server_socket.display().to_string(),
-    ])
+    ];
+    if let Some(ttl) = &x509_svid_ttl {
+        args.extend(["-x509SVIDTTL", ttl]);
+    }
+    let socket_path = spire_server_socket.display().to_string();
+    args.extend(["-socketPath", &socket_path]);
+    let output = Command::new(
+        env::var("TEST_SPIRE_SERVER_BIN")
+            .as_deref()
+            .unwrap_or("spire-server"),
+    )
+    .args(args)
     .output()
     .await
     .context("failed to register create SPIFFE entry for workload")?;
@@ -0,0 +1,134 @@
+#![cfg(feature = "wasmcloud")]
+
+use core::time::Duration;
+
+use anyhow::{anyhow, ensure, Context as _};
+use wasmcloud_test_util::{component::assert_scale_component, host::WasmCloudTestHost};
+
+pub mod common;
+use common::nats::start_nats;
+
+use test_components::{RUST_HTTP_HELLO_WORLD, RUST_INTERFACES_REACTOR};
+
+const LATTICE: &str = "component-spec";
+const COMPONENT_ID: &str = "test-component";
+
+/// Returns the digest of [`COMPONENT_ID`] running on the host, if it is running
+async fn running_digest(
+    ctl_client: &wasmcloud_control_interface::Client,
+    host_id: &str,
+) -> anyhow::Result<Option<String>> {
+    let inventory = ctl_client
+        .get_host_inventory(host_id)
+        .await
+        .map_err(|e| anyhow!(e).context("failed to get host inventory"))?
+        .into_data()
+        .context("host inventory missing")?;
+    Ok(inventory
+        .components()
+        .iter()
+        .find(|component| component.id() == COMPONENT_ID)
+        .map(|component| component.digest().map(ToString::to_string))
+        .unwrap_or_default())
+}
+
+/// Ensure that the digest recorded for a component ID:
+///
+/// - prevents starting different bytes under the ID on another host
+/// - no longer applies once the component was scaled to zero
+#[tokio::test(flavor = "multi_thread")]
+async fn component_digest_scale_to_zero() -> anyhow::Result<()> {
+    let (nats_server, nats_url, nats_client) = start_nats(None, true)
+        .await
+        .map(|res| (res.0, res.1, res.2.unwrap()))
+        .context("failed to start NATS")?;
+
+    let ctl_client = wasmcloud_control_interface::ClientBuilder::new(nats_client)
+        .lattice(LATTICE.to_string())
+        .build();
+
+    let host_a = WasmCloudTestHost::start(&nats_url, LATTICE)
+        .await
+        .context("failed to start first test host")?;
+    let host_a_id = host_a.host_key().public_key();
+    let host_b = WasmCloudTestHost::start(&nats_url, LATTICE)
+        .await
+        .context("failed to start second test host")?;
+    let host_b_id = host_b.host_key().public_key();
+
+    let reactor_ref = format!("file://{RUST_INTERFACES_REACTOR}");
+    let hello_ref = format!("file://{RUST_HTTP_HELLO_WORLD}");
+
+    assert_scale_component(
+        &ctl_client,
+        &host_a_id,
+        &reactor_ref,
+        COMPONENT_ID,
+        None,
+        1,
+        Vec::new(),
+        Duration::from_secs(10),
+    )
+    .await
+    .context("failed to scale component")?;
+    let reactor_digest = running_digest(&ctl_client, &host_a_id)
+        .await?
+        .context("component digest missing")?;
+
+    // Different bytes are refused under the same ID while the component is running
+    assert!(
+        assert_scale_component(
+            &ctl_client,
+            &host_b_id,
+            &hello_ref,
+            COMPONENT_ID,
+            None,
+            1,
+            Vec::new(),
+            Duration::from_secs(5),
+        )
+        .await
+        .is_err(),
+        "starting a different digest should fail"
+    );
+    assert_eq!(running_digest(&ctl_client, &host_b_id).await?, None);
+
+    // Scale to zero, after which the digest is no longer enforced. The scaled event is only
+    // published once the component specification has been updated.
+    let mut receiver = ctl_client
+        .events_receiver(vec!["component_scaled".into()])
+        .await
+        .map_err(|e| anyhow!(e).context("failed to subscribe to events"))?;
+    let resp = ctl_client
+        .scale_component(&host_a_id, &reactor_ref, COMPONENT_ID, 0, None, Vec::new())
+        .await
+        .map_err(|e| anyhow!(e).context("failed to scale component to zero"))?;
+    ensure!(resp.succeeded());
+    tokio::time::timeout(Duration::from_secs(10), receiver.recv())
+        .await
+        .context("timed out waiting for component scale event")?
+        .context("failed to get event")?;
+    assert_eq!(running_digest(&ctl_client, &host_a_id).await?, None);
+
+    assert_scale_component(
+        &ctl_client,
+        &host_b_id,
+        &hello_ref,
+        COMPONENT_ID,
+        None,
+        1,
+        Vec::new(),
+        Duration::from_secs(10),
+    )
+    .await
+    .context("failed to scale component with new digest after scaling to zero")?;
+    let hello_digest = running_digest(&ctl_client, &host_b_id)
+        .await?
+        .context("component digest missing")?;
+    assert_ne!(reactor_digest, hello_digest);
+
+    host_b.stop().await.context("failed to stop second host")?;
+    host_a.stop().await.context("failed to stop first host")?;
+    nats_server.stop().await.context("failed to stop NATS")?;
+    Ok(())
+}
@@ -0,0 +1,163 @@
+#![cfg(feature = "wasmcloud")]
+
+use core::time::Duration;
+
+use std::net::Ipv4Addr;
+
+use anyhow::{anyhow, ensure, Context as _};
+use serde_json::json;
+use wasmcloud_test_util::host::WasmCloudTestHost;
+use wasmcloud_test_util::lattice::config::assert_config_put;
+use wasmcloud_test_util::lattice::link::assert_advertise_link;
+use wasmcloud_test_util::provider::{assert_start_provider, StartProviderArgs};
+
+pub mod common;
+use common::free_port;
+use common::nats::start_nats;
+use common::providers::Provider;
+
+const LATTICE: &str = "config-schema";
+const COMPONENT_ID: &str = "http_hello_world";
+
+/// Ensure that the host refuses to start a provider with configuration not matching the schema of
+/// its start configuration and refuses links supplying configuration to a provider, which does not
+/// match the schema of its link configuration
+#[tokio::test(flavor = "multi_thread")]
+async fn provider_config_schema() -> anyhow::Result<()> {
+    let (nats_server, nats_url, nats_client) = start_nats(None, true)
+        .await
+        .map(|res| (res.0, res.1, res.2.unwrap()))
+        .context("failed to start NATS")?;
+
+    let ctl_client = wasmcloud_control_interface::ClientBuilder::new(nats_client)
+        .lattice(LATTICE.to_string())
+        .build();
+
+    let host = WasmCloudTestHost::start(&nats_url, LATTICE)
+        .await
+        .context("failed to start test host")?;
+    let host_id = host.host_key().public_key();
+
+    let provider = Provider::with_schemas(
+        "wasmcloud-provider-http-server",
+        env!("CARGO_BIN_EXE_http-server-provider"),
+        Some(json!({
+            "properties": { "path": { "type": "string", "minLength": 1 } },
+            "required": ["path"],
+        })),
+        Some(json!({
+            "properties": { "routing_mode": { "enum": ["address", "path"] } },
+            "required": ["default_address"],
+        })),
+    )
+    .await
+    .context("failed to build http-server PAR")?;
+    let provider_id = provider.subject.public_key();
+    let provider_url = provider.url();
+
+    let http_port = free_port().await?;
+    let default_address = format!("{}:{http_port}", Ipv4Addr::LOCALHOST);
+    assert_config_put(
+        &ctl_client,
+        "http-server-invalid",
+        [
+            ("default_address".to_string(), default_address.clone()),
+            ("routing_mode".to_string(), "host".to_string()),
+        ],
+    )
+    .await
+    .context("failed to put configuration")?;
+    assert_config_put(
+        &ctl_client,
+        "http-server",
+        [
+            ("default_address".to_string(), default_address),
+            ("routing_mode".to_string(), "path".to_string()),
+        ],
+    )
+    .await
+    .context("failed to put configuration")?;
+    assert_config_put(
+        &ctl_client,
+        "path",
+        [("path".to_string(), "/hello".to_string())],
+    )
+    .await
+    .context("failed to put configuration")?;
+
+    // Start configuration not matching the schema is rejected
+    let mut receiver = ctl_client
+        .events_receiver(vec!["provider_start_failed".into()])
+        .await
+        .map_err(|e| anyhow!(e).context("failed to subscribe to events"))?;
+    let resp = ctl_client
+        .start_provider(
+            &host_id,
+            provider_url.as_str(),
+            &provider_id,
+            None,
+            vec!["http-server-invalid".to_string()],
+        )
+        .await
+        .map_err(|e| anyhow!(e).context("failed to start provider"))?;
+    ensure!(resp.succeeded());
+    tokio::time::timeout(Duration::from_secs(10), receiver.recv())
+        .await
+        .context("timed out waiting for provider start failed event")?
+        .context("failed to get event")?;
+    let inventory = ctl_client
+        .get_host_inventory(&host_id)
+        .await
+        .map_err(|e| anyhow!(e).context("failed to get host inventory"))?
+        .into_data()
+        .context("host inventory missing")?;
+    ensure!(inventory.providers().is_empty());
+
+    assert_start_provider(StartProviderArgs {
+        client: &ctl_client,
+        host_id: &host_id,
+        provider_id: &provider_id,
+        provider_ref: provider_url.as_str(),
+        config: vec!["http-server".to_string()],
+    })
+    .await
+    .context("failed to start provider")?;
+
+    // Link configuration supplied to the provider not matching the schema is rejected
+    let resp = assert_advertise_link(
+        &ctl_client,
+        &provider_id,
+        COMPONENT_ID,
+        "default",
+        "wasi",
+        "http",
+        vec!["incoming-handler".to_string()],
+        vec![],
+        vec![],
+    )
+    .await?;
+    ensure!(!resp.succeeded(), "link without a path should be rejected");
+    ensure!(
+        resp.message().contains("required key `path` is missing"),
+        "unexpected error: {}",
+        resp.message()
+    );
+
+    let resp = assert_advertise_link(
+        &ctl_client,
+        &provider_id,
+        COMPONENT_ID,
+        "default",
+        "wasi",
+        "http",
+        vec!["incoming-handler".to_string()],
+        vec!["path".to_string()],
+        vec![],
+    )
+    .await?;
+    ensure!(resp.succeeded(), "{}", resp.message());
+
+    host.stop().await.context("failed to stop host")?;
+    nats_server.stop().await.context("failed to stop NATS")?;
+    Ok(())
+}
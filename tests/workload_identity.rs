@@ -20,8 +20,10 @@ use nkeys::KeyPair;
 use tokio::time::sleep;
 use tokio::try_join;
 use tracing_subscriber::prelude::*;
+use wasmcloud_core::rpc::provider_secrets_update_subject;
 use wasmcloud_core::tls::NativeRootsExt as _;
 use wasmcloud_host::nats::connect_nats;
+use wasmcloud_host::wasmbus::HostConfig;
 use wasmcloud_host::workload_identity::WorkloadIdentityConfig;
 use wasmcloud_test_util::component::assert_scale_component;
 use wasmcloud_test_util::env::EnvVarGuard;
@@ -35,9 +37,10 @@ use test_components::RUST_WORKLOAD_IDENTITY_COMPONENT;
 
 pub mod common;
 use common::nats::{ensure_nats_connection_until_timeout, start_nats};
+use common::providers;
 use common::spire::{
-    generate_join_token, register_spiffe_workload, start_spire_agent, start_spire_server,
-    validate_workload_registration_within_timeout,
+    generate_join_token, register_spiffe_workload, register_spiffe_workload_with_x509_svid_ttl,
+    start_spire_agent, start_spire_server, validate_workload_registration_within_timeout,
 };
 use common::{free_port, tempdir};
 
@@ -363,6 +366,121 @@ async fn workload_identity_component_interface() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn workload_identity_provider_x509_svid_rotation() -> anyhow::Result<()> {
+    let (nats_server, nats_url, nats_client) = start_nats(None, true)
+        .await
+        .map(|res| (res.0, res.1, res.2.unwrap()))
+        .context("failed to start NATS")?;
+
+    let ctl_client = wasmcloud_control_interface::ClientBuilder::new(nats_client.clone())
+        .lattice(LATTICE.to_string())
+        .build();
+
+    let rust_http_client = providers::rust_http_client().await;
+    let provider_id = rust_http_client.subject.public_key();
+
+    // --- Set up SPIRE Server & Agent
+    let tmp_dir = tempdir().context("should have create a temporary directory for SPIRE")?;
+
+    let (spire_server, spire_server_url, spire_server_socket_path) =
+        start_spire_server(tmp_dir.path()).await?;
+
+    let agent_spiffe_id = "spiffe://wasmcloud.dev/test-agent";
+    let host_spiffe_id = "spiffe://wasmcloud.dev/wasmcloud-host";
+    let provider_spiffe_id = "spiffe://wasmcloud.dev/http-client-provider";
+
+    let agent_join_token = generate_join_token(agent_spiffe_id, &spire_server_socket_path)
+        .await
+        .context("should have generated join token for SPIRE Agent")?;
+
+    let (spire_agent, api_socket, admin_socket) =
+        start_spire_agent(&agent_join_token, spire_server_url, tmp_dir.path()).await?;
+
+    let metadata = std::fs::metadata(admin_socket.clone())
+        .context("should have read file metadata for the SPIRE Agent Workload API socket")?;
+
+    register_spiffe_workload(
+        agent_spiffe_id,
+        host_spiffe_id,
+        format!("unix:uid:{}", metadata.uid()).as_str(),
+        &spire_server_socket_path,
+    )
+    .await
+    .context("should have registered workload on the SPIRE Server")?;
+
+    // The SPIRE Agent rotates X.509-SVIDs once half of their lifetime has passed
+    register_spiffe_workload_with_x509_svid_ttl(
+        host_spiffe_id,
+        provider_spiffe_id,
+        format!("wasmcloud:provider:{provider_id}").as_str(),
+        Some(Duration::from_secs(30)),
+        &spire_server_socket_path,
+    )
+    .await
+    .context("should have registered workload on the SPIRE Server")?;
+
+    validate_workload_registration_within_timeout(&api_socket, Duration::from_secs(15)).await?;
+
+    let _spire_admin_socket = EnvVarGuard::set(
+        "SPIRE_ADMIN_ENDPOINT_SOCKET",
+        format!("unix:{}", admin_socket.display()),
+    );
+
+    let host = WasmCloudTestHost::start_custom_with_config(
+        &nats_url,
+        LATTICE,
+        None,
+        None,
+        None,
+        None,
+        HostConfig {
+            provider_shutdown_delay: Some(Duration::from_millis(300)),
+            allow_file_load: true,
+            experimental_features: wasmcloud_host::wasmbus::Features::new()
+                .enable_workload_identity_interface(),
+            provider_x509_svids: true,
+            ..Default::default()
+        },
+    )
+    .await
+    .context("failed to start test host")?;
+
+    let mut updates = nats_client
+        .subscribe(provider_secrets_update_subject(LATTICE, &provider_id))
+        .await
+        .context("failed to subscribe to provider secrets updates")?;
+
+    let rust_http_client_url = rust_http_client.url();
+    assert_start_provider(StartProviderArgs {
+        client: &ctl_client,
+        host_id: &host.host_id(),
+        provider_id: &provider_id,
+        provider_ref: rust_http_client_url.as_str(),
+        config: vec![],
+    })
+    .await
+    .context("failed to start provider")?;
+
+    // The current X.509-SVID is sent right away, the rotated one once it has been renewed
+    for _ in 0..2 {
+        let update = tokio::time::timeout(Duration::from_secs(60), updates.next())
+            .await
+            .context("timed out waiting for X.509-SVID update")?
+            .context("secrets update subscription ended")?;
+        assert!(
+            !String::from_utf8_lossy(&update.payload).contains("BEGIN"),
+            "X.509-SVID update must be encrypted"
+        );
+    }
+
+    let _ = host.stop().await;
+    let _ = spire_agent.stop().await;
+    let _ = spire_server.stop().await;
+    let _ = nats_server.stop().await;
+    Ok(())
+}
+
 // A very naive Auth Callout service implementation for the purposes of
 // validating that the workload under test is passing a valid JWT-SVID
 async fn start_workload_identity_auth_callout(